@@ -0,0 +1,457 @@
+//! This binary crate converts datetimes between time scales:
+//! [UTC](https://en.wikipedia.org/wiki/Coordinated_Universal_Time),
+//! [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time),
+//! [TT](https://en.wikipedia.org/wiki/Terrestrial_Time),
+//! [GPS time](https://en.wikipedia.org/wiki/Global_Positioning_System#Timekeeping) and
+//! the other GNSS system times:
+//! [Galileo System Time (GST)](https://en.wikipedia.org/wiki/Galileo_(satellite_navigation)),
+//! [BeiDou Time (BDT)](https://en.wikipedia.org/wiki/BeiDou),
+//! [GLONASS time](https://en.wikipedia.org/wiki/GLONASS) and
+//! [QZSS time](https://en.wikipedia.org/wiki/Quasi-Zenith_Satellite_System),
+//! and the relativistic time scales:
+//! [TCG](https://en.wikipedia.org/wiki/Geocentric_Coordinate_Time),
+//! [TCB](https://en.wikipedia.org/wiki/Barycentric_Coordinate_Time) and
+//! [TDB](https://en.wikipedia.org/wiki/Barycentric_Dynamical_Time),
+//! [UT1](https://en.wikipedia.org/wiki/Universal_Time)
+//! and smeared UTC (`smeared-utc`), which spreads leap seconds over a window instead of inserting `:60`.
+//!
+//! For example:
+//! ```bash
+//! $ convdate --from utc --to tt 2016-12-31T23:59:60
+//! 2017-01-01T00:01:08.184
+//! ```
+//!
+//! As you can see from the above example, it takes leap seconds into account.
+//! Before 1972, it also takes into account the "rubber seconds" of UTC, whose difference from TAI is not an integer and drifts.
+//! If the TAI-UTC table inserts two or more seconds at once, the seconds after `:60` are labeled `:61`, `:62`, ...;
+//! if it removes seconds, the labels of the removed seconds, for example `23:59:59`, do not exist and are reported as errors.
+//!
+//! In this execution, it assume that
+//! TT = [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time) + 32.184
+//! , GPS time = GST = QZSS time = TAI - 19,
+//! BDT = TAI - 33
+//! and GLONASS time = UTC + 3 hours; GLONASS time follows leap seconds like UTC.
+//! TCG and TCB follow the IAU resolutions (L<sub>G</sub>, L<sub>B</sub> and TDB<sub>0</sub> of IAU 2006),
+//! and TDB - TT is calculated with the model specified by `--tdb-model`.
+//! UT1 - UTC is interpolated linearly between the daily values of the EOP file specified by `--eop-table`.
+//! Out of the TAI-UTC table and the EOP file, UT is related to TT by ΔT of the model specified by `--delta-t`.
+//!
+//! # Aliases
+//! The executables below are aliases of `convdate` with fixed time scales.
+//! They take the same arguments, options, environment variables and standard input as `convdate`,
+//! except `--from` and `--to`.
+//! - [gps2utc](../gps2utc/index.html) - same as `convdate --from gps --to utc`
+//! - [tai2utc](../tai2utc/index.html) - same as `convdate --from tai --to utc`
+//! - [tt2utc](../tt2utc/index.html) - same as `convdate --from tt --to utc`
+//! - [utc2gps](../utc2gps/index.html) - same as `convdate --from utc --to gps`
+//! - [utc2tai](../utc2tai/index.html) - same as `convdate --from utc --to tai`
+//! - [utc2tt](../utc2tt/index.html) - same as `convdate --from utc --to tt`
+//!
+//! # Arguments
+//! It takes one or more datetimes as argument.
+//! ```bash
+//! $ convdate --from utc --to tt 2016-12-31T23:59:59 2016-12-31T23:59:60 2017-01-01T00:00:00
+//! 2017-01-01T00:01:07.184
+//! 2017-01-01T00:01:08.184
+//! 2017-01-01T00:01:09.184
+//! ```
+//!
+//! Instead of specifying the datetimes as arguments,
+//! they can be specified from the standard input.
+//!
+//! # Options
+//! - `--from <from>`, `--to <to>`
+//!
+//!     The time scales of input datetimes and output datetimes. Each of them is one of `utc`, `tai`, `tt`, `gps`, `gst`, `bdt`, `glonass`, `qzss`, `tcg`, `tcb`, `tdb`, `ut1` and `smeared-utc`; the case of letters is ignored. They are required. Besides, `unix` and `ntp` can be specified as the shorthands of UTC in Unix time and in NTP timestamps, and `ptp` as the shorthand of TAI in PTP records. Similarly, `era`, `gmst`, `gast` and `lst` can be specified only to `--to` as the shorthands of `--to ut1 --out-repr <repr>`; `--from unix` is same as `--from utc --in-repr unix`.
+//!     ```bash
+//!     $ convdate --from tai --to tt 2017-01-01T00:00:00
+//!     2017-01-01T00:00:32.184
+//!     ```
+//!
+//! - `--dt-fmt <dt_fmt>`
+//!
+//!     [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of input/output datetimes. For example:
+//!     ```bash
+//!     $ convdate --from utc --to tt --dt-fmt %Y%m%d%H%M%S%.3f 20161231235960.000
+//!     20170101000108.184
+//!     ```
+//!
+//!     If both of this option and an environment variable `DT_FMT` are not specified, it uses the default: `%Y-%m-%dT%H:%M:%S%.3f`.
+//!
+//!     Input UTC datetimes can have the UTC offset; they are converted to UTC before the conversion. The offset is parsed by `%z` or `%:z` in the format, or taken from the trailing `Z`, `±HH:MM`, `±HHMM` or `±HH` with the default format. With the other formats without the offset, such datetimes fail to parse. The output datetimes of the time scales other than UTC have no UTC offset.
+//!     ```bash
+//!     $ convdate --from utc --to tai 2017-01-01T08:59:60+09:00 2016-12-31T23:59:60Z
+//!     2017-01-01T00:00:36.000
+//!     2017-01-01T00:00:36.000
+//!     ```
+//!
+//!     Instead of a format, `iso8601` accepts the whole grammar of ISO 8601: the basic and extended formats, calendar dates (`2016-12-31`), ordinal dates (`2016-366`), week dates (`2016-W52-6`), reduced precision (`2016-12`, `2016-12-31T23:59`), decimal fractions with any number of digits, the comma as the decimal sign, the second 60 and the UTC offsets. Output datetimes keep the style of the input; the date form, the basic or extended format, the number of fraction digits, the decimal sign and the UTC offset, which is kept only for UTC. The time is always written down to the second. The suffixes `-calendar`, `-ordinal` or `-week` and `-basic` or `-extended` override the style; for example, `iso8601-week-basic`.
+//!     ```bash
+//!     $ convdate --from utc --to tai --dt-fmt iso8601 2016-366T23:59:60,5 20170101T085960+0900
+//!     2017-001T00:00:36,5
+//!     20170101T000036
+//!     $ convdate --from tai --to utc --dt-fmt iso8601-week 2017-01-01T00:00:36.5Z
+//!     2016-W52-6T23:59:60.5Z
+//!     ```
+//!
+//!     The format can have the extended specifiers below for the fields which strftime cannot express; both input and output datetimes are read and written along them. The numeric fields except `%{gpsweek}` take the number of digits after the decimal point, such as `%{mjd:.6}`; they are truncated. Without it, they are integers except `%{dAT}`, which has as many digits as it needs.
+//!     - `%{gpsweek}`, `%{sow}`: the number of weeks since 1980-01-06T00:00:00 and the seconds of the week, in the time scale of the datetime
+//!     - `%{doy}`: the day of the year; 1 at 0h of January 1, in three digits at least. When it is read, the year is taken from `%Y`.
+//!     - `%{mjd}`: the modified Julian date
+//!     - `%{scale}`: the name of the time scale in upper case like `%Z`, such as `TAI`; when it is read, the case of letters is ignored and it must be the time scale of `--from`.
+//!     - `%{dAT}`: TAI - UTC in seconds at the datetime; when it is read, it is ignored.
+//!
+//!     When `%{doy}` or `%{mjd}` without the fractional part is read, the time of day is taken from the other specifiers such as `%H`, or 0h without them.
+//!     ```bash
+//!     $ convdate --from gps --to utc --in-dt-fmt "%{gpsweek}_%{sow:.1}" --out-dt-fmt "%Y-%{doy:.6} %{mjd:.6} dAT=%{dAT}" 1930_17.5
+//!     2016-366.999994 57753.999994 dAT=36
+//!     ```
+//!
+//!     It can be specified two or more times, or as a list separated by `|` in `DT_FMT`. Each input datetime is parsed along the first format which it matches, and output in the same format. If it matches none of them, the error lists all the formats.
+//!     ```bash
+//!     $ convdate --from utc --to tai --dt-fmt "%Y/%m/%d %H:%M:%S" --dt-fmt iso8601 "2016/12/31 23:59:60" 2016-366T23:59:60Z
+//!     2017/01/01 00:00:36
+//!     2017-001T00:00:36Z
+//!     ```
+//!
+//! - `--in-dt-fmt <in_dt_fmt>`, `--out-dt-fmt <out_dt_fmt>`
+//!
+//!     The formats of input datetimes and output datetimes; formats of chrono or ISO 8601 formats as `--dt-fmt`, which is a shorthand for both of them. `--in-dt-fmt` can be specified two or more times as `--dt-fmt`.
+//!     ```bash
+//!     $ convdate --from utc --to tai --in-dt-fmt %Y%m%d%H%M%S --out-dt-fmt iso8601 20161231235960
+//!     2017-01-01T00:00:36
+//!     $ DT_FMT="%Y%m%d%H%M%S|iso8601" convdate --from utc --to tai --out-dt-fmt "%Y-%m-%d %H:%M:%S" 20161231235960 2016-W52-6T23:59:60
+//!     2017-01-01 00:00:36
+//!     2017-01-01 00:00:36
+//!     ```
+//!
+//!     If `--in-dt-fmt` is not specified, `--dt-fmt`, environment variables `IN_DT_FMT` and `DT_FMT` are used in this order. If `--out-dt-fmt` is not specified, `--dt-fmt`, environment variables `OUT_DT_FMT` and `DT_FMT` are used in this order; if none of them is specified or the used one is a list of two or more formats, each output datetime is in the format which the input matched.
+//!
+//! - `--in-repr <in_repr>`, `--out-repr <out_repr>`
+//!
+//!     The representations of input datetimes and output datetimes. Each of them is one of below; the case of letters is ignored.
+//!     - `datetime` (default): datetimes formatted along `--dt-fmt`
+//!     - `week`: `week:seconds-of-week`, where `week` is the number of weeks since the epoch of the time scale. The time scales below support it.
+//!         - `gps`, `qzss`: epoch 1980-01-06T00:00:00
+//!         - `gst`: epoch 1999-08-22T00:00:00
+//!         - `bdt`: epoch 2006-01-01T00:00:00
+//!
+//!         `glonass` does not support it, because GLONASS time counts days in four-year intervals instead of weeks; use `n4nt`.
+//!     - `n4nt`: `N4:NT:seconds-of-day` of GLONASS time, where `N4` is the number of the four-year interval since 1996 (1 for 1996-1999) and `NT` is the day number in it (1 for January 1 of the leap year); for example, `6:367:10800`. It can be used only in `glonass`, and it cannot express the leap seconds.
+//!     - `jd`: [Julian date](https://en.wikipedia.org/wiki/Julian_day) in the time scale; for example, `2457754.5`
+//!     - `mjd`: modified Julian date (JD - 2400000.5) in the time scale; for example, `57754`
+//!     - `two-part-jd`: Julian date at 0h of the day and the fraction of the day, separated by a space; for example, `2457754.5 0.25`. In input, any two numbers whose sum is the Julian date are accepted.
+//!
+//!     - `unix`: seconds since 1970-01-01T00:00:00 in the time scale; for example, `1483228800`
+//!     - `seconds-since-1958`: seconds since 1958-01-01T00:00:00 in the time scale, which is the origin of TAI; for example, `--to tai --out-repr seconds-since-1958`
+//!     - `j2000-seconds`: seconds since 2000-01-01T12:00:00 in the time scale, which is usually TT
+//!     - `excel`: serial days of Excel and LibreOffice, which are days since 1899-12-30T00:00:00 in the time scale; for example, `42736.5`
+//!     - `ntp`: [NTP](https://www.rfc-editor.org/rfc/rfc5905) timestamp, 32 bits of seconds since 1900-01-01T00:00:00 and 32 bits of the fraction in hexadecimal; for example, `dc12c500.80000000`. The era is written before it only if it is not 0, such as `1:00000000.00000000` (2036-02-07T06:28:16). See `--ntp-pivot`.
+//!     - `ptp`: [PTP](https://standards.ieee.org/ieee/1588/6825/) record, seconds and nanoseconds since 1970-01-01T00:00:00 in TAI optionally followed by `currentUtcOffset` and a leap flag `leap61` or `leap59`; for example, `1483228836.500000000 36 leap61`. It can be used only in `tai`. See `--ptp-utc-offset`.
+//!     - `cuc`, `cds`, `ccs`: [CCSDS](https://public.ccsds.org/Pubs/301x0b4e1.pdf) binary time codes in hexadecimal, beginning with the P-field; for example, `1E6EFAA5258000`. See `--ccsds-pfield`.
+//!     - `ascii-a`, `ascii-b`: CCSDS ASCII time codes; for example, `2017-01-01T00:00:37.5Z` and `2017-001T00:00:37.5Z`
+//!     - `era`, `gmst`, `gast`, `lst` (output only): the Earth Rotation Angle, the Greenwich mean sidereal time (IAU 2006), the Greenwich apparent sidereal time and the local apparent sidereal time at `--longitude`, in hours, minutes and seconds; for example, `06:42:01.637380`. They are calculated from UT1 and TT, so they can be used only with `--to ut1` and require the EOP table. The equation of the equinoxes in GAST uses a truncated IAU 2000B nutation, whose error is within about 10 milliarcseconds.
+//!
+//!     Julian dates are written with up to 14 digits after the decimal point, which are enough to keep nanoseconds. In UTC, a day containing a leap second is 86401 seconds long and the fraction of the day is stretched over it, as in [SOFA](http://www.iausofa.org/).
+//!
+//!     ```bash
+//!     $ convdate --from utc --to gps --out-repr week 2016-12-31T23:59:60.5
+//!     1930:17.5
+//!     $ convdate --from gps --to utc --in-repr week 1930:17.5
+//!     2016-12-31T23:59:60.500
+//!     $ convdate --from utc --to bdt --out-repr week 2017-01-01T00:00:00
+//!     574:4
+//!     $ convdate --from utc --to glonass --out-repr n4nt 2017-01-01T00:00:00
+//!     6:367:10800
+//!     $ convdate --from tai --to utc --out-repr mjd 2017-01-01T00:00:36
+//!     57753.99998842605988
+//!     $ convdate --from utc --to tt --in-repr two-part-jd "2457754.5 0.5"
+//!     2017-01-01T12:01:09.184
+//!     $ convdate --from unix --to tai 1483228800
+//!     2017-01-01T00:00:37.000
+//!     $ convdate --from tai --to utc --in-repr cuc 1E6EFAA5258000
+//!     2017-01-01T00:00:00.500
+//!     ```
+//!
+//!     The numeric representations `unix`, `seconds-since-1958`, `j2000-seconds`, `excel` and `ntp` count every day as 86400 seconds, as Unix time ignores leap seconds. Therefore, they cannot express an inserted leap second of UTC and it is reported as an error. To count SI seconds including leap seconds, use them in TAI or TT. So does `cuc`, while `cds`, `ccs` and the ASCII time codes can express the leap second.
+//!
+//! - `--ntp-pivot <datetime>`, `--ntp-leap-indicator`
+//!
+//!     `--ntp-pivot` specifies the pivot datetime to resolve the eras of input NTP timestamps without eras; it is formatted along `--dt-fmt`. An input timestamp is resolved to the first datetime not before the pivot. If it is not specified, the pivot is 1968-01-20T03:14:08 as [RFC 4330](https://www.rfc-editor.org/rfc/rfc4330), so the timestamps are in 1968-2104.
+//!
+//!     If `--ntp-leap-indicator` is specified, the leap indicator which an NTP server should advertise at the datetime is appended to each output as `LI=<leap_indicator>`. It is calculated from the TAI-UTC table: 1 throughout the day at the end of which a leap second is inserted, 2 throughout the day at the end of which a leap second is deleted, and 0 otherwise. With it, an inserted leap second is output as the NTP timestamps of the last second before it, repeated as an NTP server does, instead of an error.
+//!     ```bash
+//!     $ convdate --from ntp --to utc 00000000.00000000
+//!     2036-02-07T06:28:16.000
+//!     $ convdate --from utc --to ntp --ntp-leap-indicator 2016-12-31T12:00:00
+//!     dc121c40.00000000 LI=1
+//!     ```
+//!
+//! - `--ptp-utc-offset <ptp_utc_offset>`
+//!
+//!     The source of TAI - UTC used to convert input PTP records; `table` (default) or `record`. If it is `record`, `currentUtcOffset` and the leap flag of each record are used, and the records without them are converted with the TAI-UTC table. Output PTP records always carry `currentUtcOffset` and the leap flag calculated from the TAI-UTC table.
+//!
+//!     In both cases, the input records whose `currentUtcOffset` or leap flag disagree with the TAI-UTC table are reported to the standard error and the exit code becomes 2, while they are still converted. It is useful to audit grandmaster clocks from captured logs.
+//!     ```bash
+//!     $ convdate --from ptp --to utc "1483228836.5 36 leap61"
+//!     2016-12-31T23:59:60.500
+//!     $ convdate --from ptp --to utc --ptp-utc-offset record "1483228836.5 37"
+//!     convdate: The currentUtcOffset of the PTP record disagrees with the TAI-UTC table (36 s): 1483228836.5 37
+//!     2016-12-31T23:59:59.500
+//!     $ convdate --from utc --to ptp 2016-12-31T12:00:00
+//!     1483185636.000000000 36 leap61
+//!     ```
+//!
+//! - `--longitude <degrees>`
+//!
+//!     The longitude for the local sidereal time, in degrees and positive to the east. It is required if the output representation is `lst`.
+//!     ```bash
+//!     $ convdate --from utc --to gmst --eop-table finals2000A.all 2017-01-01T12:00:00
+//!     18:45:19.977458
+//!     $ convdate --from utc --to lst --longitude 139.7 --eop-table finals2000A.all 2017-01-01T12:00:00
+//!     04:04:07.583478
+//!     ```
+//!
+//! - `--ccsds-pfield <hex>`, `--ccsds-implicit-pfield`, `--ccsds-epoch <datetime>`
+//!
+//!     The parameters of the CCSDS binary time codes. The P-field of `--ccsds-pfield` is used for the input or the output whose representation is the time code which it defines; it is an error if it defines neither of them. Otherwise, the default P-field is used: `1E` for `cuc` (4 octets of seconds and 2 octets of the fraction), `40` for `cds` (16 bits of days and milliseconds of the day) and `53` for `ccs` (month and day, and microseconds).
+//!
+//!     If `--ccsds-implicit-pfield` is specified, input time codes are only the T-fields and output time codes omit the P-fields.
+//!
+//!     `--ccsds-epoch` specifies the agency-defined epoch formatted along `--dt-fmt`, which is required by the P-fields specifying it. If it is specified, the default P-fields of `cuc` and `cds` are `2E` and `48`, which use it. Otherwise, the epoch is 1958-01-01T00:00:00 of the time scale.
+//!     ```bash
+//!     $ convdate --from tai --to tai --in-repr cuc --out-repr cds --ccsds-pfield 41 1E6EFAA5258000
+//!     41542E0000927C0000
+//!     $ convdate --from utc --to tai --out-repr cuc --ccsds-implicit-pfield 2017-01-01T00:00:00
+//!     6EFAA5250000
+//!     ```
+//!
+//! - `--week-rollover-ref <datetime>`
+//!
+//!     The reference datetime for truncated week numbers, such as the ones broadcast by the satellites; it is formatted along `--dt-fmt`. If it is specified, an input week number is resolved to the first week not before the week containing the reference datetime, and output week numbers roll over to 0. The week numbers roll over every 1024 weeks in `gps` and `qzss`, 4096 weeks in `gst` and 8192 weeks in `bdt`.
+//!     ```bash
+//!     $ convdate --from gps --to utc --in-repr week --week-rollover-ref 2019-04-07T00:00:00 0:0
+//!     2019-04-06T23:59:42.000
+//!     ```
+//!
+//! - `--tdb-model <tdb_model>`
+//!
+//!     The model of TDB - TT used when the conversion passes through TDB, such as `--from tt --to tcb`. It is one of below; the case of letters is ignored.
+//!     - `fairhead-bretagnon-truncated` (default): the Fairhead–Bretagnon series truncated to the first 23 of its 787 terms in the order of `iauDtdb` of SOFA; the error from the full series is estimated at up to about 5 microseconds between 1600 and 2200.
+//!     - `simplified`: TDB - TT = 0.001657 sin *g* + 0.000014 sin 2*g*, where *g* is the mean anomaly of the Earth; the error is up to about 40 microseconds.
+//!
+//!     The model is recorded in the standard error output, so that the lines of the output correspond to the inputs one-to-one.
+//!     ```bash
+//!     $ convdate --from tt --to tdb --dt-fmt %Y-%m-%dT%H:%M:%S%.6f 2017-01-01T12:00:00.000000
+//!     # TDB model: fairhead-bretagnon-truncated
+//!     2017-01-01T11:59:59.999965
+//!     ```
+//!
+//! - `--eop-table <eop_table_file>`
+//!
+//!     It specifies a file of the Earth orientation parameters (EOP) published by [IERS](https://www.iers.org/), which is required when the conversion passes through UT1. The file is one of below.
+//!     - [finals2000A.all](https://datacenter.iers.org/versionMetadata.php?filename=latestVersionMeta/10_FINALS.DATA_IAU2000_V2013_0110.txt) (or `finals2000A.data` and so on): it is recognized by its fixed-width lines beginning with the date and the MJD.
+//!     - [Bulletin A](https://www.iers.org/IERS/EN/Publications/Bulletins/bulletins.html): the rows of UT1 - UTC in it are used.
+//!
+//!     UT1 - UTC is interpolated linearly between the daily values; the datetimes out of the coverage of the file cannot be converted.
+//!     ```bash
+//!     $ convdate --from utc --to ut1 --eop-table finals2000A.all 2017-01-01T12:00:00
+//!     2017-01-01T12:00:00.592
+//!     ```
+//!
+//!     If both of this option and an environment variable `EOP_TABLE` are not specified, it uses the default: `finals2000A.all` in directory of executable file. There is no built-in EOP table.
+//!
+//! - `--delta-t <delta_t>`, `--delta-t-table <delta_t_table_file>`
+//!
+//!     The model of ΔT (TT - UT) used when the TAI-UTC table or the EOP table does not cover the datetime; UTC before the TAI-UTC table is regarded as UT. As the model does not agree with the table at its start, a datetime before the start which the model converts to a datetime after the start cannot be converted; for example, with `espenak-meeus`, TT from about 1961-01-01T00:00:33.5503 to just before 1961-01-01T00:00:33.606818, the start of the table in TT, has no corresponding UTC. The value of `--delta-t` is one of below.
+//!     - `espenak-meeus`: the [polynomials of Espenak and Meeus](https://eclipse.gsfc.nasa.gov/SEhelp/deltatpoly2004.html).
+//!     - `table`: the ΔT table file specified by `--delta-t-table`. Each line is `<decimal_year> <delta_t>` or `<year> <month> <day> <delta_t>` such as [deltat.data of USNO](https://maia.usno.navy.mil/ser7/deltat.data), where ΔT is in seconds; it is interpolated linearly, and the datetimes out of the coverage of the file cannot be converted.
+//!
+//!     The results derived from the model are approximations, so they are followed by `(delta-t: <delta_t>)`.
+//!     ```bash
+//!     $ convdate --from utc --to tt --delta-t espenak-meeus 1900-01-01T00:00:00 2017-01-01T00:00:00
+//!     1899-12-31T23:59:57.210 (delta-t: espenak-meeus)
+//!     2017-01-01T00:01:09.184
+//!     ```
+//!
+//!     If `--delta-t-table` or an environment variable `DELTA_T_TABLE` is specified without `--delta-t`, `--delta-t table` is assumed. If none of them are specified, such datetimes cannot be converted.
+//!
+//!  - `-H`, `--io-pair`
+//!
+//!     If it is specified, not only converted datetime but also input datetime are output. For example:
+//!     ```bash
+//!     $ convdate --from utc --to tt -H 2016-12-31T23:59:59 2016-12-31T23:59:60 2017-01-01T00:00:00
+//!     2016-12-31T23:59:59 2017-01-01T00:01:07.184
+//!     2016-12-31T23:59:60 2017-01-01T00:01:08.184
+//!     2017-01-01T00:00:00 2017-01-01T00:01:09.184
+//!     ```
+//!
+//! - `--tai-utc-table <tai_utc_table_file>`
+//!
+//!     It specifies a file which contains definition of TAI-UTC differences. If you use it, the option `--tai-utc-table-dt-fmt` may be useful.
+//!
+//!     Each line of the file consists of a datetime, a difference TAI - UTC in seconds and an optional note. For example:
+//!     ```text
+//!     # comment
+//!     2015-07-01T00:00:00  36  IERS Bulletin C 49
+//!     2017-01-01T00:00:00  37  IERS Bulletin C 52  # comment
+//!     ```
+//!     Blank lines and the part after `#` are ignored.
+//!
+//!     [The IETF leap-seconds.list](https://www.ietf.org/timezones/data/leap-seconds.list) can also be specified as it is. It is recognized by the lines beginning with `#$`, `#@` or `#h`, and its SHA-1 hash is checked.
+//!
+//!     [The USNO tai-utc.dat](https://maia.usno.navy.mil/ser7/tai-utc.dat) can also be specified as it is. It is recognized by `TAI-UTC=` in the lines. It defines TAI - UTC since 1961, including the era of "rubber seconds" before 1972 in which TAI - UTC is not an integer and drifts day by day.
+//!
+//!     If both of this option and an environment variable `TAI_UTC_TABLE` are not specified, it uses the default: `tai-utc.txt` in directory of executable file. If the default file also does not exist, use the built-in table in the program, which covers UTC since 1961-01-01.
+//!
+//! - `--tai-utc-table-dt-fmt <tai_utc_table_dt_fmt>`
+//!
+//!     [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of datetimes in TAI-UTC table file. It will be used when you use your TAI-UTC table file with the option `--tai-utc-table`.
+//!
+//!     If both of this option and an environment variable `TAI_UTC_TABLE_DT_FMT` are not specified, it uses the default: `%Y-%m-%dT%H:%M:%S%.3f`.
+//!
+//! - `--tai-utc-table-sep <tai_utc_table_sep>`
+//!
+//!     The separator between columns in TAI-UTC table file.
+//!
+//!     If both of this option and an environment variable `TAI_UTC_TABLE_SEP` are not specified, runs of whitespaces (spaces and tabs) are used as the separator.
+//!
+//! - `--beyond-table <policy>`, `--tai-utc-table-expiration <datetime>`
+//!
+//!     The TAI-UTC table cannot know about leap seconds after its expiration date, which is read from the line beginning with `#@` in the IETF leap-seconds.list or specified by `--tai-utc-table-expiration` formatted along `--dt-fmt`. `--beyond-table` specifies the policy for UTC datetimes after the expiration date; it is one of below.
+//!     - `assume-constant` (default): TAI - UTC of the last row is used silently.
+//!     - `warn`: TAI - UTC of the last row is used, and a warning is output to stderr; the exit code is not affected.
+//!     - `error`: the datetime cannot be converted. If `--delta-t` is specified, ΔT is used instead.
+//!
+//!     If the expiration date is unknown, for example with the built-in table, the datetimes are converted as `assume-constant`.
+//!     ```bash
+//!     $ convdate --from utc --to tai --beyond-table error --tai-utc-table-expiration 2024-06-28T00:00:00 2024-06-27T00:00:00 2030-01-01T00:00:00
+//!     2024-06-27T00:00:37.000
+//!     convdate: The datetime is too high: 2030-01-01 00:00:00
+//!     ```
+//!
+//! - `--lenient`
+//!
+//!     By default, input UTC datetimes with the second 60 are checked against the TAI-UTC table, and the ones which are not leap seconds are reported as errors with the nearest leap second. If `--lenient` is specified, they are accepted and converted as the first second of the next minute.
+//!     ```bash
+//!     $ convdate --from utc --to tai 2016-06-30T23:59:60
+//!     convdate: The datetime is not a leap second of the TAI-UTC table; the nearest leap second is 2016-12-31 23:59:60: 2016-06-30 23:59:60
+//!     $ convdate --from utc --to tai --lenient 2016-06-30T23:59:60
+//!     2016-07-01T00:00:36.000
+//!     ```
+//!
+//! - `--out-leap <policy>`
+//!
+//!     Policy for output UTC datetimes in leap seconds, which many systems such as databases and Unix time cannot accept. The policy applies to every representation of `--out-repr`, and the adjusted outputs are followed by `(leap: <policy>)`. The policy is one of the followings:
+//!
+//!     - `keep` (default): output the second 60 as it is; for example, `23:59:60.500`
+//!     - `repeat-59`: repeat the second 59; for example, `23:59:59.500`
+//!     - `freeze`: freeze at `23:59:59.999999999`
+//!     - `roll`: roll to `00:00:00` of the next minute
+//!     - `error`: report as an error
+//!     ```bash
+//!     $ convdate --from tai --to utc --out-leap repeat-59 2017-01-01T00:00:36.5 2017-01-01T00:00:37
+//!     2016-12-31T23:59:59.500 (leap: repeat-59)
+//!     2017-01-01T00:00:00.000
+//!     $ convdate --from tai --to utc --out-leap roll --out-repr unix 2017-01-01T00:00:36.5
+//!     1483228800 (leap: roll)
+//!     ```
+//!
+//! - `--out-tz <zone>`, `--zoneinfo-dir <dir>`
+//!
+//!     The time zone of output UTC datetimes; a name of the IANA time zone database such as `Asia/Tokyo`, a UTC offset such as `+09:00`, or a POSIX TZ string such as `JST-9`. Leap seconds are shown as the second 60 of the local time in the same way as the `right/` zones of tzdata. `%z`, `%:z` and `%Z` in `--dt-fmt` are replaced with the UTC offset and the abbreviation. It can be used only when `--to` is `utc` and `--out-repr` is `datetime`.
+//!     ```bash
+//!     $ convdate --from tai --to utc --out-tz Asia/Tokyo --dt-fmt "%Y-%m-%dT%H:%M:%S%:z" 2017-01-01T00:00:36+00:00
+//!     2017-01-01T08:59:60+09:00
+//!     ```
+//!
+//!     The names of the database are read from the TZif files in the zoneinfo directory `--zoneinfo-dir`. If it is not specified, the environment variable `TZDIR` is used. If both of them are not specified, the default directory `/usr/share/zoneinfo` is used. The database is not embedded in `convdate`; on hosts without the zoneinfo directory, install the tzdata package or specify the directory, otherwise the names of the database fail with an error. The UTC offsets and the POSIX TZ strings require no files.
+//!
+//! - `--smear <shape>`
+//!
+//!     If it is specified, the output UTC is smeared: each leap second is spread over a window around it instead of being labeled `:60`. It is same as specifying `--to smeared-utc`, and the shape is also used by `--to smeared-utc`; the default shape is `google`. The shape is one of the followings:
+//!
+//!     - `google`, `aws`: linear smear over 24 hours, from noon before the leap to noon after it
+//!     - `utc-sls`: linear smear over the last 1000 seconds before the leap
+//!     - `custom:<start>,<length>[,<curve>]`: the window starts `<start>` seconds from the leap (negative for before) and lasts `<length>` seconds; `<curve>` is `linear` (default) or `cosine`
+//!     ```bash
+//!     $ convdate --from tai --to utc --smear google 2017-01-01T00:00:36.5
+//!     2017-01-01T00:00:00.000
+//!     ```
+//!
+//! - `--from-smear`
+//!
+//!     If it is specified, the input UTC is regarded as smeared by the shape `--smear` (default `google`). As smeared UTC has no leap seconds, the input datetimes with the second 60 are rejected.
+//!     ```bash
+//!     $ convdate --from utc --to tai --from-smear 2017-01-01T00:00:00
+//!     2017-01-01T00:00:36.500
+//!     ```
+//!
+//! - `-V`, `--version`
+//!
+//!     Show the version of this executable.
+//!
+//! - `-h`, `--help`
+//!
+//!     Show help document of this executable.
+//!
+//! # Environment variables
+//!
+//! It looks for below environment variables.
+//!
+//! - `DT_FMT`
+//!
+//!     Look for a description for an option `--dt-fmt`. Two or more formats are separated by `|`.
+//!
+//! - `DELTA_T_TABLE`
+//!
+//!     Look for a description for an option `--delta-t-table`.
+//!
+//! - `EOP_TABLE`
+//!
+//!     Look for a description for an option `--eop-table`.
+//!
+//! - `IN_DT_FMT`
+//!
+//!     Look for a description for an option `--in-dt-fmt`. Two or more formats are separated by `|`.
+//!
+//! - `OUT_DT_FMT`
+//!
+//!     Look for a description for an option `--out-dt-fmt`.
+//!
+//! - `TAI_UTC_TABLE`
+//!
+//!     Look for a description for an option `--tai-utc-table`.
+//!
+//! - `TAI_UTC_TABLE_DT_FMT`
+//!
+//!     Look for a description for an option `--tai-utc-table-dt-fmt`.
+//!
+//! - `TAI_UTC_TABLE_SEP`
+//!
+//!     Look for a description for an option `--tai-utc-table-sep`.
+//!
+//! - `TZDIR`
+//!
+//!     Look for a description for an option `--zoneinfo-dir`.
+//!
+//! # Standard input
+//!
+//! If the datetimes are not specified as arguments,
+//! they can be entered from the standard input instead.
+
+use convdate::exe::convdate::main_inner;
+use std::env;
+use std::io;
+
+#[doc(hidden)]
+fn main() {
+    let exit_code = main_inner(
+        env::args(),
+        env::vars(),
+        &mut io::stdin().lock(),
+        &mut io::stdout().lock(),
+        &mut io::stderr(),
+    );
+    std::process::exit(exit_code);
+}
@@ -0,0 +1,47 @@
+//! This binary crate converts datetimes
+//! from [GPS time](https://en.wikipedia.org/wiki/Global_Positioning_System#Timekeeping)
+//! to [UTC](https://en.wikipedia.org/wiki/Coordinated_Universal_Time).
+//!
+//! For example:
+//! ```bash
+//! $ gps2utc 2017-01-01T00:00:17
+//! 2016-12-31T23:59:60.000
+//! ```
+//!
+//! As you can see from the above example, it takes leap seconds into account.
+//!
+//! It is an alias of [convdate](../convdate/index.html) with the options `--from gps --to utc`.
+//!
+//! In this execution, it assume that
+//! GPS time = [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time) - 19.
+//!
+//! # Arguments
+//! See [convdate#Arguments](../convdate/index.html#arguments).
+//!
+//! # Options
+//! See [convdate#Options](../convdate/index.html#options).
+//!
+//! # Environment variables
+//! See [convdate#Environment variables](../convdate/index.html#environment-variables).
+//!
+//! # Standard input
+//! See [convdate#Standard input](../convdate/index.html#standard-input).
+
+use convdate::exe::convdate::main_inner_alias;
+use convdate::TimeScale;
+use std::env;
+use std::io;
+
+#[doc(hidden)]
+fn main() {
+    let exit_code = main_inner_alias(
+        TimeScale::Gps,
+        TimeScale::Utc,
+        env::args(),
+        env::vars(),
+        &mut io::stdin().lock(),
+        &mut io::stdout().lock(),
+        &mut io::stderr(),
+    );
+    std::process::exit(exit_code);
+}
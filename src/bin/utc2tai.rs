@@ -10,25 +10,30 @@
 //!
 //! As you can see from the above example, it takes leap seconds into account.
 //!
+//! It is an alias of [convdate](../convdate/index.html) with the options `--from utc --to tai`.
+//!
 //! # Arguments
-//! See [utc2tt#Arguments](../utc2tt/index.html#arguments).
+//! See [convdate#Arguments](../convdate/index.html#arguments).
 //!
 //! # Options
-//! See [utc2tt#Options](../utc2tt/index.html#options).
+//! See [convdate#Options](../convdate/index.html#options).
 //!
 //! # Environment variables
-//! See [utc2tt#Environment variables](../utc2tt/index.html#environment-variables).
+//! See [convdate#Environment variables](../convdate/index.html#environment-variables).
 //!
 //! # Standard input
-//! See [utc2tt#Standard input](../utc2tt/index.html#standard-input).
+//! See [convdate#Standard input](../convdate/index.html#standard-input).
 
-use convdate::exe::utc2tai::main_inner;
+use convdate::exe::convdate::main_inner_alias;
+use convdate::TimeScale;
 use std::env;
 use std::io;
 
 #[doc(hidden)]
 fn main() {
-    let exit_code = main_inner(
+    let exit_code = main_inner_alias(
+        TimeScale::Utc,
+        TimeScale::Tai,
         env::args(),
         env::vars(),
         &mut io::stdin().lock(),
@@ -0,0 +1,404 @@
+//! CCSDS time codes; [CCSDS 301.0-B](https://public.ccsds.org/Pubs/301x0b4e1.pdf).
+//!
+//! The time codes below are supported.
+//! - CUC (CCSDS Unsegmented time Code): seconds and binary fractions of a second since the epoch
+//! - CDS (CCSDS Day Segmented time code): days since the epoch, milliseconds of the day and optional microseconds or picoseconds
+//! - CCS (CCSDS Calendar Segmented time code): BCD calendar fields with optional sub-second digits
+//! - ASCII A: `YYYY-MM-DDThh:mm:ss.d->dZ`
+//! - ASCII B: `YYYY-DDDThh:mm:ss.d->dZ`
+//!
+//! The binary time codes begin with the P-field (preamble field), which defines the layout of the following T-field (time field).
+//! If the P-field is not transmitted, i.e. it is implicit, it has to be given to [`decode`] separately.
+//!
+//! The time codes are interpreted in any time scale; for example, a CUC is usually in TAI but it can be decoded as UTC.
+//! The epoch is 1958-01-01T00:00:00 of the time scale or the agency-defined epoch, according to the P-field.
+//!
+//! CDS, CCS and the ASCII time codes can express a leap second; the millisecond of the day of CDS reaches 86400999 and the second of the others reaches 60.
+//! CUC counts every day as 86400 seconds, so it cannot express an inserted leap second of UTC.
+use crate::epoch::epoch_1958;
+use crate::error::Error;
+use chrono::NaiveDateTime;
+use std::fmt::Write;
+mod ascii;
+mod ccs;
+mod cds;
+mod cuc;
+
+/// Kinds of CCSDS time codes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TimeCode {
+    /// CCSDS Unsegmented time Code
+    Cuc,
+    /// CCSDS Day Segmented time code
+    Cds,
+    /// CCSDS Calendar Segmented time code
+    Ccs,
+    /// ASCII calendar segmented time code, type A (month and day)
+    AsciiA,
+    /// ASCII calendar segmented time code, type B (day of year)
+    AsciiB,
+}
+
+impl TimeCode {
+    /// The kind of the binary time code which the P-field defines
+    ///
+    /// # Arguments
+    /// * `pfield` - The P-field, or the time code beginning with it
+    ///
+    /// # Returns
+    /// Returns `None` if the time code ID in the P-field is unknown.
+    pub fn of_pfield(pfield: &[u8]) -> Option<TimeCode> {
+        match pfield.first()? >> 4 & 0b0111 {
+            0b001 | 0b010 => Some(TimeCode::Cuc),
+            0b100 => Some(TimeCode::Cds),
+            0b101 => Some(TimeCode::Ccs),
+            _ => None,
+        }
+    }
+
+    /// Whether the time code is binary, which has the P-field, or not.
+    pub fn is_binary(&self) -> bool {
+        matches!(self, TimeCode::Cuc | TimeCode::Cds | TimeCode::Ccs)
+    }
+
+    /// The P-field used when it is not specified.
+    ///
+    /// - CUC: 4 octets of seconds and 2 octets of the fraction (`1E`, or `2E` with the agency-defined epoch)
+    /// - CDS: 16 bits of days and milliseconds of the day (`40`, or `48` with the agency-defined epoch)
+    /// - CCS: month and day, and 3 octets of the sub-second, i.e. microseconds (`53`)
+    ///
+    /// The ASCII time codes have no P-field.
+    ///
+    /// # Arguments
+    /// * `agency_epoch` - Whether the agency-defined epoch is used or not; CCS and the ASCII time codes ignore it.
+    pub fn default_pfield(&self, agency_epoch: bool) -> Vec<u8> {
+        match (self, agency_epoch) {
+            (TimeCode::Cuc, false) => vec![0x1E],
+            (TimeCode::Cuc, true) => vec![0x2E],
+            (TimeCode::Cds, false) => vec![0x40],
+            (TimeCode::Cds, true) => vec![0x48],
+            (TimeCode::Ccs, _) => vec![0x53],
+            (TimeCode::AsciiA | TimeCode::AsciiB, _) => vec![],
+        }
+    }
+}
+
+/// Decode the CCSDS time code.
+///
+/// # Arguments
+/// * `code` - The kind of the time code
+/// * `bytes` - The time code; the ASCII time codes are the ASCII strings.
+/// * `implicit_pfield` - If it is specified, `bytes` is only the T-field and it is decoded with this P-field.
+///   Otherwise, `bytes` begins with the P-field. The ASCII time codes ignore it.
+/// * `agency_epoch` - The agency-defined epoch; it is required if the P-field says so.
+///
+/// # Returns
+/// Returns the datetime.
+///
+/// Returns [`Error`](crate::error::Error) if `bytes` is illegal.
+///
+/// # Examples
+/// ```
+/// use convdate::ccsds::{decode, TimeCode};
+/// use chrono::NaiveDate;
+///
+/// // CUC: 4 octets of seconds since 1958-01-01 and 2 octets of the fraction
+/// let cuc = [0x1E, 0x6E, 0xFA, 0xA5, 0x25, 0x80, 0x00];
+/// assert_eq!(
+///     decode(TimeCode::Cuc, &cuc, None, None),
+///     Ok(NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 37, 500)),
+/// );
+///
+/// // CDS: days since 1958-01-01 and milliseconds of the day; it can express the leap second.
+/// let cds = [0x40, 0x54, 0x2D, 0x05, 0x26, 0x5C, 0x00];
+/// assert_eq!(
+///     decode(TimeCode::Cds, &cds, None, None),
+///     Ok(NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1000)),
+/// );
+///
+/// assert_eq!(
+///     decode(TimeCode::AsciiB, b"2016-366T23:59:60.5Z", None, None),
+///     Ok(NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1500)),
+/// );
+/// ```
+pub fn decode(
+    code: TimeCode,
+    bytes: &[u8],
+    implicit_pfield: Option<&[u8]>,
+    agency_epoch: Option<&NaiveDateTime>,
+) -> Result<NaiveDateTime, Error> {
+    let parse_err = || Error::DatetimeParseError(describe(code, bytes));
+    if !code.is_binary() {
+        let text = std::str::from_utf8(bytes).map_err(|_| parse_err())?;
+        return ascii::decode(code, text).ok_or_else(parse_err);
+    }
+
+    let (pfield, tfield) = match implicit_pfield {
+        Some(pfield) => (pfield, bytes),
+        None => bytes.split_at(pfield_len(bytes).ok_or_else(parse_err)?.min(bytes.len())),
+    };
+    if TimeCode::of_pfield(pfield) != Some(code) || pfield_len(pfield) != Some(pfield.len()) {
+        Err(Error::CcsdsPFieldError(format_hex(pfield)))?;
+    }
+    let datetime = match code {
+        TimeCode::Cuc => cuc::decode(pfield, tfield, agency_epoch)?,
+        TimeCode::Cds => cds::decode(pfield, tfield, agency_epoch)?,
+        _ => ccs::decode(pfield, tfield)?,
+    };
+    datetime.ok_or_else(parse_err)
+}
+
+/// Encode the datetime into the CCSDS time code.
+///
+/// # Arguments
+/// * `code` - The kind of the time code
+/// * `datetime` - A datetime
+/// * `pfield` - The P-field which defines the layout of the T-field; the ASCII time codes ignore it.
+///   See [`TimeCode::default_pfield`] about the default.
+/// * `with_pfield` - Whether the result begins with `pfield` or not; the ASCII time codes ignore it.
+/// * `agency_epoch` - The agency-defined epoch; it is required if the P-field says so.
+///
+/// # Returns
+/// Returns the time code; the ASCII time codes are the ASCII strings.
+///
+/// Returns [`Error`](crate::error::Error) if the time code cannot express `datetime`.
+///
+/// # Examples
+/// ```
+/// use convdate::ccsds::{encode, TimeCode};
+/// use chrono::NaiveDate;
+///
+/// let datetime = NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 37, 500);
+///
+/// assert_eq!(
+///     encode(TimeCode::Cuc, &datetime, &[0x1E], true, None),
+///     Ok(vec![0x1E, 0x6E, 0xFA, 0xA5, 0x25, 0x80, 0x00]),
+/// );
+/// assert_eq!(
+///     encode(TimeCode::AsciiA, &datetime, &[], false, None),
+///     Ok(b"2017-01-01T00:00:37.5Z".to_vec()),
+/// );
+/// ```
+pub fn encode(
+    code: TimeCode,
+    datetime: &NaiveDateTime,
+    pfield: &[u8],
+    with_pfield: bool,
+    agency_epoch: Option<&NaiveDateTime>,
+) -> Result<Vec<u8>, Error> {
+    if !code.is_binary() {
+        return Ok(ascii::encode(code, datetime)?.into_bytes());
+    }
+
+    if TimeCode::of_pfield(pfield) != Some(code) || pfield_len(pfield) != Some(pfield.len()) {
+        Err(Error::CcsdsPFieldError(format_hex(pfield)))?;
+    }
+    let tfield = match code {
+        TimeCode::Cuc => cuc::encode(pfield, datetime, agency_epoch)?,
+        TimeCode::Cds => cds::encode(pfield, datetime, agency_epoch)?,
+        _ => ccs::encode(pfield, datetime)?,
+    };
+    let mut bytes = if with_pfield { pfield.to_vec() } else { vec![] };
+    bytes.extend(tfield);
+    Ok(bytes)
+}
+
+/// Parse the hexadecimal string into bytes.
+///
+/// Whitespaces between octets and the prefix `0x` are allowed.
+///
+/// # Examples
+/// ```
+/// use convdate::ccsds::parse_hex;
+///
+/// assert_eq!(parse_hex("0x1E 6EFAA525 8000"), Ok(vec![0x1E, 0x6E, 0xFA, 0xA5, 0x25, 0x80, 0x00]));
+/// ```
+pub fn parse_hex(value: &str) -> Result<Vec<u8>, Error> {
+    let parse_err = || Error::DatetimeParseError(value.to_string());
+    let trimmed = value.trim();
+    let digits: Vec<char> = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .unwrap_or(trimmed)
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    if digits.is_empty() || !digits.len().is_multiple_of(2) {
+        Err(parse_err())?;
+    }
+    digits
+        .chunks(2)
+        .map(|octet| {
+            let octet: String = octet.iter().collect();
+            u8::from_str_radix(&octet, 16).map_err(|_| parse_err())
+        })
+        .collect()
+}
+
+/// Format bytes as the hexadecimal string; for example, `1E6EFAA5258000`.
+pub fn format_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, octet| {
+        write!(hex, "{:02X}", octet).unwrap();
+        hex
+    })
+}
+
+/// The length of the P-field at the beginning of the time code.
+fn pfield_len(bytes: &[u8]) -> Option<usize> {
+    let first = bytes.first()?;
+    match (TimeCode::of_pfield(bytes)?, first & 0x80 != 0) {
+        // The second octet of CUC has no more extension.
+        (TimeCode::Cuc, true) => bytes.get(1).filter(|o| *o & 0x80 == 0).map(|_| 2),
+        (_, false) => Some(1),
+        _ => None,
+    }
+}
+
+/// The epoch which the P-field specifies.
+fn epoch(agency: bool, agency_epoch: Option<&NaiveDateTime>) -> Result<NaiveDateTime, Error> {
+    match (agency, agency_epoch) {
+        (false, _) => Ok(epoch_1958()),
+        (true, Some(agency_epoch)) => Ok(*agency_epoch),
+        (true, None) => Err(Error::CcsdsAgencyEpochNotSpecifiedError()),
+    }
+}
+
+/// Concatenate octets as a big-endian unsigned integer.
+fn read_uint(octets: &[u8]) -> u128 {
+    octets
+        .iter()
+        .fold(0, |value, octet| value << 8 | u128::from(*octet))
+}
+
+/// Split an unsigned integer into big-endian octets; it returns `None` if `len` octets cannot express it.
+fn write_uint(value: u128, len: usize) -> Option<Vec<u8>> {
+    if len < 16 && value >> (8 * len) != 0 {
+        return None;
+    }
+    Some((0..len).rev().map(|i| (value >> (8 * i)) as u8).collect())
+}
+
+/// Describe the time code in error messages; the binary time codes are written in hexadecimal.
+fn describe(code: TimeCode, bytes: &[u8]) -> String {
+    if code.is_binary() {
+        format_hex(bytes)
+    } else {
+        String::from_utf8_lossy(bytes).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testmod;
+    use chrono::NaiveDate;
+    use rstest::*;
+
+    #[rstest]
+    #[case("1E6EFAA5258000", Some(vec![0x1E, 0x6E, 0xFA, 0xA5, 0x25, 0x80, 0x00]), None)]
+    #[case(" 0x1e 6e ", Some(vec![0x1E, 0x6E]), None)]
+    #[case("1E6", None, Some(Error::DatetimeParseError(value.to_string())))]
+    #[case("1G", None, Some(Error::DatetimeParseError(value.to_string())))]
+    #[case("", None, Some(Error::DatetimeParseError(value.to_string())))]
+    fn test_parse_hex(
+        #[case] value: &str,
+        #[case] expected_ok: Option<Vec<u8>>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok, expected_err);
+
+        assert_eq!(parse_hex(value), expected);
+    }
+
+    #[rstest]
+    #[case(&[0x1E], Some(TimeCode::Cuc))]
+    #[case(&[0xAE, 0x40], Some(TimeCode::Cuc))]
+    #[case(&[0x40], Some(TimeCode::Cds))]
+    #[case(&[0x53], Some(TimeCode::Ccs))]
+    #[case(&[0x60], None)]
+    #[case(&[], None)]
+    fn test_of_pfield(#[case] pfield: &[u8], #[case] expected: Option<TimeCode>) {
+        assert_eq!(TimeCode::of_pfield(pfield), expected);
+    }
+
+    #[rstest]
+    // implicit P-field
+    #[case(TimeCode::Cuc, "6EFAA5258000", Some("1E"), None, Some(NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 37, 500)), None)]
+    // agency-defined epoch
+    #[case(TimeCode::Cuc, "2C00000025", None, Some(NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0)), Some(NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 37)), None)]
+    #[case(
+        TimeCode::Cuc,
+        "2C00000025",
+        None,
+        None,
+        None,
+        Some(Error::CcsdsAgencyEpochNotSpecifiedError())
+    )]
+    // the P-field of the other time code
+    #[case(TimeCode::Cuc, "40542D05265C00", None, None, None, Some(Error::CcsdsPFieldError("40".to_string())))]
+    #[case(TimeCode::Cds, "6EFAA5258000", Some("1E"), None, None, Some(Error::CcsdsPFieldError("1E".to_string())))]
+    // the length of the T-field does not match the P-field
+    #[case(TimeCode::Cuc, "1E6EFAA52580", None, None, None, Some(Error::DatetimeParseError("1E6EFAA52580".to_string())))]
+    #[case(TimeCode::Cuc, "", None, None, None, Some(Error::DatetimeParseError("".to_string())))]
+    fn test_decode(
+        #[case] code: TimeCode,
+        #[case] bytes: &str,
+        #[case] implicit_pfield: Option<&str>,
+        #[case] agency_epoch: Option<NaiveDateTime>,
+        #[case] expected_ok: Option<NaiveDateTime>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok, expected_err);
+        let bytes = if bytes.is_empty() {
+            vec![]
+        } else {
+            parse_hex(bytes).unwrap()
+        };
+        let implicit_pfield = implicit_pfield.map(|p| parse_hex(p).unwrap());
+
+        let result = decode(
+            code,
+            &bytes,
+            implicit_pfield.as_deref(),
+            agency_epoch.as_ref(),
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case(TimeCode::Cuc, "1E", false, None, Some("6EFAA5258000"), None)]
+    #[case(TimeCode::Cds, "40", true, None, Some("40542E0000927C"), None)]
+    #[case(TimeCode::Ccs, "53", true, None, Some("5320170101000037500000"), None)]
+    #[case(
+        TimeCode::Cuc,
+        "2E",
+        true,
+        None,
+        None,
+        Some(Error::CcsdsAgencyEpochNotSpecifiedError())
+    )]
+    #[case(TimeCode::Cuc, "40", true, None, None, Some(Error::CcsdsPFieldError("40".to_string())))]
+    #[case(TimeCode::Cuc, "9E", true, None, None, Some(Error::CcsdsPFieldError("9E".to_string())))]
+    fn test_encode(
+        #[case] code: TimeCode,
+        #[case] pfield: &str,
+        #[case] with_pfield: bool,
+        #[case] agency_epoch: Option<NaiveDateTime>,
+        #[case] expected_ok: Option<&str>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok.map(ToString::to_string), expected_err);
+        let datetime = NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 37, 500);
+
+        let result = encode(
+            code,
+            &datetime,
+            &parse_hex(pfield).unwrap(),
+            with_pfield,
+            agency_epoch.as_ref(),
+        );
+
+        assert_eq!(result.map(|bytes| format_hex(&bytes)), expected);
+    }
+}
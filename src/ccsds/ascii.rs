@@ -0,0 +1,127 @@
+use super::TimeCode;
+use crate::error::Error;
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+/// Decode the ASCII time code; it returns `None` if the text is illegal.
+pub(super) fn decode(code: TimeCode, text: &str) -> Option<NaiveDateTime> {
+    let text = text.trim();
+    let text = text.strip_suffix('Z').unwrap_or(text);
+    let (date, time) = text.split_once('T')?;
+
+    let date = match (code, date.split('-').collect::<Vec<_>>().as_slice()) {
+        (TimeCode::AsciiA, [year, month, day]) => {
+            NaiveDate::from_ymd_opt(number(year, 4)? as i32, number(month, 2)?, number(day, 2)?)?
+        }
+        (TimeCode::AsciiB, [year, day]) => {
+            NaiveDate::from_yo_opt(number(year, 4)? as i32, number(day, 3)?)?
+        }
+        _ => return None,
+    };
+
+    let (time, fraction) = match time.split_once('.') {
+        Some((time, fraction)) => (time, Some(fraction)),
+        None => (time, None),
+    };
+    let nanos = match fraction {
+        // ナノ秒より細かい桁は切り捨てる
+        Some(fraction) if !fraction.is_empty() && fraction.bytes().all(|c| c.is_ascii_digit()) => {
+            format!("{:0<9.9}", fraction).parse().unwrap()
+        }
+        Some(_) => return None,
+        None => 0,
+    };
+    let time = match time.split(':').collect::<Vec<_>>().as_slice() {
+        [hour, minute, second] => {
+            let (hour, minute, second) = (number(hour, 2)?, number(minute, 2)?, number(second, 2)?);
+            if second == 60 {
+                NaiveTime::from_hms_nano_opt(hour, minute, 59, 1_000_000_000 + nanos)?
+            } else {
+                NaiveTime::from_hms_nano_opt(hour, minute, second, nanos)?
+            }
+        }
+        _ => return None,
+    };
+
+    Some(date.and_time(time))
+}
+
+/// Encode the datetime into the ASCII time code.
+///
+/// The fraction of a second is written only if it is needed.
+pub(super) fn encode(code: TimeCode, datetime: &NaiveDateTime) -> Result<String, Error> {
+    if !(0..=9999).contains(&datetime.year()) {
+        Err(Error::CcsdsOutOfRangeError(datetime.to_string()))?;
+    }
+    let date_fmt = match code {
+        TimeCode::AsciiB => "%Y-%j",
+        _ => "%Y-%m-%d",
+    };
+    let nanos = datetime.nanosecond() % 1_000_000_000;
+    let fraction = if nanos == 0 {
+        String::new()
+    } else {
+        format!(".{}", format!("{:09}", nanos).trim_end_matches('0'))
+    };
+
+    // chrono writes the leap second as 60.
+    Ok(format!(
+        "{}{}Z",
+        datetime.format(&format!("{}T%H:%M:%S", date_fmt)),
+        fraction
+    ))
+}
+
+/// Parse the number which has exactly `len` digits.
+fn number(text: &str, len: usize) -> Option<u32> {
+    if text.len() != len || !text.bytes().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testmod;
+    use rstest::*;
+
+    #[rstest]
+    #[case(TimeCode::AsciiA, "2017-01-01T00:00:37.5Z", Some(NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 37, 500)))]
+    #[case(TimeCode::AsciiA, "2017-01-01T00:00:37", Some(NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 37)))]
+    #[case(TimeCode::AsciiA, "2016-12-31T23:59:60.1234567891Z", Some(NaiveDate::from_ymd(2016, 12, 31).and_hms_nano(23, 59, 59, 1_123_456_789)))]
+    #[case(TimeCode::AsciiB, "2017-001T00:00:37.5Z", Some(NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 37, 500)))]
+    #[case(TimeCode::AsciiB, "2016-366T23:59:60Z", Some(NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1000)))]
+    // the notation of the other type
+    #[case(TimeCode::AsciiA, "2017-001T00:00:37Z", None)]
+    #[case(TimeCode::AsciiB, "2017-01-01T00:00:37Z", None)]
+    // illegal notations
+    #[case(TimeCode::AsciiA, "2017-1-1T00:00:37Z", None)]
+    #[case(TimeCode::AsciiA, "2017-01-01 00:00:37Z", None)]
+    #[case(TimeCode::AsciiA, "2017-01-01T00:00:37.Z", None)]
+    #[case(TimeCode::AsciiA, "2017-01-01T00:00:61Z", None)]
+    #[case(TimeCode::AsciiB, "2017-366T00:00:00Z", None)]
+    fn test_decode(
+        #[case] code: TimeCode,
+        #[case] text: &str,
+        #[case] expected: Option<NaiveDateTime>,
+    ) {
+        assert_eq!(decode(code, text), expected);
+    }
+
+    #[rstest]
+    #[case(TimeCode::AsciiA, NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 37, 500), Some("2017-01-01T00:00:37.5Z"), None)]
+    #[case(TimeCode::AsciiA, NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 37), Some("2017-01-01T00:00:37Z"), None)]
+    #[case(TimeCode::AsciiA, NaiveDate::from_ymd(2016, 12, 31).and_hms_nano(23, 59, 59, 1_000_000_001), Some("2016-12-31T23:59:60.000000001Z"), None)]
+    #[case(TimeCode::AsciiB, NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1000), Some("2016-366T23:59:60Z"), None)]
+    #[case(TimeCode::AsciiB, NaiveDate::from_ymd(10000, 1, 1).and_hms(0, 0, 0), None, Some(Error::CcsdsOutOfRangeError("+10000-01-01 00:00:00".to_string())))]
+    fn test_encode(
+        #[case] code: TimeCode,
+        #[case] datetime: NaiveDateTime,
+        #[case] expected_ok: Option<&str>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok.map(ToString::to_string), expected_err);
+
+        assert_eq!(encode(code, &datetime), expected);
+    }
+}
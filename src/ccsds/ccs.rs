@@ -0,0 +1,173 @@
+use super::format_hex;
+use crate::error::Error;
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+/// The number of octets of the T-field except the sub-second
+const CALENDAR_LEN: usize = 7;
+
+/// The layout of the T-field of CCS which the P-field defines
+struct Layout {
+    /// Whether the date is expressed by the day of year or the month and day
+    day_of_year: bool,
+    /// The number of octets of the sub-second; each octet has 2 decimal digits.
+    subsecond: usize,
+}
+
+impl Layout {
+    fn of(pfield: &[u8]) -> Result<Layout, Error> {
+        let subsecond = usize::from(pfield[0] & 0b111);
+        if subsecond == 0b111 {
+            Err(Error::CcsdsPFieldError(format_hex(pfield)))?;
+        }
+        Ok(Layout {
+            day_of_year: pfield[0] & 0b1000 != 0,
+            subsecond,
+        })
+    }
+}
+
+/// Decode the T-field of CCS; it returns `Ok(None)` if the T-field is illegal.
+pub(super) fn decode(pfield: &[u8], tfield: &[u8]) -> Result<Option<NaiveDateTime>, Error> {
+    let layout = Layout::of(pfield)?;
+    if tfield.len() != CALENDAR_LEN + layout.subsecond {
+        return Ok(None);
+    }
+    let digits = match tfield
+        .iter()
+        .map(|o| from_bcd(*o))
+        .collect::<Option<Vec<_>>>()
+    {
+        Some(digits) => digits,
+        None => return Ok(None),
+    };
+
+    let year = (digits[0] * 100 + digits[1]) as i32;
+    let date = if layout.day_of_year {
+        NaiveDate::from_yo_opt(year, digits[2] * 100 + digits[3])
+    } else {
+        NaiveDate::from_ymd_opt(year, digits[2], digits[3])
+    };
+
+    // サブ秒はナノ秒 (9 桁) までを使い、それ以降は切り捨てる
+    let subsecond: String = digits[CALENDAR_LEN..]
+        .iter()
+        .map(|d| format!("{:02}", d))
+        .collect();
+    let nanos: u32 = format!("{:0<9.9}", subsecond).parse().unwrap();
+    let (hour, minute, second) = (digits[4], digits[5], digits[6]);
+    let time = if second == 60 {
+        NaiveTime::from_hms_nano_opt(hour, minute, 59, 1_000_000_000 + nanos)
+    } else {
+        NaiveTime::from_hms_nano_opt(hour, minute, second, nanos)
+    };
+
+    Ok(date.zip(time).map(|(date, time)| date.and_time(time)))
+}
+
+/// Encode the datetime into the T-field of CCS.
+pub(super) fn encode(pfield: &[u8], datetime: &NaiveDateTime) -> Result<Vec<u8>, Error> {
+    let layout = Layout::of(pfield)?;
+    let year = datetime.year();
+    if !(0..=9999).contains(&year) {
+        Err(Error::CcsdsOutOfRangeError(datetime.to_string()))?;
+    }
+    let year = year as u32;
+    let (days_high, days_low) = if layout.day_of_year {
+        (datetime.ordinal() / 100, datetime.ordinal() % 100)
+    } else {
+        (datetime.month(), datetime.day())
+    };
+    // The nanosecond is over 1e9 in the leap second.
+    let second = datetime.second() + datetime.nanosecond() / 1_000_000_000;
+    let nanos = datetime.nanosecond() % 1_000_000_000;
+
+    let mut tfield: Vec<u8> = [
+        year / 100,
+        year % 100,
+        days_high,
+        days_low,
+        datetime.hour(),
+        datetime.minute(),
+        second,
+    ]
+    .iter()
+    .map(|d| to_bcd(*d))
+    .collect();
+    // ナノ秒より細かい桁は 0 とする
+    let subsecond = format!("{:0<18}", format!("{:09}", nanos));
+    tfield.extend(
+        subsecond.as_bytes()[..layout.subsecond * 2]
+            .chunks(2)
+            .map(|d| (d[0] - b'0') << 4 | (d[1] - b'0')),
+    );
+    Ok(tfield)
+}
+
+/// Read the octet as 2 digits of BCD.
+fn from_bcd(octet: u8) -> Option<u32> {
+    let (high, low) = (octet >> 4, octet & 0x0F);
+    if high > 9 || low > 9 {
+        return None;
+    }
+    Some(u32::from(high * 10 + low))
+}
+
+/// Write the number less than 100 as 2 digits of BCD.
+fn to_bcd(value: u32) -> u8 {
+    ((value / 10) << 4 | (value % 10)) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ccsds::parse_hex;
+    use crate::testmod;
+    use rstest::*;
+
+    #[rstest]
+    #[case("53", "20170101000037500000", Some(NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 37, 500)), None)]
+    #[case("50", "20161231235960", Some(NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1000)), None)]
+    #[case("58", "20160366235960", Some(NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1000)), None)]
+    #[case("55", "201701010000371234567891", Some(NaiveDate::from_ymd(2017, 1, 1).and_hms_nano(0, 0, 37, 123_456_789)), None)]
+    // illegal fields
+    #[case("50", "20170230000000", None, None)]
+    #[case("58", "20170366000000", None, None)]
+    #[case("50", "20170101000061", None, None)]
+    #[case("50", "2017010100003A", None, None)]
+    // the length of the T-field does not match the P-field
+    #[case("53", "20170101000037", None, None)]
+    // reserved
+    #[case("57", "20170101000037", None, Some(Error::CcsdsPFieldError("57".to_string())))]
+    fn test_decode(
+        #[case] pfield: &str,
+        #[case] tfield: &str,
+        #[case] expected_ok: Option<NaiveDateTime>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        // None of both means the illegal T-field.
+        let expected = expected_err.map_or(Ok(expected_ok), Err);
+
+        let result = decode(&parse_hex(pfield).unwrap(), &parse_hex(tfield).unwrap());
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("53", NaiveDate::from_ymd(2017, 1, 1).and_hms_nano(0, 0, 37, 500_999_999), Some("20170101000037500999"), None)]
+    #[case("50", NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1500), Some("20161231235960"), None)]
+    #[case("59", NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1500), Some("2016036623596050"), None)]
+    #[case("56", NaiveDate::from_ymd(2017, 1, 1).and_hms_nano(0, 0, 37, 123_456_789), Some("20170101000037123456789000"), None)]
+    #[case("50", NaiveDate::from_ymd(10000, 1, 1).and_hms(0, 0, 0), None, Some(Error::CcsdsOutOfRangeError("+10000-01-01 00:00:00".to_string())))]
+    fn test_encode(
+        #[case] pfield: &str,
+        #[case] datetime: NaiveDateTime,
+        #[case] expected_ok: Option<&str>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok.map(ToString::to_string), expected_err);
+
+        let result = encode(&parse_hex(pfield).unwrap(), &datetime);
+
+        assert_eq!(result.map(|tfield| format_hex(&tfield)), expected);
+    }
+}
@@ -0,0 +1,182 @@
+use super::{epoch, format_hex, read_uint, write_uint};
+use crate::error::Error;
+use chrono::{Duration, NaiveDateTime, NaiveTime, Timelike};
+use std::convert::TryFrom;
+
+/// Milliseconds of a day without leap seconds
+const MILLIS_PER_DAY: u128 = 86_400_000;
+
+/// The layout of the T-field of CDS which the P-field defines
+struct Layout {
+    /// Whether the epoch is agency-defined or 1958-01-01
+    agency_epoch: bool,
+    /// The number of octets of days
+    day: usize,
+    /// The number of octets of the sub-millisecond; 0, 2 (microseconds) or 4 (picoseconds)
+    submillis: usize,
+}
+
+impl Layout {
+    fn of(pfield: &[u8]) -> Result<Layout, Error> {
+        let submillis = match pfield[0] & 0b11 {
+            0b00 => 0,
+            0b01 => 2,
+            0b10 => 4,
+            _ => Err(Error::CcsdsPFieldError(format_hex(pfield)))?,
+        };
+        Ok(Layout {
+            agency_epoch: pfield[0] & 0b1000 != 0,
+            day: if pfield[0] & 0b100 != 0 { 3 } else { 2 },
+            submillis,
+        })
+    }
+
+    /// The exclusive upper limit of the sub-millisecond
+    fn submillis_max(&self) -> u128 {
+        match self.submillis {
+            0 => 1,
+            2 => 1_000,
+            _ => 1_000_000_000,
+        }
+    }
+}
+
+/// Decode the T-field of CDS; it returns `Ok(None)` if the T-field is illegal.
+pub(super) fn decode(
+    pfield: &[u8],
+    tfield: &[u8],
+    agency_epoch: Option<&NaiveDateTime>,
+) -> Result<Option<NaiveDateTime>, Error> {
+    let layout = Layout::of(pfield)?;
+    let epoch = epoch(layout.agency_epoch, agency_epoch)?;
+    if tfield.len() != layout.day + 4 + layout.submillis {
+        return Ok(None);
+    }
+
+    let days = read_uint(&tfield[..layout.day]);
+    let millis = read_uint(&tfield[layout.day..layout.day + 4]);
+    let submillis = read_uint(&tfield[layout.day + 4..]);
+    // The milliseconds of the day reach 86400999 only in the leap second.
+    if millis >= MILLIS_PER_DAY + 1000 || submillis >= layout.submillis_max() {
+        return Ok(None);
+    }
+    let submillis_nanos = match layout.submillis {
+        0 => 0,
+        2 => submillis * 1000,
+        // ナノ秒未満は切り捨てる
+        _ => submillis / 1000,
+    };
+
+    let date = match epoch.date().checked_add_signed(Duration::days(days as i64)) {
+        Some(date) => date,
+        None => return Ok(None),
+    };
+    let time = if millis >= MILLIS_PER_DAY {
+        let nanos = (millis - MILLIS_PER_DAY + 1000) * 1_000_000 + submillis_nanos;
+        NaiveTime::from_hms_nano_opt(23, 59, 59, nanos as u32)
+    } else {
+        NaiveTime::from_num_seconds_from_midnight_opt(
+            (millis / 1000) as u32,
+            ((millis % 1000) * 1_000_000 + submillis_nanos) as u32,
+        )
+    };
+    Ok(time.map(|time| date.and_time(time)))
+}
+
+/// Encode the datetime into the T-field of CDS.
+pub(super) fn encode(
+    pfield: &[u8],
+    datetime: &NaiveDateTime,
+    agency_epoch: Option<&NaiveDateTime>,
+) -> Result<Vec<u8>, Error> {
+    let out_of_range = || Error::CcsdsOutOfRangeError(datetime.to_string());
+    let layout = Layout::of(pfield)?;
+    let epoch = epoch(layout.agency_epoch, agency_epoch)?;
+
+    let days =
+        u128::try_from((datetime.date() - epoch.date()).num_days()).map_err(|_| out_of_range())?;
+    // The nanosecond is over 1e9 in the leap second, so the milliseconds reach 86400999.
+    let nanos = u128::from(datetime.nanosecond());
+    let millis = u128::from(datetime.num_seconds_from_midnight()) * 1000 + nanos / 1_000_000;
+    let submillis = match layout.submillis {
+        0 => 0,
+        2 => nanos % 1_000_000 / 1000,
+        _ => nanos % 1_000_000 * 1000,
+    };
+
+    let mut tfield = write_uint(days, layout.day).ok_or_else(out_of_range)?;
+    tfield.extend(write_uint(millis, 4).unwrap());
+    tfield.extend(write_uint(submillis, layout.submillis).unwrap());
+    Ok(tfield)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ccsds::parse_hex;
+    use crate::testmod;
+    use chrono::NaiveDate;
+    use rstest::*;
+
+    #[rstest]
+    #[case("40", "542E0000927C", Some(NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 37, 500)), None)]
+    #[case("40", "542D05265C00", Some(NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1000)), None)]
+    #[case("40", "542D05265FE7", Some(NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1999)), None)]
+    #[case("41", "542E0000927C03E7", Some(NaiveDate::from_ymd(2017, 1, 1).and_hms_micro(0, 0, 37, 500_999)), None)]
+    #[case("42", "542E0000927C3B9AC9FF", Some(NaiveDate::from_ymd(2017, 1, 1).and_hms_nano(0, 0, 37, 500_999_999)), None)]
+    #[case("44", "00542E0000927C", Some(NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 37, 500)), None)]
+    // too large milliseconds or sub-milliseconds
+    #[case("40", "542D05265FE8", None, None)]
+    #[case("41", "542E0000927C03E8", None, None)]
+    #[case("42", "542E0000927C3B9ACA00", None, None)]
+    // the length of the T-field does not match the P-field
+    #[case("41", "542E0000927C", None, None)]
+    // reserved
+    #[case("43", "542E0000927C", None, Some(Error::CcsdsPFieldError("43".to_string())))]
+    fn test_decode(
+        #[case] pfield: &str,
+        #[case] tfield: &str,
+        #[case] expected_ok: Option<NaiveDateTime>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        // None of both means the illegal T-field.
+        let expected = expected_err.map_or(Ok(expected_ok), Err);
+
+        let result = decode(
+            &parse_hex(pfield).unwrap(),
+            &parse_hex(tfield).unwrap(),
+            None,
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("40", NaiveDate::from_ymd(2017, 1, 1).and_hms_micro(0, 0, 37, 500_999), None, Some("542E0000927C"), None)]
+    #[case("40", NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1999), None, Some("542D05265FE7"), None)]
+    #[case("41", NaiveDate::from_ymd(2017, 1, 1).and_hms_nano(0, 0, 37, 500_999_999), None, Some("542E0000927C03E7"), None)]
+    #[case("42", NaiveDate::from_ymd(2017, 1, 1).and_hms_nano(0, 0, 37, 500_999_999), None, Some("542E0000927C3B9AC618"), None)]
+    #[case("48", NaiveDate::from_ymd(2017, 1, 2).and_hms(0, 0, 0), Some(NaiveDate::from_ymd(2017, 1, 1).and_hms(12, 0, 0)), Some("000100000000"), None)]
+    #[case("40", NaiveDate::from_ymd(1957, 12, 31).and_hms(23, 59, 59), None, None, Some(Error::CcsdsOutOfRangeError("1957-12-31 23:59:59".to_string())))]
+    // 16 bits of days expresses until 2137-06-06.
+    #[case("40", NaiveDate::from_ymd(2137, 6, 6).and_hms(0, 0, 0), None, Some("FFFF00000000"), None)]
+    #[case("40", NaiveDate::from_ymd(2137, 6, 7).and_hms(0, 0, 0), None, None, Some(Error::CcsdsOutOfRangeError("2137-06-07 00:00:00".to_string())))]
+    #[case("44", NaiveDate::from_ymd(2137, 6, 7).and_hms(0, 0, 0), None, Some("01000000000000"), None)]
+    fn test_encode(
+        #[case] pfield: &str,
+        #[case] datetime: NaiveDateTime,
+        #[case] agency_epoch: Option<NaiveDateTime>,
+        #[case] expected_ok: Option<&str>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok.map(ToString::to_string), expected_err);
+
+        let result = encode(
+            &parse_hex(pfield).unwrap(),
+            &datetime,
+            agency_epoch.as_ref(),
+        );
+
+        assert_eq!(result.map(|tfield| format_hex(&tfield)), expected);
+    }
+}
@@ -0,0 +1,148 @@
+use super::{epoch, read_uint, write_uint};
+use crate::epoch::reject_leap_second;
+use crate::error::Error;
+use chrono::{Duration, NaiveDateTime};
+use std::convert::TryFrom;
+
+/// The upper limit of seconds which chrono can add to the epoch
+const MAX_SECONDS: u128 = 10_000_000_000_000;
+
+/// The layout of the T-field of CUC which the P-field defines
+struct Layout {
+    /// Whether the epoch is agency-defined or 1958-01-01
+    agency_epoch: bool,
+    /// The number of octets of seconds
+    coarse: usize,
+    /// The number of octets of the fraction of a second
+    fine: usize,
+}
+
+impl Layout {
+    fn of(pfield: &[u8]) -> Layout {
+        let mut coarse = usize::from(pfield[0] >> 2 & 0b11) + 1;
+        let mut fine = usize::from(pfield[0] & 0b11);
+        if let Some(extension) = pfield.get(1) {
+            coarse += usize::from(extension >> 5 & 0b11);
+            fine += usize::from(extension >> 2 & 0b111);
+        }
+        Layout {
+            agency_epoch: pfield[0] >> 4 & 0b111 == 0b010,
+            coarse,
+            fine,
+        }
+    }
+}
+
+/// Decode the T-field of CUC; it returns `Ok(None)` if the T-field is illegal.
+pub(super) fn decode(
+    pfield: &[u8],
+    tfield: &[u8],
+    agency_epoch: Option<&NaiveDateTime>,
+) -> Result<Option<NaiveDateTime>, Error> {
+    let layout = Layout::of(pfield);
+    let epoch = epoch(layout.agency_epoch, agency_epoch)?;
+    if tfield.len() != layout.coarse + layout.fine {
+        return Ok(None);
+    }
+
+    let seconds = read_uint(&tfield[..layout.coarse]);
+    if seconds > MAX_SECONDS {
+        return Ok(None);
+    }
+    // 最も近いナノ秒に丸める
+    let denominator = 1_u128 << (8 * layout.fine);
+    let nanos =
+        (read_uint(&tfield[layout.coarse..]) * 2_000_000_000 + denominator) / (denominator * 2);
+    Ok(epoch.checked_add_signed(
+        Duration::seconds(seconds as i64) + Duration::nanoseconds(nanos as i64),
+    ))
+}
+
+/// Encode the datetime into the T-field of CUC.
+pub(super) fn encode(
+    pfield: &[u8],
+    datetime: &NaiveDateTime,
+    agency_epoch: Option<&NaiveDateTime>,
+) -> Result<Vec<u8>, Error> {
+    let out_of_range = || Error::CcsdsOutOfRangeError(datetime.to_string());
+    let layout = Layout::of(pfield);
+    let epoch = epoch(layout.agency_epoch, agency_epoch)?;
+    reject_leap_second(datetime)?;
+
+    let elapsed = *datetime - epoch;
+    let seconds = u128::try_from(elapsed.num_seconds()).map_err(|_| out_of_range())?;
+    let nanos = (elapsed - Duration::seconds(elapsed.num_seconds()))
+        .num_nanoseconds()
+        .unwrap() as u128;
+    // The fraction is truncated.
+    let fine = (nanos << (8 * layout.fine)) / 1_000_000_000;
+
+    let mut tfield = write_uint(seconds, layout.coarse).ok_or_else(out_of_range)?;
+    tfield.extend(write_uint(fine, layout.fine).unwrap());
+    Ok(tfield)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ccsds::{format_hex, parse_hex};
+    use crate::testmod;
+    use chrono::NaiveDate;
+    use rstest::*;
+
+    #[rstest]
+    #[case("1E", "6EFAA5258000", Some(NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 37, 500)))]
+    #[case("1C", "6EFAA525", Some(NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 37)))]
+    #[case("10", "25", Some(NaiveDate::from_ymd(1958, 1, 1).and_hms(0, 0, 37)))]
+    // 1 octet of the fraction is 1/256 seconds.
+    #[case("11", "2501", Some(NaiveDate::from_ymd(1958, 1, 1).and_hms_nano(0, 0, 37, 3_906_250)))]
+    // The extension of the P-field: 4 + 3 octets of seconds and 3 + 3 octets of the fraction
+    #[case("9F6C", "00000000000025FFFFFFFFFFFF", Some(NaiveDate::from_ymd(1958, 1, 1).and_hms(0, 0, 38)))]
+    #[case("9F6C", "00000000000025800000000000", Some(NaiveDate::from_ymd(1958, 1, 1).and_hms_milli(0, 0, 37, 500)))]
+    // the length of the T-field does not match the P-field
+    #[case("1E", "6EFAA52580", None)]
+    #[case("1E", "6EFAA525800000", None)]
+    // too large
+    #[case("9C60", "FFFFFFFFFFFFFF", None)]
+    fn test_decode(
+        #[case] pfield: &str,
+        #[case] tfield: &str,
+        #[case] expected: Option<NaiveDateTime>,
+    ) {
+        let result = decode(
+            &parse_hex(pfield).unwrap(),
+            &parse_hex(tfield).unwrap(),
+            None,
+        );
+
+        assert_eq!(result, Ok(expected));
+    }
+
+    #[rstest]
+    #[case("1E", NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 37, 500), None, Some("6EFAA5258000"), None)]
+    #[case("1C", NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 37, 999), None, Some("6EFAA525"), None)]
+    #[case("2D", NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 1, 0, 250), Some(NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0)), Some("0000003C40"), None)]
+    #[case("9F6C", NaiveDate::from_ymd(1958, 1, 1).and_hms_nano(0, 0, 37, 1), None, Some("00000000000025000000044B82"), None)]
+    // 1 octet of seconds expresses until 255 seconds.
+    #[case("10", NaiveDate::from_ymd(1958, 1, 1).and_hms(0, 4, 15), None, Some("FF"), None)]
+    #[case("10", NaiveDate::from_ymd(1958, 1, 1).and_hms(0, 4, 16), None, None, Some(Error::CcsdsOutOfRangeError("1958-01-01 00:04:16".to_string())))]
+    #[case("1E", NaiveDate::from_ymd(1957, 12, 31).and_hms(23, 59, 59), None, None, Some(Error::CcsdsOutOfRangeError("1957-12-31 23:59:59".to_string())))]
+    #[case("1E", NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1000), None, None, Some(Error::LeapSecondNotRepresentableError("2016-12-31 23:59:60".to_string())))]
+    fn test_encode(
+        #[case] pfield: &str,
+        #[case] datetime: NaiveDateTime,
+        #[case] agency_epoch: Option<NaiveDateTime>,
+        #[case] expected_ok: Option<&str>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok.map(ToString::to_string), expected_err);
+
+        let result = encode(
+            &parse_hex(pfield).unwrap(),
+            &datetime,
+            agency_epoch.as_ref(),
+        );
+
+        assert_eq!(result.map(|tfield| format_hex(&tfield)), expected);
+    }
+}
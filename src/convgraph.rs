@@ -0,0 +1,1121 @@
+//! Conversion between arbitrary time scales.
+//!
+//! The conversion is represented as a graph whose nodes are [`TimeScale`]s and whose edges are
+//! single conversion steps such as [`utc2tai_dt`](crate::utc2tai::utc2tai_dt).
+//! A conversion between two time scales is done by following the shortest route on the graph.
+use crate::convtbl::{TaiUtcTable, Ut1UtcTable, UtcTaiTable};
+use crate::deltat::{tt2ut_dt, ut2tt_dt, DeltaTModel};
+use crate::error::Error;
+use crate::gnss::{
+    bdt2tai_dt, glonass2utc_dt, gst2tai_dt, qzss2tai_dt, tai2bdt_dt, tai2gst_dt, tai2qzss_dt,
+    utc2glonass_dt,
+};
+use crate::gps::{gps2tai_dt, tai2gps_dt};
+use crate::scale::TimeScale;
+use crate::smear::{smear2tai_dt, tai2smear_dt, Smear};
+use crate::tai2utc::tai2utc_dt;
+use crate::tcb::{tcb2tdb_dt, tdb2tcb_dt};
+use crate::tcg::{tcg2tt_dt, tt2tcg_dt};
+use crate::tdb::{tdb2tt_dt, tt2tdb_dt, TdbModel};
+use crate::tt::{tai2tt_dt, tt2tai_dt};
+use crate::ut1::{tai2ut1_dt, ut12tai_dt};
+use crate::utc2tai::utc2tai_dt;
+use crate::DtFmts;
+use chrono::NaiveDateTime;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+
+/// Policies for UTC datetimes beyond the expiration date of the TAI-UTC table
+///
+/// After the expiration date, the table cannot know about new leap seconds; see [`TaiUtcTable::expiration`].
+/// If the expiration date is unknown, every policy works as [`BeyondTable::AssumeConstant`].
+///
+/// # Examples
+/// ```
+/// use convdate::convgraph::BeyondTable;
+///
+/// let policy: BeyondTable = "error".parse().unwrap();
+/// assert_eq!(policy, BeyondTable::Error);
+/// assert_eq!(BeyondTable::default(), BeyondTable::AssumeConstant);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BeyondTable {
+    /// The conversion fails with [`DatetimeTooHighError`](crate::error::Error::DatetimeTooHighError).
+    Error,
+    /// The conversion assumes no new leap seconds, and the caller should warn about it.
+    Warn,
+    /// The conversion assumes no new leap seconds; TAI - UTC of the last row is used.
+    #[default]
+    AssumeConstant,
+}
+
+impl BeyondTable {
+    /// All policies which convdate supports
+    pub const ALL: [BeyondTable; 3] = [
+        BeyondTable::Error,
+        BeyondTable::Warn,
+        BeyondTable::AssumeConstant,
+    ];
+
+    /// The names of [`BeyondTable::ALL`] in the same order
+    pub const NAMES: [&'static str; 3] = ["error", "warn", "assume-constant"];
+
+    /// The name of the policy used in command arguments; for example, `"assume-constant"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            BeyondTable::Error => "error",
+            BeyondTable::Warn => "warn",
+            BeyondTable::AssumeConstant => "assume-constant",
+        }
+    }
+}
+
+impl FromStr for BeyondTable {
+    type Err = Error;
+
+    /// Parse the name of a policy. The case of letters is ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BeyondTable::ALL
+            .iter()
+            .find(|policy| policy.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| Error::UnknownBeyondTablePolicyError(s.to_string()))
+    }
+}
+
+impl fmt::Display for BeyondTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Tables and models which the conversion steps refer to
+///
+/// # Examples
+/// ```
+/// use convdate::convgraph::ConvTables;
+/// use convdate::convtbl::TaiUtcTable;
+///
+/// let tai_utc_table = TaiUtcTable::from_lines(vec!["2017-01-01T00:00:00 37"], "%Y-%m-%dT%H:%M:%S").unwrap();
+/// let tables = ConvTables::new(tai_utc_table);
+/// assert_eq!(tables.tai_utc_table().len(), 1);
+/// ```
+///
+/// The model of TDB is [`TdbModel::FairheadBretagnonTruncated`] unless it is specified:
+/// ```
+/// use convdate::convgraph::ConvTables;
+/// use convdate::convtbl::TaiUtcTable;
+/// use convdate::TdbModel;
+///
+/// let tai_utc_table = TaiUtcTable::from_lines(vec!["2017-01-01T00:00:00 37"], "%Y-%m-%dT%H:%M:%S").unwrap();
+/// let tables = ConvTables::new(tai_utc_table).with_tdb_model(TdbModel::Simplified);
+/// assert_eq!(tables.tdb_model(), TdbModel::Simplified);
+/// ```
+#[derive(Debug)]
+pub struct ConvTables {
+    tai_utc_table: TaiUtcTable,
+    utc_tai_table: UtcTaiTable,
+    tdb_model: TdbModel,
+    ut1_utc_table: Option<Ut1UtcTable>,
+    delta_t: Option<DeltaTModel>,
+    beyond_table: BeyondTable,
+    smear: Smear,
+}
+
+impl ConvTables {
+    /// Construct `ConvTables` from the TAI-UTC table.
+    ///
+    /// The UTC-TAI table is derived from `tai_utc_table`.
+    pub fn new(tai_utc_table: TaiUtcTable) -> ConvTables {
+        let utc_tai_table = UtcTaiTable::from(&tai_utc_table);
+        ConvTables {
+            tai_utc_table,
+            utc_tai_table,
+            tdb_model: TdbModel::default(),
+            ut1_utc_table: None,
+            delta_t: None,
+            beyond_table: BeyondTable::default(),
+            smear: Smear::default(),
+        }
+    }
+
+    /// Set the model of TDB - TT used by the conversion between TT and TDB.
+    pub fn with_tdb_model(self, tdb_model: TdbModel) -> ConvTables {
+        ConvTables { tdb_model, ..self }
+    }
+
+    /// Set the UT1-UTC table used by the conversion between TAI and UT1.
+    ///
+    /// Without it, the conversion from/to UT1 fails.
+    pub fn with_ut1_utc_table(self, ut1_utc_table: Ut1UtcTable) -> ConvTables {
+        ConvTables {
+            ut1_utc_table: Some(ut1_utc_table),
+            ..self
+        }
+    }
+
+    /// Set the model of ΔT used when the TAI-UTC table or the UT1-UTC table does not cover the datetime.
+    ///
+    /// Without it, such conversion fails; see [`convert_dt_marked`].
+    pub fn with_delta_t(self, delta_t: DeltaTModel) -> ConvTables {
+        ConvTables {
+            delta_t: Some(delta_t),
+            ..self
+        }
+    }
+
+    /// Set the policy for UTC datetimes beyond the expiration date of the TAI-UTC table.
+    pub fn with_beyond_table(self, beyond_table: BeyondTable) -> ConvTables {
+        ConvTables {
+            beyond_table,
+            ..self
+        }
+    }
+
+    /// Set the shape of smearing used by the conversion between TAI and smeared UTC.
+    ///
+    /// Without it, [`Smear::google`] is used.
+    pub fn with_smear(self, smear: Smear) -> ConvTables {
+        ConvTables { smear, ..self }
+    }
+
+    pub fn tai_utc_table(&self) -> &TaiUtcTable {
+        &self.tai_utc_table
+    }
+
+    pub fn utc_tai_table(&self) -> &UtcTaiTable {
+        &self.utc_tai_table
+    }
+
+    pub fn tdb_model(&self) -> TdbModel {
+        self.tdb_model
+    }
+
+    pub fn ut1_utc_table(&self) -> Option<&Ut1UtcTable> {
+        self.ut1_utc_table.as_ref()
+    }
+
+    pub fn delta_t(&self) -> Option<&DeltaTModel> {
+        self.delta_t.as_ref()
+    }
+
+    pub fn beyond_table(&self) -> BeyondTable {
+        self.beyond_table
+    }
+
+    pub fn smear(&self) -> &Smear {
+        &self.smear
+    }
+
+    /// Check the UTC datetime against the expiration date of the TAI-UTC table under the policy.
+    fn check_beyond_table(&self, utc: &NaiveDateTime) -> Result<(), Error> {
+        if self.beyond_table == BeyondTable::Error && self.tai_utc_table.is_expired_at(utc) {
+            Err(Error::DatetimeTooHighError(utc.to_string()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl From<TaiUtcTable> for ConvTables {
+    fn from(tai_utc_table: TaiUtcTable) -> Self {
+        ConvTables::new(tai_utc_table)
+    }
+}
+
+/// A single conversion step; an edge of the conversion graph.
+type Step = fn(&NaiveDateTime, &ConvTables) -> Result<NaiveDateTime, Error>;
+
+fn step_utc2tai(datetime: &NaiveDateTime, tables: &ConvTables) -> Result<NaiveDateTime, Error> {
+    tables.check_beyond_table(datetime)?;
+    utc2tai_dt(datetime, tables.tai_utc_table())
+}
+
+fn step_tai2utc(datetime: &NaiveDateTime, tables: &ConvTables) -> Result<NaiveDateTime, Error> {
+    let utc = tai2utc_dt(datetime, tables.utc_tai_table())?;
+    tables.check_beyond_table(&utc)?;
+    Ok(utc)
+}
+
+fn step_smear2tai(datetime: &NaiveDateTime, tables: &ConvTables) -> Result<NaiveDateTime, Error> {
+    tables.check_beyond_table(datetime)?;
+    smear2tai_dt(datetime, tables.tai_utc_table(), tables.smear())
+}
+
+fn step_tai2smear(datetime: &NaiveDateTime, tables: &ConvTables) -> Result<NaiveDateTime, Error> {
+    let smeared = tai2smear_dt(
+        datetime,
+        tables.tai_utc_table(),
+        tables.utc_tai_table(),
+        tables.smear(),
+    )?;
+    tables.check_beyond_table(&smeared)?;
+    Ok(smeared)
+}
+
+fn step_tai2tt(datetime: &NaiveDateTime, _: &ConvTables) -> Result<NaiveDateTime, Error> {
+    Ok(tai2tt_dt(datetime))
+}
+
+fn step_tt2tai(datetime: &NaiveDateTime, _: &ConvTables) -> Result<NaiveDateTime, Error> {
+    Ok(tt2tai_dt(datetime))
+}
+
+fn step_tai2gps(datetime: &NaiveDateTime, _: &ConvTables) -> Result<NaiveDateTime, Error> {
+    Ok(tai2gps_dt(datetime))
+}
+
+fn step_gps2tai(datetime: &NaiveDateTime, _: &ConvTables) -> Result<NaiveDateTime, Error> {
+    Ok(gps2tai_dt(datetime))
+}
+
+fn step_tai2gst(datetime: &NaiveDateTime, _: &ConvTables) -> Result<NaiveDateTime, Error> {
+    Ok(tai2gst_dt(datetime))
+}
+
+fn step_gst2tai(datetime: &NaiveDateTime, _: &ConvTables) -> Result<NaiveDateTime, Error> {
+    Ok(gst2tai_dt(datetime))
+}
+
+fn step_tai2bdt(datetime: &NaiveDateTime, _: &ConvTables) -> Result<NaiveDateTime, Error> {
+    Ok(tai2bdt_dt(datetime))
+}
+
+fn step_bdt2tai(datetime: &NaiveDateTime, _: &ConvTables) -> Result<NaiveDateTime, Error> {
+    Ok(bdt2tai_dt(datetime))
+}
+
+fn step_utc2glonass(datetime: &NaiveDateTime, _: &ConvTables) -> Result<NaiveDateTime, Error> {
+    Ok(utc2glonass_dt(datetime))
+}
+
+fn step_glonass2utc(datetime: &NaiveDateTime, _: &ConvTables) -> Result<NaiveDateTime, Error> {
+    Ok(glonass2utc_dt(datetime))
+}
+
+fn step_tai2qzss(datetime: &NaiveDateTime, _: &ConvTables) -> Result<NaiveDateTime, Error> {
+    Ok(tai2qzss_dt(datetime))
+}
+
+fn step_qzss2tai(datetime: &NaiveDateTime, _: &ConvTables) -> Result<NaiveDateTime, Error> {
+    Ok(qzss2tai_dt(datetime))
+}
+
+fn step_tt2tcg(datetime: &NaiveDateTime, _: &ConvTables) -> Result<NaiveDateTime, Error> {
+    Ok(tt2tcg_dt(datetime))
+}
+
+fn step_tcg2tt(datetime: &NaiveDateTime, _: &ConvTables) -> Result<NaiveDateTime, Error> {
+    Ok(tcg2tt_dt(datetime))
+}
+
+fn step_tt2tdb(datetime: &NaiveDateTime, tables: &ConvTables) -> Result<NaiveDateTime, Error> {
+    Ok(tt2tdb_dt(datetime, tables.tdb_model()))
+}
+
+fn step_tdb2tt(datetime: &NaiveDateTime, tables: &ConvTables) -> Result<NaiveDateTime, Error> {
+    Ok(tdb2tt_dt(datetime, tables.tdb_model()))
+}
+
+fn step_tdb2tcb(datetime: &NaiveDateTime, _: &ConvTables) -> Result<NaiveDateTime, Error> {
+    Ok(tdb2tcb_dt(datetime))
+}
+
+fn step_tai2ut1(datetime: &NaiveDateTime, tables: &ConvTables) -> Result<NaiveDateTime, Error> {
+    let ut1_utc_table = tables
+        .ut1_utc_table()
+        .ok_or(Error::EopTableNotSpecifiedError())?;
+    tai2ut1_dt(datetime, tables.tai_utc_table(), ut1_utc_table)
+}
+
+fn step_ut12tai(datetime: &NaiveDateTime, tables: &ConvTables) -> Result<NaiveDateTime, Error> {
+    let ut1_utc_table = tables
+        .ut1_utc_table()
+        .ok_or(Error::EopTableNotSpecifiedError())?;
+    ut12tai_dt(datetime, tables.tai_utc_table(), ut1_utc_table)
+}
+
+fn step_tcb2tdb(datetime: &NaiveDateTime, _: &ConvTables) -> Result<NaiveDateTime, Error> {
+    Ok(tcb2tdb_dt(datetime))
+}
+
+/// The edges of the conversion graph
+const STEPS: &[(TimeScale, TimeScale, Step)] = &[
+    (TimeScale::Utc, TimeScale::Tai, step_utc2tai),
+    (TimeScale::Tai, TimeScale::Utc, step_tai2utc),
+    (TimeScale::Tai, TimeScale::Tt, step_tai2tt),
+    (TimeScale::Tt, TimeScale::Tai, step_tt2tai),
+    (TimeScale::Tai, TimeScale::Gps, step_tai2gps),
+    (TimeScale::Gps, TimeScale::Tai, step_gps2tai),
+    (TimeScale::Tai, TimeScale::Gst, step_tai2gst),
+    (TimeScale::Gst, TimeScale::Tai, step_gst2tai),
+    (TimeScale::Tai, TimeScale::Bdt, step_tai2bdt),
+    (TimeScale::Bdt, TimeScale::Tai, step_bdt2tai),
+    (TimeScale::Utc, TimeScale::Glonass, step_utc2glonass),
+    (TimeScale::Glonass, TimeScale::Utc, step_glonass2utc),
+    (TimeScale::Tai, TimeScale::Qzss, step_tai2qzss),
+    (TimeScale::Qzss, TimeScale::Tai, step_qzss2tai),
+    (TimeScale::Tt, TimeScale::Tcg, step_tt2tcg),
+    (TimeScale::Tcg, TimeScale::Tt, step_tcg2tt),
+    (TimeScale::Tt, TimeScale::Tdb, step_tt2tdb),
+    (TimeScale::Tdb, TimeScale::Tt, step_tdb2tt),
+    (TimeScale::Tdb, TimeScale::Tcb, step_tdb2tcb),
+    (TimeScale::Tcb, TimeScale::Tdb, step_tcb2tdb),
+    (TimeScale::Tai, TimeScale::Ut1, step_tai2ut1),
+    (TimeScale::Ut1, TimeScale::Tai, step_ut12tai),
+    (TimeScale::SmearedUtc, TimeScale::Tai, step_smear2tai),
+    (TimeScale::Tai, TimeScale::SmearedUtc, step_tai2smear),
+];
+
+/// A conversion step with ΔT
+type FallbackStep = fn(&NaiveDateTime, &DeltaTModel, &ConvTables) -> Result<NaiveDateTime, Error>;
+
+/// The conversion steps with ΔT, used instead of the edges between UT (UTC or UT1) and TAI
+/// when the tables do not cover the datetime.
+///
+/// UTC before the TAI-UTC table is regarded as UT.
+/// Since ΔT of the model differs from TT - UTC of the table at its start, the model may convert a datetime before the table
+/// to a datetime after the start; it is an error because the table converts the latter differently.
+fn find_fallback_step(from: TimeScale, to: TimeScale) -> Option<FallbackStep> {
+    match (from, to) {
+        (TimeScale::Utc, TimeScale::Tai) => Some(|datetime, model, tables| {
+            let tai = tt2tai_dt(&ut2tt_dt(datetime, model)?);
+            match tai_utc_table_start(tables) {
+                Some((utc_start, tai_start)) if *datetime < utc_start && tai >= tai_start => {
+                    Err(Error::DatetimeTooLowError(datetime.to_string()))
+                }
+                _ => Ok(tai),
+            }
+        }),
+        (TimeScale::Tai, TimeScale::Utc) => Some(|datetime, model, tables| {
+            let utc = tt2ut_dt(&tai2tt_dt(datetime), model)?;
+            match tai_utc_table_start(tables) {
+                Some((utc_start, tai_start)) if *datetime < tai_start && utc >= utc_start => {
+                    Err(Error::DatetimeTooLowError(datetime.to_string()))
+                }
+                _ => Ok(utc),
+            }
+        }),
+        (TimeScale::Ut1, TimeScale::Tai) => {
+            Some(|datetime, model, _| Ok(tt2tai_dt(&ut2tt_dt(datetime, model)?)))
+        }
+        (TimeScale::Tai, TimeScale::Ut1) => {
+            Some(|datetime, model, _| tt2ut_dt(&tai2tt_dt(datetime), model))
+        }
+        _ => None,
+    }
+}
+
+/// The start of the TAI-UTC table in UTC and in TAI
+fn tai_utc_table_start(tables: &ConvTables) -> Option<(NaiveDateTime, NaiveDateTime)> {
+    let utc_start = tables.tai_utc_table().first()?.datetime;
+    let tai_start = tables.utc_tai_table().first()?.datetime;
+    Some((utc_start, tai_start))
+}
+
+fn find_step(from: TimeScale, to: TimeScale) -> Option<Step> {
+    STEPS
+        .iter()
+        .find(|(f, t, _)| *f == from && *t == to)
+        .map(|(_, _, step)| *step)
+}
+
+/// Find the route to convert datetimes from `from` to `to`.
+///
+/// # Arguments
+/// * `from` - The time scale of input datetimes
+/// * `to` - The time scale of output datetimes
+///
+/// # Returns
+/// Returns the time scales passed through in order, including `from` and `to`.
+///
+/// Returns [`Error`](crate::error::Error) if there is no route.
+///
+/// # Examples
+/// ```
+/// use convdate::convgraph::route;
+/// use convdate::TimeScale;
+///
+/// assert_eq!(
+///     route(TimeScale::Utc, TimeScale::Tt),
+///     Ok(vec![TimeScale::Utc, TimeScale::Tai, TimeScale::Tt])
+/// );
+/// ```
+pub fn route(from: TimeScale, to: TimeScale) -> Result<Vec<TimeScale>, Error> {
+    // 幅優先探索
+    let mut prev: HashMap<TimeScale, TimeScale> = HashMap::new();
+    let mut queue = VecDeque::from([from]);
+    while let Some(scale) = queue.pop_front() {
+        if scale == to {
+            let mut route = vec![to];
+            while let Some(p) = prev.get(route.last().unwrap()) {
+                route.push(*p);
+            }
+            route.reverse();
+            return Ok(route);
+        }
+        for (_, next, _) in STEPS.iter().filter(|(f, _, _)| *f == scale) {
+            if *next != from && !prev.contains_key(next) {
+                prev.insert(*next, scale);
+                queue.push_back(*next);
+            }
+        }
+    }
+    Err(Error::ConversionRouteNotFoundError(
+        from.to_string(),
+        to.to_string(),
+    ))
+}
+
+/// Convert datetime from the time scale `from` to the time scale `to`.
+///
+/// # Arguments
+/// * `datetime` - Datetime in `from`.
+/// * `from` - The time scale of `datetime`
+/// * `to` - The time scale of the result
+/// * `tables` - The tables used by the conversion
+///
+/// # Returns
+/// Returns the datetime in `to`.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # See also
+/// * [`convert`] - It is same as `convert_dt`, except that the argument and the result are [`str`] and [`String`].
+pub fn convert_dt(
+    datetime: &NaiveDateTime,
+    from: TimeScale,
+    to: TimeScale,
+    tables: &ConvTables,
+) -> Result<NaiveDateTime, Error> {
+    convert_dt_marked(datetime, from, to, tables).map(|(result, _)| result)
+}
+
+/// Convert datetime from the time scale `from` to the time scale `to`, with ΔT if necessary.
+///
+/// If the model of ΔT is set by [`ConvTables::with_delta_t`], it is used
+/// instead of the TAI-UTC table and the UT1-UTC table when they do not cover the datetime.
+/// Under [`BeyondTable::Error`], the TAI-UTC table does not cover the datetimes after its expiration date.
+///
+/// # Arguments
+/// * `datetime` - Datetime in `from`.
+/// * `from` - The time scale of `datetime`
+/// * `to` - The time scale of the result
+/// * `tables` - The tables used by the conversion
+///
+/// # Returns
+/// Returns the datetime in `to` and whether it is derived from the model of ΔT.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # Examples
+/// ```
+/// use convdate::convgraph::{convert_dt_marked, ConvTables};
+/// use convdate::convtbl::TaiUtcTable;
+/// use convdate::deltat::DeltaTModel;
+/// use convdate::TimeScale;
+/// use chrono::NaiveDate;
+///
+/// let tai_utc_table = TaiUtcTable::from_lines(vec!["2017-01-01T00:00:00 37"], "%Y-%m-%dT%H:%M:%S").unwrap();
+/// let tables = ConvTables::new(tai_utc_table).with_delta_t(DeltaTModel::EspenakMeeus);
+///
+/// let (tt, marked) = convert_dt_marked(
+///     &NaiveDate::from_ymd(1900, 1, 1).and_hms(0, 0, 0),
+///     TimeScale::Utc,
+///     TimeScale::Tt,
+///     &tables,
+/// ).unwrap();
+///
+/// assert_eq!(tt, NaiveDate::from_ymd(1899, 12, 31).and_hms_milli(23, 59, 57, 210));
+/// assert!(marked);
+/// ```
+pub fn convert_dt_marked(
+    datetime: &NaiveDateTime,
+    from: TimeScale,
+    to: TimeScale,
+    tables: &ConvTables,
+) -> Result<(NaiveDateTime, bool), Error> {
+    let route = route(from, to)?;
+    let mut result = *datetime;
+    let mut marked = false;
+    for pair in route.windows(2) {
+        let step = find_step(pair[0], pair[1]).unwrap();
+        let fallback = tables.delta_t().zip(find_fallback_step(pair[0], pair[1]));
+        let step_result = match (step(&result, tables), fallback) {
+            (
+                Err(Error::DatetimeTooLowError(_))
+                | Err(Error::DatetimeTooHighError(_))
+                | Err(Error::EopOutOfCoverageError(_))
+                | Err(Error::EopTableNotSpecifiedError()),
+                Some((model, fallback_step)),
+            ) => {
+                marked = true;
+                fallback_step(&result, model, tables).map_err(|e| match e {
+                    Error::DatetimeTooLowError(_) => e,
+                    _ => Error::DeltaTOutOfCoverageError(datetime.to_string()),
+                })
+            }
+            (other, _) => other,
+        };
+        result = match step_result {
+            Err(Error::DatetimeTooLowError(_)) => {
+                // 多段階で変換を行う場合、中間の日時文字列がエラーメッセージに使われている場合があるため、入力された日時文字列に置き換える。
+                Err(Error::DatetimeTooLowError(datetime.to_string()))
+            }
+            Err(Error::DatetimeTooHighError(_)) => {
+                Err(Error::DatetimeTooHighError(datetime.to_string()))
+            }
+            Err(Error::EopOutOfCoverageError(_)) => {
+                Err(Error::EopOutOfCoverageError(datetime.to_string()))
+            }
+            other => other,
+        }?;
+    }
+    Ok((result, marked))
+}
+
+/// Convert datetime from the time scale `from` to the time scale `to`.
+///
+/// # Arguments
+/// * `datetime` - Datetime in `from`.
+/// * `from` - The time scale of `datetime`
+/// * `to` - The time scale of the result
+/// * `tables` - The tables used by the conversion
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
+///
+/// # Returns
+/// Returns the datetime in `to`.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # Examples
+/// ```
+/// use convdate::convgraph::{convert, ConvTables};
+/// use convdate::convtbl::TaiUtcTable;
+/// use convdate::TimeScale;
+///
+/// // Usually, lines read from the file are used as the argument of `from_lines`.
+/// let tai_utc_table = TaiUtcTable::from_lines(vec!["2017-01-01T00:00:00 37"], "%Y-%m-%dT%H:%M:%S").unwrap();
+/// let tables = ConvTables::new(tai_utc_table);
+///
+/// let tt = convert(
+///     "2017-01-01T12:00:00.000",
+///     TimeScale::Utc,
+///     TimeScale::Tt,
+///     &tables,
+///     "%Y-%m-%dT%H:%M:%S%.3f");
+///
+/// assert_eq!(tt, Ok("2017-01-01T12:01:09.184".to_string()));
+/// ```
+///
+/// # See also
+/// * [`convdate`](../../convdate/index.html) (Binary crate) - The executable program which do same conversion.
+pub fn convert<'a>(
+    datetime: &str,
+    from: TimeScale,
+    to: TimeScale,
+    tables: &ConvTables,
+    dt_fmt: impl Into<DtFmts<'a>>,
+) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
+        .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
+    let result = convert_dt(&datetime, from, to, tables)?;
+    Ok(result.format(dt_fmt.format).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convtbl::{DeltaTTable, DiffTaiUtc};
+    use crate::testmod;
+    use chrono::{NaiveDate, SubsecRound};
+    use rstest::*;
+
+    const DT_FMT: &str = "%Y-%m-%dT%H:%M:%S%.3f";
+
+    #[rstest]
+    #[case(TimeScale::Utc, TimeScale::Utc, vec![TimeScale::Utc])]
+    #[case(TimeScale::Utc, TimeScale::Tai, vec![TimeScale::Utc, TimeScale::Tai])]
+    #[case(TimeScale::Utc, TimeScale::Tt, vec![TimeScale::Utc, TimeScale::Tai, TimeScale::Tt])]
+    #[case(TimeScale::Tai, TimeScale::Utc, vec![TimeScale::Tai, TimeScale::Utc])]
+    #[case(TimeScale::Tai, TimeScale::Tt, vec![TimeScale::Tai, TimeScale::Tt])]
+    #[case(TimeScale::Tt, TimeScale::Utc, vec![TimeScale::Tt, TimeScale::Tai, TimeScale::Utc])]
+    #[case(TimeScale::Tt, TimeScale::Tai, vec![TimeScale::Tt, TimeScale::Tai])]
+    #[case(TimeScale::Utc, TimeScale::Gps, vec![TimeScale::Utc, TimeScale::Tai, TimeScale::Gps])]
+    #[case(TimeScale::Gps, TimeScale::Tt, vec![TimeScale::Gps, TimeScale::Tai, TimeScale::Tt])]
+    #[case(TimeScale::Bdt, TimeScale::Gst, vec![TimeScale::Bdt, TimeScale::Tai, TimeScale::Gst])]
+    #[case(TimeScale::Utc, TimeScale::Glonass, vec![TimeScale::Utc, TimeScale::Glonass])]
+    #[case(TimeScale::Glonass, TimeScale::Qzss, vec![TimeScale::Glonass, TimeScale::Utc, TimeScale::Tai, TimeScale::Qzss])]
+    #[case(TimeScale::Tcb, TimeScale::Tcg, vec![TimeScale::Tcb, TimeScale::Tdb, TimeScale::Tt, TimeScale::Tcg])]
+    #[case(TimeScale::SmearedUtc, TimeScale::Utc, vec![TimeScale::SmearedUtc, TimeScale::Tai, TimeScale::Utc])]
+    fn test_route(
+        #[case] from: TimeScale,
+        #[case] to: TimeScale,
+        #[case] expected: Vec<TimeScale>,
+    ) {
+        assert_eq!(route(from, to), Ok(expected));
+    }
+
+    #[rstest]
+    // regular cases
+    #[case(
+        TimeScale::Utc,
+        TimeScale::Tai,
+        "2017-01-02T11:22:33.000",
+        Some("2017-01-02T11:23:10.000"),
+        None
+    )]
+    #[case(
+        TimeScale::Utc,
+        TimeScale::Tt,
+        "2017-01-02T11:22:33.000",
+        Some("2017-01-02T11:23:42.184"),
+        None
+    )]
+    #[case(
+        TimeScale::Tai,
+        TimeScale::Utc,
+        "2017-01-02T11:23:10.000",
+        Some("2017-01-02T11:22:33.000"),
+        None
+    )]
+    #[case(
+        TimeScale::Tai,
+        TimeScale::Tt,
+        "2017-01-02T11:23:10.000",
+        Some("2017-01-02T11:23:42.184"),
+        None
+    )]
+    #[case(
+        TimeScale::Tt,
+        TimeScale::Utc,
+        "2017-01-02T11:23:42.184",
+        Some("2017-01-02T11:22:33.000"),
+        None
+    )]
+    #[case(
+        TimeScale::Tt,
+        TimeScale::Tai,
+        "2017-01-02T11:23:42.184",
+        Some("2017-01-02T11:23:10.000"),
+        None
+    )]
+    #[case(
+        TimeScale::Utc,
+        TimeScale::Gps,
+        "2017-01-02T11:22:33.000",
+        Some("2017-01-02T11:22:51.000"),
+        None
+    )]
+    #[case(
+        TimeScale::Gps,
+        TimeScale::Tt,
+        "2017-01-02T11:22:51.000",
+        Some("2017-01-02T11:23:42.184"),
+        None
+    )]
+    #[case(
+        TimeScale::Tai,
+        TimeScale::Tai,
+        "2017-01-02T11:23:10.000",
+        Some("2017-01-02T11:23:10.000"),
+        None
+    )]
+    #[case(
+        TimeScale::Utc,
+        TimeScale::Gst,
+        "2017-01-02T11:22:33.000",
+        Some("2017-01-02T11:22:51.000"),
+        None
+    )]
+    #[case(
+        TimeScale::Utc,
+        TimeScale::Bdt,
+        "2017-01-02T11:22:33.000",
+        Some("2017-01-02T11:22:37.000"),
+        None
+    )]
+    #[case(
+        TimeScale::Bdt,
+        TimeScale::Qzss,
+        "2017-01-02T11:22:37.000",
+        Some("2017-01-02T11:22:51.000"),
+        None
+    )]
+    #[case(
+        TimeScale::Glonass,
+        TimeScale::Tai,
+        "2017-01-02T14:22:33.000",
+        Some("2017-01-02T11:23:10.000"),
+        None
+    )]
+    // うるう秒が挿入される瞬間のテスト
+    #[case(
+        TimeScale::Utc,
+        TimeScale::Tt,
+        "2016-12-31T23:59:60.123",
+        Some("2017-01-01T00:01:08.307"),
+        None
+    )]
+    #[case(
+        TimeScale::Tt,
+        TimeScale::Utc,
+        "2017-01-01T00:01:08.307",
+        Some("2016-12-31T23:59:60.123"),
+        None
+    )]
+    #[case(
+        TimeScale::Utc,
+        TimeScale::Glonass,
+        "2016-12-31T23:59:60.123",
+        Some("2017-01-01T02:59:60.123"),
+        None
+    )]
+    #[case(
+        TimeScale::Glonass,
+        TimeScale::Tt,
+        "2017-01-01T02:59:60.123",
+        Some("2017-01-01T00:01:08.307"),
+        None
+    )]
+    #[case(
+        TimeScale::Utc,
+        TimeScale::Tcg,
+        "2016-12-31T23:59:60.123",
+        Some("2017-01-01T00:01:09.186"),
+        None
+    )]
+    #[case(
+        TimeScale::Tcb,
+        TimeScale::Utc,
+        "2017-01-01T00:01:27.880",
+        Some("2016-12-31T23:59:60.123"),
+        None
+    )]
+    // The error message contains the input datetime, not the intermediate one.
+    #[case(TimeScale::Glonass, TimeScale::Bdt, "2015-07-01T02:59:59.000", None, Some(Error::DatetimeTooLowError("2015-07-01 02:59:59".to_string())))]
+    #[case(TimeScale::Tt, TimeScale::Utc, "2015-07-01T00:01:08.183", None, Some(Error::DatetimeTooLowError("2015-07-01 00:01:08.183".to_string())))]
+    #[case(TimeScale::Utc, TimeScale::Tt, "2015-06-30T23:59:59.000", None, Some(Error::DatetimeTooLowError("2015-06-30 23:59:59".to_string())))]
+    // Error when the input datetime is illegal format.
+    #[case(TimeScale::Tai, TimeScale::Tt, "2019-12-31 23:59:57.000", None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    fn test_convert(
+        #[case] from: TimeScale,
+        #[case] to: TimeScale,
+        #[case] datetime: &str,
+        #[case] expected_ok: Option<&str>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok.map(ToString::to_string), expected_err);
+
+        let tai_utc_table: TaiUtcTable = vec![
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 0),
+                diff_seconds: 36,
+                drift: None,
+            },
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0),
+                diff_seconds: 37,
+                drift: None,
+            },
+        ]
+        .into();
+        let tables = ConvTables::new(tai_utc_table);
+        let result = convert(datetime, from, to, &tables, DT_FMT);
+
+        assert_eq!(result, expected);
+    }
+
+    /// Test separate formats to parse and to format.
+    #[rstest]
+    #[case(
+        TimeScale::Utc,
+        TimeScale::Tai,
+        "20161231235960",
+        "%Y%m%d%H%M%S",
+        DT_FMT,
+        Some("2017-01-01T00:00:36.000"),
+        None
+    )]
+    #[case(
+        TimeScale::Tai,
+        TimeScale::Utc,
+        "2017-01-01T00:00:36.500",
+        DT_FMT,
+        "%Y%m%d%H%M%S%.3f",
+        Some("20161231235960.500"),
+        None
+    )]
+    #[case(TimeScale::Tai, TimeScale::Tt, "2017/01/01 00:00:00", DT_FMT, "%Y/%m/%d %H:%M:%S", None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    fn test_convert_dt_fmts(
+        #[case] from: TimeScale,
+        #[case] to: TimeScale,
+        #[case] datetime: &str,
+        #[case] parse_dt_fmt: &str,
+        #[case] format_dt_fmt: &str,
+        #[case] expected_ok: Option<&str>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok.map(ToString::to_string), expected_err);
+
+        let tai_utc_table: TaiUtcTable = vec![
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 0),
+                diff_seconds: 36,
+                drift: None,
+            },
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0),
+                diff_seconds: 37,
+                drift: None,
+            },
+        ]
+        .into();
+        let tables = ConvTables::new(tai_utc_table);
+        let result = convert(datetime, from, to, &tables, (parse_dt_fmt, format_dt_fmt));
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case(
+        TimeScale::Utc,
+        TimeScale::Ut1,
+        "2016-12-31T12:00:00.000",
+        Some("2016-12-31T11:59:59.592"),
+        None
+    )]
+    #[case(
+        TimeScale::Utc,
+        TimeScale::Ut1,
+        "2016-12-31T23:59:60.500",
+        Some("2017-01-01T00:00:00.092"),
+        None
+    )]
+    #[case(
+        TimeScale::Ut1,
+        TimeScale::Utc,
+        "2017-01-01T00:00:00.593",
+        Some("2017-01-01T00:00:00.000"),
+        None
+    )]
+    #[case(
+        TimeScale::Tt,
+        TimeScale::Ut1,
+        "2017-01-01T00:01:09.184",
+        Some("2017-01-01T00:00:00.592"),
+        None
+    )]
+    // The error message contains the input datetime, not the intermediate one.
+    #[case(TimeScale::Utc, TimeScale::Ut1, "2017-01-02T00:00:00.001", None, Some(Error::EopOutOfCoverageError("2017-01-02 00:00:00.001".to_string())))]
+    #[case(TimeScale::Ut1, TimeScale::Tt, "2016-12-29T23:59:59.000", None, Some(Error::EopOutOfCoverageError("2016-12-29 23:59:59".to_string())))]
+    fn test_convert_ut1(
+        #[case] from: TimeScale,
+        #[case] to: TimeScale,
+        #[case] datetime: &str,
+        #[case] expected_ok: Option<&str>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok.map(ToString::to_string), expected_err);
+
+        let tai_utc_table = TaiUtcTable::from_lines(
+            vec!["20150701000000 36", "20170101000000 37"],
+            "%Y%m%d%H%M%S",
+        )
+        .unwrap();
+        let ut1_utc_table = Ut1UtcTable::from_finals2000a(testmod::FINALS2000A).unwrap();
+        let tables = ConvTables::new(tai_utc_table).with_ut1_utc_table(ut1_utc_table);
+        let result = convert(datetime, from, to, &tables, DT_FMT);
+
+        assert_eq!(result, expected);
+    }
+
+    /// Tests that UT1 requires the UT1-UTC table.
+    #[test]
+    fn test_convert_ut1_without_table() {
+        let tai_utc_table =
+            TaiUtcTable::from_lines(vec!["20170101000000 37"], "%Y%m%d%H%M%S").unwrap();
+        let tables = ConvTables::new(tai_utc_table);
+
+        let result = convert(
+            "2017-01-01T12:00:00.000",
+            TimeScale::Utc,
+            TimeScale::Ut1,
+            &tables,
+            DT_FMT,
+        );
+
+        assert_eq!(result, Err(Error::EopTableNotSpecifiedError()));
+    }
+
+    #[rstest]
+    // The tables cover the datetime; ΔT is not used.
+    #[case(TimeScale::Utc, TimeScale::Tt, "2017-01-01T12:00:00.000", Some(("2017-01-01T12:01:09.184", false)), None)]
+    #[case(TimeScale::Utc, TimeScale::Ut1, "2016-12-31T12:00:00.000", Some(("2016-12-31T11:59:59.592", false)), None)]
+    // Before the TAI-UTC table, UTC is regarded as UT.
+    #[case(TimeScale::Utc, TimeScale::Tt, "1900-01-01T00:00:00.000", Some(("1899-12-31T23:59:57.210", true)), None)]
+    #[case(TimeScale::Tt, TimeScale::Utc, "1850-01-01T00:00:07.107", Some(("1850-01-01T00:00:00.000", true)), None)]
+    #[case(TimeScale::Utc, TimeScale::Ut1, "1850-01-01T00:00:00.000", Some(("1850-01-01T00:00:00.000", true)), None)]
+    // After the UT1-UTC table
+    #[case(TimeScale::Tt, TimeScale::Ut1, "2000-01-01T00:01:03.860", Some(("2000-01-01T00:00:00.000", true)), None)]
+    #[case(TimeScale::Ut1, TimeScale::Tt, "2000-01-01T00:00:00.000", Some(("2000-01-01T00:01:03.860", true)), None)]
+    fn test_convert_dt_marked(
+        #[case] from: TimeScale,
+        #[case] to: TimeScale,
+        #[case] datetime: &str,
+        #[case] expected_ok: Option<(&str, bool)>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(
+            expected_ok.map(|(dt, marked)| (dt.to_string(), marked)),
+            expected_err,
+        );
+
+        let tai_utc_table = TaiUtcTable::from_lines(
+            vec!["20150701000000 36", "20170101000000 37"],
+            "%Y%m%d%H%M%S",
+        )
+        .unwrap();
+        let ut1_utc_table = Ut1UtcTable::from_finals2000a(testmod::FINALS2000A).unwrap();
+        let tables = ConvTables::new(tai_utc_table)
+            .with_ut1_utc_table(ut1_utc_table)
+            .with_delta_t(DeltaTModel::EspenakMeeus);
+        let datetime = NaiveDateTime::parse_from_str(datetime, DT_FMT).unwrap();
+        let result = convert_dt_marked(&datetime, from, to, &tables)
+            .map(|(dt, marked)| (dt.round_subsecs(6).format(DT_FMT).to_string(), marked));
+
+        assert_eq!(result, expected);
+    }
+
+    /// Tests that the error of ΔT contains the input datetime.
+    #[test]
+    fn test_convert_dt_marked_out_of_delta_t_table() {
+        let tai_utc_table =
+            TaiUtcTable::from_lines(vec!["20170101000000 37"], "%Y%m%d%H%M%S").unwrap();
+        let delta_t_table =
+            DeltaTTable::from_lines(vec!["1800 1 1 13.7", "1800 1 2 13.9"]).unwrap();
+        let tables = ConvTables::new(tai_utc_table).with_delta_t(DeltaTModel::Table(delta_t_table));
+        let datetime = NaiveDate::from_ymd(1900, 1, 1).and_hms(0, 0, 0);
+
+        let result = convert_dt_marked(&datetime, TimeScale::Utc, TimeScale::Tt, &tables);
+
+        assert_eq!(
+            result,
+            Err(Error::DeltaTOutOfCoverageError(
+                "1900-01-01 00:00:00".to_string()
+            ))
+        );
+    }
+
+    /// Tests the round trip across the start of the TAI-UTC table, where the model of ΔT is switched to the table.
+    #[rstest]
+    #[case("1960-12-31T23:59:59.000")]
+    #[case("1960-12-31T23:59:59.999")]
+    #[case("1961-01-01T00:00:00.000")]
+    #[case("1961-01-01T00:00:00.026")]
+    #[case("1961-01-01T00:00:01.000")]
+    fn test_convert_dt_round_trip_at_tai_utc_table_start(#[case] utc: &str) {
+        let tai_utc_table = TaiUtcTable::from_tai_utc_dat(testmod::TAI_UTC_DAT).unwrap();
+        let tables = ConvTables::new(tai_utc_table).with_delta_t(DeltaTModel::EspenakMeeus);
+        let utc = NaiveDateTime::parse_from_str(utc, DT_FMT).unwrap();
+
+        let tt = convert_dt(&utc, TimeScale::Utc, TimeScale::Tt, &tables).unwrap();
+        let result = convert_dt(&tt, TimeScale::Tt, TimeScale::Utc, &tables).unwrap();
+
+        assert_eq!(result.round_subsecs(6), utc);
+    }
+
+    /// Tests that TT before the start of the TAI-UTC table is converted to UTC before the start with the model of ΔT,
+    /// and that TT which the model converts to UTC after the start is an error.
+    #[rstest]
+    #[case("1961-01-01T00:00:33.500", Some(true), None)]
+    #[case("1961-01-01T00:00:33.550", Some(true), None)]
+    #[case("1961-01-01T00:00:33.551", None, Some(Error::DatetimeTooLowError("1961-01-01 00:00:33.551".to_string())))]
+    #[case("1961-01-01T00:00:33.606", None, Some(Error::DatetimeTooLowError("1961-01-01 00:00:33.606".to_string())))]
+    #[case("1961-01-01T00:00:33.607", Some(false), None)]
+    fn test_convert_dt_marked_before_tai_utc_table_start(
+        #[case] tt: &str,
+        #[case] expected_marked: Option<bool>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let tai_utc_table = TaiUtcTable::from_tai_utc_dat(testmod::TAI_UTC_DAT).unwrap();
+        let tables = ConvTables::new(tai_utc_table).with_delta_t(DeltaTModel::EspenakMeeus);
+        let tt = NaiveDateTime::parse_from_str(tt, DT_FMT).unwrap();
+        let start = NaiveDate::from_ymd(1961, 1, 1).and_hms(0, 0, 0);
+
+        let result = convert_dt_marked(&tt, TimeScale::Tt, TimeScale::Utc, &tables)
+            .map(|(utc, marked)| (utc < start, marked));
+
+        let expected =
+            testmod::result(expected_marked.map(|marked| (marked, marked)), expected_err);
+        assert_eq!(result, expected);
+    }
+
+    /// Tests that UTC before the start of the TAI-UTC table is an error if the model of ΔT converts it to TT after the start.
+    #[test]
+    fn test_convert_dt_marked_overlapping_tai_utc_table_start() {
+        let tai_utc_table = TaiUtcTable::from_tai_utc_dat(testmod::TAI_UTC_DAT).unwrap();
+        let delta_t_table =
+            DeltaTTable::from_lines(vec!["1960 12 31 40.0", "1961 1 2 40.0"]).unwrap();
+        let tables = ConvTables::new(tai_utc_table).with_delta_t(DeltaTModel::Table(delta_t_table));
+
+        let utc = NaiveDate::from_ymd(1960, 12, 31).and_hms(23, 59, 59);
+        assert_eq!(
+            convert_dt_marked(&utc, TimeScale::Utc, TimeScale::Tt, &tables),
+            Err(Error::DatetimeTooLowError(
+                "1960-12-31 23:59:59".to_string()
+            ))
+        );
+        let utc = NaiveDate::from_ymd(1960, 12, 31).and_hms(0, 0, 0);
+        assert_eq!(
+            convert_dt_marked(&utc, TimeScale::Utc, TimeScale::Tt, &tables),
+            Ok((NaiveDate::from_ymd(1960, 12, 31).and_hms(0, 0, 40), true))
+        );
+    }
+
+    /// Tests the policies for the datetimes beyond the expiration date of the TAI-UTC table.
+    #[rstest]
+    #[case(BeyondTable::AssumeConstant, false, TimeScale::Utc, TimeScale::Tai, "2017-07-01T00:00:00.000", Some(("2017-07-01T00:00:37.000", false)), None)]
+    #[case(BeyondTable::Warn, false, TimeScale::Utc, TimeScale::Tai, "2017-07-01T00:00:00.000", Some(("2017-07-01T00:00:37.000", false)), None)]
+    #[case(BeyondTable::Error, false, TimeScale::Utc, TimeScale::Tai, "2017-06-30T23:59:59.999", Some(("2017-07-01T00:00:36.999", false)), None)]
+    #[case(BeyondTable::Error, false, TimeScale::Utc, TimeScale::Tt, "2017-07-01T00:00:00.000", None, Some(Error::DatetimeTooHighError("2017-07-01 00:00:00".to_string())))]
+    #[case(BeyondTable::Error, false, TimeScale::Tt, TimeScale::Utc, "2017-07-01T00:01:09.184", None, Some(Error::DatetimeTooHighError("2017-07-01 00:01:09.184".to_string())))]
+    #[case(BeyondTable::Error, false, TimeScale::Tai, TimeScale::Tt, "2100-01-01T00:00:00.000", Some(("2100-01-01T00:00:32.184", false)), None)]
+    // The model of ΔT is used beyond the table under the policy "error".
+    #[case(BeyondTable::Error, true, TimeScale::Utc, TimeScale::Tt, "2017-07-01T00:00:00.000", Some(("2017-07-01T00:01:10.267", true)), None)]
+    #[case(BeyondTable::AssumeConstant, true, TimeScale::Utc, TimeScale::Tt, "2017-07-01T00:00:00.000", Some(("2017-07-01T00:01:09.184", false)), None)]
+    fn test_convert_beyond_table(
+        #[case] beyond_table: BeyondTable,
+        #[case] with_delta_t: bool,
+        #[case] from: TimeScale,
+        #[case] to: TimeScale,
+        #[case] datetime: &str,
+        #[case] expected_ok: Option<(&str, bool)>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(
+            expected_ok.map(|(dt, marked)| (dt.to_string(), marked)),
+            expected_err,
+        );
+
+        let tai_utc_table = TaiUtcTable::from_lines(
+            vec!["20150701000000 36", "20170101000000 37"],
+            "%Y%m%d%H%M%S",
+        )
+        .unwrap()
+        .with_expiration(NaiveDate::from_ymd(2017, 7, 1).and_hms(0, 0, 0));
+        let tables = ConvTables::new(tai_utc_table).with_beyond_table(beyond_table);
+        let tables = if with_delta_t {
+            tables.with_delta_t(DeltaTModel::EspenakMeeus)
+        } else {
+            tables
+        };
+        let datetime = NaiveDateTime::parse_from_str(datetime, DT_FMT).unwrap();
+        let result = convert_dt_marked(&datetime, from, to, &tables)
+            .map(|(dt, marked)| (dt.round_subsecs(6).format(DT_FMT).to_string(), marked));
+
+        assert_eq!(result, expected);
+    }
+}
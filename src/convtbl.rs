@@ -1,5 +1,9 @@
 //! Provide tables for time system conversion.
+mod deltat;
 mod taiutc;
+mod ut1utc;
 mod utctai;
-pub use taiutc::{DiffTaiUtc, TaiUtcTable};
+pub use deltat::{DeltaTTable, DiffTtUt};
+pub use taiutc::{DiffTaiUtc, Drift, TaiUtcTable};
+pub use ut1utc::{DiffUt1Utc, Ut1UtcTable};
 pub use utctai::{DiffUtcTai, UtcTaiTable};
@@ -0,0 +1,233 @@
+use crate::error::Error;
+use crate::parse_decimal_nanos;
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use std::convert::TryFrom;
+
+/// Difference (TT - UT), that is, ΔT at a datetime
+///
+/// # See also
+/// - [`DeltaTTable`] - It express the ΔT table.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DiffTtUt {
+    /// The datetime at which the difference is given
+    pub datetime: NaiveDateTime,
+    /// The difference (TT - UT) in nanoseconds
+    pub diff_nanos: i64,
+}
+
+/// ΔT table
+///
+/// It expresses the values of ΔT (TT - UT) given by a user; ΔT between the rows is interpolated linearly.
+///
+/// # As Iterable Object
+///
+/// It behaves as an iterable object of row sorted by datetime.
+///
+/// # Creation new DeltaTTable
+///
+/// This object is created from lines; see [`DeltaTTable::from_lines`].
+#[derive(Debug)]
+pub struct DeltaTTable(Vec<DiffTtUt>);
+
+impl DeltaTTable {
+    /// Construct `DeltaTTable` from lines.
+    ///
+    /// Each line is one of below:
+    /// - `year delta_t`, where `year` is a decimal year such as `1850.5`
+    /// - `year month day delta_t`, as [`deltat.data` of USNO](https://maia.usno.navy.mil/ser7/deltat.data)
+    ///
+    /// `delta_t` is in seconds. Blank lines and lines beginning with `#` are ignored.
+    ///
+    /// # Arguments
+    /// - `lines` - a iterable of lines
+    ///
+    /// # Returns
+    /// Returns the `DeltaTTable` if `lines` are collect.
+    ///
+    /// Returns [`Error`](crate::error::Error) if some of `lines` are illegal or there are no rows.
+    /// The error of an illegal line contains the line number (1-based) of it.
+    ///
+    /// # Examples
+    /// ```
+    /// use convdate::convtbl::DeltaTTable;
+    /// use chrono::NaiveDate;
+    ///
+    /// let table = DeltaTTable::from_lines(vec![
+    ///     "# year delta_t",
+    ///     "1800.0 13.7",
+    ///     "1973  2  1  43.4724",
+    /// ]).unwrap();
+    /// assert_eq!(table[0].datetime, NaiveDate::from_ymd(1800, 1, 1).and_hms(0, 0, 0));
+    /// assert_eq!(table[1].datetime, NaiveDate::from_ymd(1973, 2, 1).and_hms(0, 0, 0));
+    /// assert_eq!(table[1].diff_nanos, 43_472_400_000);
+    /// ```
+    pub fn from_lines(
+        lines: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<DeltaTTable, Error> {
+        let mut diff_list = Vec::new();
+        for (i, line) in lines.into_iter().enumerate() {
+            let line = line.as_ref();
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+            let diff =
+                parse_line(line).map_err(|e| Error::DeltaTTableLineError(i + 1, Box::new(e)))?;
+            diff_list.push(diff);
+        }
+        if diff_list.is_empty() {
+            Err(Error::DeltaTTableParseError(
+                "no values of ΔT are found".to_string(),
+            ))?;
+        }
+        Ok(DeltaTTable::from(diff_list))
+    }
+
+    /// Calculate ΔT at the datetime by the linear interpolation.
+    ///
+    /// # Returns
+    /// Returns ΔT.
+    ///
+    /// Returns [`DeltaTOutOfCoverageError`](crate::error::Error::DeltaTOutOfCoverageError) if the table does not cover the datetime.
+    pub fn delta_t(&self, datetime: &NaiveDateTime) -> Result<Duration, Error> {
+        let out_of_coverage = || Error::DeltaTOutOfCoverageError(datetime.to_string());
+        let index = self.0.partition_point(|diff| diff.datetime <= *datetime);
+        if index == 0 {
+            return Err(out_of_coverage());
+        }
+        let prev = &self.0[index - 1];
+        if prev.datetime == *datetime {
+            return Ok(Duration::nanoseconds(prev.diff_nanos));
+        }
+        let next = self.0.get(index).ok_or_else(out_of_coverage)?;
+
+        let span = (next.datetime - prev.datetime).num_nanoseconds().unwrap() as i128;
+        let elapsed = (*datetime - prev.datetime).num_nanoseconds().unwrap() as i128;
+        let diff = i128::from(prev.diff_nanos)
+            + i128::from(next.diff_nanos - prev.diff_nanos) * elapsed / span;
+        Ok(Duration::nanoseconds(diff as i64))
+    }
+}
+
+impl From<Vec<DiffTtUt>> for DeltaTTable {
+    fn from(mut diff_list: Vec<DiffTtUt>) -> Self {
+        // 同じ日時の値が複数ある場合は先に現れたものを使う
+        diff_list.sort_by_key(|diff| diff.datetime);
+        diff_list.dedup_by_key(|diff| diff.datetime);
+        DeltaTTable(diff_list)
+    }
+}
+
+impl std::ops::Deref for DeltaTTable {
+    type Target = [DiffTtUt];
+    fn deref(&self) -> &[DiffTtUt] {
+        self.0.deref()
+    }
+}
+
+/// Parse a line of the ΔT table.
+fn parse_line(line: &str) -> Result<DiffTtUt, Error> {
+    let parse_err = || Error::DeltaTTableParseError(line.to_string());
+    let columns: Vec<&str> = line.split_whitespace().collect();
+    let (datetime, diff) = match columns.as_slice() {
+        [year, diff] => (decimal_year(year).ok_or_else(parse_err)?, diff),
+        [year, month, day, diff] => {
+            let date = NaiveDate::from_ymd_opt(
+                year.parse().map_err(|_| parse_err())?,
+                month.parse().map_err(|_| parse_err())?,
+                day.parse().map_err(|_| parse_err())?,
+            )
+            .ok_or_else(parse_err)?;
+            (date.and_hms(0, 0, 0), diff)
+        }
+        _ => Err(parse_err())?,
+    };
+    Ok(DiffTtUt {
+        datetime,
+        diff_nanos: parse_decimal_nanos(diff).ok_or_else(parse_err)?,
+    })
+}
+
+/// Convert a decimal year such as `1850.5` into the datetime; the fraction is proportional to the length of the year.
+fn decimal_year(year: &str) -> Option<NaiveDateTime> {
+    let nanos = parse_decimal_nanos(year)?;
+    let whole = nanos.div_euclid(1_000_000_000);
+    let fraction = nanos.rem_euclid(1_000_000_000);
+    let year = i32::try_from(whole).ok()?;
+    let start = NaiveDate::from_ymd_opt(year, 1, 1)?;
+    let end = NaiveDate::from_ymd_opt(year.checked_add(1)?, 1, 1)?;
+    let year_nanos = (end - start).num_nanoseconds()? as i128;
+    let offset = year_nanos * i128::from(fraction) / 1_000_000_000;
+    Some(start.and_hms(0, 0, 0) + Duration::nanoseconds(offset as i64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[test]
+    fn test_from_lines() {
+        let lines = vec![
+            "# comment",
+            "",
+            "1973  2  1  43.4724",
+            "1800.5 13.7",
+            "1800.5 99.9",
+        ];
+
+        let table = DeltaTTable::from_lines(lines).unwrap();
+
+        assert_eq!(table.len(), 2);
+        assert_eq!(
+            table[0].datetime,
+            NaiveDate::from_ymd(1800, 7, 2).and_hms(12, 0, 0)
+        );
+        assert_eq!(table[0].diff_nanos, 13_700_000_000);
+        assert_eq!(
+            table[1].datetime,
+            NaiveDate::from_ymd(1973, 2, 1).and_hms(0, 0, 0)
+        );
+    }
+
+    #[rstest]
+    #[case("1800.0")]
+    #[case("1800.0 13.7 1.0")]
+    #[case("1800.A 13.7")]
+    #[case("1973 2 30 43.4724")]
+    #[case("1973 2 1 43.47A")]
+    fn test_from_lines_illegal(#[case] line: &str) {
+        let lines = vec!["1800.0 13.7", line];
+
+        let result = DeltaTTable::from_lines(lines);
+
+        assert_eq!(
+            result.unwrap_err(),
+            Error::DeltaTTableLineError(
+                2,
+                Box::new(Error::DeltaTTableParseError(line.to_string()))
+            )
+        );
+    }
+
+    #[test]
+    fn test_from_lines_empty() {
+        let result = DeltaTTable::from_lines(vec!["# comment only"]);
+
+        assert_eq!(
+            result.unwrap_err(),
+            Error::DeltaTTableParseError("no values of ΔT are found".to_string())
+        );
+    }
+
+    #[rstest]
+    #[case(NaiveDate::from_ymd(1800, 1, 1).and_hms(0, 0, 0), Ok(Duration::milliseconds(13_700)))]
+    #[case(NaiveDate::from_ymd(1800, 1, 1).and_hms(12, 0, 0), Ok(Duration::milliseconds(13_800)))]
+    #[case(NaiveDate::from_ymd(1800, 1, 2).and_hms(0, 0, 0), Ok(Duration::milliseconds(13_900)))]
+    #[case(NaiveDate::from_ymd(1799, 12, 31).and_hms(23, 59, 59), Err(Error::DeltaTOutOfCoverageError("1799-12-31 23:59:59".to_string())))]
+    #[case(NaiveDate::from_ymd(1800, 1, 2).and_hms(0, 0, 1), Err(Error::DeltaTOutOfCoverageError("1800-01-02 00:00:01".to_string())))]
+    fn test_delta_t(#[case] datetime: NaiveDateTime, #[case] expected: Result<Duration, Error>) {
+        let table = DeltaTTable::from_lines(vec!["1800 1 1 13.7", "1800 1 2 13.9"]).unwrap();
+
+        assert_eq!(table.delta_t(&datetime), expected);
+    }
+}
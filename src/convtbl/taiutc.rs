@@ -1,7 +1,13 @@
+use crate::epoch::mjd_epoch;
 use crate::error::Error;
-use chrono::NaiveDateTime;
+use crate::ntp::ntp_epoch;
+use crate::{nanos, parse_decimal_nanos};
+use chrono::{Duration, NaiveDateTime};
 use std::fmt;
 
+/// The number of nanoseconds in a day
+const NANOS_PER_DAY: i128 = 86_400_000_000_000;
+
 /// Difference (TAI - UTC) and the datetime at which it is applied
 ///
 /// It expresses a row of [the TAI-UTC table](https://www.ietf.org/timezones/data/leap-seconds.list).
@@ -12,16 +18,94 @@ use std::fmt;
 pub struct DiffTaiUtc {
     /// (UTC) The moment when the difference (TAI - UTC) changes due to a leap second
     pub datetime: NaiveDateTime,
-    /// The difference (TAI - UTC)
+    /// The difference (TAI - UTC); before 1972, it is the integer part of the difference at [`Drift::base_mjd`].
     pub diff_seconds: i64,
+    /// The drift of the difference (TAI - UTC) before 1972; it is `None` after 1972.
+    pub drift: Option<Drift>,
+}
+
+/// The drift of the difference (TAI - UTC) in the era of "rubber seconds" (1961-1972)
+///
+/// In the era, TAI - UTC is not an integer and changes linearly, as
+/// TAI - UTC = [`DiffTaiUtc::diff_seconds`] + (`offset_nanos` + (MJD - `base_mjd`) × `rate_nanos`) × 10<sup>-9</sup> seconds,
+/// where MJD is the modified julian date of the UTC datetime.
+///
+/// # See also
+/// - [`TaiUtcTable::from_tai_utc_dat`] - It loads the drifts from the USNO tai-utc.dat.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Drift {
+    /// The fractional part of the difference (TAI - UTC) at `base_mjd`, in nanoseconds
+    pub offset_nanos: i64,
+    /// The modified julian date from which the difference drifts
+    pub base_mjd: i64,
+    /// The drift rate of the difference (TAI - UTC), in nanoseconds per day
+    pub rate_nanos: i64,
+}
+
+impl Drift {
+    /// The drifting part of the difference (TAI - UTC) at the UTC datetime
+    ///
+    /// # Arguments
+    /// * `utc` - An UTC datetime, whose leap second is normalized
+    pub fn at(&self, utc: &NaiveDateTime) -> Duration {
+        let elapsed = nanos(*utc - self.base_datetime());
+        Duration::nanoseconds(self.drift_nanos(elapsed) as i64)
+    }
+
+    /// Remove the drifting part from the datetime; it is the inverse of adding [`Drift::at`].
+    ///
+    /// # Arguments
+    /// * `shifted` - A datetime which is the UTC datetime plus [`Drift::at`] of it
+    ///
+    /// # Returns
+    /// Returns the UTC datetime, whose leap second is normalized.
+    pub fn remove_from(&self, shifted: &NaiveDateTime) -> NaiveDateTime {
+        let base = self.base_datetime();
+        let shifted = nanos(*shifted - base);
+        let mut elapsed = (shifted - self.offset_nanos as i128) * NANOS_PER_DAY
+            / (NANOS_PER_DAY + self.rate_nanos as i128);
+        // Correct the rounding error so that the result is the latest one which does not exceed `shifted` after adding the drift.
+        while elapsed + 1 + self.drift_nanos(elapsed + 1) <= shifted {
+            elapsed += 1;
+        }
+        while elapsed + self.drift_nanos(elapsed) > shifted {
+            elapsed -= 1;
+        }
+        base + Duration::nanoseconds(elapsed as i64)
+    }
+
+    /// The drifting part in nanoseconds after `elapsed` nanoseconds from `base_mjd`
+    fn drift_nanos(&self, elapsed: i128) -> i128 {
+        self.offset_nanos as i128 + elapsed * self.rate_nanos as i128 / NANOS_PER_DAY
+    }
+
+    fn base_datetime(&self) -> NaiveDateTime {
+        mjd_epoch().and_hms(0, 0, 0) + Duration::days(self.base_mjd)
+    }
 }
 
 impl DiffTaiUtc {
+    /// The difference (TAI - UTC) at the UTC datetime
+    ///
+    /// It is constant [`DiffTaiUtc::diff_seconds`] after 1972, but it drifts before 1972; see [`Drift`].
+    ///
+    /// # Arguments
+    /// * `utc` - An UTC datetime, whose leap second is normalized
+    pub fn diff_at(&self, utc: &NaiveDateTime) -> Duration {
+        let diff = Duration::seconds(self.diff_seconds);
+        match &self.drift {
+            Some(drift) => diff + drift.at(utc),
+            None => diff,
+        }
+    }
+
     /// Construct `DiffTaiUtc` from line of the TAI-UTC table file.
     ///
+    /// It is same as [`DiffTaiUtc::from_line_with_sep`] with `Some(sep)`.
+    ///
     /// # Arguments
     /// - `line` - a line of the TAI-UTC table file
-    /// - `sep` - the separator between a datetime and a difference value in `line`
+    /// - `sep` - the separator between columns in `line`
     /// - `fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of datetimes in the TAI-UTC table file
     ///
     /// # Returns
@@ -38,31 +122,94 @@ impl DiffTaiUtc {
     /// assert_eq!(diff_tai_utc, Ok(DiffTaiUtc {
     ///     datetime: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0),
     ///     diff_seconds: 37,
+    ///     drift: None,
     /// }));
     /// ```
     pub fn from_line(line: &str, sep: &str, fmt: &str) -> Result<DiffTaiUtc, Error> {
-        let parts: Vec<&str> = line.splitn(3, sep).collect();
-        if parts.len() != 2 {
-            Err(Error::TaiUtcTableParseError(line.to_string()))?;
-        }
+        DiffTaiUtc::from_line_with_sep(line, Some(sep), fmt)
+    }
+
+    /// Construct `DiffTaiUtc` from line of the TAI-UTC table file with an optional separator.
+    ///
+    /// A line consists of a datetime, a difference value and an optional note, separated by `sep`.
+    /// The part after `#` is a comment and ignored.
+    ///
+    /// # Arguments
+    /// - `line` - a line of the TAI-UTC table file
+    /// - `sep` - the separator between columns in `line`. If it is `None`, runs of whitespaces (spaces and tabs) are used as the separator.
+    /// - `fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of datetimes in the TAI-UTC table file
+    ///
+    /// # Returns
+    /// Returns the `DiffTaiUtc` if `line` is collect.
+    ///
+    /// Returns [`Error`](crate::error::Error) if `line` is illegal.
+    ///
+    /// # Examples
+    /// ```
+    /// use convdate::convtbl::DiffTaiUtc;
+    /// use chrono::NaiveDate;
+    ///
+    /// let diff_tai_utc = DiffTaiUtc::from_line_with_sep("2017-01-01T00:00:00\t37\tBulletin C52", None, "%Y-%m-%dT%H:%M:%S");
+    /// assert_eq!(diff_tai_utc, Ok(DiffTaiUtc {
+    ///     datetime: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0),
+    ///     diff_seconds: 37,
+    ///     drift: None,
+    /// }));
+    /// ```
+    pub fn from_line_with_sep(
+        line: &str,
+        sep: Option<&str>,
+        fmt: &str,
+    ) -> Result<DiffTaiUtc, Error> {
+        let data = strip_comment(line);
+        let (datetime_str, diff_str) = match sep {
+            Some(sep) => {
+                let parts: Vec<&str> = data.splitn(3, sep).map(str::trim).collect();
+                if parts.len() < 2 {
+                    Err(Error::TaiUtcTableParseError(line.to_string()))?;
+                }
+                (parts[0].to_string(), parts[1])
+            }
+            None => {
+                // The datetime contains as many whitespaces as its format does.
+                let datetime_words = fmt.split_whitespace().count().max(1);
+                let words: Vec<&str> = data.split_whitespace().collect();
+                if words.len() <= datetime_words {
+                    Err(Error::TaiUtcTableParseError(line.to_string()))?;
+                }
+                (words[..datetime_words].join(" "), words[datetime_words])
+            }
+        };
 
-        let datetime = NaiveDateTime::parse_from_str(parts[0], fmt)
-            .map_err(|_| Error::TaiUtcTableDatetimeParseError(parts[0].to_string()))?;
+        let datetime = NaiveDateTime::parse_from_str(&datetime_str, fmt)
+            .map_err(|_| Error::TaiUtcTableDatetimeParseError(datetime_str.to_string()))?;
 
-        let diff_seconds: i64 = parts[1]
+        let diff_seconds: i64 = diff_str
             .parse()
             .map_err(|_| Error::TaiUtcTableParseError(line.to_string()))?;
 
         Ok(DiffTaiUtc {
             datetime,
             diff_seconds,
+            drift: None,
         })
     }
 }
 
 impl fmt::Display for DiffTaiUtc {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "({}, {})", self.datetime, self.diff_seconds)
+        match &self.drift {
+            Some(drift) => write!(
+                f,
+                "({}, {}+({}+(MJD-{})*{})ns)",
+                self.datetime,
+                self.diff_seconds,
+                drift.offset_nanos,
+                drift.base_mjd,
+                drift.rate_nanos
+            ),
+            None => write!(f, "({}, {})", self.datetime, self.diff_seconds),
+        }
     }
 }
 
@@ -93,12 +240,23 @@ impl fmt::Display for DiffTaiUtc {
 /// #     try_main().unwrap();
 /// # }
 /// ```
+///
+/// # Expiration
+///
+/// The table may have the expiration date, after which the table cannot know about new leap seconds.
+/// It is available when the table is loaded from [the IETF leap-seconds.list](https://www.ietf.org/timezones/data/leap-seconds.list);
+/// see [`TaiUtcTable::from_leap_seconds_list`]. It can be also configured by [`TaiUtcTable::with_expiration`].
 #[derive(Debug)]
-pub struct TaiUtcTable(Vec<DiffTaiUtc>);
+pub struct TaiUtcTable {
+    rows: Vec<DiffTaiUtc>,
+    expiration: Option<NaiveDateTime>,
+}
 
 impl TaiUtcTable {
     /// Construct `TaiUtcTable` from lines of the TAI-UTC table file.
     ///
+    /// It is same as [`TaiUtcTable::from_lines_with_sep`] with `None`; the columns are separated by runs of whitespaces.
+    ///
     /// # Arguments
     /// - `lines` - a iterable of lines of the TAI-UTC table file
     /// - `fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of datetimes in the TAI-UTC table file
@@ -107,16 +265,234 @@ impl TaiUtcTable {
     /// Returns the `TaiUtcTable` if `lines` are collect.
     ///
     /// Returns [`Error`](crate::error::Error) if some of `lines` are illegal.
-    // TODO: Add sep to arguments
     pub fn from_lines(
         lines: impl IntoIterator<Item = impl AsRef<str>>,
         fmt: &str,
+    ) -> Result<TaiUtcTable, Error> {
+        TaiUtcTable::from_lines_with_sep(lines, None, fmt)
+    }
+
+    /// Construct `TaiUtcTable` from lines of the TAI-UTC table file with an optional separator.
+    ///
+    /// Blank lines and lines beginning with `#` are ignored.
+    /// See [`DiffTaiUtc::from_line_with_sep`] about the other lines.
+    ///
+    /// # Arguments
+    /// - `lines` - a iterable of lines of the TAI-UTC table file
+    /// - `sep` - the separator between columns in `lines`. If it is `None`, runs of whitespaces (spaces and tabs) are used as the separator.
+    /// - `fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of datetimes in the TAI-UTC table file
+    ///
+    /// # Returns
+    /// Returns the `TaiUtcTable` if `lines` are collect.
+    ///
+    /// Returns [`Error`](crate::error::Error) if some of `lines` are illegal.
+    /// The error contains the line number (1-based) of the illegal line.
+    pub fn from_lines_with_sep(
+        lines: impl IntoIterator<Item = impl AsRef<str>>,
+        sep: Option<&str>,
+        fmt: &str,
+    ) -> Result<TaiUtcTable, Error> {
+        let diff_list: Vec<DiffTaiUtc> = lines
+            .into_iter()
+            .enumerate()
+            .filter(|(_, line)| !strip_comment(line.as_ref()).trim().is_empty())
+            .map(|(i, line)| {
+                DiffTaiUtc::from_line_with_sep(line.as_ref(), sep, fmt)
+                    .map_err(|e| Error::TaiUtcTableLineError(i + 1, Box::new(e)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(TaiUtcTable::from(diff_list))
+    }
+
+    /// Construct `TaiUtcTable` from lines of [the IETF leap-seconds.list](https://www.ietf.org/timezones/data/leap-seconds.list).
+    ///
+    /// The lines are read as below:
+    /// - `#$` line: the last update of the file (it is used only for the hash)
+    /// - `#@` line: the expiration date of the file; it is kept as [`TaiUtcTable::expiration`]
+    /// - `#h` line: the SHA-1 hash of the data; it is checked
+    /// - other lines beginning with `#`: comments
+    /// - the others: a NTP timestamp (seconds since 1900-01-01T00:00:00) and the difference TAI - UTC
+    ///
+    /// # Arguments
+    /// - `lines` - a iterable of lines of the leap-seconds.list
+    ///
+    /// # Returns
+    /// Returns the `TaiUtcTable` if `lines` are collect.
+    ///
+    /// Returns [`Error`](crate::error::Error) if some of `lines` are illegal or the hash does not match the data.
+    ///
+    /// # Examples
+    /// ```
+    /// use convdate::convtbl::TaiUtcTable;
+    /// use chrono::NaiveDate;
+    ///
+    /// # use std::error::Error;
+    ///
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// let table = TaiUtcTable::from_leap_seconds_list(vec![
+    ///     "#\tFile expires on:  28 June 2024",
+    ///     "#$\t 3676924800",
+    ///     "#@\t 3928521600",
+    ///     "3644697600\t36\t# 1 Jul 2015",
+    ///     "3692217600\t37\t# 1 Jan 2017",
+    ///     "#h\t7e3e2943 3fa11956 e4e658de c4dca0e6 bf6622c9",
+    /// ])?;
+    /// assert_eq!(table[1].datetime, NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0));
+    /// assert_eq!(table[1].diff_seconds, 37);
+    /// assert_eq!(table.expiration(), Some(&NaiveDate::from_ymd(2024, 6, 28).and_hms(0, 0, 0)));
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn from_leap_seconds_list(
+        lines: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<TaiUtcTable, Error> {
+        let mut diff_list = Vec::new();
+        let mut expiration = None;
+        let mut expected_hash = None;
+        let mut hashed = sha1_smol::Sha1::new();
+
+        for (i, line) in lines.into_iter().enumerate() {
+            let line = line.as_ref().trim_end();
+            let with_line_no = |e| Error::TaiUtcTableLineError(i + 1, Box::new(e));
+            if let Some(value) = line.strip_prefix("#$") {
+                parse_ntp_timestamp(value).map_err(with_line_no)?;
+                hashed.update(remove_whitespace(value).as_bytes());
+            } else if let Some(value) = line.strip_prefix("#@") {
+                expiration = Some(parse_ntp_timestamp(value).map_err(with_line_no)?);
+                hashed.update(remove_whitespace(value).as_bytes());
+            } else if let Some(value) = line.strip_prefix("#h") {
+                expected_hash = Some(parse_hash(value).map_err(with_line_no)?);
+            } else if !strip_comment(line).trim().is_empty() {
+                let data = strip_comment(line);
+                let parts: Vec<&str> = data.split_whitespace().collect();
+                if parts.len() != 2 {
+                    Err(with_line_no(Error::TaiUtcTableParseError(line.to_string())))?;
+                }
+                let datetime = parse_ntp_timestamp(parts[0]).map_err(with_line_no)?;
+                let diff_seconds: i64 = parts[1]
+                    .parse()
+                    .map_err(|_| with_line_no(Error::TaiUtcTableParseError(line.to_string())))?;
+                diff_list.push(DiffTaiUtc {
+                    datetime,
+                    diff_seconds,
+                    drift: None,
+                });
+                hashed.update(remove_whitespace(data).as_bytes());
+            }
+        }
+
+        let expected_hash = expected_hash.ok_or(Error::LeapSecondsListHashNotFoundError())?;
+        let actual_hash = hashed.digest().bytes();
+        if actual_hash != expected_hash {
+            Err(Error::LeapSecondsListHashMismatchError(
+                format_hash(&expected_hash),
+                format_hash(&actual_hash),
+            ))?;
+        }
+
+        Ok(TaiUtcTable {
+            rows: diff_list,
+            expiration,
+        })
+    }
+
+    /// Construct `TaiUtcTable` from lines of [the USNO tai-utc.dat](https://maia.usno.navy.mil/ser7/tai-utc.dat).
+    ///
+    /// Each line of the tai-utc.dat defines TAI - UTC = A + (MJD - B) × C seconds from the julian date, for example:
+    /// ```text
+    ///  1961 JAN  1 =JD 2437300.5  TAI-UTC=   1.4228180 S + (MJD - 37300.) X 0.001296 S
+    /// ```
+    /// It covers the era of "rubber seconds" (1961-1972), in which the difference drifts; see [`Drift`].
+    /// Blank lines are ignored.
+    ///
+    /// # Arguments
+    /// - `lines` - a iterable of lines of the tai-utc.dat
+    ///
+    /// # Returns
+    /// Returns the `TaiUtcTable` if `lines` are collect.
+    ///
+    /// Returns [`Error`](crate::error::Error) if some of `lines` are illegal.
+    /// The error contains the line number (1-based) of the illegal line.
+    ///
+    /// # Examples
+    /// ```
+    /// use convdate::convtbl::{Drift, TaiUtcTable};
+    /// use chrono::NaiveDate;
+    ///
+    /// # use std::error::Error;
+    ///
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// let table = TaiUtcTable::from_tai_utc_dat(vec![
+    ///     " 1968 FEB  1 =JD 2439887.5  TAI-UTC=   4.2131700 S + (MJD - 39126.) X 0.002592 S",
+    ///     " 1972 JAN  1 =JD 2441317.5  TAI-UTC=  10.0       S + (MJD - 41317.) X 0.0      S",
+    /// ])?;
+    /// assert_eq!(table[0].datetime, NaiveDate::from_ymd(1968, 2, 1).and_hms(0, 0, 0));
+    /// assert_eq!(table[0].diff_seconds, 4);
+    /// assert_eq!(table[0].drift, Some(Drift { offset_nanos: 213_170_000, base_mjd: 39126, rate_nanos: 2_592_000 }));
+    /// assert_eq!(table[1].datetime, NaiveDate::from_ymd(1972, 1, 1).and_hms(0, 0, 0));
+    /// assert_eq!(table[1].diff_seconds, 10);
+    /// assert_eq!(table[1].drift, None);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn from_tai_utc_dat(
+        lines: impl IntoIterator<Item = impl AsRef<str>>,
     ) -> Result<TaiUtcTable, Error> {
         let diff_list: Vec<DiffTaiUtc> = lines
             .into_iter()
-            .map(|line| DiffTaiUtc::from_line(line.as_ref(), " ", fmt))
+            .enumerate()
+            .filter(|(_, line)| !line.as_ref().trim().is_empty())
+            .map(|(i, line)| {
+                parse_tai_utc_dat_line(line.as_ref())
+                    .map_err(|e| Error::TaiUtcTableLineError(i + 1, Box::new(e)))
+            })
             .collect::<Result<Vec<_>, _>>()?;
-        Ok(TaiUtcTable(diff_list))
+        Ok(TaiUtcTable::from(diff_list))
+    }
+
+    /// The expiration date of the table (UTC), if it is known
+    ///
+    /// After this datetime, the table may not know about new leap seconds.
+    pub fn expiration(&self) -> Option<&NaiveDateTime> {
+        self.expiration.as_ref()
+    }
+
+    /// Set the expiration date of the table (UTC); it overrides the one in the file.
+    ///
+    /// # Examples
+    /// ```
+    /// use convdate::convtbl::TaiUtcTable;
+    /// use chrono::NaiveDate;
+    ///
+    /// let table = TaiUtcTable::from_lines(vec!["2017-01-01T00:00:00 37"], "%Y-%m-%dT%H:%M:%S")
+    ///     .unwrap()
+    ///     .with_expiration(NaiveDate::from_ymd(2024, 6, 28).and_hms(0, 0, 0));
+    /// assert!(!table.is_expired_at(&NaiveDate::from_ymd(2024, 6, 27).and_hms(23, 59, 59)));
+    /// assert!(table.is_expired_at(&NaiveDate::from_ymd(2024, 6, 28).and_hms(0, 0, 0)));
+    /// ```
+    pub fn with_expiration(self, expiration: NaiveDateTime) -> TaiUtcTable {
+        TaiUtcTable {
+            expiration: Some(expiration),
+            ..self
+        }
+    }
+
+    /// Whether the UTC datetime is beyond the expiration date of the table.
+    ///
+    /// If the expiration date is unknown, it always returns `false`.
+    pub fn is_expired_at(&self, datetime: &NaiveDateTime) -> bool {
+        self.expiration
+            .is_some_and(|expiration| *datetime >= expiration)
     }
 
     /// Pick the row to use to calculate TAI from the UTC datetime.
@@ -136,24 +512,112 @@ impl TaiUtcTable {
             }
             prev_diff = Some(diff_utc_tai);
         }
-        return match prev_diff {
+        match prev_diff {
             Some(diff_utc_tai) => Ok(diff_utc_tai),
             None => Err(Error::DatetimeTooLowError(datetime.to_string()))?,
-        };
+        }
     }
 }
 
 impl From<Vec<DiffTaiUtc>> for TaiUtcTable {
     fn from(diff_list: Vec<DiffTaiUtc>) -> Self {
-        TaiUtcTable(diff_list)
+        TaiUtcTable {
+            rows: diff_list,
+            expiration: None,
+        }
     }
 }
 
 impl std::ops::Deref for TaiUtcTable {
     type Target = [DiffTaiUtc];
     fn deref(&self) -> &[DiffTaiUtc] {
-        self.0.deref()
+        self.rows.deref()
+    }
+}
+
+/// Parse NTP timestamp; seconds since 1900-01-01T00:00:00.
+fn parse_ntp_timestamp(value: &str) -> Result<NaiveDateTime, Error> {
+    let seconds: i64 = value
+        .trim()
+        .parse()
+        .map_err(|_| Error::TaiUtcTableDatetimeParseError(value.trim().to_string()))?;
+    Ok(ntp_epoch() + Duration::seconds(seconds))
+}
+
+/// Parse a line of the USNO tai-utc.dat.
+fn parse_tai_utc_dat_line(line: &str) -> Result<DiffTaiUtc, Error> {
+    let parse_err = || Error::TaiUtcTableParseError(line.to_string());
+    let (_, rest) = line.split_once("=JD").ok_or_else(parse_err)?;
+    let (jd, rest) = rest.split_once("TAI-UTC=").ok_or_else(parse_err)?;
+    let (a, rest) = rest.split_once("S + (MJD -").ok_or_else(parse_err)?;
+    let (b, rest) = rest.split_once(") X").ok_or_else(parse_err)?;
+    let c = rest.trim_end().strip_suffix('S').ok_or_else(parse_err)?;
+
+    // JD 2400000.5 is MJD 0.
+    let mjd = parse_decimal_nanos(jd)
+        .map(|jd| jd - 2_400_000_500_000_000)
+        .filter(|mjd| mjd % 1_000_000_000 == 0)
+        .ok_or_else(|| Error::TaiUtcTableDatetimeParseError(jd.trim().to_string()))?
+        / 1_000_000_000;
+    let a = parse_decimal_nanos(a).ok_or_else(parse_err)?;
+    let b = parse_decimal_nanos(b)
+        .filter(|b| b % 1_000_000_000 == 0)
+        .ok_or_else(parse_err)?;
+    let c = parse_decimal_nanos(c).ok_or_else(parse_err)?;
+
+    let diff_seconds = a.div_euclid(1_000_000_000);
+    let offset_nanos = a.rem_euclid(1_000_000_000);
+    let drift = if offset_nanos == 0 && c == 0 {
+        None
+    } else {
+        Some(Drift {
+            offset_nanos,
+            base_mjd: b / 1_000_000_000,
+            rate_nanos: c,
+        })
+    };
+    Ok(DiffTaiUtc {
+        datetime: mjd_epoch().and_hms(0, 0, 0) + Duration::days(mjd),
+        diff_seconds,
+        drift,
+    })
+}
+
+/// Parse the value of `#h` line; five 32-bit words in hexadecimal.
+///
+/// Leading zeros of each word may be omitted in leap-seconds.list, so each word is parsed as a number.
+fn parse_hash(value: &str) -> Result<[u8; 20], Error> {
+    let words: Vec<&str> = value.split_whitespace().collect();
+    if words.len() != 5 {
+        Err(Error::TaiUtcTableParseError(format!("#h{}", value)))?;
     }
+    let mut hash = [0u8; 20];
+    for (i, word) in words.iter().enumerate() {
+        let word = u32::from_str_radix(word, 16)
+            .map_err(|_| Error::TaiUtcTableParseError(format!("#h{}", value)))?;
+        hash[i * 4..i * 4 + 4].copy_from_slice(&word.to_be_bytes());
+    }
+    Ok(hash)
+}
+
+fn format_hash(hash: &[u8; 20]) -> String {
+    hash.chunks(4)
+        .map(|word| {
+            word.iter()
+                .map(|b| format!("{:02x}", b))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Remove the comment, which begins with `#`, from the line.
+fn strip_comment(line: &str) -> &str {
+    line.split('#').next().unwrap()
+}
+
+fn remove_whitespace(value: &str) -> String {
+    value.split_whitespace().collect()
 }
 
 #[cfg(test)]
@@ -166,28 +630,80 @@ mod tests {
     #[rstest]
     #[case(
         "2017-01-02T11:22:33 15",
-        " ",
+        None,
         "%Y-%m-%dT%H:%M:%S",
-        Some(DiffTaiUtc{datetime:NaiveDate::from_ymd(2017, 1, 2).and_hms(11, 22, 33), diff_seconds: 15}),
+        Some(DiffTaiUtc{datetime:NaiveDate::from_ymd(2017, 1, 2).and_hms(11, 22, 33), diff_seconds: 15, drift: None}),
         None
     )]
     #[case(
         "20170102112233,15",
-        ",",
+        Some(","),
+        "%Y%m%d%H%M%S",
+        Some(DiffTaiUtc{datetime:NaiveDate::from_ymd(2017, 1, 2).and_hms(11, 22, 33), diff_seconds: 15, drift: None}),
+        None,
+    )]
+    // Runs of whitespaces are a separator
+    #[case(
+        "2017-01-02T11:22:33 \t  15",
+        None,
+        "%Y-%m-%dT%H:%M:%S",
+        Some(DiffTaiUtc{datetime:NaiveDate::from_ymd(2017, 1, 2).and_hms(11, 22, 33), diff_seconds: 15, drift: None}),
+        None
+    )]
+    // The datetime can contain whitespaces as well as its format
+    #[case(
+        "2017-01-02 11:22:33\t15",
+        None,
+        "%Y-%m-%d %H:%M:%S",
+        Some(DiffTaiUtc{datetime:NaiveDate::from_ymd(2017, 1, 2).and_hms(11, 22, 33), diff_seconds: 15, drift: None}),
+        None
+    )]
+    // The note column and the comment are ignored
+    #[case(
+        "2017-01-02T11:22:33 15 IERS Bulletin C 52",
+        None,
+        "%Y-%m-%dT%H:%M:%S",
+        Some(DiffTaiUtc{datetime:NaiveDate::from_ymd(2017, 1, 2).and_hms(11, 22, 33), diff_seconds: 15, drift: None}),
+        None
+    )]
+    #[case(
+        "20170102112233, 15, IERS Bulletin C 52 # comment",
+        Some(","),
         "%Y%m%d%H%M%S",
-        Some(DiffTaiUtc{datetime:NaiveDate::from_ymd(2017, 1, 2).and_hms(11, 22, 33), diff_seconds: 15}),
+        Some(DiffTaiUtc{datetime:NaiveDate::from_ymd(2017, 1, 2).and_hms(11, 22, 33), diff_seconds: 15, drift: None}),
+        None,
+    )]
+    #[case(
+        "2017-01-02T11:22:33 15  # comment",
         None,
+        "%Y-%m-%dT%H:%M:%S",
+        Some(DiffTaiUtc{datetime:NaiveDate::from_ymd(2017, 1, 2).and_hms(11, 22, 33), diff_seconds: 15, drift: None}),
+        None
     )]
     #[case(
-        "2017-01-02T11:22:33 15 1", // Too many values lead the error
-        " ",
+        "2017-01-02T11:22:33", // Too few values lead the error
+        None,
+        "%Y-%m-%dT%H:%M:%S",
+        None,
+        Some(Error::TaiUtcTableParseError(line.to_string())),
+    )]
+    #[case(
+        "2017-01-02T11:22:33 # 15", // A commented value is not a value
+        None,
+        "%Y-%m-%dT%H:%M:%S",
+        None,
+        Some(Error::TaiUtcTableParseError(line.to_string())),
+    )]
+    #[case(
+        "2017-01-02T11:22:33 15", // The separator is not found
+        Some(","),
         "%Y-%m-%dT%H:%M:%S",
         None,
         Some(Error::TaiUtcTableParseError(line.to_string())),
     )]
     #[case(
         "2017-01-0211:22:33 15", // Illegal datetime format leads the error
-        " ",
+        None,
         "%Y-%m-%dT%H:%M:%S",
         None,
         Some(Error::TaiUtcTableDatetimeParseError(
@@ -196,18 +712,324 @@ mod tests {
     )]
     fn test_diff_tai_utc_from_line(
         #[case] line: &str,
-        #[case] sep: &str,
+        #[case] sep: Option<&str>,
         #[case] fmt: &str,
         #[case] expected_ok: Option<DiffTaiUtc>,
         #[case] expected_err: Option<Error>,
     ) {
         let expected = testmod::result(expected_ok, expected_err);
 
-        let result = DiffTaiUtc::from_line(line, sep, fmt);
+        let result = DiffTaiUtc::from_line_with_sep(line, sep, fmt);
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_diff_tai_utc_from_line_with_str_sep() {
+        assert_eq!(
+            DiffTaiUtc::from_line("20170102112233,15", ",", "%Y%m%d%H%M%S"),
+            DiffTaiUtc::from_line_with_sep("20170102112233,15", Some(","), "%Y%m%d%H%M%S")
+        );
+    }
+
+    /// Tests that comments and blank lines are skipped and illegal lines are reported with their line numbers.
+    #[rstest]
+    #[case(
+        vec!["# comment", "", "2015-07-01T00:00:00 36", "  ", "\t# comment", "2017-01-01T00:00:00\t37\tnote", ""],
+        Some(vec![
+            DiffTaiUtc{datetime: NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 0), diff_seconds: 36, drift: None},
+            DiffTaiUtc{datetime: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0), diff_seconds: 37, drift: None},
+        ]),
+        None,
+    )]
+    #[case(
+        vec!["# comment", "", "2015-07-01T00:00:00 36", "2017-01-01T00:00:00 A"],
+        None,
+        Some(Error::TaiUtcTableLineError(4, Box::new(Error::TaiUtcTableParseError("2017-01-01T00:00:00 A".to_string())))),
+    )]
+    fn test_from_lines(
+        #[case] lines: Vec<&str>,
+        #[case] expected_ok: Option<Vec<DiffTaiUtc>>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok, expected_err);
+
+        let result =
+            TaiUtcTable::from_lines(lines, "%Y-%m-%dT%H:%M:%S").map(|table| table.to_vec());
 
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_from_lines_with_sep() {
+        let result = TaiUtcTable::from_lines_with_sep(
+            vec![
+                "# comment",
+                "2015-07-01 00:00:00, 36",
+                "2017-01-01 00:00:00, 37, note",
+            ],
+            Some(","),
+            "%Y-%m-%d %H:%M:%S",
+        )
+        .map(|table| table.to_vec());
+
+        assert_eq!(
+            result,
+            Ok(vec![
+                DiffTaiUtc {
+                    datetime: NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 0),
+                    diff_seconds: 36,
+                    drift: None
+                },
+                DiffTaiUtc {
+                    datetime: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0),
+                    diff_seconds: 37,
+                    drift: None
+                },
+            ])
+        );
+    }
+
+    /// A part of the leap-seconds.list published by IETF; it expires on 28 June 2024.
+    const LEAP_SECONDS_LIST: &[&str] = &[
+        "#\tUpdated through IERS Bulletin C66",
+        "#\tFile expires on:  28 June 2024",
+        "#",
+        "#$\t 3676924800",
+        "#@\t 3928521600",
+        "#",
+        "2272060800\t10\t# 1 Jan 1972",
+        "2287785600\t11\t# 1 Jul 1972",
+        "2303683200\t12\t# 1 Jan 1973",
+        "2335219200\t13\t# 1 Jan 1974",
+        "2366755200\t14\t# 1 Jan 1975",
+        "2398291200\t15\t# 1 Jan 1976",
+        "2429913600\t16\t# 1 Jan 1977",
+        "2461449600\t17\t# 1 Jan 1978",
+        "2492985600\t18\t# 1 Jan 1979",
+        "2524521600\t19\t# 1 Jan 1980",
+        "2571782400\t20\t# 1 Jul 1981",
+        "2603318400\t21\t# 1 Jul 1982",
+        "2634854400\t22\t# 1 Jul 1983",
+        "2698012800\t23\t# 1 Jul 1985",
+        "2776982400\t24\t# 1 Jan 1988",
+        "2840140800\t25\t# 1 Jan 1990",
+        "2871676800\t26\t# 1 Jan 1991",
+        "2918937600\t27\t# 1 Jul 1992",
+        "2950473600\t28\t# 1 Jul 1993",
+        "2982009600\t29\t# 1 Jul 1994",
+        "3029443200\t30\t# 1 Jan 1996",
+        "3076704000\t31\t# 1 Jul 1997",
+        "3124137600\t32\t# 1 Jan 1999",
+        "3345062400\t33\t# 1 Jan 2006",
+        "3439756800\t34\t# 1 Jan 2009",
+        "3550089600\t35\t# 1 Jul 2012",
+        "3644697600\t36\t# 1 Jul 2015",
+        "3692217600\t37\t# 1 Jan 2017",
+        "#",
+        "#\tthe SHA-1 hash of the data in this file",
+        "#",
+        "#h\t16edd0f0 3666784f 37db6bdd e74ced87 59af48f1",
+    ];
+
+    /// Tests construction of TaiUtcTable from leap-seconds.list.
+    #[test]
+    fn test_from_leap_seconds_list() {
+        let table = TaiUtcTable::from_leap_seconds_list(LEAP_SECONDS_LIST).unwrap();
+
+        assert_eq!(table.len(), 28);
+        assert_eq!(
+            table[0],
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(1972, 1, 1).and_hms(0, 0, 0),
+                diff_seconds: 10,
+                drift: None
+            }
+        );
+        assert_eq!(
+            table[27],
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0),
+                diff_seconds: 37,
+                drift: None
+            }
+        );
+        assert_eq!(
+            table.expiration(),
+            Some(&NaiveDate::from_ymd(2024, 6, 28).and_hms(0, 0, 0))
+        );
+    }
+
+    #[rstest]
+    // The data are modified.
+    #[case(
+        "3692217600\t38\t# 1 Jan 2017",
+        Error::LeapSecondsListHashMismatchError(
+            "16edd0f0 3666784f 37db6bdd e74ced87 59af48f1".to_string(),
+            "9be6721d c7925a1d f49aa6fb 324e65cf a186d42f".to_string(),
+        )
+    )]
+    // Too many values lead the error
+    #[case(
+        "3692217600\t37\t1",
+        Error::TaiUtcTableLineError(34, Box::new(Error::TaiUtcTableParseError("3692217600\t37\t1".to_string())))
+    )]
+    // Illegal timestamp leads the error
+    #[case(
+        "369221760A\t37",
+        Error::TaiUtcTableLineError(34, Box::new(Error::TaiUtcTableDatetimeParseError("369221760A".to_string())))
+    )]
+    fn test_from_leap_seconds_list_illegal(#[case] last_row: &str, #[case] expected_err: Error) {
+        let mut lines = LEAP_SECONDS_LIST.to_vec();
+        lines[33] = last_row;
+
+        let result = TaiUtcTable::from_leap_seconds_list(lines);
+
+        assert_eq!(result.unwrap_err(), expected_err);
+    }
+
+    /// Tests the expiration date read from leap-seconds.list and configured.
+    #[rstest]
+    #[case(None, NaiveDate::from_ymd(2024, 6, 27).and_hms_milli(23, 59, 59, 1_500), false)]
+    #[case(None, NaiveDate::from_ymd(2024, 6, 28).and_hms(0, 0, 0), true)]
+    #[case(Some(NaiveDate::from_ymd(2020, 1, 1).and_hms(0, 0, 0)), NaiveDate::from_ymd(2024, 6, 27).and_hms(0, 0, 0), true)]
+    #[case(Some(NaiveDate::from_ymd(2030, 1, 1).and_hms(0, 0, 0)), NaiveDate::from_ymd(2025, 1, 1).and_hms(0, 0, 0), false)]
+    fn test_is_expired_at(
+        #[case] configured: Option<NaiveDateTime>,
+        #[case] datetime: NaiveDateTime,
+        #[case] expected: bool,
+    ) {
+        let table = TaiUtcTable::from_leap_seconds_list(LEAP_SECONDS_LIST).unwrap();
+        let table = match configured {
+            Some(expiration) => table.with_expiration(expiration),
+            None => table,
+        };
+
+        assert_eq!(table.is_expired_at(&datetime), expected);
+    }
+
+    /// Tests that the table without the expiration date never expires.
+    #[test]
+    fn test_is_expired_at_without_expiration() {
+        let table = TaiUtcTable::from_lines(vec!["20170101000000 37"], "%Y%m%d%H%M%S").unwrap();
+
+        assert!(!table.is_expired_at(&NaiveDate::from_ymd(2100, 1, 1).and_hms(0, 0, 0)));
+    }
+
+    /// Tests that leap-seconds.list without the hash is rejected.
+    #[test]
+    fn test_from_leap_seconds_list_without_hash() {
+        let lines = &LEAP_SECONDS_LIST[..LEAP_SECONDS_LIST.len() - 1];
+
+        let result = TaiUtcTable::from_leap_seconds_list(lines);
+
+        assert_eq!(
+            result.unwrap_err(),
+            Error::LeapSecondsListHashNotFoundError()
+        );
+    }
+
+    /// Tests construction of TaiUtcTable from the USNO tai-utc.dat.
+    #[test]
+    fn test_from_tai_utc_dat() {
+        let table = TaiUtcTable::from_tai_utc_dat(testmod::TAI_UTC_DAT).unwrap();
+
+        assert_eq!(
+            table.to_vec(),
+            vec![
+                DiffTaiUtc {
+                    datetime: NaiveDate::from_ymd(1961, 1, 1).and_hms(0, 0, 0),
+                    diff_seconds: 1,
+                    drift: Some(Drift {
+                        offset_nanos: 422_818_000,
+                        base_mjd: 37300,
+                        rate_nanos: 1_296_000
+                    }),
+                },
+                DiffTaiUtc {
+                    datetime: NaiveDate::from_ymd(1961, 8, 1).and_hms(0, 0, 0),
+                    diff_seconds: 1,
+                    drift: Some(Drift {
+                        offset_nanos: 372_818_000,
+                        base_mjd: 37300,
+                        rate_nanos: 1_296_000
+                    }),
+                },
+                DiffTaiUtc {
+                    datetime: NaiveDate::from_ymd(1968, 2, 1).and_hms(0, 0, 0),
+                    diff_seconds: 4,
+                    drift: Some(Drift {
+                        offset_nanos: 213_170_000,
+                        base_mjd: 39126,
+                        rate_nanos: 2_592_000
+                    }),
+                },
+                DiffTaiUtc {
+                    datetime: NaiveDate::from_ymd(1972, 1, 1).and_hms(0, 0, 0),
+                    diff_seconds: 10,
+                    drift: None,
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    // The julian date must be at midnight.
+    #[case(
+        " 1961 JAN  1 =JD 2437300.0  TAI-UTC=   1.4228180 S + (MJD - 37300.) X 0.001296 S",
+        Error::TaiUtcTableDatetimeParseError("2437300.0".to_string())
+    )]
+    #[case(
+        " 1961 JAN  1 =JD 2437300.5  TAI-UTC=   1.42281X0 S + (MJD - 37300.) X 0.001296 S",
+        Error::TaiUtcTableParseError(line.to_string())
+    )]
+    #[case(
+        " 1961 JAN  1 =JD 2437300.5  TAI-UTC=   1.4228180 S",
+        Error::TaiUtcTableParseError(line.to_string())
+    )]
+    fn test_from_tai_utc_dat_illegal(#[case] line: &str, #[case] expected_err: Error) {
+        let mut lines = testmod::TAI_UTC_DAT.to_vec();
+        lines[1] = line;
+
+        let result = TaiUtcTable::from_tai_utc_dat(lines);
+
+        assert_eq!(
+            result.unwrap_err(),
+            Error::TaiUtcTableLineError(2, Box::new(expected_err))
+        );
+    }
+
+    /// Tests that `Drift::remove_from` is the inverse of adding `Drift::at`.
+    #[rstest]
+    #[case(NaiveDate::from_ymd(1961, 1, 1).and_hms(0, 0, 0))]
+    #[case(NaiveDate::from_ymd(1965, 6, 15).and_hms_nano(12, 34, 56, 123_456_789))]
+    #[case(NaiveDate::from_ymd(1971, 12, 31).and_hms_nano(23, 59, 59, 999_999_999))]
+    fn test_drift_round_trip(#[case] utc: NaiveDateTime) {
+        let drift = Drift {
+            offset_nanos: 213_170_000,
+            base_mjd: 39126,
+            rate_nanos: 2_592_000,
+        };
+
+        let shifted = utc + drift.at(&utc);
+
+        assert_eq!(drift.remove_from(&shifted), utc);
+    }
+
+    #[rstest]
+    #[case("\t1 2 3 4 5", Some([0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0, 5]), None)]
+    #[case("\t1 2 3 4", None, Some(Error::TaiUtcTableParseError("#h\t1 2 3 4".to_string())))]
+    #[case("\t1 2 3 4 X", None, Some(Error::TaiUtcTableParseError("#h\t1 2 3 4 X".to_string())))]
+    fn test_parse_hash(
+        #[case] value: &str,
+        #[case] expected_ok: Option<[u8; 20]>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok, expected_err);
+
+        assert_eq!(parse_hash(value), expected);
+    }
+
     #[rstest]
     #[case(
         NaiveDate::from_ymd(2012, 6, 30).and_hms_milli(23, 59, 59, 1_000),
@@ -216,20 +1038,20 @@ mod tests {
     )]
     #[case(
         NaiveDate::from_ymd(2012, 7, 1).and_hms(0, 0, 0),
-        Some(DiffTaiUtc{datetime: NaiveDate::from_ymd(2012, 7, 1).and_hms(0, 0, 0), diff_seconds: 35}),
+        Some(DiffTaiUtc{datetime: NaiveDate::from_ymd(2012, 7, 1).and_hms(0, 0, 0), diff_seconds: 35, drift: None}),
         None,
     )]
     #[case(
         NaiveDate::from_ymd(2015, 6, 30).and_hms_milli(23, 59, 59, 1_000),
-        Some(DiffTaiUtc{datetime: NaiveDate::from_ymd(2012, 7, 1).and_hms(0, 0, 0), diff_seconds: 35}),
+        Some(DiffTaiUtc{datetime: NaiveDate::from_ymd(2012, 7, 1).and_hms(0, 0, 0), diff_seconds: 35, drift: None}),
         None,
     )]
     #[case(
         NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 0),
-        Some(DiffTaiUtc{datetime: NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 0), diff_seconds: 36}),
+        Some(DiffTaiUtc{datetime: NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 0), diff_seconds: 36, drift: None}),
         None,
     )]
-    fn test_pick_dominant_row<'a>(
+    fn test_pick_dominant_row(
         #[case] dt_input: NaiveDateTime,
         #[case] expected_ok: Option<DiffTaiUtc>,
         #[case] expected_err: Option<Error>,
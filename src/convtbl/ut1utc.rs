@@ -0,0 +1,264 @@
+use crate::epoch::mjd_epoch;
+use crate::error::Error;
+use crate::parse_decimal_nanos;
+use chrono::{Datelike, Duration, NaiveDate};
+
+/// Difference (UT1 - UTC) at 0h UTC of a day
+///
+/// It expresses a row of the Earth orientation parameters (EOP) published by [IERS](https://www.iers.org/).
+///
+/// # See also
+/// - [`Ut1UtcTable`] - It express the UT1-UTC table.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DiffUt1Utc {
+    /// The day (UTC) at whose 0h the difference is given
+    pub date: NaiveDate,
+    /// The difference (UT1 - UTC) in nanoseconds
+    pub diff_nanos: i64,
+}
+
+/// UT1-UTC conversion table
+///
+/// It expresses the daily values of UT1 - UTC; it is used for conversion between UT1 and the other time scales.
+///
+/// # As Iterable Object
+///
+/// It behaves as an iterable object of row sorted by date.
+///
+/// # Creation new Ut1UtcTable
+///
+/// This object is created from the files published by [IERS](https://www.iers.org/):
+/// - `finals2000A.all` (or `finals2000A.data` and so on); see [`Ut1UtcTable::from_finals2000a`]
+/// - Bulletin A; see [`Ut1UtcTable::from_bulletin_a`]
+#[derive(Debug)]
+pub struct Ut1UtcTable(Vec<DiffUt1Utc>);
+
+impl Ut1UtcTable {
+    /// Construct `Ut1UtcTable` from lines of [`finals2000A.all`](https://datacenter.iers.org/versionMetadata.php?filename=latestVersionMeta/10_FINALS.DATA_IAU2000_V2013_0110.txt).
+    ///
+    /// The values of UT1 - UTC of Bulletin A (columns 59-68) are used; they include the predictions.
+    /// Lines without the value, such as the lines of the far future, and blank lines are ignored.
+    ///
+    /// # Arguments
+    /// - `lines` - a iterable of lines of `finals2000A.all`
+    ///
+    /// # Returns
+    /// Returns the `Ut1UtcTable` if `lines` are collect.
+    ///
+    /// Returns [`Error`](crate::error::Error) if some of `lines` are illegal.
+    /// The error contains the line number (1-based) of the illegal line.
+    ///
+    /// # Examples
+    /// ```
+    /// use convdate::convtbl::Ut1UtcTable;
+    /// use chrono::NaiveDate;
+    ///
+    /// let table = Ut1UtcTable::from_finals2000a(vec![
+    ///     "17 1 1 57754.00 I  0.048778 0.000031  0.266532 0.000030  I 0.5925650 0.0000088  1.2071 0.0069",
+    ///     "17 1 2 57755.00 I  0.048778 0.000031  0.266532 0.000030  I 0.5918034 0.0000088  1.2071 0.0069",
+    /// ]).unwrap();
+    /// assert_eq!(table[1].date, NaiveDate::from_ymd(2017, 1, 2));
+    /// assert_eq!(table[1].diff_nanos, 591_803_400);
+    /// ```
+    pub fn from_finals2000a(
+        lines: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Ut1UtcTable, Error> {
+        let mut diff_list = Vec::new();
+        for (i, line) in lines.into_iter().enumerate() {
+            let line = line.as_ref();
+            if line.trim().is_empty() {
+                continue;
+            }
+            let diff = parse_finals2000a_line(line)
+                .map_err(|e| Error::EopTableLineError(i + 1, Box::new(e)))?;
+            if let Some(diff) = diff {
+                diff_list.push(diff);
+            }
+        }
+        Ok(Ut1UtcTable::from(diff_list))
+    }
+
+    /// Construct `Ut1UtcTable` from lines of [IERS Bulletin A](https://www.iers.org/IERS/EN/Publications/Bulletins/bulletins.html).
+    ///
+    /// The rows of the rapid service (`year month day MJD x error y error UT1-UTC error`)
+    /// and the rows of the predictions (`year month day MJD x y UT1-UTC`) are used; the other lines are ignored.
+    ///
+    /// # Arguments
+    /// - `lines` - a iterable of lines of Bulletin A
+    ///
+    /// # Returns
+    /// Returns the `Ut1UtcTable` if `lines` are collect.
+    ///
+    /// Returns [`Error`](crate::error::Error) if `lines` contain no rows.
+    ///
+    /// # Examples
+    /// ```
+    /// use convdate::convtbl::Ut1UtcTable;
+    /// use chrono::NaiveDate;
+    ///
+    /// let table = Ut1UtcTable::from_bulletin_a(vec![
+    ///     "              IERS Rapid Service",
+    ///     "       2017  1  1  57754 0.04878 .00003 0.26653 .00003  0.592565 0.000009",
+    ///     "         2017  1  2  57755       0.0488   0.2665   0.59180",
+    /// ]).unwrap();
+    /// assert_eq!(table[0].date, NaiveDate::from_ymd(2017, 1, 1));
+    /// assert_eq!(table[0].diff_nanos, 592_565_000);
+    /// assert_eq!(table[1].diff_nanos, 591_800_000);
+    /// ```
+    pub fn from_bulletin_a(
+        lines: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Ut1UtcTable, Error> {
+        let diff_list: Vec<DiffUt1Utc> = lines
+            .into_iter()
+            .filter_map(|line| parse_bulletin_a_line(line.as_ref()))
+            .collect();
+        if diff_list.is_empty() {
+            Err(Error::EopTableParseError(
+                "no values of UT1-UTC are found".to_string(),
+            ))?;
+        }
+        Ok(Ut1UtcTable::from(diff_list))
+    }
+}
+
+impl From<Vec<DiffUt1Utc>> for Ut1UtcTable {
+    fn from(mut diff_list: Vec<DiffUt1Utc>) -> Self {
+        // 同じ日の値が複数ある場合は先に現れたものを使う
+        diff_list.sort_by_key(|diff| diff.date);
+        diff_list.dedup_by_key(|diff| diff.date);
+        Ut1UtcTable(diff_list)
+    }
+}
+
+impl std::ops::Deref for Ut1UtcTable {
+    type Target = [DiffUt1Utc];
+    fn deref(&self) -> &[DiffUt1Utc] {
+        self.0.deref()
+    }
+}
+
+/// Parse a line of `finals2000A.all`; it returns `None` if the line has no value of UT1 - UTC.
+fn parse_finals2000a_line(line: &str) -> Result<Option<DiffUt1Utc>, Error> {
+    let parse_err = || Error::EopTableParseError(line.to_string());
+    let mjd = line.get(7..15).ok_or_else(parse_err)?.trim();
+    let mjd = match mjd.strip_suffix(".00") {
+        Some(mjd) => mjd.parse::<i64>().map_err(|_| parse_err())?,
+        None => Err(parse_err())?,
+    };
+    let diff = match line.get(58..68).or_else(|| line.get(58..)) {
+        Some(diff) if !diff.trim().is_empty() => diff,
+        _ => return Ok(None),
+    };
+    let diff_nanos = parse_decimal_nanos(diff).ok_or_else(parse_err)?;
+    Ok(Some(DiffUt1Utc {
+        date: mjd_epoch() + Duration::days(mjd),
+        diff_nanos,
+    }))
+}
+
+/// Parse a line of Bulletin A; it returns `None` if the line is not a row of UT1 - UTC.
+fn parse_bulletin_a_line(line: &str) -> Option<DiffUt1Utc> {
+    let columns: Vec<&str> = line.split_whitespace().collect();
+    let diff = match columns.len() {
+        // predictions: year month day MJD x y UT1-UTC
+        7 => columns[6],
+        // rapid service: year month day MJD x error y error UT1-UTC error
+        10 => columns[8],
+        _ => return None,
+    };
+    let year: i32 = columns[0].parse().ok()?;
+    let month: u32 = columns[1].parse().ok()?;
+    let day: u32 = columns[2].parse().ok()?;
+    let mjd: i64 = columns[3].parse().ok()?;
+    let date = NaiveDate::from_ymd_opt(year, month, day)?;
+    // 日付と MJD が一致しない行は表の行ではない
+    if i64::from(date.num_days_from_ce()) - i64::from(mjd_epoch().num_days_from_ce()) != mjd {
+        return None;
+    }
+    Some(DiffUt1Utc {
+        date,
+        diff_nanos: parse_decimal_nanos(diff)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testmod;
+    use rstest::*;
+
+    #[test]
+    fn test_from_finals2000a() {
+        let mut lines = testmod::FINALS2000A.to_vec();
+        lines.push("17 1 3 57756.00");
+        lines.push("");
+
+        let table = Ut1UtcTable::from_finals2000a(lines).unwrap();
+
+        assert_eq!(table.len(), 4);
+        assert_eq!(table[0].date, NaiveDate::from_ymd(2016, 12, 30));
+        assert_eq!(table[0].diff_nanos, -405_912_400);
+        assert_eq!(table[3].date, NaiveDate::from_ymd(2017, 1, 2));
+        assert_eq!(table[3].diff_nanos, 591_803_400);
+    }
+
+    #[rstest]
+    #[case("17 1 1 57754.5  I  0.048778 0.000031  0.266532 0.000030  I 0.5925650 0.0000088")]
+    #[case("17 1 1 5775A.00 I  0.048778 0.000031  0.266532 0.000030  I 0.5925650 0.0000088")]
+    #[case("17 1 1 57754.00 I  0.048778 0.000031  0.266532 0.000030  I 0.59256A0 0.0000088")]
+    #[case("2017-01-01 0.5925650")]
+    fn test_from_finals2000a_illegal(#[case] line: &str) {
+        let lines = vec![testmod::FINALS2000A[0], line];
+
+        let result = Ut1UtcTable::from_finals2000a(lines);
+
+        assert_eq!(
+            result.unwrap_err(),
+            Error::EopTableLineError(2, Box::new(Error::EopTableParseError(line.to_string())))
+        );
+    }
+
+    #[test]
+    fn test_from_bulletin_a() {
+        let lines = vec![
+            "      MJD      x    error     y    error   UT1-UTC   error",
+            "       2017  1  2  57755 0.04878 .00003 0.26653 .00003  0.591803 0.000009",
+            "       2017  1  1  57754 0.04878 .00003 0.26653 .00003  0.592565 0.000009",
+            // celestial pole offsets
+            "       2017  1  1  57754    0.252    0.102  -0.149    0.049",
+            "     2017  1  3  57756       0.0488   0.2665   0.59105",
+            // the MJD does not match the date
+            "     2017  1  4  57754       0.0488   0.2665   0.59105",
+        ];
+
+        let table = Ut1UtcTable::from_bulletin_a(lines).unwrap();
+
+        assert_eq!(
+            table.to_vec(),
+            vec![
+                DiffUt1Utc {
+                    date: NaiveDate::from_ymd(2017, 1, 1),
+                    diff_nanos: 592_565_000
+                },
+                DiffUt1Utc {
+                    date: NaiveDate::from_ymd(2017, 1, 2),
+                    diff_nanos: 591_803_000
+                },
+                DiffUt1Utc {
+                    date: NaiveDate::from_ymd(2017, 1, 3),
+                    diff_nanos: 591_050_000
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_bulletin_a_empty() {
+        let result = Ut1UtcTable::from_bulletin_a(vec!["IERS BULLETIN - A"]);
+
+        assert_eq!(
+            result.unwrap_err(),
+            Error::EopTableParseError("no values of UT1-UTC are found".to_string())
+        );
+    }
+}
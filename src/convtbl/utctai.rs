@@ -1,5 +1,5 @@
-use super::TaiUtcTable;
-use crate::{error::Error, normalize_leap};
+use super::{DiffTaiUtc, Drift, TaiUtcTable};
+use crate::{error::Error, normalize_leap, UtcLabel};
 use chrono::{Duration, NaiveDateTime};
 use std::fmt;
 
@@ -14,14 +14,37 @@ pub struct DiffUtcTai {
     pub diff_seconds: i64,
     /// The part of the difference (UTC - TAI) that is not carried forward to the minute.
     pub corr_seconds: u32,
+    /// The sub-second part of `corr_seconds`; it is not zero only before 1972.
+    pub corr_nanos: u32,
+    /// The drift of the difference (TAI - UTC) before 1972; see [`Drift`].
+    pub drift: Option<Drift>,
+}
+
+impl DiffUtcTai {
+    /// Calculate the UTC datetime from the TAI datetime with this row.
+    ///
+    /// # Arguments
+    /// * `tai` - A TAI datetime which this row dominates
+    ///
+    /// # Returns
+    /// Returns the label of UTC; it may have the second 61 or more if two or more leap seconds are inserted at once.
+    pub fn apply(&self, tai: &NaiveDateTime) -> UtcLabel {
+        let shifted = *tai + Duration::seconds(self.diff_seconds);
+        let utc = match &self.drift {
+            Some(drift) => drift.remove_from(&shifted),
+            None => shifted,
+        };
+        UtcLabel::from(&utc)
+            .stretched(u64::from(self.corr_seconds) * 1_000_000_000 + u64::from(self.corr_nanos))
+    }
 }
 
 impl fmt::Display for DiffUtcTai {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "({}, {}+{})",
-            self.datetime, self.diff_seconds, self.corr_seconds
+            "({}, {}+{}.{:09})",
+            self.datetime, self.diff_seconds, self.corr_seconds, self.corr_nanos
         )
     }
 }
@@ -82,36 +105,48 @@ impl UtcTaiTable {
             }
             prev_diff = Some(diff_utc_tai);
         }
-        return match prev_diff {
+        match prev_diff {
             Some(diff_utc_tai) => Ok(diff_utc_tai),
             None => Err(Error::DatetimeTooLowError(datetime.to_string()))?,
-        };
+        }
     }
 }
 
 impl From<&TaiUtcTable> for UtcTaiTable {
     fn from(tai_utc_table: &TaiUtcTable) -> Self {
         let mut diff_list = Vec::new();
-        let mut prev_diff = i64::MAX;
+        let mut prev_diff: Option<&DiffTaiUtc> = None;
         for diff_tai_utc in tai_utc_table.iter() {
-            if prev_diff < diff_tai_utc.diff_seconds {
-                let corr_seconds = diff_tai_utc.diff_seconds - prev_diff;
-                diff_list.push(DiffUtcTai {
-                    datetime: normalize_leap(&diff_tai_utc.datetime)
-                        + Duration::seconds(diff_tai_utc.diff_seconds - corr_seconds),
-                    diff_seconds: -diff_tai_utc.diff_seconds,
-                    corr_seconds: corr_seconds as u32,
-                })
+            let utc = normalize_leap(&diff_tai_utc.datetime);
+            let start = utc + diff_tai_utc.diff_at(&utc);
+            if let Some(prev_diff) = prev_diff {
+                // The TAI datetime at which the previous difference ends
+                let end = utc + prev_diff.diff_at(&utc);
+                if end < start {
+                    let corr = start - end;
+                    let corr_seconds = corr.num_seconds();
+                    let corr_nanos = (corr - Duration::seconds(corr_seconds))
+                        .num_nanoseconds()
+                        .unwrap();
+                    diff_list.push(DiffUtcTai {
+                        datetime: end,
+                        diff_seconds: -diff_tai_utc.diff_seconds,
+                        corr_seconds: corr_seconds as u32,
+                        corr_nanos: corr_nanos as u32,
+                        drift: diff_tai_utc.drift,
+                    })
+                }
             }
             diff_list.push(DiffUtcTai {
-                datetime: normalize_leap(&diff_tai_utc.datetime)
-                    + Duration::seconds(diff_tai_utc.diff_seconds),
+                datetime: start,
                 diff_seconds: -diff_tai_utc.diff_seconds,
                 corr_seconds: 0,
+                corr_nanos: 0,
+                drift: diff_tai_utc.drift,
             });
-            prev_diff = diff_tai_utc.diff_seconds;
+            prev_diff = Some(diff_tai_utc);
         }
-        return UtcTaiTable(diff_list);
+        UtcTaiTable(diff_list)
     }
 }
 
@@ -139,25 +174,25 @@ mod tests {
     )]
     #[case(
         NaiveDate::from_ymd(2012, 7, 1).and_hms(0, 0, 35),
-        Some(DiffUtcTai{datetime: NaiveDate::from_ymd(2012, 7, 1).and_hms(0, 0, 35), diff_seconds: -35, corr_seconds: 0}),
+        Some(DiffUtcTai{datetime: NaiveDate::from_ymd(2012, 7, 1).and_hms(0, 0, 35), diff_seconds: -35, corr_seconds: 0, corr_nanos: 0, drift: None}),
         None,
     )]
     #[case(
         NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 34),
-        Some(DiffUtcTai{datetime: NaiveDate::from_ymd(2012, 7, 1).and_hms(0, 0, 35), diff_seconds: -35, corr_seconds: 0}),
+        Some(DiffUtcTai{datetime: NaiveDate::from_ymd(2012, 7, 1).and_hms(0, 0, 35), diff_seconds: -35, corr_seconds: 0, corr_nanos: 0, drift: None}),
         None,
     )]
     #[case(
         NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 35),
-        Some(DiffUtcTai{datetime: NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 35), diff_seconds: -36, corr_seconds: 1}),
+        Some(DiffUtcTai{datetime: NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 35), diff_seconds: -36, corr_seconds: 1, corr_nanos: 0, drift: None}),
         None,
     )]
     #[case(
         NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 36),
-        Some(DiffUtcTai{datetime: NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 36), diff_seconds: -36, corr_seconds: 0}),
+        Some(DiffUtcTai{datetime: NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 36), diff_seconds: -36, corr_seconds: 0, corr_nanos: 0, drift: None}),
         None,
     )]
-    fn test_pick_dominant_row<'a>(
+    fn test_pick_dominant_row(
         #[case] dt_input: NaiveDateTime,
         #[case] expected_ok: Option<DiffUtcTai>,
         #[case] expected_err: Option<Error>,
@@ -218,4 +253,36 @@ mod tests {
         assert_eq!(diff_utc_tai_list[3].diff_seconds, -35);
         assert_eq!(diff_utc_tai_list[3].corr_seconds, 0);
     }
+
+    /// Tests that the steps of TAI - UTC before 1972 are also taken into account.
+    #[test]
+    fn test_from_tai_utc_table_rubber_seconds() {
+        let tai_utc_table = TaiUtcTable::from_tai_utc_dat(testmod::TAI_UTC_DAT).unwrap();
+
+        let utc_tai_table = UtcTaiTable::from(&tai_utc_table);
+
+        let diff_utc_tai_list: Vec<&DiffUtcTai> = utc_tai_table.iter().collect();
+        assert_eq!(diff_utc_tai_list.len(), 6);
+        // TAI - UTC decreases by 0.05 seconds at 1961-08-01; no row is inserted.
+        assert_eq!(
+            diff_utc_tai_list[1].datetime,
+            NaiveDateTime::from_str("1961-08-01T00:00:01.647570").unwrap()
+        );
+        assert_eq!(diff_utc_tai_list[1].corr_seconds, 0);
+        assert_eq!(diff_utc_tai_list[1].corr_nanos, 0);
+        // TAI - UTC increases by 0.107758 seconds at 1972-01-01; the row for the inserted time precedes.
+        assert_eq!(
+            diff_utc_tai_list[4].datetime,
+            NaiveDateTime::from_str("1972-01-01T00:00:09.892242").unwrap()
+        );
+        assert_eq!(diff_utc_tai_list[4].diff_seconds, -10);
+        assert_eq!(diff_utc_tai_list[4].corr_seconds, 0);
+        assert_eq!(diff_utc_tai_list[4].corr_nanos, 107_758_000);
+        assert_eq!(diff_utc_tai_list[4].drift, None);
+        assert_eq!(
+            diff_utc_tai_list[5].datetime,
+            NaiveDateTime::from_str("1972-01-01T00:00:10").unwrap()
+        );
+        assert_eq!(diff_utc_tai_list[5].corr_nanos, 0);
+    }
 }
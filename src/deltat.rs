@@ -0,0 +1,299 @@
+//! ΔT (TT - UT) for the datetimes which the TAI-UTC table or the EOP table does not cover.
+//!
+//! Before UTC was defined, the leap seconds cannot relate UT to TT.
+//! Instead, ΔT is given by a model; the polynomials of Espenak and Meeus or a table given by a user.
+//! The results calculated with ΔT are approximations and should be marked as model-derived;
+//! see [`convert_dt_marked`](crate::convgraph::convert_dt_marked).
+use crate::convtbl::DeltaTTable;
+use crate::error::Error;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+
+/// Models of ΔT
+#[derive(Debug)]
+pub enum DeltaTModel {
+    /// The polynomials of [Espenak and Meeus](https://eclipse.gsfc.nasa.gov/SEhelp/deltatpoly2004.html) (2006)
+    ///
+    /// The linear coefficient of 1860–1900 is adjusted so that ΔT does not jump by 0.088 seconds at 1900.
+    EspenakMeeus,
+    /// The table given by a user; see [`DeltaTTable`]
+    Table(DeltaTTable),
+}
+
+impl DeltaTModel {
+    /// The names of the models in the order of the variants
+    pub const NAMES: [&'static str; 2] = ["espenak-meeus", "table"];
+
+    /// The name of the model used in command arguments; for example, `"espenak-meeus"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DeltaTModel::EspenakMeeus => "espenak-meeus",
+            DeltaTModel::Table(_) => "table",
+        }
+    }
+
+    /// Calculate ΔT.
+    ///
+    /// # Arguments
+    /// * `datetime` - Datetime in UT; TT can be also used since the difference does not matter.
+    ///
+    /// # Returns
+    /// Returns ΔT.
+    ///
+    /// Returns [`Error`](crate::error::Error) if the table does not cover the datetime.
+    ///
+    /// # Examples
+    /// ```
+    /// use convdate::deltat::DeltaTModel;
+    /// use chrono::{Duration, NaiveDate};
+    ///
+    /// let delta_t = DeltaTModel::EspenakMeeus.delta_t(&NaiveDate::from_ymd(1900, 1, 1).and_hms(0, 0, 0));
+    /// assert_eq!(delta_t, Ok(Duration::milliseconds(-2790)));
+    /// ```
+    pub fn delta_t(&self, datetime: &NaiveDateTime) -> Result<Duration, Error> {
+        match self {
+            DeltaTModel::EspenakMeeus => {
+                let seconds = espenak_meeus(decimal_year(datetime));
+                Ok(Duration::nanoseconds((seconds * 1e9).round() as i64))
+            }
+            DeltaTModel::Table(table) => table.delta_t(datetime),
+        }
+    }
+}
+
+/// Convert datetime from UT to TT with ΔT.
+///
+/// # Arguments
+/// * `datetime` - Datetime in UT
+/// * `model` - The model of ΔT
+///
+/// # Returns
+/// Returns the datetime in TT.
+///
+/// Returns [`Error`](crate::error::Error) if the model cannot give ΔT at the datetime.
+pub fn ut2tt_dt(datetime: &NaiveDateTime, model: &DeltaTModel) -> Result<NaiveDateTime, Error> {
+    Ok(*datetime + model.delta_t(datetime)?)
+}
+
+/// Convert datetime from TT to UT with ΔT.
+///
+/// It is the inverse of [`ut2tt_dt`]; ΔT is evaluated at the datetime in UT approximated once.
+///
+/// # Arguments
+/// * `datetime` - Datetime in TT
+/// * `model` - The model of ΔT
+///
+/// # Returns
+/// Returns the datetime in UT.
+///
+/// Returns [`Error`](crate::error::Error) if the model cannot give ΔT at the datetime.
+pub fn tt2ut_dt(datetime: &NaiveDateTime, model: &DeltaTModel) -> Result<NaiveDateTime, Error> {
+    let approx = *datetime - model.delta_t(datetime)?;
+    Ok(*datetime
+        - model
+            .delta_t(&approx)
+            .map_err(|_e| Error::DeltaTOutOfCoverageError(datetime.to_string()))?)
+}
+
+/// The decimal year of the datetime; for example, 2000.5 for the middle of 2000.
+fn decimal_year(datetime: &NaiveDateTime) -> f64 {
+    let year = datetime.year();
+    let start = NaiveDate::from_ymd(year, 1, 1).and_hms(0, 0, 0);
+    let end = NaiveDate::from_ymd(year + 1, 1, 1).and_hms(0, 0, 0);
+    let elapsed = (*datetime - start).num_milliseconds() as f64;
+    f64::from(year) + elapsed / (end - start).num_milliseconds() as f64
+}
+
+/// Calculate ΔT in seconds by the polynomials of Espenak and Meeus.
+fn espenak_meeus(y: f64) -> f64 {
+    let polynomial = |t: f64, coefficients: &[f64]| {
+        coefficients
+            .iter()
+            .rev()
+            .fold(0.0, |sum, coefficient| sum * t + coefficient)
+    };
+    // The long-term parabola of Morrison and Stephenson
+    let parabola = |y: f64| -20.0 + 32.0 * ((y - 1820.0) / 100.0).powi(2);
+
+    match y {
+        y if y < -500.0 => parabola(y),
+        y if y < 500.0 => polynomial(
+            y / 100.0,
+            &[
+                10583.6,
+                -1014.41,
+                33.78311,
+                -5.952053,
+                -0.1798452,
+                0.022174192,
+                0.0090316521,
+            ],
+        ),
+        y if y < 1600.0 => polynomial(
+            (y - 1000.0) / 100.0,
+            &[
+                1574.2,
+                -556.01,
+                71.23472,
+                0.319781,
+                -0.8503463,
+                -0.005050998,
+                0.0083572073,
+            ],
+        ),
+        y if y < 1700.0 => polynomial(y - 1600.0, &[120.0, -0.9808, -0.01532, 1.0 / 7129.0]),
+        y if y < 1800.0 => polynomial(
+            y - 1700.0,
+            &[8.83, 0.1603, -0.0059285, 0.00013336, -1.0 / 1_174_000.0],
+        ),
+        y if y < 1860.0 => polynomial(
+            y - 1800.0,
+            &[
+                13.72,
+                -0.332447,
+                0.0068612,
+                0.0041116,
+                -0.00037436,
+                0.0000121272,
+                -0.0000001699,
+                0.000000000875,
+            ],
+        ),
+        // 線形の係数を 0.5737 から補正し、1900年で次の多項式 (-2.79) に連続させる
+        y if y < 1900.0 => polynomial(
+            y - 1860.0,
+            &[
+                7.62,
+                0.5737 - 0.088_400_368 / 40.0,
+                -0.251754,
+                0.01680668,
+                -0.0004473624,
+                1.0 / 233_174.0,
+            ],
+        ),
+        y if y < 1920.0 => polynomial(
+            y - 1900.0,
+            &[-2.79, 1.494119, -0.0598939, 0.0061966, -0.000197],
+        ),
+        y if y < 1941.0 => polynomial(y - 1920.0, &[21.20, 0.84493, -0.076100, 0.0020936]),
+        y if y < 1961.0 => polynomial(y - 1950.0, &[29.07, 0.407, -1.0 / 233.0, 1.0 / 2547.0]),
+        y if y < 1986.0 => polynomial(y - 1975.0, &[45.45, 1.067, -1.0 / 260.0, -1.0 / 718.0]),
+        y if y < 2005.0 => polynomial(
+            y - 2000.0,
+            &[
+                63.86,
+                0.3345,
+                -0.060374,
+                0.0017275,
+                0.000651814,
+                0.00002373599,
+            ],
+        ),
+        y if y < 2050.0 => polynomial(y - 2000.0, &[62.92, 0.32217, 0.005589]),
+        y if y < 2150.0 => parabola(y) - 0.5628 * (2150.0 - y),
+        y => parabola(y),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convtbl::DeltaTTable;
+    use chrono::SubsecRound;
+    use rstest::*;
+
+    #[rstest]
+    #[case(-1000.0, 25427.68)]
+    #[case(0.0, 10583.6)]
+    #[case(1000.0, 1574.2)]
+    #[case(1600.0, 120.0)]
+    #[case(1700.0, 8.83)]
+    #[case(1800.0, 13.72)]
+    #[case(1900.0, -2.79)]
+    #[case(1950.0, 29.07)]
+    #[case(2000.0, 63.86)]
+    #[case(2010.0, 66.7006)]
+    #[case(2100.0, 202.74)]
+    #[case(3000.0, 4435.68)]
+    fn test_espenak_meeus(#[case] year: f64, #[case] expected: f64) {
+        assert!((espenak_meeus(year) - expected).abs() < 1e-6);
+    }
+
+    /// The polynomials are almost continuous at the boundaries.
+    #[rstest]
+    #[case(-500.0)]
+    #[case(500.0)]
+    #[case(1600.0)]
+    #[case(1700.0)]
+    #[case(1800.0)]
+    #[case(1860.0)]
+    #[case(1900.0)]
+    #[case(1920.0)]
+    #[case(1941.0)]
+    #[case(1961.0)]
+    #[case(1986.0)]
+    #[case(2005.0)]
+    #[case(2050.0)]
+    #[case(2150.0)]
+    fn test_espenak_meeus_continuity(#[case] year: f64) {
+        assert!((espenak_meeus(year - 1e-9) - espenak_meeus(year)).abs() < 2.0);
+    }
+
+    /// The polynomials before and after 1900 meet at the boundary.
+    #[test]
+    fn test_espenak_meeus_continuity_at_1900() {
+        assert!((espenak_meeus(1900.0 - 1e-9) - espenak_meeus(1900.0)).abs() < 1e-6);
+    }
+
+    #[rstest]
+    #[case(NaiveDate::from_ymd(2000, 1, 1).and_hms(0, 0, 0), 2000.0)]
+    #[case(NaiveDate::from_ymd(2000, 7, 2).and_hms(0, 0, 0), 2000.5)]
+    #[case(NaiveDate::from_ymd(2001, 7, 2).and_hms(12, 0, 0), 2001.5)]
+    #[case(NaiveDate::from_ymd(-1, 1, 1).and_hms(0, 0, 0), -1.0)]
+    fn test_decimal_year(#[case] datetime: NaiveDateTime, #[case] expected: f64) {
+        assert!((decimal_year(&datetime) - expected).abs() < 1e-12);
+    }
+
+    #[rstest]
+    #[case(DeltaTModel::EspenakMeeus, NaiveDate::from_ymd(1800, 1, 1).and_hms(0, 0, 0), Ok(NaiveDate::from_ymd(1800, 1, 1).and_hms_milli(0, 0, 13, 720)))]
+    #[case(
+        DeltaTModel::Table(DeltaTTable::from_lines(vec!["1800 1 1 13.7", "1800 1 2 13.9"]).unwrap()),
+        NaiveDate::from_ymd(1800, 1, 1).and_hms(12, 0, 0),
+        Ok(NaiveDate::from_ymd(1800, 1, 1).and_hms_milli(12, 0, 13, 800))
+    )]
+    #[case(
+        DeltaTModel::Table(DeltaTTable::from_lines(vec!["1800 1 1 13.7", "1800 1 2 13.9"]).unwrap()),
+        NaiveDate::from_ymd(1800, 1, 3).and_hms(0, 0, 0),
+        Err(Error::DeltaTOutOfCoverageError("1800-01-03 00:00:00".to_string()))
+    )]
+    fn test_ut2tt_dt(
+        #[case] model: DeltaTModel,
+        #[case] datetime: NaiveDateTime,
+        #[case] expected: Result<NaiveDateTime, Error>,
+    ) {
+        assert_eq!(ut2tt_dt(&datetime, &model), expected);
+    }
+
+    #[rstest]
+    #[case(DeltaTModel::EspenakMeeus, NaiveDate::from_ymd(1800, 1, 1).and_hms_milli(0, 0, 13, 720), Ok(NaiveDate::from_ymd(1800, 1, 1).and_hms(0, 0, 0)))]
+    #[case(
+        DeltaTModel::Table(DeltaTTable::from_lines(vec!["1800 1 1 13.7", "1800 1 2 13.9"]).unwrap()),
+        NaiveDate::from_ymd(1800, 1, 1).and_hms_milli(12, 0, 13, 800),
+        Ok(NaiveDate::from_ymd(1800, 1, 1).and_hms(12, 0, 0))
+    )]
+    #[case(
+        DeltaTModel::Table(DeltaTTable::from_lines(vec!["1800 1 1 13.7", "1800 1 2 13.9"]).unwrap()),
+        NaiveDate::from_ymd(1800, 1, 1).and_hms_milli(0, 0, 13, 700),
+        Err(Error::DeltaTOutOfCoverageError("1800-01-01 00:00:13.700".to_string()))
+    )]
+    fn test_tt2ut_dt(
+        #[case] model: DeltaTModel,
+        #[case] datetime: NaiveDateTime,
+        #[case] expected: Result<NaiveDateTime, Error>,
+    ) {
+        // 反復が1回のため、ナノ秒単位の誤差は許容する
+        assert_eq!(
+            tt2ut_dt(&datetime, &model).map(|dt| dt.round_subsecs(6)),
+            expected
+        );
+    }
+}
@@ -0,0 +1,55 @@
+/// The formats to parse input datetimes and to format output datetimes
+///
+/// The functions converting datetime strings, for example [`utc2tai`](crate::utc2tai), take it as `impl Into<DtFmts>`;
+/// a format `&str` is used for both of them, and a pair `(&str, &str)` specifies the format to parse and the one to format.
+///
+/// # Examples
+/// ```
+/// use convdate;
+/// use convdate::convtbl::TaiUtcTable;
+///
+/// // Usually, lines read from the file are used as the argument of `from_lines`.
+/// let tai_utc_table = TaiUtcTable::from_lines(vec!["2015-07-01T00:00:00 36", "2017-01-01T00:00:00 37"], "%Y-%m-%dT%H:%M:%S").unwrap();
+///
+/// let tai = convdate::utc2tai(
+///     "2016-12-31T23:59:60",
+///     &tai_utc_table,
+///     ("%Y-%m-%dT%H:%M:%S", "%Y%m%d%H%M%S%.3f"));
+///
+/// assert_eq!(tai, Ok("20170101000036.000".to_string()));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DtFmts<'a> {
+    /// [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) to parse input datetimes
+    pub parse: &'a str,
+    /// [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) to format output datetimes
+    pub format: &'a str,
+}
+
+impl<'a> DtFmts<'a> {
+    /// Create the formats to parse input datetimes along `parse` and to format output datetimes along `format`.
+    pub fn new(parse: &'a str, format: &'a str) -> DtFmts<'a> {
+        DtFmts { parse, format }
+    }
+}
+
+impl<'a> From<&'a str> for DtFmts<'a> {
+    /// Use the format both to parse and to format.
+    fn from(dt_fmt: &'a str) -> Self {
+        DtFmts::new(dt_fmt, dt_fmt)
+    }
+}
+
+impl<'a> From<&'a String> for DtFmts<'a> {
+    /// Use the format both to parse and to format.
+    fn from(dt_fmt: &'a String) -> Self {
+        DtFmts::new(dt_fmt, dt_fmt)
+    }
+}
+
+impl<'a> From<(&'a str, &'a str)> for DtFmts<'a> {
+    /// Use the first format to parse and the second one to format.
+    fn from((parse, format): (&'a str, &'a str)) -> Self {
+        DtFmts::new(parse, format)
+    }
+}
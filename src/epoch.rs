@@ -0,0 +1,277 @@
+//! Numeric representations of datetimes; the number of seconds or days since an epoch.
+//!
+//! The representations below are supported; each of them can be used in any time scale.
+//! - Unix time: seconds since [`unix_epoch`]
+//! - seconds since [`epoch_1958`], the origin of TAI
+//! - seconds since [`j2000_epoch`], usually in TT
+//! - serial days of Excel and LibreOffice since [`excel_epoch`]
+//!
+//! Every day is counted as 86400 seconds, as Unix time ignores leap seconds.
+//! Therefore, an inserted leap second in UTC cannot be represented
+//! and [`format_seconds`] and [`format_excel`] return an error for it.
+//! To count the SI seconds including leap seconds, convert the datetimes to TAI or TT; for example, seconds since 1958-01-01 in TAI.
+use crate::error::Error;
+use crate::julian::{format_mjd, parse_mjd};
+use crate::parse_decimal_nanos;
+use chrono::{Duration, NaiveDate, NaiveDateTime, Timelike};
+
+/// MJD of [`excel_epoch`]
+const EXCEL_EPOCH_MJD: i64 = 15018;
+
+/// The epoch of Unix time; 1970-01-01T00:00:00
+pub fn unix_epoch() -> NaiveDateTime {
+    NaiveDate::from_ymd(1970, 1, 1).and_hms(0, 0, 0)
+}
+
+/// The origin of TAI; 1958-01-01T00:00:00
+pub fn epoch_1958() -> NaiveDateTime {
+    NaiveDate::from_ymd(1958, 1, 1).and_hms(0, 0, 0)
+}
+
+/// The epoch J2000.0; 2000-01-01T12:00:00
+pub fn j2000_epoch() -> NaiveDateTime {
+    NaiveDate::from_ymd(2000, 1, 1).and_hms(12, 0, 0)
+}
+
+/// The epoch of the modified julian date; 1858-11-17
+pub(crate) fn mjd_epoch() -> NaiveDate {
+    NaiveDate::from_ymd(1858, 11, 17)
+}
+
+/// The epoch of the serial days of Excel and LibreOffice; 1899-12-30T00:00:00
+///
+/// Excel regards 1900 as a leap year, so its serial days before 1900-03-01 differ from this by one day.
+pub fn excel_epoch() -> NaiveDateTime {
+    NaiveDate::from_ymd(1899, 12, 30).and_hms(0, 0, 0)
+}
+
+/// Parse the number of seconds since the epoch.
+///
+/// # Arguments
+/// * `datetime` - The number of seconds; for example, `1483228800.5`
+/// * `epoch` - The epoch; for example, [`unix_epoch`]
+///
+/// # Returns
+/// Returns the datetime.
+///
+/// Returns [`Error`](crate::error::Error) if `datetime` is illegal.
+///
+/// # Examples
+/// ```
+/// use convdate::epoch::{parse_seconds, unix_epoch};
+/// use chrono::NaiveDate;
+///
+/// assert_eq!(
+///     parse_seconds("1483228800.5", &unix_epoch()),
+///     Ok(NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 0, 500)),
+/// );
+/// ```
+pub fn parse_seconds(datetime: &str, epoch: &NaiveDateTime) -> Result<NaiveDateTime, Error> {
+    let parse_err = || Error::DatetimeParseError(datetime.to_string());
+    let nanos = parse_decimal_nanos(datetime).ok_or_else(parse_err)?;
+    epoch
+        .checked_add_signed(Duration::nanoseconds(nanos))
+        .ok_or_else(parse_err)
+}
+
+/// Format the datetime as the number of seconds since the epoch.
+///
+/// The number has the fractional part only if it is needed.
+///
+/// # Arguments
+/// * `datetime` - A datetime
+/// * `epoch` - The epoch; for example, [`unix_epoch`]
+///
+/// # Returns
+/// Returns the number of seconds.
+///
+/// Returns [`Error`](crate::error::Error) if `datetime` is in a leap second.
+///
+/// # Examples
+/// ```
+/// use convdate::epoch::{format_seconds, unix_epoch};
+/// use convdate::error::Error;
+/// use chrono::NaiveDate;
+///
+/// let datetime = NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 0, 500);
+/// assert_eq!(format_seconds(&datetime, &unix_epoch()), Ok("1483228800.5".to_string()));
+///
+/// // 2016-12-31T23:59:60.5
+/// let datetime = NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1500);
+/// assert_eq!(
+///     format_seconds(&datetime, &unix_epoch()),
+///     Err(Error::LeapSecondNotRepresentableError("2016-12-31 23:59:60.500".to_string())),
+/// );
+/// ```
+pub fn format_seconds(datetime: &NaiveDateTime, epoch: &NaiveDateTime) -> Result<String, Error> {
+    reject_leap_second(datetime)?;
+    let elapsed = *datetime - *epoch;
+    let seconds = elapsed.num_seconds();
+    let nanos = (elapsed - Duration::seconds(seconds))
+        .num_nanoseconds()
+        .unwrap();
+    // Both of `seconds` and `nanos` have the same sign.
+    let sign = if seconds < 0 || nanos < 0 { "-" } else { "" };
+    let (seconds, nanos) = (seconds.abs(), nanos.abs());
+
+    if nanos == 0 {
+        Ok(format!("{}{}", sign, seconds))
+    } else {
+        let fraction = format!("{:09}", nanos);
+        Ok(format!(
+            "{}{}.{}",
+            sign,
+            seconds,
+            fraction.trim_end_matches('0')
+        ))
+    }
+}
+
+/// Parse the serial day of Excel and LibreOffice.
+///
+/// # Arguments
+/// * `datetime` - The serial day; for example, `42736.5`
+///
+/// # Returns
+/// Returns the datetime.
+///
+/// Returns [`Error`](crate::error::Error) if `datetime` is illegal.
+///
+/// # Examples
+/// ```
+/// use convdate::epoch::parse_excel;
+/// use chrono::NaiveDate;
+///
+/// assert_eq!(
+///     parse_excel("42736.5"),
+///     Ok(NaiveDate::from_ymd(2017, 1, 1).and_hms(12, 0, 0)),
+/// );
+/// ```
+pub fn parse_excel(datetime: &str) -> Result<NaiveDateTime, Error> {
+    let parse_err = || Error::DatetimeParseError(datetime.to_string());
+    // serial day = MJD - EXCEL_EPOCH_MJD
+    // 範囲外の日数は parse_mjd が解析エラーにする
+    parse_mjd(datetime, None)
+        .map_err(|_| parse_err())?
+        .checked_add_signed(Duration::days(EXCEL_EPOCH_MJD))
+        .ok_or_else(parse_err)
+}
+
+/// Format the datetime as the serial day of Excel and LibreOffice.
+///
+/// The serial day has the fractional part only if it is needed.
+///
+/// # Arguments
+/// * `datetime` - A datetime
+///
+/// # Returns
+/// Returns the serial day.
+///
+/// Returns [`Error`](crate::error::Error) if `datetime` is in a leap second.
+///
+/// # Examples
+/// ```
+/// use convdate::epoch::format_excel;
+/// use chrono::NaiveDate;
+///
+/// let datetime = NaiveDate::from_ymd(2017, 1, 1).and_hms(12, 0, 0);
+///
+/// assert_eq!(format_excel(&datetime), Ok("42736.5".to_string()));
+/// ```
+pub fn format_excel(datetime: &NaiveDateTime) -> Result<String, Error> {
+    reject_leap_second(datetime)?;
+    let shifted = datetime
+        .checked_sub_signed(Duration::days(EXCEL_EPOCH_MJD))
+        .ok_or_else(|| Error::DatetimeTooLowError(datetime.to_string()))?;
+    Ok(format_mjd(&shifted, None))
+}
+
+/// Return an error if the datetime is in a leap second, which the numeric representations cannot express.
+pub(crate) fn reject_leap_second(datetime: &NaiveDateTime) -> Result<(), Error> {
+    if datetime.nanosecond() >= 1_000_000_000 {
+        Err(Error::LeapSecondNotRepresentableError(datetime.to_string()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testmod;
+    use rstest::*;
+
+    #[rstest]
+    #[case("0", unix_epoch(), Some(NaiveDate::from_ymd(1970, 1, 1).and_hms(0, 0, 0)), None)]
+    #[case("1483228800", unix_epoch(), Some(NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0)), None)]
+    #[case("1483228799.123456789", unix_epoch(), Some(NaiveDate::from_ymd(2016, 12, 31).and_hms_nano(23, 59, 59, 123_456_789)), None)]
+    #[case("-0.5", unix_epoch(), Some(NaiveDate::from_ymd(1969, 12, 31).and_hms_milli(23, 59, 59, 500)), None)]
+    #[case("1861920037", epoch_1958(), Some(NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 37)), None)]
+    #[case("536500869.184", j2000_epoch(), Some(NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 1, 9, 184)), None)]
+    // Illegal notations
+    #[case("1483228800.0000000001", unix_epoch(), None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("1.4832288e9", unix_epoch(), None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("2017-01-01T00:00:00", unix_epoch(), None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    // Out of the range of datetimes
+    #[case("99999999999999999999", unix_epoch(), None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    fn test_parse_seconds(
+        #[case] datetime: &str,
+        #[case] epoch: NaiveDateTime,
+        #[case] expected_ok: Option<NaiveDateTime>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok, expected_err);
+
+        assert_eq!(parse_seconds(datetime, &epoch), expected);
+    }
+
+    #[rstest]
+    #[case(NaiveDate::from_ymd(1970, 1, 1).and_hms(0, 0, 0), unix_epoch(), Some("0"), None)]
+    #[case(NaiveDate::from_ymd(2016, 12, 31).and_hms_nano(23, 59, 59, 120_000_000), unix_epoch(), Some("1483228799.12"), None)]
+    #[case(NaiveDate::from_ymd(1969, 12, 31).and_hms_milli(23, 59, 59, 500), unix_epoch(), Some("-0.5"), None)]
+    #[case(NaiveDate::from_ymd(1969, 12, 31).and_hms_milli(23, 59, 58, 500), unix_epoch(), Some("-1.5"), None)]
+    #[case(NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 37), epoch_1958(), Some("1861920037"), None)]
+    // うるう秒は Unix 時間で表せない
+    #[case(NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1000), unix_epoch(), None, Some(Error::LeapSecondNotRepresentableError("2016-12-31 23:59:60".to_string())))]
+    fn test_format_seconds(
+        #[case] datetime: NaiveDateTime,
+        #[case] epoch: NaiveDateTime,
+        #[case] expected_ok: Option<&str>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok.map(ToString::to_string), expected_err);
+
+        assert_eq!(format_seconds(&datetime, &epoch), expected);
+    }
+
+    #[rstest]
+    #[case(NaiveDate::from_ymd(1899, 12, 30).and_hms(0, 0, 0), "0")]
+    #[case(NaiveDate::from_ymd(1900, 3, 1).and_hms(6, 0, 0), "61.25")]
+    #[case(NaiveDate::from_ymd(2017, 1, 1).and_hms(12, 0, 0), "42736.5")]
+    fn test_excel(#[case] datetime: NaiveDateTime, #[case] serial: &str) {
+        assert_eq!(format_excel(&datetime), Ok(serial.to_string()));
+        assert_eq!(parse_excel(serial), Ok(datetime));
+    }
+
+    #[rstest]
+    #[case("9999999999999")]
+    #[case("-9999999999999")]
+    #[case("99999999999999999")]
+    fn test_parse_excel_out_of_range(#[case] serial: &str) {
+        assert_eq!(
+            parse_excel(serial),
+            Err(Error::DatetimeParseError(serial.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_excel_leap_second() {
+        let datetime = NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1000);
+
+        assert_eq!(
+            format_excel(&datetime),
+            Err(Error::LeapSecondNotRepresentableError(
+                "2016-12-31 23:59:60".to_string()
+            ))
+        );
+    }
+}
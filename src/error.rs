@@ -6,12 +6,118 @@ pub enum Error {
     TaiUtcTableParseError(String),
     #[error("Illegal definition of TAI-UTC difference (datetime): {0}")]
     TaiUtcTableDatetimeParseError(String),
+    #[error("line {0}: {1}")]
+    TaiUtcTableLineError(usize, Box<Error>),
+    #[error("{0}: {1}")]
+    TaiUtcTableFileError(PathBuf, Box<Error>),
     #[error("The datetime is too low: {0}")]
     DatetimeTooLowError(String),
+    #[error("The datetime is too high: {0}")]
+    DatetimeTooHighError(String),
     #[error("Cannot parse the datetime: {0}")]
     DatetimeParseError(String),
+    #[error("Cannot parse the datetime along any of the formats {1}: {0}")]
+    DatetimeParseFormatsError(String, String),
+    #[error("Unknown or illegal extended specifier in the datetime format: {0}")]
+    DtFmtSpecifierError(String),
     #[error("The TAI-UTC table file isn't available: {0}")]
     TaiUtcTableIOError(PathBuf),
     #[error("Cannot read the TAI-UTC table file as text: {0}")]
     TaiUtcTableNotTextError(PathBuf),
+    #[error("The hash of the leap-seconds.list is not found")]
+    LeapSecondsListHashNotFoundError(),
+    #[error("The hash of the leap-seconds.list does not match the data: expected {0}, actual {1}")]
+    LeapSecondsListHashMismatchError(String, String),
+    #[error("Unknown time scale: {0}")]
+    UnknownTimeScaleError(String),
+    #[error("There is no way to convert from {0} to {1}")]
+    ConversionRouteNotFoundError(String, String),
+    #[error("Unknown representation of datetimes: {0}")]
+    UnknownReprError(String),
+    #[error("The time scale {0} has no week number")]
+    WeekNotSupportedError(String),
+    #[error("Unknown model of TDB: {0}")]
+    UnknownTdbModelError(String),
+    #[error("Illegal definition of UT1-UTC difference: {0}")]
+    EopTableParseError(String),
+    #[error("line {0}: {1}")]
+    EopTableLineError(usize, Box<Error>),
+    #[error("{0}: {1}")]
+    EopTableFileError(PathBuf, Box<Error>),
+    #[error("The EOP table file isn't available: {0}")]
+    EopTableIOError(PathBuf),
+    #[error("Cannot read the EOP table file as text: {0}")]
+    EopTableNotTextError(PathBuf),
+    #[error("The EOP table is required to convert from/to UT1")]
+    EopTableNotSpecifiedError(),
+    #[error("The datetime is out of the coverage of the EOP table: {0}")]
+    EopOutOfCoverageError(String),
+    #[error("The leap second cannot be represented as a number of seconds or days: {0}")]
+    LeapSecondNotRepresentableError(String),
+    #[error("Illegal P-field of the CCSDS time code: {0}")]
+    CcsdsPFieldError(String),
+    #[error("The agency-defined epoch of the CCSDS time code is not specified")]
+    CcsdsAgencyEpochNotSpecifiedError(),
+    #[error("The datetime cannot be expressed in the CCSDS time code: {0}")]
+    CcsdsOutOfRangeError(String),
+    #[error("The time scale {0} cannot be represented as PTP timestamps")]
+    PtpNotSupportedError(String),
+    #[error("The time scale {0} has no four-year interval number N4; only GLONASS time has it")]
+    N4NtNotSupportedError(String),
+    #[error(
+        "The currentUtcOffset of the PTP record disagrees with the TAI-UTC table ({1} s): {0}"
+    )]
+    PtpUtcOffsetMismatchError(String, i64),
+    #[error("The {1} flag of the PTP record disagrees with the TAI-UTC table: {0}")]
+    PtpLeapFlagMismatchError(String, String),
+    #[error("The representation {0} can be used only for output")]
+    OutputOnlyReprError(String),
+    #[error("Sidereal time and ERA are calculated only from UT1, not {0}")]
+    SiderealNotSupportedError(String),
+    #[error("Cannot parse the longitude: {0}")]
+    LongitudeParseError(String),
+    #[error("The longitude is required to calculate the local sidereal time")]
+    LongitudeNotSpecifiedError(),
+    #[error("Illegal definition of ΔT: {0}")]
+    DeltaTTableParseError(String),
+    #[error("line {0}: {1}")]
+    DeltaTTableLineError(usize, Box<Error>),
+    #[error("{0}: {1}")]
+    DeltaTTableFileError(PathBuf, Box<Error>),
+    #[error("The ΔT table file isn't available: {0}")]
+    DeltaTTableIOError(PathBuf),
+    #[error("Cannot read the ΔT table file as text: {0}")]
+    DeltaTTableNotTextError(PathBuf),
+    #[error("The ΔT table file is required for the ΔT model \"table\"")]
+    DeltaTTableNotSpecifiedError(),
+    #[error("The datetime is out of the coverage of the ΔT table: {0}")]
+    DeltaTOutOfCoverageError(String),
+    #[error("Unknown model of ΔT: {0}")]
+    UnknownDeltaTModelError(String),
+    #[error("Unknown policy for datetimes beyond the TAI-UTC table: {0}")]
+    UnknownBeyondTablePolicyError(String),
+    #[error("The datetime is beyond the expiration of the TAI-UTC table ({1}), so leap seconds after it are unknown: {0}")]
+    TaiUtcTableExpiredError(String, String),
+    #[error("The datetime does not exist in UTC because of the leap seconds: {0}")]
+    NonexistentUtcError(String),
+    #[error("The second 61 or more cannot be represented as a datetime without leap seconds: {0}")]
+    UtcLabelNotRepresentableError(String),
+    #[error("The datetime is not a leap second of the TAI-UTC table; the nearest leap second is {1}: {0}")]
+    NotLeapSecondError(String, String),
+    #[error("Unknown or illegal shape of smearing: {0}")]
+    SmearParseError(String),
+    #[error("Unknown policy for leap seconds in output: {0}")]
+    UnknownLeapPolicyError(String),
+    #[error("The output datetime is in a leap second: {0}")]
+    LeapSecondOutputError(String),
+    #[error("Illegal definition of the time zone: {0}")]
+    TimeZoneParseError(String),
+    #[error("{0}: {1}")]
+    TimeZoneFileError(PathBuf, Box<Error>),
+    #[error("Unknown time zone: {0}")]
+    UnknownTimeZoneError(String),
+    #[error("The zoneinfo directory {0} is not found; the time zone {1} requires the TZif files of the IANA time zone database")]
+    ZoneinfoDirNotFoundError(PathBuf, String),
+    #[error("The time zone can be used only for the output datetimes of UTC, not {0}")]
+    TimeZoneNotSupportedError(String),
 }
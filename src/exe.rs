@@ -1,38 +1,131 @@
-use crate::convtbl::TaiUtcTable;
-use crate::{error::Error, DT_FMT};
-use clap::{App, Arg, ArgMatches, Values};
+use crate::convgraph::BeyondTable;
+use crate::convtbl::{DeltaTTable, TaiUtcTable, Ut1UtcTable};
+use crate::deltat::DeltaTModel;
+use crate::extfmt::ExtFormat;
+use crate::iso8601::{self, Iso8601Format};
+use crate::zoneinfo::DEFAULT_ZONEINFO_DIR;
+use crate::{error::Error, LeapPolicy, TdbModel, TimeScale, DT_FMT};
+use chrono::NaiveDateTime;
+use clap::{App, AppSettings, Arg, ArgMatches, Values};
 use std::collections::HashMap;
 use std::env;
 use std::ffi::OsString;
 use std::fs::File;
 use std::io::{BufRead, BufReader, Write};
 use std::path::PathBuf;
+pub mod convdate;
 mod converter;
 pub mod error;
 pub mod execcode;
-pub use converter::{main_convertion, Converter};
-pub mod tai2utc;
-pub mod tt2utc;
-pub mod utc2tai;
-pub mod utc2tt;
+mod repr;
+pub use converter::{main_convertion, Converter, ScaleConverter};
+pub use repr::Repr;
 
 const TAI_UTC_TABLE_FILENAME: &str = "tai-utc.txt";
-const TAI_UTC_TABLE: &str = include_str!("tai-utc.txt");
+const TAI_UTC_TABLE: &str = include_str!("tai-utc.dat");
+const EOP_TABLE_FILENAME: &str = "finals2000A.all";
+/// The separator of the formats in the environment variables `DT_FMT` and `IN_DT_FMT`
+const DT_FMT_SEP: char = '|';
+
+/// The names which can be specified as `--from` and `--to` instead of time scales;
+/// each of them stands for a time scale in a representation.
+/// The sidereal ones are accepted only by `--to`.
+const SCALE_ALIASES: [(&str, TimeScale, Repr); 7] = [
+    ("unix", TimeScale::Utc, Repr::Unix),
+    ("ntp", TimeScale::Utc, Repr::Ntp),
+    ("ptp", TimeScale::Tai, Repr::Ptp),
+    ("era", TimeScale::Ut1, Repr::Era),
+    ("gmst", TimeScale::Ut1, Repr::Gmst),
+    ("gast", TimeScale::Ut1, Repr::Gast),
+    ("lst", TimeScale::Ut1, Repr::Lst),
+];
+
+/// Parse the value of `--from` or `--to` into the time scale and the representation which it implies.
+fn parse_scale_arg(value: &str) -> (TimeScale, Option<Repr>) {
+    match SCALE_ALIASES
+        .iter()
+        .find(|(name, _, _)| name.eq_ignore_ascii_case(value))
+    {
+        Some((_, scale, repr)) => (*scale, Some(*repr)),
+        // The possible values are checked by clap.
+        None => (value.parse().unwrap(), None),
+    }
+}
 
 pub fn print_err(stderr: &mut impl Write, err: &dyn std::fmt::Display) {
     writeln!(stderr, "{}: {}", exe_name(), err).unwrap();
 }
 
 pub fn exe_name() -> String {
-    return PathBuf::from(env::args().next().unwrap())
+    PathBuf::from(env::args().next().unwrap())
         .file_name()
         .and_then(std::ffi::OsStr::to_str)
         .unwrap()
-        .to_string();
+        .to_string()
+}
+
+/// Parse the datetime along the formats in order.
+///
+/// # Arguments
+/// * `datetime` - The datetime to parse
+/// * `dt_fmts` - The formats tried in order
+/// * `parse` - The function which parses `datetime` along a format
+///
+/// # Returns
+/// Returns the result of the first format which `datetime` matches, and the format.
+///
+/// Returns [`Error`](crate::error::Error) if `datetime` matches none of the formats; it lists the formats if there are two or more.
+pub fn parse_along_dt_fmts<'f, T>(
+    datetime: &str,
+    dt_fmts: &[&'f str],
+    parse: impl Fn(&'f str) -> Option<T>,
+) -> Result<(T, &'f str), Error> {
+    for dt_fmt in dt_fmts {
+        if let Some(parsed) = parse(dt_fmt) {
+            return Ok((parsed, dt_fmt));
+        }
+    }
+    match dt_fmts {
+        [_] => Err(Error::DatetimeParseError(datetime.to_string())),
+        _ => Err(Error::DatetimeParseFormatsError(
+            datetime.to_string(),
+            dt_fmts
+                .iter()
+                .map(|dt_fmt| format!("{:?}", dt_fmt))
+                .collect::<Vec<_>>()
+                .join(", "),
+        )),
+    }
+}
+
+/// Parse the datetime along the format, which may be an ISO 8601 format or have the extended specifiers;
+/// see [`Iso8601Format::from_dt_fmt`] and [`ExtFormat::from_dt_fmt`].
+///
+/// The UTC offset of ISO 8601 is subtracted, and the second 61 or more is not accepted.
+/// `%{scale}` must match `scale`.
+pub fn parse_naive(datetime: &str, dt_fmt: &str, scale: TimeScale) -> Option<NaiveDateTime> {
+    if Iso8601Format::from_dt_fmt(dt_fmt).is_some() {
+        return iso8601::parse(datetime).ok()?.label().to_naive();
+    }
+    match ExtFormat::from_dt_fmt(dt_fmt).ok()? {
+        Some(format) => format
+            .parse(datetime, scale, None)
+            .ok()
+            .map(|(datetime, _)| datetime),
+        None => NaiveDateTime::parse_from_str(datetime, dt_fmt).ok(),
+    }
+}
+
+/// Check the extended specifiers in the formats; see [`ExtFormat::from_dt_fmt`].
+pub fn check_dt_fmts<'a>(dt_fmts: impl IntoIterator<Item = &'a str>) -> Result<(), Error> {
+    dt_fmts
+        .into_iter()
+        .try_for_each(|dt_fmt| ExtFormat::from_dt_fmt(dt_fmt).map(|_| ()))
 }
 
 pub fn load_tai_utc_table(
     table_file_path: Option<&PathBuf>,
+    sep: Option<&str>,
     datetime_fmt: &str,
 ) -> Result<TaiUtcTable, Error> {
     match table_file_path {
@@ -43,15 +136,109 @@ pub fn load_tai_utc_table(
                 .lines()
                 .collect::<Result<Vec<_>, _>>()
                 .map_err(|_| Error::TaiUtcTableNotTextError(table_file_path.clone()))?;
-            TaiUtcTable::from_lines(table_lines, datetime_fmt)
+            if is_leap_seconds_list(&table_lines) {
+                TaiUtcTable::from_leap_seconds_list(table_lines)
+            } else if is_tai_utc_dat(&table_lines) {
+                TaiUtcTable::from_tai_utc_dat(table_lines)
+            } else {
+                TaiUtcTable::from_lines_with_sep(table_lines, sep, datetime_fmt)
+            }
+            .map_err(|e| Error::TaiUtcTableFileError(table_file_path.clone(), Box::new(e)))
         }
         None => {
-            let table_lines: Vec<_> = TAI_UTC_TABLE.split("\n").collect();
-            TaiUtcTable::from_lines(table_lines, datetime_fmt)
+            let table_lines: Vec<_> = TAI_UTC_TABLE.split('\n').collect();
+            TaiUtcTable::from_tai_utc_dat(table_lines)
         }
     }
 }
 
+/// Whether the lines are [the IETF leap-seconds.list](https://www.ietf.org/timezones/data/leap-seconds.list) or not.
+///
+/// The leap-seconds.list is distinguished from the TAI-UTC table file of convdate by the special comment lines: `#$`, `#@` and `#h`.
+fn is_leap_seconds_list(lines: &[impl AsRef<str>]) -> bool {
+    lines.iter().any(|line| {
+        let line = line.as_ref();
+        line.starts_with("#$") || line.starts_with("#@") || line.starts_with("#h")
+    })
+}
+
+/// Whether the lines are [the USNO tai-utc.dat](https://maia.usno.navy.mil/ser7/tai-utc.dat) or not.
+///
+/// The tai-utc.dat is distinguished from the TAI-UTC table file of convdate by `TAI-UTC=` in the lines.
+fn is_tai_utc_dat(lines: &[impl AsRef<str>]) -> bool {
+    lines.iter().any(|line| line.as_ref().contains("TAI-UTC="))
+}
+
+pub fn load_eop_table(table_file_path: Option<&PathBuf>) -> Result<Option<Ut1UtcTable>, Error> {
+    let table_file_path = match table_file_path {
+        Some(table_file_path) => table_file_path,
+        None => return Ok(None),
+    };
+    let table_file =
+        File::open(table_file_path).map_err(|_| Error::EopTableIOError(table_file_path.clone()))?;
+    let table_lines = BufReader::new(table_file)
+        .lines()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| Error::EopTableNotTextError(table_file_path.clone()))?;
+    if is_finals2000a(&table_lines) {
+        Ut1UtcTable::from_finals2000a(table_lines)
+    } else {
+        Ut1UtcTable::from_bulletin_a(table_lines)
+    }
+    .map(Some)
+    .map_err(|e| Error::EopTableFileError(table_file_path.clone(), Box::new(e)))
+}
+
+/// Load the model of ΔT specified by `--delta-t`.
+///
+/// # Arguments
+/// * `model` - The name of the model; see [`DeltaTModel::NAMES`]
+/// * `table_file_path` - The ΔT table file, required if `model` is `"table"`
+pub fn load_delta_t_model(
+    model: &str,
+    table_file_path: Option<&PathBuf>,
+) -> Result<DeltaTModel, Error> {
+    if model.eq_ignore_ascii_case("espenak-meeus") {
+        return Ok(DeltaTModel::EspenakMeeus);
+    }
+    if !model.eq_ignore_ascii_case("table") {
+        return Err(Error::UnknownDeltaTModelError(model.to_string()));
+    }
+
+    let table_file_path = table_file_path.ok_or(Error::DeltaTTableNotSpecifiedError())?;
+    let table_file = File::open(table_file_path)
+        .map_err(|_| Error::DeltaTTableIOError(table_file_path.clone()))?;
+    let table_lines = BufReader::new(table_file)
+        .lines()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| Error::DeltaTTableNotTextError(table_file_path.clone()))?;
+    DeltaTTable::from_lines(table_lines)
+        .map(DeltaTModel::Table)
+        .map_err(|e| Error::DeltaTTableFileError(table_file_path.clone(), Box::new(e)))
+}
+
+/// Whether the lines are [the IERS finals2000A.all](https://datacenter.iers.org/versionMetadata.php?filename=latestVersionMeta/10_FINALS.DATA_IAU2000_V2013_0110.txt) or not.
+///
+/// The finals2000A.all is distinguished from Bulletin A by its fixed-width first line, which begins with the date and the MJD.
+fn is_finals2000a(lines: &[impl AsRef<str>]) -> bool {
+    let first_line = match lines
+        .iter()
+        .map(AsRef::as_ref)
+        .find(|l| !l.trim().is_empty())
+    {
+        Some(line) => line,
+        None => return false,
+    };
+    first_line.len() >= 68
+        && first_line[..6]
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == ' ')
+        && first_line
+            .get(7..15)
+            .map(|mjd| mjd.trim().parse::<f64>().is_ok())
+            .unwrap_or(false)
+}
+
 /// Serve a method for output to stdout
 ///
 /// # Arguments
@@ -59,7 +246,7 @@ pub fn load_tai_utc_table(
 ///
 /// # Returns
 /// A method for output to stdout. It requires arguments which it needs for output.
-pub fn get_print_line(params: &Parameters) -> fn(&mut dyn Write, &str, &str) -> () {
+pub fn get_print_line(params: &Parameters) -> fn(&mut dyn Write, &str, &str) {
     match params.io_pair_flg() {
         false => |out: &mut dyn Write, _: &str, o: &str| writeln!(out, "{}", o).unwrap(),
         true => |out: &mut dyn Write, i: &str, o: &str| writeln!(out, "{} {}", i, o).unwrap(),
@@ -70,10 +257,37 @@ pub fn get_print_line(params: &Parameters) -> fn(&mut dyn Write, &str, &str) ->
 #[derive(Debug)]
 pub struct Arguments<'a> {
     matches: ArgMatches<'a>,
+    from: Option<TimeScale>,
+    to: Option<TimeScale>,
     tai_utc_table_dt_fmt: Option<String>,
-    dt_fmt: Option<String>,
+    tai_utc_table_sep: Option<String>,
+    dt_fmt: Option<Vec<String>>,
+    in_dt_fmt: Option<Vec<String>>,
+    out_dt_fmt: Option<String>,
+    in_repr: Option<Repr>,
+    out_repr: Option<Repr>,
+    week_rollover_ref: Option<String>,
+    ccsds_pfield: Option<String>,
+    ccsds_implicit_pfield: bool,
+    ccsds_epoch: Option<String>,
+    ntp_pivot: Option<String>,
+    ntp_leap_indicator: bool,
+    ptp_record_offset: bool,
+    longitude: Option<String>,
+    tdb_model: Option<TdbModel>,
     io_pair_flg: bool,
     tai_utc_table_path: Option<String>,
+    eop_table_path: Option<String>,
+    delta_t: Option<String>,
+    delta_t_table_path: Option<String>,
+    beyond_table: Option<BeyondTable>,
+    tai_utc_table_expiration: Option<String>,
+    lenient: bool,
+    out_leap: Option<LeapPolicy>,
+    out_tz: Option<String>,
+    zoneinfo_dir: Option<String>,
+    smear: Option<String>,
+    from_smear: bool,
 }
 
 impl Arguments<'_> {
@@ -82,18 +296,141 @@ impl Arguments<'_> {
         args: impl IntoIterator<Item = impl Into<OsString> + Clone>,
     ) -> Arguments<'a> {
         let app: App<'a, 'a> = App::new(app_name)
+            // Numeric representations of datetimes may be negative.
+            .setting(AppSettings::AllowNegativeNumbers)
+            .arg(
+                Arg::with_name("from")
+                    .help("Time scale of input datetimes. \"unix\", \"ntp\" and \"ptp\" are the shorthands of \"--from utc --in-repr unix\", \"--from utc --in-repr ntp\" and \"--from tai --in-repr ptp\".")
+                    .takes_value(true)
+                    .required(true)
+                    .possible_values(&TimeScale::NAMES)
+                    // Sidereal times and ERA can be used only for output.
+                    .possible_values(
+                        &SCALE_ALIASES
+                            .iter()
+                            .filter(|(_, _, repr)| !repr.is_sidereal())
+                            .map(|(name, _, _)| *name)
+                            .collect::<Vec<_>>(),
+                    )
+                    .case_insensitive(true)
+                    .long("from"),
+            )
+            .arg(
+                Arg::with_name("to")
+                    .help("Time scale of output datetimes. \"unix\", \"ntp\" and \"ptp\" are the shorthands of \"--to utc --out-repr unix\", \"--to utc --out-repr ntp\" and \"--to tai --out-repr ptp\". \"era\", \"gmst\", \"gast\" and \"lst\" are the shorthands of \"--to ut1 --out-repr <repr>\".")
+                    .takes_value(true)
+                    .required(true)
+                    .possible_values(&TimeScale::NAMES)
+                    .possible_values(&SCALE_ALIASES.map(|(name, _, _)| name))
+                    .case_insensitive(true)
+                    .long("to"),
+            )
             .arg(
                 Arg::with_name("tai_utc_table_dt_fmt")
                     .help("Format of datetime in TAI-UTC table file. If it is not specified, the environment variable 'TAI_UTC_TABLE_DT_FMT' is used. If both of them are not specified, the default value \"%Y-%m-%dT%H:%M:%S%.3f\" is used.")
                     .takes_value(true)
                     .long("tai-utc-table-dt-fmt"),
             )
+            .arg(
+                Arg::with_name("tai_utc_table_sep")
+                    .help("Separator between columns in TAI-UTC table file. If it is not specified, the environment variable 'TAI_UTC_TABLE_SEP' is used. If both of them are not specified, runs of whitespaces are used as the separator.")
+                    .takes_value(true)
+                    .long("tai-utc-table-sep"),
+            )
             .arg(
                 Arg::with_name("dt_fmt")
-                    .help("Format of <datetime>. If it is not specified, the environment variable 'DT_FMT' is used. If both of them are not specified, the default value \"%Y-%m-%dT%H:%M:%S%.3f\" is used.")
+                    .help("Format of <datetime>. If it is not specified, the environment variable 'DT_FMT' is used. If both of them are not specified, the default value \"%Y-%m-%dT%H:%M:%S%.3f\" is used. \"iso8601\" accepts any ISO 8601 datetimes and outputs them in the style of the input; the suffixes -calendar, -ordinal or -week and -basic or -extended override the style, for example \"iso8601-week-basic\". It can be specified two or more times, or as a list separated by \"|\" in 'DT_FMT'; each input datetime is parsed along the first matching format, and output in the same format unless --out-dt-fmt is specified. It is a shorthand for both --in-dt-fmt and --out-dt-fmt, which take precedence over it. The extended specifiers %{gpsweek}, %{sow}, %{doy}, %{mjd}, %{scale} and %{dAT} can be mixed with the strftime specifiers, such as \"%{gpsweek}_%{sow:.3}\"; the numeric ones take the number of digits after the decimal point, such as %{mjd:.6}.")
                     .takes_value(true)
+                    .multiple(true)
+                    .number_of_values(1)
                     .long("dt-fmt"),
             )
+            .arg(
+                Arg::with_name("in_dt_fmt")
+                    .help("Format of <datetime>. If it is not specified, --dt-fmt, the environment variable 'IN_DT_FMT' and 'DT_FMT' are used in this order. It can be specified two or more times, or as a list separated by \"|\" in 'IN_DT_FMT', like --dt-fmt.")
+                    .takes_value(true)
+                    .multiple(true)
+                    .number_of_values(1)
+                    .long("in-dt-fmt"),
+            )
+            .arg(
+                Arg::with_name("out_dt_fmt")
+                    .help("Format of output datetimes. If it is not specified, --dt-fmt, the environment variable 'OUT_DT_FMT' and 'DT_FMT' are used in this order; if none of them is specified or the used one is a list of two or more formats, each datetime is output in the format which the input matched.")
+                    .takes_value(true)
+                    .long("out-dt-fmt"),
+            )
+            .arg(
+                Arg::with_name("in_repr")
+                    .help("Representation of input datetimes. If it is \"week\", the datetimes are \"week:seconds-of-week\" in the time scale specified by --from. If it is \"jd\", \"mjd\" or \"two-part-jd\", the datetimes are Julian dates, modified Julian dates or two-part Julian dates such as \"2457754.5 0.5\" in the time scale. If it is \"unix\", \"seconds-since-1958\", \"j2000-seconds\" or \"excel\", the datetimes are numbers of seconds since 1970-01-01T00:00:00, 1958-01-01T00:00:00 or 2000-01-01T12:00:00, or serial days of Excel, in the time scale; every day is counted as 86400 seconds, so they cannot express leap seconds. If it is \"ntp\", the datetimes are NTP timestamps such as \"dc12c500.80000000\", optionally with the era such as \"1:00000000.00000000\"; see --ntp-pivot. If it is \"ptp\", the datetimes are PTP records such as \"1483228836.5 36 leap61\", seconds since 1970-01-01T00:00:00 in TAI optionally followed by currentUtcOffset and a leap flag; it can be used only in TAI. See --ptp-utc-offset. If it is \"cuc\", \"cds\" or \"ccs\", the datetimes are CCSDS binary time codes in hexadecimal such as \"1E6EFAA5258000\"; see --ccsds-pfield. If it is \"ascii-a\" or \"ascii-b\", the datetimes are CCSDS ASCII time codes such as \"2017-01-01T00:00:00.5Z\" or \"2017-001T00:00:00.5Z\". If it is not specified, the default value \"datetime\" is used.")
+                    .takes_value(true)
+                    .possible_values(&Repr::NAMES)
+                    .case_insensitive(true)
+                    .long("in-repr"),
+            )
+            .arg(
+                Arg::with_name("out_repr")
+                    .help("Representation of output datetimes. If it is \"week\", the datetimes are \"week:seconds-of-week\" in the time scale specified by --to. If it is \"jd\", \"mjd\" or \"two-part-jd\", the datetimes are Julian dates, modified Julian dates or two-part Julian dates such as \"2457754.5 0.5\" in the time scale. If it is \"unix\", \"seconds-since-1958\", \"j2000-seconds\" or \"excel\", the datetimes are numbers of seconds since 1970-01-01T00:00:00, 1958-01-01T00:00:00 or 2000-01-01T12:00:00, or serial days of Excel, in the time scale; every day is counted as 86400 seconds, so they cannot express leap seconds. If it is \"ntp\", the datetimes are NTP timestamps such as \"dc12c500.80000000\", optionally with the era such as \"1:00000000.00000000\"; see --ntp-pivot. If it is \"ptp\", the datetimes are PTP records such as \"1483228836.5 36 leap61\", seconds since 1970-01-01T00:00:00 in TAI optionally followed by currentUtcOffset and a leap flag; it can be used only in TAI. See --ptp-utc-offset. If it is \"cuc\", \"cds\" or \"ccs\", the datetimes are CCSDS binary time codes in hexadecimal such as \"1E6EFAA5258000\"; see --ccsds-pfield. If it is \"ascii-a\" or \"ascii-b\", the datetimes are CCSDS ASCII time codes such as \"2017-01-01T00:00:00.5Z\" or \"2017-001T00:00:00.5Z\". If it is \"era\", \"gmst\", \"gast\" or \"lst\", the Earth Rotation Angle, the Greenwich mean sidereal time (IAU 2006), the Greenwich apparent sidereal time or the local apparent sidereal time at --longitude is output in hours, minutes and seconds such as \"06:42:01.637380\"; they can be used only in UT1 and only for output. If it is not specified, the default value \"datetime\" is used.")
+                    .takes_value(true)
+                    .possible_values(&Repr::NAMES)
+                    .case_insensitive(true)
+                    .long("out-repr"),
+            )
+            .arg(
+                Arg::with_name("week_rollover_ref")
+                    .help("Reference datetime for truncated week numbers, formatted along <dt_fmt>. If it is specified, input week numbers are resolved to the first week not before the week containing it, and output week numbers roll over; every 1024 weeks in gps and qzss, 4096 weeks in gst and 8192 weeks in bdt.")
+                    .takes_value(true)
+                    .long("week-rollover-ref"),
+            )
+            .arg(
+                Arg::with_name("ccsds_pfield")
+                    .help("P-field of CCSDS binary time codes in hexadecimal. It is used for the input or the output whose representation is the time code which it defines, and the other uses the default P-field; \"1E\" for cuc, \"40\" for cds and \"53\" for ccs, or \"2E\" for cuc and \"48\" for cds if --ccsds-epoch is specified.")
+                    .takes_value(true)
+                    .long("ccsds-pfield"),
+            )
+            .arg(
+                Arg::with_name("ccsds_implicit_pfield")
+                    .help("If it is specified, input CCSDS binary time codes have no P-field and output ones omit it.")
+                    .long("ccsds-implicit-pfield"),
+            )
+            .arg(
+                Arg::with_name("ccsds_epoch")
+                    .help("Agency-defined epoch of CCSDS binary time codes, formatted along <dt_fmt>. It is required if the P-field specifies the agency-defined epoch.")
+                    .takes_value(true)
+                    .long("ccsds-epoch"),
+            )
+            .arg(
+                Arg::with_name("ntp_pivot")
+                    .help("Pivot datetime for NTP timestamps without eras, formatted along <dt_fmt>. Input NTP timestamps are resolved to the first datetime not before it. If it is not specified, the default value 1968-01-20T03:14:08 is used, so the timestamps are in 1968-2104.")
+                    .takes_value(true)
+                    .long("ntp-pivot"),
+            )
+            .arg(
+                Arg::with_name("ntp_leap_indicator")
+                    .help("If it is specified, the NTP leap indicator which a server should advertise at the datetime is appended to each output as \"LI=<leap_indicator>\"; 1 throughout the day before an inserted leap second, 2 throughout the day before a deleted one and 0 otherwise. With it, an inserted leap second is output as the repeated NTP timestamps of the last second before it.")
+                    .long("ntp-leap-indicator"),
+            )
+            .arg(
+                Arg::with_name("ptp_utc_offset")
+                    .help("Source of TAI - UTC used to convert input PTP records. If it is \"record\", currentUtcOffset and the leap flag in each record are used; the records without them are converted with the TAI-UTC table. If it is not specified, the default value \"table\" is used. In both cases, the records which disagree with the TAI-UTC table are reported.")
+                    .takes_value(true)
+                    .possible_values(&["table", "record"])
+                    .case_insensitive(true)
+                    .long("ptp-utc-offset"),
+            )
+            .arg(
+                Arg::with_name("longitude")
+                    .help("Longitude in degrees, positive to the east, used for the local sidereal time; for example, \"139.7\". It is required if the output representation is \"lst\".")
+                    .takes_value(true)
+                    .long("longitude"),
+            )
+            .arg(
+                Arg::with_name("tdb_model")
+                    .help("Model of TDB - TT used when the conversion passes through TDB. It is recorded in the standard error output as \"# TDB model: <tdb_model>\". If it is not specified, the default value \"fairhead-bretagnon-truncated\" is used.")
+                    .takes_value(true)
+                    .possible_values(&TdbModel::NAMES)
+                    .case_insensitive(true)
+                    .long("tdb-model"),
+            )
             .arg(
                 Arg::with_name("io_pair_flg")
                     .help("If it is specified, input datetime is also output to stdin.")
@@ -102,47 +439,276 @@ impl Arguments<'_> {
             )
             .arg(
                 Arg::with_name("tai_utc_table_file")
-                    .help("Filepath of TAI-UTC table file. If it is not specified, the environment variable 'TAI_UTC_TABLE' is used. If both of them are not specified, the default file ({binaries_directory}/tai-utc.txt) is used. If the default file also does not exist, use the built-in table in the program.")
+                    .help("Filepath of TAI-UTC table file. If it is not specified, the environment variable 'TAI_UTC_TABLE' is used. If both of them are not specified, the default file ({binaries_directory}/tai-utc.txt) is used. If the default file also does not exist, use the built-in table in the program. The IETF leap-seconds.list and the USNO tai-utc.dat can also be specified.")
                     .takes_value(true)
                     .long("tai-utc-table"),
             )
+            .arg(
+                Arg::with_name("tai_utc_table_expiration")
+                    .help("Expiration date of the TAI-UTC table in UTC, after which the table cannot know about new leap seconds; for example, \"2024-06-28T00:00:00.000\" in the format of --dt-fmt. It overrides the expiration date in the IETF leap-seconds.list (the line beginning with \"#@\"). See --beyond-table.")
+                    .takes_value(true)
+                    .long("tai-utc-table-expiration"),
+            )
+            .arg(
+                Arg::with_name("beyond_table")
+                    .help("Policy for UTC datetimes after the expiration date of the TAI-UTC table. If it is \"error\", such datetimes cannot be converted unless --delta-t is specified. If it is \"warn\", they are converted assuming no new leap seconds, and warnings are output to stderr without failing the exit code. If it is \"assume-constant\", they are converted assuming no new leap seconds silently. If the expiration date is unknown, all datetimes are converted as \"assume-constant\". If it is not specified, the default value \"assume-constant\" is used.")
+                    .takes_value(true)
+                    .possible_values(&BeyondTable::NAMES)
+                    .case_insensitive(true)
+                    .long("beyond-table"),
+            )
+            .arg(
+                Arg::with_name("smear")
+                    .help("Shape of smeared UTC; \"google\", \"aws\", \"utc-sls\" or \"custom:<start>,<length>[,<curve>]\". If it is specified, output UTC datetimes are smeared. For \"custom\", <start> and <length> of the window are in seconds relative to the end of the leap second, and <curve> is \"linear\" or \"cosine\"; for example, \"custom:-43200,86400,cosine\". It is also used for the time scale \"smeared-utc\" and --from-smear. If it is not specified, the default value \"google\" is used for them.")
+                    .takes_value(true)
+                    .long("smear"),
+            )
+            .arg(
+                Arg::with_name("from_smear")
+                    .help("If it is specified, input UTC datetimes are regarded as smeared UTC; the shape is specified by --smear.")
+                    .long("from-smear"),
+            )
+            .arg(
+                Arg::with_name("lenient")
+                    .help("If it is specified, input UTC datetimes with the second 60 are accepted even if no leap second is inserted at the time, and converted as the first second of the next minute. If it is not specified, such datetimes are reported as errors with the nearest leap second.")
+                    .long("lenient"),
+            )
+            .arg(
+                Arg::with_name("out_leap")
+                    .help("Policy for output UTC datetimes in leap seconds, which many systems cannot accept. If it is \"keep\", they are output as they are; for example, \"23:59:60.500\". If it is \"repeat-59\", the second 59 is repeated; \"23:59:59.500\". If it is \"freeze\", they are frozen at \"23:59:59.999999999\". If it is \"roll\", they are rolled to \"00:00:00\" of the next minute. If it is \"error\", they are reported as errors. The policy applies to every representation, and the adjusted outputs are followed by \"(leap: <policy>)\". If it is not specified, the default value \"keep\" is used.")
+                    .takes_value(true)
+                    .possible_values(&LeapPolicy::NAMES)
+                    .case_insensitive(true)
+                    .long("out-leap"),
+            )
+            .arg(
+                Arg::with_name("out_tz")
+                    .help("Time zone of output UTC datetimes; a name of the IANA time zone database such as \"Asia/Tokyo\", a UTC offset such as \"+09:00\", or a POSIX TZ string such as \"JST-9\". Leap seconds are shown as the second 60 of the local time like the \"right/\" zones of tzdata. Use \"%:z\" or \"%Z\" in --dt-fmt to output the UTC offset or the abbreviation. It can be used only when --to is utc and --out-repr is datetime. Input UTC datetimes can have the UTC offset such as \"+09:00\" or \"Z\" without this option.")
+                    .takes_value(true)
+                    .allow_hyphen_values(true)
+                    .long("out-tz"),
+            )
+            .arg(
+                Arg::with_name("zoneinfo_dir")
+                    .help("The zoneinfo directory which contains the TZif files of the IANA time zone database, used by --out-tz. If it is not specified, the environment variable 'TZDIR' is used. If both of them are not specified, the default directory (/usr/share/zoneinfo) is used. The database is not embedded, so the names of the database fail without the directory.")
+                    .takes_value(true)
+                    .long("zoneinfo-dir"),
+            )
+            .arg(
+                Arg::with_name("eop_table_file")
+                    .help("Filepath of the IERS EOP file, used when the conversion passes through UT1. If it is not specified, the environment variable 'EOP_TABLE' is used. If both of them are not specified, the default file ({binaries_directory}/finals2000A.all) is used. Both finals2000A.all and Bulletin A can be specified.")
+                    .takes_value(true)
+                    .long("eop-table"),
+            )
+            .arg(
+                Arg::with_name("delta_t")
+                    .help("Model of ΔT (TT - UT) used when the TAI-UTC table or the EOP table does not cover the datetime; before the TAI-UTC table, UTC is regarded as UT. If it is \"espenak-meeus\", the polynomials of Espenak and Meeus are used. If it is \"table\", the ΔT table file specified by --delta-t-table is used. The output datetimes derived from the model are followed by \" (delta-t: <delta_t>)\". If it is not specified, such conversion fails unless --delta-t-table is specified.")
+                    .takes_value(true)
+                    .possible_values(&DeltaTModel::NAMES)
+                    .case_insensitive(true)
+                    .long("delta-t"),
+            )
+            .arg(
+                Arg::with_name("delta_t_table_file")
+                    .help("Filepath of the ΔT table file, whose lines are \"<decimal_year> <delta_t>\" or \"<year> <month> <day> <delta_t>\" such as the USNO deltat.data; ΔT is in seconds. If it is not specified, the environment variable 'DELTA_T_TABLE' is used. If it is specified and --delta-t is not specified, \"--delta-t table\" is assumed.")
+                    .takes_value(true)
+                    .long("delta-t-table"),
+            )
             .arg(
                 Arg::with_name("datetime")
                     .help("datetime to convert. Instead of specifying it here, you can also enter it from the standard input.")
                     .multiple(true),
             );
         let matches: ArgMatches<'a> = app.get_matches_from(args);
+        let from = matches.value_of("from").map(parse_scale_arg);
+        let to = matches.value_of("to").map(parse_scale_arg);
         Arguments::<'a> {
+            from: from.map(|(scale, _)| scale),
+            to: to.map(|(scale, _)| scale),
             tai_utc_table_dt_fmt: matches
                 .value_of("tai_utc_table_dt_fmt")
                 .map(|s| s.to_string()),
-            dt_fmt: matches.value_of("dt_fmt").map(|s| s.to_string()),
+            tai_utc_table_sep: matches.value_of("tai_utc_table_sep").map(|s| s.to_string()),
+            dt_fmt: matches
+                .values_of("dt_fmt")
+                .map(|values| values.map(|s| s.to_string()).collect()),
+            in_dt_fmt: matches
+                .values_of("in_dt_fmt")
+                .map(|values| values.map(|s| s.to_string()).collect()),
+            out_dt_fmt: matches.value_of("out_dt_fmt").map(|s| s.to_string()),
+            // The explicit representations take precedence over the ones implied by the time scales.
+            in_repr: matches
+                .value_of("in_repr")
+                .map(|s| s.parse().unwrap())
+                .or_else(|| from.and_then(|(_, repr)| repr)),
+            out_repr: matches
+                .value_of("out_repr")
+                .map(|s| s.parse().unwrap())
+                .or_else(|| to.and_then(|(_, repr)| repr)),
+            week_rollover_ref: matches.value_of("week_rollover_ref").map(|s| s.to_string()),
+            ccsds_pfield: matches.value_of("ccsds_pfield").map(|s| s.to_string()),
+            ccsds_implicit_pfield: matches.is_present("ccsds_implicit_pfield"),
+            ccsds_epoch: matches.value_of("ccsds_epoch").map(|s| s.to_string()),
+            ntp_pivot: matches.value_of("ntp_pivot").map(|s| s.to_string()),
+            ntp_leap_indicator: matches.is_present("ntp_leap_indicator"),
+            ptp_record_offset: matches
+                .value_of("ptp_utc_offset")
+                .is_some_and(|s| s.eq_ignore_ascii_case("record")),
+            longitude: matches.value_of("longitude").map(|s| s.to_string()),
+            tdb_model: matches.value_of("tdb_model").map(|s| s.parse().unwrap()),
             io_pair_flg: matches.is_present("io_pair_flg"),
             tai_utc_table_path: matches
                 .value_of("tai_utc_table_file")
                 .map(|s| s.to_string()),
-            matches: matches,
+            eop_table_path: matches.value_of("eop_table_file").map(|s| s.to_string()),
+            delta_t: matches.value_of("delta_t").map(|s| s.to_string()),
+            beyond_table: matches.value_of("beyond_table").map(|s| s.parse().unwrap()),
+            lenient: matches.is_present("lenient"),
+            out_leap: matches.value_of("out_leap").map(|s| s.parse().unwrap()),
+            out_tz: matches.value_of("out_tz").map(|s| s.to_string()),
+            zoneinfo_dir: matches.value_of("zoneinfo_dir").map(|s| s.to_string()),
+            smear: matches.value_of("smear").map(|s| s.to_string()),
+            from_smear: matches.is_present("from_smear"),
+            tai_utc_table_expiration: matches
+                .value_of("tai_utc_table_expiration")
+                .map(|s| s.to_string()),
+            delta_t_table_path: matches
+                .value_of("delta_t_table_file")
+                .map(|s| s.to_string()),
+            matches,
         }
     }
 
-    pub fn get_dt_fmt(&self) -> Option<&str> {
-        self.dt_fmt.as_ref().map(|s| s.as_str())
+    pub fn get_from(&self) -> Option<TimeScale> {
+        self.from
+    }
+
+    pub fn get_to(&self) -> Option<TimeScale> {
+        self.to
+    }
+
+    pub fn get_dt_fmt(&self) -> Option<Vec<&str>> {
+        self.dt_fmt
+            .as_ref()
+            .map(|dt_fmts| dt_fmts.iter().map(String::as_str).collect())
+    }
+
+    pub fn get_in_dt_fmt(&self) -> Option<Vec<&str>> {
+        self.in_dt_fmt
+            .as_ref()
+            .map(|dt_fmts| dt_fmts.iter().map(String::as_str).collect())
+    }
+
+    pub fn get_out_dt_fmt(&self) -> Option<&str> {
+        self.out_dt_fmt.as_deref()
+    }
+
+    pub fn get_in_repr(&self) -> Option<Repr> {
+        self.in_repr
+    }
+
+    pub fn get_out_repr(&self) -> Option<Repr> {
+        self.out_repr
+    }
+
+    pub fn get_week_rollover_ref(&self) -> Option<&str> {
+        self.week_rollover_ref.as_deref()
+    }
+
+    pub fn get_ccsds_pfield(&self) -> Option<&str> {
+        self.ccsds_pfield.as_deref()
+    }
+
+    pub fn get_ccsds_implicit_pfield(&self) -> bool {
+        self.ccsds_implicit_pfield
+    }
+
+    pub fn get_ccsds_epoch(&self) -> Option<&str> {
+        self.ccsds_epoch.as_deref()
+    }
+
+    pub fn get_ntp_pivot(&self) -> Option<&str> {
+        self.ntp_pivot.as_deref()
+    }
+
+    pub fn get_ntp_leap_indicator(&self) -> bool {
+        self.ntp_leap_indicator
+    }
+
+    pub fn get_ptp_record_offset(&self) -> bool {
+        self.ptp_record_offset
+    }
+
+    pub fn get_lenient(&self) -> bool {
+        self.lenient
+    }
+
+    pub fn get_out_leap(&self) -> Option<LeapPolicy> {
+        self.out_leap
+    }
+
+    pub fn get_out_tz(&self) -> Option<&str> {
+        self.out_tz.as_deref()
+    }
+
+    pub fn get_zoneinfo_dir(&self) -> Option<&str> {
+        self.zoneinfo_dir.as_deref()
+    }
+
+    pub fn get_longitude(&self) -> Option<&str> {
+        self.longitude.as_deref()
+    }
+
+    pub fn get_tdb_model(&self) -> Option<TdbModel> {
+        self.tdb_model
     }
 
     pub fn get_tai_utc_table_dt_fmt(&self) -> Option<&str> {
-        self.tai_utc_table_dt_fmt.as_ref().map(|s| s.as_str())
+        self.tai_utc_table_dt_fmt.as_deref()
+    }
+
+    pub fn get_tai_utc_table_sep(&self) -> Option<&str> {
+        self.tai_utc_table_sep.as_deref()
     }
 
     pub fn get_tai_utc_table_path(&self) -> Option<&str> {
-        self.tai_utc_table_path.as_ref().map(|s| s.as_str())
+        self.tai_utc_table_path.as_deref()
+    }
+
+    pub fn get_eop_table_path(&self) -> Option<&str> {
+        self.eop_table_path.as_deref()
+    }
+
+    pub fn get_delta_t(&self) -> Option<&str> {
+        self.delta_t.as_deref()
+    }
+
+    pub fn get_delta_t_table_path(&self) -> Option<&str> {
+        self.delta_t_table_path.as_deref()
+    }
+
+    pub fn get_beyond_table(&self) -> Option<BeyondTable> {
+        self.beyond_table
+    }
+
+    pub fn get_tai_utc_table_expiration(&self) -> Option<&str> {
+        self.tai_utc_table_expiration.as_deref()
+    }
+
+    pub fn get_smear(&self) -> Option<&str> {
+        self.smear.as_deref()
+    }
+
+    pub fn get_from_smear(&self) -> bool {
+        self.from_smear
     }
 
     pub fn get_io_pair_flg(&self) -> bool {
         self.io_pair_flg
     }
 
-    pub fn get_datetimes(&self) -> Option<Values> {
-        return self.matches.values_of("datetime");
+    pub fn get_datetimes(&self) -> Option<Values<'_>> {
+        self.matches.values_of("datetime")
     }
 }
 
@@ -150,8 +716,14 @@ impl Arguments<'_> {
 #[derive(Debug)]
 pub struct EnvValues {
     dt_fmt: Option<String>,
+    in_dt_fmt: Option<String>,
+    out_dt_fmt: Option<String>,
     tai_utc_table_dt_fmt: Option<String>,
+    tai_utc_table_sep: Option<String>,
     tai_utc_table_path: Option<String>,
+    eop_table_path: Option<String>,
+    delta_t_table_path: Option<String>,
+    zoneinfo_dir: Option<String>,
 }
 
 impl EnvValues {
@@ -162,60 +734,246 @@ impl EnvValues {
             .collect::<HashMap<_, _>>();
         EnvValues {
             dt_fmt: map.get("DT_FMT").map(|s| s.to_string()),
+            in_dt_fmt: map.get("IN_DT_FMT").map(|s| s.to_string()),
+            out_dt_fmt: map.get("OUT_DT_FMT").map(|s| s.to_string()),
             tai_utc_table_dt_fmt: map.get("TAI_UTC_TABLE_DT_FMT").map(|s| s.to_string()),
+            tai_utc_table_sep: map.get("TAI_UTC_TABLE_SEP").map(|s| s.to_string()),
             tai_utc_table_path: map.get("TAI_UTC_TABLE").map(|s| s.to_string()),
+            eop_table_path: map.get("EOP_TABLE").map(|s| s.to_string()),
+            delta_t_table_path: map.get("DELTA_T_TABLE").map(|s| s.to_string()),
+            zoneinfo_dir: map.get("TZDIR").map(|s| s.to_string()),
         }
     }
 
-    pub fn get_dt_fmt(&self) -> Option<&str> {
-        self.dt_fmt.as_ref().map(|s| s.as_str())
+    /// The formats in `DT_FMT`, which are separated by `|`.
+    pub fn get_dt_fmt(&self) -> Option<Vec<&str>> {
+        self.dt_fmt
+            .as_deref()
+            .map(|dt_fmts| dt_fmts.split(DT_FMT_SEP).collect())
+    }
+
+    /// The formats in `IN_DT_FMT`, which are separated by `|`.
+    pub fn get_in_dt_fmt(&self) -> Option<Vec<&str>> {
+        self.in_dt_fmt
+            .as_deref()
+            .map(|dt_fmts| dt_fmts.split(DT_FMT_SEP).collect())
+    }
+
+    pub fn get_out_dt_fmt(&self) -> Option<&str> {
+        self.out_dt_fmt.as_deref()
     }
 
     pub fn get_tai_utc_table_dt_fmt(&self) -> Option<&str> {
-        self.tai_utc_table_dt_fmt.as_ref().map(|s| s.as_str())
+        self.tai_utc_table_dt_fmt.as_deref()
+    }
+
+    pub fn get_tai_utc_table_sep(&self) -> Option<&str> {
+        self.tai_utc_table_sep.as_deref()
     }
 
     pub fn get_tai_utc_table_path(&self) -> Option<&str> {
-        self.tai_utc_table_path.as_ref().map(|s| s.as_str())
+        self.tai_utc_table_path.as_deref()
+    }
+
+    pub fn get_eop_table_path(&self) -> Option<&str> {
+        self.eop_table_path.as_deref()
+    }
+
+    pub fn get_delta_t_table_path(&self) -> Option<&str> {
+        self.delta_t_table_path.as_deref()
+    }
+
+    pub fn get_zoneinfo_dir(&self) -> Option<&str> {
+        self.zoneinfo_dir.as_deref()
     }
 }
 
 #[derive(Debug)]
 pub struct Parameters<'a> {
     args: &'a Arguments<'a>,
-    dt_fmt: &'a str,
+    from: TimeScale,
+    to: TimeScale,
+    dt_fmt: Vec<&'a str>,
+    out_dt_fmt: Option<&'a str>,
+    in_repr: Repr,
+    out_repr: Repr,
+    week_rollover_ref: Option<&'a str>,
+    ccsds_pfield: Option<&'a str>,
+    ccsds_implicit_pfield: bool,
+    ccsds_epoch: Option<&'a str>,
+    ntp_pivot: Option<&'a str>,
+    ntp_leap_indicator: bool,
+    ptp_record_offset: bool,
+    lenient: bool,
+    out_leap: LeapPolicy,
+    out_tz: Option<&'a str>,
+    zoneinfo_dir: PathBuf,
+    smear: Option<&'a str>,
+    longitude: Option<&'a str>,
+    tdb_model: TdbModel,
     tai_utc_table_dt_fmt: &'a str,
+    tai_utc_table_sep: Option<&'a str>,
     tai_utc_table_path: Option<PathBuf>,
+    eop_table_path: Option<PathBuf>,
+    delta_t: Option<&'a str>,
+    delta_t_table_path: Option<PathBuf>,
+    beyond_table: BeyondTable,
+    tai_utc_table_expiration: Option<&'a str>,
     io_pair_flg: bool,
 }
 
 impl Parameters<'_> {
     pub fn new<'a>(args: &'a Arguments, env_vars: &'a EnvValues) -> Parameters<'a> {
-        return Parameters {
+        Parameters {
             args,
+            from: Parameters::decide_from(args),
+            to: Parameters::decide_to(args),
             dt_fmt: Parameters::decide_dt_fmt(args, env_vars),
+            out_dt_fmt: Parameters::decide_out_dt_fmt(args, env_vars),
+            in_repr: args.get_in_repr().unwrap_or_default(),
+            out_repr: args.get_out_repr().unwrap_or_default(),
+            week_rollover_ref: args.get_week_rollover_ref(),
+            ccsds_pfield: args.get_ccsds_pfield(),
+            ccsds_implicit_pfield: args.get_ccsds_implicit_pfield(),
+            ccsds_epoch: args.get_ccsds_epoch(),
+            ntp_pivot: args.get_ntp_pivot(),
+            ntp_leap_indicator: args.get_ntp_leap_indicator(),
+            ptp_record_offset: args.get_ptp_record_offset(),
+            lenient: args.get_lenient(),
+            out_leap: args.get_out_leap().unwrap_or_default(),
+            out_tz: args.get_out_tz(),
+            zoneinfo_dir: PathBuf::from(
+                args.get_zoneinfo_dir()
+                    .or_else(|| env_vars.get_zoneinfo_dir())
+                    .unwrap_or(DEFAULT_ZONEINFO_DIR),
+            ),
+            smear: args.get_smear(),
+            longitude: args.get_longitude(),
+            tdb_model: args.get_tdb_model().unwrap_or_default(),
             tai_utc_table_dt_fmt: Parameters::decide_tai_utc_table_dt_fmt(args, env_vars),
+            tai_utc_table_sep: Parameters::decide_tai_utc_table_sep(args, env_vars),
             tai_utc_table_path: Parameters::decide_tai_utc_table_path(args, env_vars),
+            eop_table_path: Parameters::decide_eop_table_path(args, env_vars),
+            delta_t: Parameters::decide_delta_t(args, env_vars),
+            delta_t_table_path: args
+                .get_delta_t_table_path()
+                .or_else(|| env_vars.get_delta_t_table_path())
+                .map(PathBuf::from),
+            beyond_table: args.get_beyond_table().unwrap_or_default(),
+            tai_utc_table_expiration: args.get_tai_utc_table_expiration(),
             io_pair_flg: args.io_pair_flg,
-        };
+        }
     }
 
-    pub fn get_datetimes(&self) -> Option<Values> {
-        return self.args.matches.values_of("datetime");
+    pub fn get_datetimes(&self) -> Option<Values<'_>> {
+        self.args.matches.values_of("datetime")
     }
 
-    pub fn get_dt_fmt(&self) -> &str {
-        return &self.dt_fmt;
+    pub fn get_from(&self) -> TimeScale {
+        self.from
+    }
+
+    pub fn get_to(&self) -> TimeScale {
+        self.to
+    }
+
+    pub fn get_dt_fmt(&self) -> &[&str] {
+        &self.dt_fmt
+    }
+
+    pub fn get_out_dt_fmt(&self) -> Option<&str> {
+        self.out_dt_fmt
+    }
+
+    pub fn get_in_repr(&self) -> Repr {
+        self.in_repr
+    }
+
+    pub fn get_out_repr(&self) -> Repr {
+        self.out_repr
+    }
+
+    pub fn get_week_rollover_ref(&self) -> Option<&str> {
+        self.week_rollover_ref
+    }
+
+    pub fn get_ccsds_pfield(&self) -> Option<&str> {
+        self.ccsds_pfield
+    }
+
+    pub fn get_ccsds_implicit_pfield(&self) -> bool {
+        self.ccsds_implicit_pfield
+    }
+
+    pub fn get_ccsds_epoch(&self) -> Option<&str> {
+        self.ccsds_epoch
+    }
+
+    pub fn get_ntp_pivot(&self) -> Option<&str> {
+        self.ntp_pivot
+    }
+
+    pub fn get_ntp_leap_indicator(&self) -> bool {
+        self.ntp_leap_indicator
+    }
+
+    pub fn get_ptp_record_offset(&self) -> bool {
+        self.ptp_record_offset
+    }
+
+    pub fn get_lenient(&self) -> bool {
+        self.lenient
+    }
+
+    pub fn get_out_leap(&self) -> LeapPolicy {
+        self.out_leap
+    }
+
+    pub fn get_out_tz(&self) -> Option<&str> {
+        self.out_tz
+    }
+
+    pub fn get_zoneinfo_dir(&self) -> &PathBuf {
+        &self.zoneinfo_dir
+    }
+
+    pub fn get_longitude(&self) -> Option<&str> {
+        self.longitude
+    }
+
+    pub fn get_tdb_model(&self) -> TdbModel {
+        self.tdb_model
     }
 
     pub fn get_tai_utc_table_dt_fmt(&self) -> &str {
-        return &self.tai_utc_table_dt_fmt;
+        self.tai_utc_table_dt_fmt
     }
 
-    fn decide_dt_fmt<'a>(args: &'a Arguments, env_vars: &'a EnvValues) -> &'a str {
-        args.get_dt_fmt()
+    pub fn get_tai_utc_table_sep(&self) -> Option<&str> {
+        self.tai_utc_table_sep
+    }
+
+    fn decide_dt_fmt<'a>(args: &'a Arguments, env_vars: &'a EnvValues) -> Vec<&'a str> {
+        args.get_in_dt_fmt()
+            .or_else(|| args.get_dt_fmt())
+            .or_else(|| env_vars.get_in_dt_fmt())
             .or_else(|| env_vars.get_dt_fmt())
-            .unwrap_or(DT_FMT)
+            .unwrap_or_else(|| vec![DT_FMT])
+    }
+
+    /// `None` means that each datetime is output in the format which the input matched.
+    fn decide_out_dt_fmt<'a>(args: &'a Arguments, env_vars: &'a EnvValues) -> Option<&'a str> {
+        // A list of two or more formats in `DT_FMT` means the format which the input matched.
+        let shorthand = |dt_fmts: Vec<&'a str>| match dt_fmts[..] {
+            [dt_fmt] => Some(dt_fmt),
+            _ => None,
+        };
+        args.get_out_dt_fmt()
+            .map(Some)
+            .or_else(|| args.get_dt_fmt().map(shorthand))
+            .or_else(|| env_vars.get_out_dt_fmt().map(Some))
+            .or_else(|| env_vars.get_dt_fmt().map(shorthand))
+            .flatten()
     }
 
     fn decide_tai_utc_table_dt_fmt<'a>(args: &'a Arguments, env_vars: &'a EnvValues) -> &'a str {
@@ -224,12 +982,20 @@ impl Parameters<'_> {
             .unwrap_or(DT_FMT)
     }
 
+    fn decide_tai_utc_table_sep<'a>(
+        args: &'a Arguments,
+        env_vars: &'a EnvValues,
+    ) -> Option<&'a str> {
+        args.get_tai_utc_table_sep()
+            .or_else(|| env_vars.get_tai_utc_table_sep())
+    }
+
     pub fn io_pair_flg(&self) -> bool {
-        return self.io_pair_flg;
+        self.io_pair_flg
     }
 
     pub fn get_tai_utc_table_path(&self) -> Option<&PathBuf> {
-        return self.tai_utc_table_path.as_ref();
+        self.tai_utc_table_path.as_ref()
     }
 
     fn decide_tai_utc_table_path(args: &Arguments, env_vars: &EnvValues) -> Option<PathBuf> {
@@ -252,6 +1018,78 @@ impl Parameters<'_> {
         }
 
         // use builtin default
-        return None;
+        None
+    }
+
+    pub fn get_eop_table_path(&self) -> Option<&PathBuf> {
+        self.eop_table_path.as_ref()
+    }
+
+    fn decide_eop_table_path(args: &Arguments, env_vars: &EnvValues) -> Option<PathBuf> {
+        // If it is specified as command args, use it.
+        if let Some(path) = args.get_eop_table_path() {
+            return Some(PathBuf::from(path));
+        }
+
+        // If it is specified as environment variable, use it.
+        if let Some(path) = env_vars.get_eop_table_path() {
+            return Some(PathBuf::from(path));
+        }
+
+        // If default file exists, use it.
+        let mut exe_path = env::current_exe().unwrap();
+        exe_path.pop();
+        exe_path.push(EOP_TABLE_FILENAME);
+        if exe_path.exists() {
+            return Some(exe_path);
+        }
+
+        // There is no builtin EOP table.
+        None
+    }
+
+    pub fn get_delta_t(&self) -> Option<&str> {
+        self.delta_t
+    }
+
+    pub fn get_delta_t_table_path(&self) -> Option<&PathBuf> {
+        self.delta_t_table_path.as_ref()
+    }
+
+    pub fn get_beyond_table(&self) -> BeyondTable {
+        self.beyond_table
+    }
+
+    pub fn get_tai_utc_table_expiration(&self) -> Option<&str> {
+        self.tai_utc_table_expiration
+    }
+
+    pub fn get_smear(&self) -> Option<&str> {
+        self.smear
+    }
+
+    fn decide_from(args: &Arguments) -> TimeScale {
+        // The time scales are required arguments, so they are always specified.
+        match args.get_from().unwrap() {
+            TimeScale::Utc if args.get_from_smear() => TimeScale::SmearedUtc,
+            from => from,
+        }
+    }
+
+    fn decide_to(args: &Arguments) -> TimeScale {
+        // The time scales are required arguments, so they are always specified.
+        match args.get_to().unwrap() {
+            TimeScale::Utc if args.get_smear().is_some() => TimeScale::SmearedUtc,
+            to => to,
+        }
+    }
+
+    fn decide_delta_t<'a>(args: &'a Arguments, env_vars: &'a EnvValues) -> Option<&'a str> {
+        // The ΔT table file implies the model "table".
+        args.get_delta_t().or_else(|| {
+            args.get_delta_t_table_path()
+                .or_else(|| env_vars.get_delta_t_table_path())
+                .map(|_| "table")
+        })
     }
 }
@@ -0,0 +1,3109 @@
+use super::{execcode, main_convertion, Arguments, EnvValues, Parameters, ScaleConverter};
+use crate::ccsds::parse_hex;
+use crate::convgraph::{route, ConvTables};
+use crate::error::Error;
+use crate::smear::Smear;
+use crate::zoneinfo::TimeZone;
+use crate::{exe, TimeScale};
+use std::ffi::OsString;
+use std::io::{BufRead, Write};
+
+/// The main process of the executable `convdate`.
+pub fn main_inner(
+    args: impl IntoIterator<Item = impl Into<OsString> + Clone>,
+    env_vars: impl IntoIterator<Item = (impl ToString, impl ToString)>,
+    stdin: &mut impl BufRead,
+    stdout: &mut impl Write,
+    stderr: &mut impl Write,
+) -> i32 {
+    main_inner_with_name(
+        "Converter between time scales",
+        args,
+        env_vars,
+        stdin,
+        stdout,
+        stderr,
+    )
+}
+
+/// The main process of the executables which are aliases of `convdate`; for example, `utc2tai`.
+///
+/// It runs as `convdate` with the options `--from <from> --to <to>`.
+pub fn main_inner_alias(
+    from: TimeScale,
+    to: TimeScale,
+    args: impl IntoIterator<Item = impl Into<OsString> + Clone>,
+    env_vars: impl IntoIterator<Item = (impl ToString, impl ToString)>,
+    stdin: &mut impl BufRead,
+    stdout: &mut impl Write,
+    stderr: &mut impl Write,
+) -> i32 {
+    let mut args: Vec<OsString> = args.into_iter().map(Into::into).collect();
+    let scale_args = ["--from", from.name(), "--to", to.name()].map(OsString::from);
+    args.splice(args.len().min(1)..args.len().min(1), scale_args);
+
+    main_inner_with_name(
+        &format!("Converter from {} to {}", from, to),
+        args,
+        env_vars,
+        stdin,
+        stdout,
+        stderr,
+    )
+}
+
+fn main_inner_with_name(
+    app_name: &str,
+    args: impl IntoIterator<Item = impl Into<OsString> + Clone>,
+    env_vars: impl IntoIterator<Item = (impl ToString, impl ToString)>,
+    stdin: &mut impl BufRead,
+    stdout: &mut impl Write,
+    stderr: &mut impl Write,
+) -> i32 {
+    let args = Arguments::new(app_name, args);
+    let env_vars = EnvValues::new(env_vars);
+
+    // Analyze the arguments and the environment variables.
+    let params = Parameters::new(&args, &env_vars);
+
+    let dt_fmts = params.get_dt_fmt().iter().copied();
+    if let Err(e) = exe::check_dt_fmts(dt_fmts.chain(params.get_out_dt_fmt())) {
+        exe::print_err(stderr, &e);
+        return execcode::EXIT_CODE_NG;
+    }
+
+    let parse_datetime_arg = |datetime: Option<&str>| {
+        datetime
+            .map(|datetime| {
+                exe::parse_along_dt_fmts(datetime, params.get_dt_fmt(), |dt_fmt| {
+                    exe::parse_naive(datetime, dt_fmt, params.get_from())
+                })
+                .map(|(datetime, _)| datetime)
+            })
+            .transpose()
+    };
+    let tai_utc_table_expiration = match parse_datetime_arg(params.get_tai_utc_table_expiration()) {
+        Ok(tai_utc_table_expiration) => tai_utc_table_expiration,
+        Err(e) => {
+            exe::print_err(stderr, &e);
+            return execcode::EXIT_CODE_NG;
+        }
+    };
+
+    // load TAI-UTC table
+    let tai_utc_table = exe::load_tai_utc_table(
+        params.get_tai_utc_table_path(),
+        params.get_tai_utc_table_sep(),
+        params.get_tai_utc_table_dt_fmt(),
+    )
+    .map(|tai_utc_table| match tai_utc_table_expiration {
+        Some(expiration) => tai_utc_table.with_expiration(expiration),
+        None => tai_utc_table,
+    });
+    let smear = match params.get_smear().map(str::parse::<Smear>).transpose() {
+        Ok(smear) => smear.unwrap_or_default(),
+        Err(e) => {
+            exe::print_err(stderr, &e);
+            return execcode::EXIT_CODE_NG;
+        }
+    };
+    let tables = match tai_utc_table {
+        Ok(tai_utc_table) => ConvTables::new(tai_utc_table)
+            .with_tdb_model(params.get_tdb_model())
+            .with_beyond_table(params.get_beyond_table())
+            .with_smear(smear),
+        Err(e) => {
+            exe::print_err(stderr, &e);
+            return execcode::EXIT_CODE_NG;
+        }
+    };
+
+    // load the model of ΔT
+    let tables = match params
+        .get_delta_t()
+        .map(|model| exe::load_delta_t_model(model, params.get_delta_t_table_path()))
+        .transpose()
+    {
+        Ok(Some(delta_t)) => tables.with_delta_t(delta_t),
+        Ok(None) => tables,
+        Err(e) => {
+            exe::print_err(stderr, &e);
+            return execcode::EXIT_CODE_NG;
+        }
+    };
+
+    // load UT1-UTC table only if the conversion passes through UT1
+    let conv_route = route(params.get_from(), params.get_to()).unwrap_or_default();
+    let tables = if conv_route.contains(&TimeScale::Ut1) {
+        match exe::load_eop_table(params.get_eop_table_path()) {
+            Ok(Some(ut1_utc_table)) => tables.with_ut1_utc_table(ut1_utc_table),
+            // Without the EOP table, UT1 is given by the model of ΔT.
+            Ok(None) if tables.delta_t().is_some() => tables,
+            Ok(None) => {
+                exe::print_err(stderr, &Error::EopTableNotSpecifiedError());
+                return execcode::EXIT_CODE_NG;
+            }
+            Err(e) => {
+                exe::print_err(stderr, &e);
+                return execcode::EXIT_CODE_NG;
+            }
+        }
+    } else {
+        tables
+    };
+
+    let week_rollover_ref = match parse_datetime_arg(params.get_week_rollover_ref()) {
+        Ok(week_rollover_ref) => week_rollover_ref,
+        Err(e) => {
+            exe::print_err(stderr, &e);
+            return execcode::EXIT_CODE_NG;
+        }
+    };
+    let ccsds_epoch = match parse_datetime_arg(params.get_ccsds_epoch()) {
+        Ok(ccsds_epoch) => ccsds_epoch,
+        Err(e) => {
+            exe::print_err(stderr, &e);
+            return execcode::EXIT_CODE_NG;
+        }
+    };
+    let ntp_pivot = match parse_datetime_arg(params.get_ntp_pivot()) {
+        Ok(ntp_pivot) => ntp_pivot,
+        Err(e) => {
+            exe::print_err(stderr, &e);
+            return execcode::EXIT_CODE_NG;
+        }
+    };
+    let longitude = match params
+        .get_longitude()
+        .map(|longitude| {
+            longitude
+                .parse::<f64>()
+                .ok()
+                .filter(|longitude| longitude.is_finite())
+                .ok_or_else(|| Error::LongitudeParseError(longitude.to_string()))
+        })
+        .transpose()
+    {
+        Ok(longitude) => longitude,
+        Err(e) => {
+            exe::print_err(stderr, &e);
+            return execcode::EXIT_CODE_NG;
+        }
+    };
+    let out_tz = match params
+        .get_out_tz()
+        .map(|name| TimeZone::load(name, params.get_zoneinfo_dir()))
+        .transpose()
+    {
+        Ok(out_tz) => out_tz,
+        Err(e) => {
+            exe::print_err(stderr, &e);
+            return execcode::EXIT_CODE_NG;
+        }
+    };
+    let ccsds_pfield = match params.get_ccsds_pfield().map(parse_hex).transpose() {
+        Ok(ccsds_pfield) => ccsds_pfield,
+        Err(e) => {
+            exe::print_err(stderr, &e);
+            return execcode::EXIT_CODE_NG;
+        }
+    };
+
+    let converter = ScaleConverter::new(
+        params.get_from(),
+        params.get_to(),
+        tables,
+        params.get_dt_fmt(),
+    )
+    .with_repr(
+        params.get_in_repr(),
+        params.get_out_repr(),
+        week_rollover_ref,
+    )
+    .and_then(|converter| {
+        converter.with_ccsds(
+            ccsds_pfield,
+            params.get_ccsds_implicit_pfield(),
+            ccsds_epoch,
+        )
+    })
+    .map(|converter| {
+        converter
+            .with_ntp(ntp_pivot, params.get_ntp_leap_indicator())
+            .with_ptp(params.get_ptp_record_offset())
+            .with_lenient(params.get_lenient())
+            .with_out_leap(params.get_out_leap())
+            .with_out_dt_fmt(params.get_out_dt_fmt())
+    })
+    .and_then(|converter| converter.with_longitude(longitude))
+    .and_then(|converter| converter.with_out_tz(out_tz));
+    let converter = match converter {
+        Ok(converter) => converter,
+        Err(e) => {
+            exe::print_err(stderr, &e);
+            return execcode::EXIT_CODE_NG;
+        }
+    };
+
+    // Record the model of TDB if the conversion depends on it.
+    // 出力を入力と1対1に保つため、標準エラー出力に書く。
+    if conv_route.contains(&TimeScale::Tdb) {
+        writeln!(stderr, "# TDB model: {}", params.get_tdb_model()).unwrap();
+    }
+
+    let result = main_convertion(&converter, &params, stdin, stdout, stderr);
+    execcode::execcode(&result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{main_inner, main_inner_alias};
+    use crate::{exe, testmod, TimeScale};
+    use rstest::*;
+    use std::collections::HashMap;
+
+    const EXE_NAME: &str = "convdate";
+
+    /// Test regular case.
+    #[test]
+    fn test_simply() {
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "2015-06-30T23:59:59.000",
+            "2015-06-30T23:59:60.001",
+            "2015-07-01T00:00:00.002",
+            "2015-07-01T00:00:01.003",
+            "2015-07-01T00:00:02.004",
+            "2016-12-31T23:59:59",
+            "2016-12-31T23:59:60",
+            "2017-01-01T00:00:00",
+            "2017-01-01T00:00:01",
+            "2017-01-01T00:00:02",
+        ];
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            "2015-07-01T00:00:34.000\n\
+            2015-07-01T00:00:35.001\n\
+            2015-07-01T00:00:36.002\n\
+            2015-07-01T00:00:37.003\n\
+            2015-07-01T00:00:38.004\n\
+            2017-01-01T00:00:35.000\n\
+            2017-01-01T00:00:36.000\n\
+            2017-01-01T00:00:37.000\n\
+            2017-01-01T00:00:38.000\n\
+            2017-01-01T00:00:39.000\n"
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test error when input datetimes are illegal.
+    #[test]
+    fn test_input_dt_illegal_against_default_dt_fmt() {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let tai_utc_table_path = testmod::tmp_tai_utc_table(
+            &test_dir,
+            &[
+                "2012-07-01T00:00:00 5",
+                "2015-07-01T00:00:00 6",
+                "2017-01-01T00:00:00 7",
+            ],
+        )
+        .unwrap();
+
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "2015-06-30T23:59:59.000",
+            "2015-06-30T23:59:60.001",
+            "2010-07-01T00:00:00.002",
+            "2015-07-01T00:00:01.003",
+            "2015-07-01T00:00:02.004",
+            "2016-12-3123:59:59",
+            "2016-12-3123:59:60",
+            "2017-01-01T00:00:00",
+            "2017-01-01T00:00:01",
+            "2017-01-01T00:00:02",
+        ];
+        let env_vars = HashMap::from([("TAI_UTC_TABLE", tai_utc_table_path.to_str().unwrap())]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 2);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            "2015-07-01T00:00:04.000\n\
+            2015-07-01T00:00:05.001\n\
+            2015-07-01T00:00:07.003\n\
+            2015-07-01T00:00:08.004\n\
+            2017-01-01T00:00:07.000\n\
+            2017-01-01T00:00:08.000\n\
+            2017-01-01T00:00:09.000\n"
+        );
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            format!(
+                "{}: {}\n{}: {}\n{}: {}\n",
+                exe::exe_name(),
+                "The datetime is too low: 2010-07-01 00:00:00.002",
+                exe::exe_name(),
+                "Cannot parse the datetime: 2016-12-3123:59:59",
+                exe::exe_name(),
+                "Cannot parse the datetime: 2016-12-3123:59:60"
+            )
+        );
+    }
+
+    /// Test error when TAI-UTC table data are illegal.
+    #[test]
+    fn test_tai_utc_table_illegal() {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let tai_utc_table_path = testmod::tmp_tai_utc_table(
+            &test_dir,
+            &[
+                "2012-07-01T00:00:00 5",
+                "2015-07-01T00:00:00 A",
+                "2017-01-01T00:00:00 7",
+            ],
+        )
+        .unwrap();
+
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "2015-06-30T23:59:59.000",
+            "2015-06-30T23:59:60.001",
+            "2015-07-01T00:00:00.002",
+            "2015-07-01T00:00:01.003",
+            "2015-07-01T00:00:02.004",
+            "2016-12-31T23:59:59",
+            "2016-12-31T23:59:60",
+            "2017-01-01T00:00:00",
+            "2017-01-01T00:00:01",
+            "2017-01-01T00:00:02",
+        ];
+        let env_vars = HashMap::from([("TAI_UTC_TABLE", tai_utc_table_path.to_str().unwrap())]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 1);
+        assert_eq!(String::from_utf8_lossy(&stdout_buf), "");
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            format!(
+                "{}: {}: line 2: {}\n",
+                exe::exe_name(),
+                tai_utc_table_path.to_str().unwrap(),
+                "Illegal definition of TAI-UTC difference: 2015-07-01T00:00:00 A"
+            )
+        );
+    }
+
+    /// Test error when datetimes in TAI-UTC table are illegal.
+    #[test]
+    fn test_tai_utc_table_dt_illegal_against_default_tai_utc_table_dt_fmt() {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let tai_utc_table_path = testmod::tmp_tai_utc_table(
+            &test_dir,
+            &[
+                "2012-07-01T00:00:00 5",
+                "2015-07-0100:00:00 6",
+                "2017-01-01T00:00:00 7",
+            ],
+        )
+        .unwrap();
+
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "2015-06-30T23:59:59.000",
+            "2015-06-30T23:59:60.001",
+            "2015-07-01T00:00:00.002",
+            "2015-07-01T00:00:01.003",
+            "2015-07-01T00:00:02.004",
+            "2016-12-31T23:59:59",
+            "2016-12-31T23:59:60",
+            "2017-01-01T00:00:00",
+            "2017-01-01T00:00:01",
+            "2017-01-01T00:00:02",
+        ];
+        let env_vars = HashMap::from([("TAI_UTC_TABLE", tai_utc_table_path.to_str().unwrap())]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 1);
+        assert_eq!(String::from_utf8_lossy(&stdout_buf), "");
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            format!(
+                "{}: {}: line 2: {}\n",
+                exe::exe_name(),
+                tai_utc_table_path.to_str().unwrap(),
+                "Illegal definition of TAI-UTC difference (datetime): 2015-07-0100:00:00"
+            )
+        );
+    }
+
+    /// Test error when an environment variable TAI_UTC_TABLE is a path which is not exists
+    #[test]
+    fn test_env_tai_utc_table_not_exist() {
+        let tai_utc_table_path = "/tmp/dummy/not_exists.txt";
+
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "2015-06-30T23:59:59.000",
+            "2015-06-30T23:59:60.001",
+            "2015-07-01T00:00:00.002",
+            "2015-07-01T00:00:01.003",
+            "2015-07-01T00:00:02.004",
+            "2016-12-31T23:59:59",
+            "2016-12-31T23:59:60",
+            "2017-01-01T00:00:00",
+            "2017-01-01T00:00:01",
+            "2017-01-01T00:00:02",
+        ];
+        let env_vars = HashMap::from([("TAI_UTC_TABLE", tai_utc_table_path)]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 1);
+        assert_eq!(String::from_utf8_lossy(&stdout_buf), "");
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            format!(
+                "{}: {}\n",
+                exe::exe_name(),
+                "The TAI-UTC table file isn't available: /tmp/dummy/not_exists.txt"
+            )
+        );
+    }
+
+    /// Test an argument --tai-utc-table.
+    #[test]
+    fn test_arg_tai_utc_table() {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let tai_utc_table_path = testmod::tmp_tai_utc_table(
+            &test_dir,
+            &[
+                "2012-07-01T00:00:00 5",
+                "2015-07-01T00:00:00 6",
+                "2017-01-01T00:00:00 7",
+            ],
+        )
+        .unwrap();
+
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "2015-06-30T23:59:59",
+            "2015-06-30T23:59:60",
+            "2015-07-01T00:00:00",
+            "2015-07-01T00:00:01",
+            "2015-07-01T00:00:02",
+            "2016-12-31T23:59:59",
+            "2016-12-31T23:59:60",
+            "2017-01-01T00:00:00",
+            "2017-01-01T00:00:01",
+            "2017-01-01T00:00:02",
+            "--tai-utc-table",
+            tai_utc_table_path.to_str().unwrap(),
+        ];
+        let env_vars: HashMap<&str, &str> = HashMap::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            "2015-07-01T00:00:04.000\n\
+            2015-07-01T00:00:05.000\n\
+            2015-07-01T00:00:06.000\n\
+            2015-07-01T00:00:07.000\n\
+            2015-07-01T00:00:08.000\n\
+            2017-01-01T00:00:05.000\n\
+            2017-01-01T00:00:06.000\n\
+            2017-01-01T00:00:07.000\n\
+            2017-01-01T00:00:08.000\n\
+            2017-01-01T00:00:09.000\n"
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test an argument --tai-utc-table with the IETF leap-seconds.list.
+    #[test]
+    fn test_arg_tai_utc_table_leap_seconds_list() {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let tai_utc_table_path = testmod::tmp_tai_utc_table(
+            &test_dir,
+            &[
+                "#\tFile expires on:  28 June 2024",
+                "#$\t 3676924800",
+                "#@\t 3928521600",
+                "3644697600\t36\t# 1 Jul 2015",
+                "3692217600\t37\t# 1 Jan 2017",
+                "#h\t7e3e2943 3fa11956 e4e658de c4dca0e6 bf6622c9",
+            ],
+        )
+        .unwrap();
+
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "2016-12-31T23:59:59",
+            "2016-12-31T23:59:60",
+            "2017-01-01T00:00:00",
+            "--tai-utc-table",
+            tai_utc_table_path.to_str().unwrap(),
+        ];
+        let env_vars: HashMap<&str, &str> = HashMap::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            "2017-01-01T00:00:35.000\n\
+            2017-01-01T00:00:36.000\n\
+            2017-01-01T00:00:37.000\n"
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test an argument --tai-utc-table with the USNO tai-utc.dat.
+    #[test]
+    fn test_arg_tai_utc_table_tai_utc_dat() {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let tai_utc_table_path =
+            testmod::tmp_tai_utc_table(&test_dir, testmod::TAI_UTC_DAT).unwrap();
+
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "tai",
+            "--to",
+            "utc",
+            "--dt-fmt",
+            "%Y-%m-%dT%H:%M:%S%.6f",
+            "1970-01-01T00:00:08.000082",
+            "1972-01-01T00:00:09.950000",
+            "1972-01-01T00:00:10.000000",
+            "--tai-utc-table",
+            tai_utc_table_path.to_str().unwrap(),
+        ];
+        let env_vars: HashMap<&str, &str> = HashMap::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            "1970-01-01T00:00:00.000000\n\
+            1971-12-31T23:59:60.057758\n\
+            1972-01-01T00:00:00.000000\n"
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test that the built-in table covers the era of rubber seconds since 1961.
+    #[test]
+    fn test_builtin_tai_utc_table_rubber_seconds() {
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "--dt-fmt",
+            "%Y-%m-%dT%H:%M:%S%.6f",
+            "1960-12-31T23:59:59.000000",
+            "1961-01-01T00:00:00.000000",
+            "1965-06-15T12:00:00.000000",
+            "1970-01-01T00:00:00.000000",
+            "1972-01-01T00:00:00.000000",
+        ];
+        let env_vars: HashMap<&str, &str> = HashMap::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 2);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            "1961-01-01T00:00:01.422818\n\
+            1965-06-15T12:00:03.854618\n\
+            1970-01-01T00:00:08.000082\n\
+            1972-01-01T00:00:10.000000\n"
+        );
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            format!(
+                "{}: The datetime is too low: {}\n",
+                exe::exe_name(),
+                "1960-12-31 23:59:59"
+            )
+        );
+    }
+
+    /// Test error when the hash of the IETF leap-seconds.list does not match.
+    #[test]
+    fn test_arg_tai_utc_table_leap_seconds_list_hash_mismatch() {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let tai_utc_table_path = testmod::tmp_tai_utc_table(
+            &test_dir,
+            &[
+                "#$\t 3676924800",
+                "#@\t 3928521600",
+                "3644697600\t36\t# 1 Jul 2015",
+                "3692217600\t38\t# 1 Jan 2017",
+                "#h\t7e3e2943 3fa11956 e4e658de c4dca0e6 bf6622c9",
+            ],
+        )
+        .unwrap();
+
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "2017-01-01T00:00:00",
+            "--tai-utc-table",
+            tai_utc_table_path.to_str().unwrap(),
+        ];
+        let env_vars: HashMap<&str, &str> = HashMap::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 1);
+        assert_eq!(String::from_utf8_lossy(&stdout_buf), "");
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            format!(
+                "{}: {}: {}\n",
+                exe::exe_name(),
+                tai_utc_table_path.to_str().unwrap(),
+                "The hash of the leap-seconds.list does not match the data: \
+                expected 7e3e2943 3fa11956 e4e658de c4dca0e6 bf6622c9, \
+                actual 84c81559 8217a360 20631dc9 2ad79226 332470af"
+            )
+        );
+    }
+
+    /// Test error when an argument --tai-utc-table is a path which is not exists
+    #[test]
+    fn test_arg_tai_utc_table_not_exist() {
+        let tai_utc_table_path = "/tmp/dummy/not_exists.txt";
+
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "2015-06-30T23:59:59",
+            "2015-06-30T23:59:60",
+            "2015-07-01T00:00:00",
+            "2015-07-01T00:00:01",
+            "2015-07-01T00:00:02",
+            "2016-12-31T23:59:59",
+            "2016-12-31T23:59:60",
+            "2017-01-01T00:00:00",
+            "2017-01-01T00:00:01",
+            "2017-01-01T00:00:02",
+            "--tai-utc-table",
+            tai_utc_table_path,
+        ];
+        let env_vars: HashMap<&str, &str> = HashMap::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 1);
+        assert_eq!(String::from_utf8_lossy(&stdout_buf), "");
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            format!(
+                "{}: {}\n",
+                exe::exe_name(),
+                "The TAI-UTC table file isn't available: /tmp/dummy/not_exists.txt"
+            )
+        );
+    }
+
+    /// Test an environment variable TAI_UTC_TABLE.
+    #[test]
+    fn test_env_tai_utc_table() {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let tai_utc_table_path = testmod::tmp_tai_utc_table(
+            &test_dir,
+            &[
+                "2012-07-01T00:00:00 5",
+                "2015-07-01T00:00:00 6",
+                "2017-01-01T00:00:00 7",
+            ],
+        )
+        .unwrap();
+
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "2015-06-30T23:59:59.000",
+            "2015-06-30T23:59:60.001",
+            "2015-07-01T00:00:00.002",
+            "2015-07-01T00:00:01.003",
+            "2015-07-01T00:00:02.004",
+            "2016-12-31T23:59:59",
+            "2016-12-31T23:59:60",
+            "2017-01-01T00:00:00",
+            "2017-01-01T00:00:01",
+            "2017-01-01T00:00:02",
+        ];
+        let env_vars = HashMap::from([("TAI_UTC_TABLE", tai_utc_table_path.to_str().unwrap())]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            "2015-07-01T00:00:04.000\n\
+            2015-07-01T00:00:05.001\n\
+            2015-07-01T00:00:06.002\n\
+            2015-07-01T00:00:07.003\n\
+            2015-07-01T00:00:08.004\n\
+            2017-01-01T00:00:05.000\n\
+            2017-01-01T00:00:06.000\n\
+            2017-01-01T00:00:07.000\n\
+            2017-01-01T00:00:08.000\n\
+            2017-01-01T00:00:09.000\n"
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test that an argument --tai-utc-table has a priority to an environment variable TAI_UTC_TABLE.
+    #[test]
+    fn test_arg_tai_utc_table_against_env() {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let tai_utc_table_path = testmod::tmp_tai_utc_table(
+            &test_dir,
+            &[
+                "2012-07-01T00:00:00 5",
+                "2015-07-01T00:00:00 6",
+                "2017-01-01T00:00:00 7",
+            ],
+        )
+        .unwrap();
+        let dummy_tai_utc_table_path =
+            testmod::tmp_text_file(&test_dir, "dummy_tai_utc_table.txt", &["XXX"]).unwrap();
+
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "2015-06-30T23:59:59",
+            "2015-06-30T23:59:60",
+            "2015-07-01T00:00:00",
+            "2015-07-01T00:00:01",
+            "2015-07-01T00:00:02",
+            "2016-12-31T23:59:59",
+            "2016-12-31T23:59:60",
+            "2017-01-01T00:00:00",
+            "2017-01-01T00:00:01",
+            "2017-01-01T00:00:02",
+            "--tai-utc-table",
+            tai_utc_table_path.to_str().unwrap(),
+        ];
+        let env_vars =
+            HashMap::from([("TAI_UTC_TABLE", dummy_tai_utc_table_path.to_str().unwrap())]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            "2015-07-01T00:00:04.000\n\
+            2015-07-01T00:00:05.000\n\
+            2015-07-01T00:00:06.000\n\
+            2015-07-01T00:00:07.000\n\
+            2015-07-01T00:00:08.000\n\
+            2017-01-01T00:00:05.000\n\
+            2017-01-01T00:00:06.000\n\
+            2017-01-01T00:00:07.000\n\
+            2017-01-01T00:00:08.000\n\
+            2017-01-01T00:00:09.000\n"
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test an argument --dt-fmt.
+    #[test]
+    fn test_arg_dt_fmt() {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let tai_utc_table_path = testmod::tmp_tai_utc_table(
+            &test_dir,
+            &[
+                "2012-07-01T00:00:00 5",
+                "2015-07-01T00:00:00 6",
+                "2017-01-01T00:00:00 7",
+            ],
+        )
+        .unwrap();
+
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "20150630235959",
+            "20150630235960",
+            "20150701000000",
+            "20150701000001",
+            "20150701000002",
+            "20161231235959",
+            "20161231235960",
+            "20170101000000",
+            "20170101000001",
+            "20170101000002",
+            "--dt-fmt",
+            "%Y%m%d%H%M%S",
+        ];
+        let env_vars = HashMap::from([("TAI_UTC_TABLE", tai_utc_table_path.to_str().unwrap())]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            "20150701000004\n\
+            20150701000005\n\
+            20150701000006\n\
+            20150701000007\n\
+            20150701000008\n\
+            20170101000005\n\
+            20170101000006\n\
+            20170101000007\n\
+            20170101000008\n\
+            20170101000009\n"
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test an environment variable DT_FMT.
+    #[test]
+    fn test_env_dt_fmt() {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let tai_utc_table_path = testmod::tmp_tai_utc_table(
+            &test_dir,
+            &[
+                "2012-07-01T00:00:00 5",
+                "2015-07-01T00:00:00 6",
+                "2017-01-01T00:00:00 7",
+            ],
+        )
+        .unwrap();
+
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "20150630235959",
+            "20150630235960",
+            "20150701000000",
+            "20150701000001",
+            "20150701000002",
+            "20161231235959",
+            "20161231235960",
+            "20170101000000",
+            "20170101000001",
+            "20170101000002",
+        ];
+        let env_vars = HashMap::from([
+            ("TAI_UTC_TABLE", tai_utc_table_path.to_str().unwrap()),
+            ("DT_FMT", "%Y%m%d%H%M%S"),
+        ]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            "20150701000004\n\
+            20150701000005\n\
+            20150701000006\n\
+            20150701000007\n\
+            20150701000008\n\
+            20170101000005\n\
+            20170101000006\n\
+            20170101000007\n\
+            20170101000008\n\
+            20170101000009\n"
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test that an argument --dt-fmt has a priority to an environment variable DT_FMT.
+    #[test]
+    fn test_arg_dt_fmt_against_env() {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let tai_utc_table_path = testmod::tmp_tai_utc_table(
+            &test_dir,
+            &[
+                "2012-07-01T00:00:00 5",
+                "2015-07-01T00:00:00 6",
+                "2017-01-01T00:00:00 7",
+            ],
+        )
+        .unwrap();
+
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "2015/06/30-23:59:59",
+            "2015/06/30-23:59:60",
+            "2015/07/01-00:00:00",
+            "2015/07/01-00:00:01",
+            "2015/07/01-00:00:02",
+            "2016/12/31-23:59:59",
+            "2016/12/31-23:59:60",
+            "2017/01/01-00:00:00",
+            "2017/01/01-00:00:01",
+            "2017/01/01-00:00:02",
+            "--dt-fmt",
+            "%Y/%m/%d-%H:%M:%S",
+        ];
+        let env_vars = HashMap::from([
+            ("TAI_UTC_TABLE", tai_utc_table_path.to_str().unwrap()),
+            ("DT_FMT", "%Y%m%d%H%M%S"),
+        ]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            "2015/07/01-00:00:04\n\
+            2015/07/01-00:00:05\n\
+            2015/07/01-00:00:06\n\
+            2015/07/01-00:00:07\n\
+            2015/07/01-00:00:08\n\
+            2017/01/01-00:00:05\n\
+            2017/01/01-00:00:06\n\
+            2017/01/01-00:00:07\n\
+            2017/01/01-00:00:08\n\
+            2017/01/01-00:00:09\n"
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test an argument --tai-utc-table-dt-fmt.
+    #[test]
+    fn test_arg_tai_utc_table_dt_fmt() {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let tai_utc_table_path = testmod::tmp_tai_utc_table(
+            &test_dir,
+            &[
+                "20120701000000000 5",
+                "20150701000000000 6",
+                "20170101000000000 7",
+            ],
+        )
+        .unwrap();
+
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "2015-06-30T23:59:59",
+            "2015-06-30T23:59:60",
+            "2015-07-01T00:00:00",
+            "2015-07-01T00:00:01",
+            "2015-07-01T00:00:02",
+            "2016-12-31T23:59:59",
+            "2016-12-31T23:59:60",
+            "2017-01-01T00:00:00",
+            "2017-01-01T00:00:01",
+            "2017-01-01T00:00:02",
+            "--tai-utc-table-dt-fmt",
+            "%Y%m%d%H%M%S%3f",
+        ];
+        let env_vars = HashMap::from([("TAI_UTC_TABLE", tai_utc_table_path.to_str().unwrap())]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            "2015-07-01T00:00:04.000\n\
+            2015-07-01T00:00:05.000\n\
+            2015-07-01T00:00:06.000\n\
+            2015-07-01T00:00:07.000\n\
+            2015-07-01T00:00:08.000\n\
+            2017-01-01T00:00:05.000\n\
+            2017-01-01T00:00:06.000\n\
+            2017-01-01T00:00:07.000\n\
+            2017-01-01T00:00:08.000\n\
+            2017-01-01T00:00:09.000\n"
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test an environment variable TAI_UTC_TABLE_DT_FMT.
+    #[test]
+    fn test_env_tai_utc_table_dt_fmt() {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let tai_utc_table_path = testmod::tmp_tai_utc_table(
+            &test_dir,
+            &[
+                "20120701000000000 5",
+                "20150701000000000 6",
+                "20170101000000000 7",
+            ],
+        )
+        .unwrap();
+
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "2015-06-30T23:59:59",
+            "2015-06-30T23:59:60",
+            "2015-07-01T00:00:00",
+            "2015-07-01T00:00:01",
+            "2015-07-01T00:00:02",
+            "2016-12-31T23:59:59",
+            "2016-12-31T23:59:60",
+            "2017-01-01T00:00:00",
+            "2017-01-01T00:00:01",
+            "2017-01-01T00:00:02",
+        ];
+        let env_vars = HashMap::from([
+            ("TAI_UTC_TABLE", tai_utc_table_path.to_str().unwrap()),
+            ("TAI_UTC_TABLE_DT_FMT", "%Y%m%d%H%M%S%3f"),
+        ]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            "2015-07-01T00:00:04.000\n\
+            2015-07-01T00:00:05.000\n\
+            2015-07-01T00:00:06.000\n\
+            2015-07-01T00:00:07.000\n\
+            2015-07-01T00:00:08.000\n\
+            2017-01-01T00:00:05.000\n\
+            2017-01-01T00:00:06.000\n\
+            2017-01-01T00:00:07.000\n\
+            2017-01-01T00:00:08.000\n\
+            2017-01-01T00:00:09.000\n"
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test that an argument --tai-utc-table-dt-fmt has a priority to an environment variable TAI_UTC_TABLE_DT_FMT
+    #[test]
+    fn test_arg_tai_utc_table_dt_fmt_against_env() {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let tai_utc_table_path = testmod::tmp_tai_utc_table(
+            &test_dir,
+            &[
+                "2012/07/01-00:00:00 5",
+                "2015/07/01-00:00:00 6",
+                "2017/01/01-00:00:00 7",
+            ],
+        )
+        .unwrap();
+
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "2015-06-30T23:59:59",
+            "2015-06-30T23:59:60",
+            "2015-07-01T00:00:00",
+            "2015-07-01T00:00:01",
+            "2015-07-01T00:00:02",
+            "2016-12-31T23:59:59",
+            "2016-12-31T23:59:60",
+            "2017-01-01T00:00:00",
+            "2017-01-01T00:00:01",
+            "2017-01-01T00:00:02",
+            "--tai-utc-table-dt-fmt",
+            "%Y/%m/%d-%H:%M:%S",
+        ];
+        let env_vars = HashMap::from([
+            ("TAI_UTC_TABLE", tai_utc_table_path.to_str().unwrap()),
+            ("TAI_UTC_TABLE_DT_FMT", "%Y%m%d%H%M%S%3f"),
+        ]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            "2015-07-01T00:00:04.000\n\
+            2015-07-01T00:00:05.000\n\
+            2015-07-01T00:00:06.000\n\
+            2015-07-01T00:00:07.000\n\
+            2015-07-01T00:00:08.000\n\
+            2017-01-01T00:00:05.000\n\
+            2017-01-01T00:00:06.000\n\
+            2017-01-01T00:00:07.000\n\
+            2017-01-01T00:00:08.000\n\
+            2017-01-01T00:00:09.000\n"
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test that comments, blank lines, tabs and notes in TAI-UTC table are accepted.
+    #[test]
+    fn test_tai_utc_table_with_comments() {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let tai_utc_table_path = testmod::tmp_tai_utc_table(
+            &test_dir,
+            &[
+                "# TAI-UTC table",
+                "",
+                "2012-07-01T00:00:00\t5\tBulletin C 43",
+                "2015-07-01T00:00:00    6  # Bulletin C 49",
+                "2017-01-01T00:00:00 \t 7",
+                "",
+            ],
+        )
+        .unwrap();
+
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "2015-06-30T23:59:59",
+            "2015-06-30T23:59:60",
+            "2015-07-01T00:00:00",
+            "2016-12-31T23:59:59",
+            "2016-12-31T23:59:60",
+            "2017-01-01T00:00:00",
+        ];
+        let env_vars = HashMap::from([("TAI_UTC_TABLE", tai_utc_table_path.to_str().unwrap())]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            "2015-07-01T00:00:04.000\n\
+            2015-07-01T00:00:05.000\n\
+            2015-07-01T00:00:06.000\n\
+            2017-01-01T00:00:05.000\n\
+            2017-01-01T00:00:06.000\n\
+            2017-01-01T00:00:07.000\n"
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test an argument --tai-utc-table-sep.
+    #[test]
+    fn test_arg_tai_utc_table_sep() {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let tai_utc_table_path = testmod::tmp_tai_utc_table(
+            &test_dir,
+            &[
+                "# TAI-UTC table",
+                "2012-07-01T00:00:00,5,Bulletin C 43",
+                "2015-07-01T00:00:00,6",
+                "2017-01-01T00:00:00 , 7 , Bulletin C 52",
+            ],
+        )
+        .unwrap();
+
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "2015-06-30T23:59:59",
+            "2015-06-30T23:59:60",
+            "2015-07-01T00:00:00",
+            "2016-12-31T23:59:59",
+            "2016-12-31T23:59:60",
+            "2017-01-01T00:00:00",
+            "--tai-utc-table-sep",
+            ",",
+        ];
+        let env_vars = HashMap::from([("TAI_UTC_TABLE", tai_utc_table_path.to_str().unwrap())]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            "2015-07-01T00:00:04.000\n\
+            2015-07-01T00:00:05.000\n\
+            2015-07-01T00:00:06.000\n\
+            2017-01-01T00:00:05.000\n\
+            2017-01-01T00:00:06.000\n\
+            2017-01-01T00:00:07.000\n"
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test an environment variable TAI_UTC_TABLE_SEP.
+    #[test]
+    fn test_env_tai_utc_table_sep() {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let tai_utc_table_path = testmod::tmp_tai_utc_table(
+            &test_dir,
+            &[
+                "2012-07-01T00:00:00|5",
+                "2015-07-01T00:00:00|6",
+                "2017-01-01T00:00:00|7",
+            ],
+        )
+        .unwrap();
+
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "2015-06-30T23:59:59",
+            "2015-06-30T23:59:60",
+            "2015-07-01T00:00:00",
+            "2016-12-31T23:59:59",
+            "2016-12-31T23:59:60",
+            "2017-01-01T00:00:00",
+        ];
+        let env_vars = HashMap::from([
+            ("TAI_UTC_TABLE", tai_utc_table_path.to_str().unwrap()),
+            ("TAI_UTC_TABLE_SEP", "|"),
+        ]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            "2015-07-01T00:00:04.000\n\
+            2015-07-01T00:00:05.000\n\
+            2015-07-01T00:00:06.000\n\
+            2017-01-01T00:00:05.000\n\
+            2017-01-01T00:00:06.000\n\
+            2017-01-01T00:00:07.000\n"
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test that an argument --tai-utc-table-sep has a priority to an environment variable TAI_UTC_TABLE_SEP.
+    #[test]
+    fn test_arg_tai_utc_table_sep_against_env() {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let tai_utc_table_path = testmod::tmp_tai_utc_table(
+            &test_dir,
+            &[
+                "2012-07-01T00:00:00;5",
+                "2015-07-01T00:00:00;6",
+                "2017-01-01T00:00:00;7",
+            ],
+        )
+        .unwrap();
+
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "2015-06-30T23:59:59",
+            "2015-06-30T23:59:60",
+            "2015-07-01T00:00:00",
+            "2016-12-31T23:59:59",
+            "2016-12-31T23:59:60",
+            "2017-01-01T00:00:00",
+            "--tai-utc-table-sep",
+            ";",
+        ];
+        let env_vars = HashMap::from([
+            ("TAI_UTC_TABLE", tai_utc_table_path.to_str().unwrap()),
+            ("TAI_UTC_TABLE_SEP", "|"),
+        ]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            "2015-07-01T00:00:04.000\n\
+            2015-07-01T00:00:05.000\n\
+            2015-07-01T00:00:06.000\n\
+            2017-01-01T00:00:05.000\n\
+            2017-01-01T00:00:06.000\n\
+            2017-01-01T00:00:07.000\n"
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test regular case.
+    #[test]
+    fn test_stdin() {
+        let args = vec![EXE_NAME, "--from", "utc", "--to", "tai"];
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"2015-06-30T23:59:59.000\n2015-06-30T23:59:60.001";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            "2015-07-01T00:00:34.000\n\
+            2015-07-01T00:00:35.001\n"
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test that the stdin is ignored if datetimes are specified in the arguments
+    #[test]
+    fn test_stdin_is_ignored_when_args_are_specified() {
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "2017-01-01T00:00:01",
+            "2017-01-01T00:00:02",
+        ];
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"2015-06-30T23:59:59.000\n2015-06-30T23:59:60.001";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            "2017-01-01T00:00:38.000\n\
+            2017-01-01T00:00:39.000\n"
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test error when stdin is illegal.
+    #[test]
+    fn test_illegal_stdin() {
+        let args = vec![EXE_NAME, "--from", "utc", "--to", "tai"];
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = [0x82, 0xA0, 0x82, 0xA0, 0x82, 0xA0];
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 2);
+        assert_eq!(String::from_utf8_lossy(&stdout_buf), "");
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            format!(
+                "{}: {}\n",
+                exe::exe_name(),
+                "stream did not contain valid UTF-8"
+            )
+        );
+    }
+
+    /// Test conversion between each pair of time scales.
+    #[rstest]
+    #[case("utc", "tai", "2016-12-31T23:59:60.001", "2017-01-01T00:00:36.001")]
+    #[case("utc", "tt", "2016-12-31T23:59:60.001", "2017-01-01T00:01:08.185")]
+    #[case("tai", "utc", "2017-01-01T00:00:36.001", "2016-12-31T23:59:60.001")]
+    #[case("tai", "tt", "2017-01-01T00:00:36.001", "2017-01-01T00:01:08.185")]
+    #[case("tt", "utc", "2017-01-01T00:01:08.185", "2016-12-31T23:59:60.001")]
+    #[case("tt", "tai", "2017-01-01T00:01:08.185", "2017-01-01T00:00:36.001")]
+    #[case("tai", "tai", "2017-01-01T00:00:36.001", "2017-01-01T00:00:36.001")]
+    #[case("UTC", "TT", "2016-12-31T23:59:60.001", "2017-01-01T00:01:08.185")]
+    #[case("utc", "gps", "2016-12-31T23:59:60.001", "2017-01-01T00:00:17.001")]
+    #[case("gps", "utc", "2017-01-01T00:00:17.001", "2016-12-31T23:59:60.001")]
+    #[case("gps", "tt", "2017-01-01T00:00:17.001", "2017-01-01T00:01:08.185")]
+    fn test_from_to(
+        #[case] from: &str,
+        #[case] to: &str,
+        #[case] in_dt: &str,
+        #[case] expected: &str,
+    ) {
+        let args = vec![EXE_NAME, "--from", from, "--to", to, in_dt];
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            format!("{}\n", expected)
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test that the old executables work as aliases of convdate.
+    #[rstest]
+    #[case(
+        "utc2tai",
+        TimeScale::Utc,
+        TimeScale::Tai,
+        "2016-12-31T23:59:60.001",
+        "2017-01-01T00:00:36.001"
+    )]
+    #[case(
+        "utc2tt",
+        TimeScale::Utc,
+        TimeScale::Tt,
+        "2016-12-31T23:59:60.001",
+        "2017-01-01T00:01:08.185"
+    )]
+    #[case(
+        "tai2utc",
+        TimeScale::Tai,
+        TimeScale::Utc,
+        "2017-01-01T00:00:36.001",
+        "2016-12-31T23:59:60.001"
+    )]
+    #[case(
+        "tt2utc",
+        TimeScale::Tt,
+        TimeScale::Utc,
+        "2017-01-01T00:01:08.185",
+        "2016-12-31T23:59:60.001"
+    )]
+    #[case(
+        "utc2gps",
+        TimeScale::Utc,
+        TimeScale::Gps,
+        "2016-12-31T23:59:60.001",
+        "2017-01-01T00:00:17.001"
+    )]
+    #[case(
+        "gps2utc",
+        TimeScale::Gps,
+        TimeScale::Utc,
+        "2017-01-01T00:00:17.001",
+        "2016-12-31T23:59:60.001"
+    )]
+    fn test_alias(
+        #[case] exe_name: &str,
+        #[case] from: TimeScale,
+        #[case] to: TimeScale,
+        #[case] in_dt: &str,
+        #[case] expected: &str,
+    ) {
+        let args = vec![exe_name, "-H", in_dt];
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner_alias(
+            from,
+            to,
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            format!("{} {}\n", in_dt, expected)
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test the week notation of input/output datetimes.
+    #[rstest]
+    #[case(&["--from", "utc", "--to", "gps", "--out-repr", "week", "2016-12-31T23:59:60.5"], "1930:17.5")]
+    #[case(&["--from", "gps", "--to", "utc", "--in-repr", "week", "1930:17.5"], "2016-12-31T23:59:60.500")]
+    #[case(&["--from", "gps", "--to", "gps", "--in-repr", "week", "--out-repr", "WEEK", "1930:17.5"], "1930:17.5")]
+    #[case(&["--from", "utc", "--to", "gst", "--out-repr", "week", "2017-01-01T00:00:00"], "906:18")]
+    #[case(&["--from", "utc", "--to", "bdt", "--out-repr", "week", "2017-01-01T00:00:00"], "574:4")]
+    #[case(&["--from", "utc", "--to", "qzss", "--out-repr", "week", "2017-01-01T00:00:00"], "1930:18")]
+    #[case(&["--from", "bdt", "--to", "gst", "--in-repr", "week", "--out-repr", "week", "574:4"], "906:18")]
+    // 10-bit week numbers
+    #[case(&["--from", "utc", "--to", "gps", "--out-repr", "week", "--week-rollover-ref", "2000-01-01T00:00:00", "2016-12-31T23:59:60.5"], "906:17.5")]
+    #[case(&["--from", "gps", "--to", "utc", "--in-repr", "week", "--week-rollover-ref", "2000-01-01T00:00:00", "906:17.5"], "2016-12-31T23:59:60.500")]
+    #[case(&["--from", "gps", "--to", "utc", "--in-repr", "week", "--week-rollover-ref", "2019-04-07T00:00:00", "906:17.5"], "2036-08-16T23:59:59.500")]
+    // 12-bit week numbers of Galileo
+    #[case(&["--from", "utc", "--to", "gst", "--out-repr", "week", "--week-rollover-ref", "2000-01-01T00:00:00", "2017-01-01T00:00:00"], "906:18")]
+    // GLONASS time counts days in four-year intervals instead of weeks.
+    #[case(&["--from", "utc", "--to", "glonass", "--out-repr", "n4nt", "2017-01-01T00:00:00"], "6:367:10800")]
+    #[case(&["--from", "glonass", "--to", "utc", "--in-repr", "N4NT", "6:367:10800.5"], "2017-01-01T00:00:00.500")]
+    #[case(&["--from", "gps", "--to", "glonass", "--in-repr", "week", "--out-repr", "n4nt", "1930:18"], "6:367:10800")]
+    fn test_arg_repr_week(#[case] options: &[&str], #[case] expected: &str) {
+        let mut args = vec![EXE_NAME];
+        args.extend_from_slice(options);
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            format!("{}\n", expected)
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test the Julian dates in input and output.
+    #[rstest]
+    #[case(&["--from", "tt", "--to", "tai", "--out-repr", "mjd", "2017-01-01T12:00:32.184"], "57754.5")]
+    #[case(&["--from", "tt", "--to", "tt", "--in-repr", "jd", "--out-repr", "two-part-jd", "2451545.0"], "2451544.5 0.5")]
+    #[case(&["--from", "utc", "--to", "tai", "--in-repr", "two-part-jd", "2457754.5 0.5"], "2017-01-01T12:00:37.000")]
+    #[case(&["--from", "tai", "--to", "tt", "--in-repr", "MJD", "--out-repr", "jd", "57754.000000000000011574"], "2457754.50037250000001")]
+    // The day of the leap second in UTC is 86401 seconds long.
+    #[case(&["--from", "tai", "--to", "utc", "--out-repr", "mjd", "2017-01-01T00:00:36"], "57753.99998842605988")]
+    #[case(&["--from", "utc", "--to", "tt", "--in-repr", "mjd", "57753.99998842605988"], "2017-01-01T00:01:08.184")]
+    fn test_arg_repr_julian(#[case] options: &[&str], #[case] expected: &str) {
+        let mut args = vec![EXE_NAME];
+        args.extend_from_slice(options);
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            format!("{}\n", expected)
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test the numeric representations in input and output.
+    #[rstest]
+    #[case(&["--from", "unix", "--to", "tai", "1483228800"], "2017-01-01T00:00:37.000")]
+    #[case(&["--from", "tai", "--to", "UNIX", "2017-01-01T00:00:37.5"], "1483228800.5")]
+    #[case(&["--from", "unix", "--to", "tai", "--in-repr", "datetime", "2017-01-01T00:00:00"], "2017-01-01T00:00:37.000")]
+    #[case(&["--from", "utc", "--to", "tai", "--out-repr", "seconds-since-1958", "2017-01-01T00:00:00"], "1861920037")]
+    #[case(&["--from", "tt", "--to", "tt", "--in-repr", "j2000-seconds", "-43200"], "2000-01-01T00:00:00.000")]
+    #[case(&["--from", "utc", "--to", "unix", "--in-repr", "excel", "42736.5"], "1483272000")]
+    fn test_arg_repr_epoch(#[case] options: &[&str], #[case] expected: &str) {
+        let mut args = vec![EXE_NAME];
+        args.extend_from_slice(options);
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            format!("{}\n", expected)
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test NTP timestamps in input and output.
+    #[rstest]
+    #[case(&["--from", "ntp", "--to", "tai", "dc12c500.80000000"], "2017-01-01T00:00:37.500")]
+    #[case(&["--from", "tai", "--to", "ntp", "2017-01-01T00:00:37.5"], "dc12c500.80000000")]
+    #[case(&["--from", "ntp", "--to", "utc", "00000000.00000000"], "2036-02-07T06:28:16.000")]
+    #[case(&["--from", "ntp", "--to", "utc", "--ntp-pivot", "1900-01-01T00:00:00", "00000000.00000000"], "1900-01-01T00:00:00.000")]
+    #[case(&["--from", "utc", "--to", "ntp", "2036-02-07T06:28:16"], "1:00000000.00000000")]
+    #[case(&["--from", "utc", "--to", "ntp", "--ntp-leap-indicator", "2016-12-31T12:00:00"], "dc121c40.00000000 LI=1")]
+    #[case(&["--from", "utc", "--to", "ntp", "--ntp-leap-indicator", "2017-01-01T00:00:00"], "dc12c500.00000000 LI=0")]
+    #[case(&["--from", "tai", "--to", "utc", "--ntp-leap-indicator", "2017-01-01T00:00:36"], "2016-12-31T23:59:60.000 LI=1")]
+    // The leap second repeats the timestamps of the last second before it.
+    #[case(&["--from", "tai", "--to", "ntp", "--ntp-leap-indicator", "2017-01-01T00:00:35.5"], "dc12c4ff.80000000 LI=1")]
+    #[case(&["--from", "tai", "--to", "ntp", "--ntp-leap-indicator", "2017-01-01T00:00:36.5"], "dc12c4ff.80000000 LI=1")]
+    #[case(&["--from", "utc", "--to", "ntp", "--ntp-leap-indicator", "2016-12-31T23:59:60.5"], "dc12c4ff.80000000 LI=1")]
+    #[case(&["--from", "tai", "--to", "ntp", "--ntp-leap-indicator", "2017-01-01T00:00:37.5"], "dc12c500.80000000 LI=0")]
+    fn test_arg_repr_ntp(#[case] options: &[&str], #[case] expected: &str) {
+        let mut args = vec![EXE_NAME];
+        args.extend_from_slice(options);
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            format!("{}\n", expected)
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test the CCSDS time codes in input and output.
+    #[rstest]
+    #[case(&["--from", "tai", "--to", "utc", "--in-repr", "cuc", "1E6EFAA5258000"], "2017-01-01T00:00:00.500")]
+    #[case(&["--from", "tai", "--to", "utc", "--out-repr", "cds", "2017-01-01T00:00:36"], "40542D05265C00")]
+    #[case(&["--from", "utc", "--to", "tai", "--in-repr", "ascii-a", "2016-12-31T23:59:60.5Z"], "2017-01-01T00:00:36.500")]
+    #[case(&["--from", "tai", "--to", "tai", "--in-repr", "cuc", "--out-repr", "ascii-b", "1E6EFAA5258000"], "2017-001T00:00:37.5Z")]
+    #[case(&["--from", "tai", "--to", "tai", "--in-repr", "cuc", "--out-repr", "ccs", "0x1E 6EFAA525 8000"], "5320170101000037500000")]
+    #[case(&["--from", "tai", "--to", "tai", "--in-repr", "cuc", "--out-repr", "cds", "--ccsds-pfield", "41", "1E6EFAA5258000"], "41542E0000927C0000")]
+    #[case(&["--from", "tai", "--to", "tai", "--in-repr", "cuc", "--out-repr", "cuc", "--ccsds-pfield", "1C", "--ccsds-implicit-pfield", "6EFAA525"], "6EFAA525")]
+    #[case(&["--from", "tai", "--to", "tai", "--in-repr", "cuc", "--out-repr", "cds", "--ccsds-implicit-pfield", "6EFAA5258000"], "542E0000927C")]
+    #[case(&["--from", "tai", "--to", "tai", "--in-repr", "cuc", "--ccsds-epoch", "2017-01-01T00:00:00", "2E0000003C4000"], "2017-01-01T00:01:00.250")]
+    fn test_arg_repr_ccsds(#[case] options: &[&str], #[case] expected: &str) {
+        let mut args = vec![EXE_NAME];
+        args.extend_from_slice(options);
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            format!("{}\n", expected)
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test errors about the CCSDS time codes.
+    #[rstest]
+    #[case(&["--from", "tai", "--to", "utc", "--in-repr", "cuc", "--ccsds-pfield", "40", "1E6EFAA5258000"], 1, "Illegal P-field of the CCSDS time code: 40")]
+    #[case(&["--from", "tai", "--to", "utc", "--in-repr", "cuc", "--ccsds-pfield", "4G", "1E6EFAA5258000"], 1, "Cannot parse the datetime: 4G")]
+    #[case(&["--from", "tai", "--to", "utc", "--in-repr", "cuc", "--ccsds-epoch", "2017-01-01", "2E0000003C4000"], 1, "Cannot parse the datetime: 2017-01-01")]
+    #[case(&["--from", "tai", "--to", "utc", "--in-repr", "cuc", "2E0000003C4000"], 2, "The agency-defined epoch of the CCSDS time code is not specified")]
+    #[case(&["--from", "tai", "--to", "utc", "--in-repr", "cds", "1E6EFAA5258000"], 2, "Illegal P-field of the CCSDS time code: 1E")]
+    #[case(&["--from", "tai", "--to", "utc", "--in-repr", "cuc", "1E6EFA"], 2, "Cannot parse the datetime: 1E6EFA")]
+    #[case(&["--from", "tai", "--to", "utc", "--out-repr", "cuc", "2017-01-01T00:00:36"], 2, "The leap second cannot be represented as a number of seconds or days: 2016-12-31 23:59:60")]
+    #[case(&["--from", "tai", "--to", "tai", "--out-repr", "cds", "1957-12-31T23:59:00"], 2, "The datetime cannot be expressed in the CCSDS time code: 1957-12-31 23:59:00")]
+    fn test_arg_repr_ccsds_error(
+        #[case] options: &[&str],
+        #[case] expected_exec_code: i32,
+        #[case] expected_err: &str,
+    ) {
+        let mut args = vec![EXE_NAME];
+        args.extend_from_slice(options);
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, expected_exec_code);
+        assert_eq!(String::from_utf8_lossy(&stdout_buf), "");
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            format!("{}: {}\n", exe::exe_name(), expected_err)
+        );
+    }
+
+    /// Test PTP records in input and output.
+    #[rstest]
+    #[case(&["--from", "ptp", "--to", "utc", "1483228836.5"], "2016-12-31T23:59:60.500")]
+    #[case(&["--from", "ptp", "--to", "utc", "1483228836.5 36 leap61"], "2016-12-31T23:59:60.500")]
+    #[case(&["--from", "ptp", "--to", "utc", "--ptp-utc-offset", "record", "1483228836.5 36 leap61"], "2016-12-31T23:59:60.500")]
+    #[case(&["--from", "ptp", "--to", "tai", "1483228837"], "2017-01-01T00:00:37.000")]
+    #[case(&["--from", "utc", "--to", "ptp", "2016-12-31T12:00:00"], "1483185636.000000000 36 leap61")]
+    #[case(&["--from", "utc", "--to", "ptp", "2017-01-01T00:00:00.5"], "1483228837.500000000 37")]
+    #[case(&["--from", "tai", "--to", "tai", "--out-repr", "ptp", "2017-01-01T00:00:37"], "1483228837.000000000 37")]
+    fn test_arg_repr_ptp(#[case] options: &[&str], #[case] expected: &str) {
+        let mut args = vec![EXE_NAME];
+        args.extend_from_slice(options);
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            format!("{}\n", expected)
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test the records which disagree with the TAI-UTC table; they are converted but reported.
+    #[rstest]
+    #[case(&["--from", "ptp", "--to", "utc", "1483228836.5 37"], "2016-12-31T23:59:60.500", "The currentUtcOffset of the PTP record disagrees with the TAI-UTC table (36 s): 1483228836.5 37")]
+    #[case(&["--from", "ptp", "--to", "utc", "--ptp-utc-offset", "record", "1483228836.5 37"], "2016-12-31T23:59:59.500", "The currentUtcOffset of the PTP record disagrees with the TAI-UTC table (36 s): 1483228836.5 37")]
+    #[case(&["--from", "ptp", "--to", "utc", "1483228837 37 leap61"], "2017-01-01T00:00:00.000", "The leap61 flag of the PTP record disagrees with the TAI-UTC table: 1483228837 37 leap61")]
+    fn test_arg_repr_ptp_mismatch(
+        #[case] options: &[&str],
+        #[case] expected: &str,
+        #[case] expected_err: &str,
+    ) {
+        let mut args = vec![EXE_NAME];
+        args.extend_from_slice(options);
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 2);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            format!("{}\n", expected)
+        );
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            format!("{}: {}\n", exe::exe_name(), expected_err)
+        );
+    }
+
+    /// Test errors about PTP records.
+    #[rstest]
+    #[case(&["--from", "utc", "--to", "utc", "--out-repr", "ptp", "2017-01-01T00:00:00"], 1, "The time scale UTC cannot be represented as PTP timestamps")]
+    #[case(&["--from", "ptp", "--to", "utc", "-1.5"], 2, "Cannot parse the datetime: -1.5")]
+    #[case(&["--from", "ptp", "--to", "utc", "1483228837 37 leap62"], 2, "Cannot parse the datetime: 1483228837 37 leap62")]
+    #[case(&["--from", "tai", "--to", "ptp", "1969-12-31T23:59:59"], 2, "The datetime is too low: 1969-12-31 23:59:59")]
+    fn test_arg_repr_ptp_error(
+        #[case] options: &[&str],
+        #[case] expected_exec_code: i32,
+        #[case] expected_err: &str,
+    ) {
+        let mut args = vec![EXE_NAME];
+        args.extend_from_slice(options);
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, expected_exec_code);
+        assert_eq!(String::from_utf8_lossy(&stdout_buf), "");
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            format!("{}: {}\n", exe::exe_name(), expected_err)
+        );
+    }
+
+    /// Test the model of TDB and its record in the standard error output.
+    #[rstest]
+    #[case(&["--from", "tt", "--to", "tdb", "2017-01-01T12:00:00.000000"], "2017-01-01T11:59:59.999965\n", "# TDB model: fairhead-bretagnon-truncated\n")]
+    #[case(&["--from", "tt", "--to", "tdb", "--tdb-model", "simplified", "2017-01-01T12:00:00.000000"], "2017-01-01T11:59:59.999944\n", "# TDB model: simplified\n")]
+    #[case(&["--from", "tcb", "--to", "tt", "--tdb-model", "Fairhead-Bretagnon-Truncated", "2017-01-01T12:00:19.572974"], "2017-01-01T12:00:00.000000\n", "# TDB model: fairhead-bretagnon-truncated\n")]
+    // TDB is not used in the conversion between TT and TCG.
+    #[case(&["--from", "tt", "--to", "tcg", "--tdb-model", "simplified", "2017-01-01T12:00:00.000000"], "2017-01-01T12:00:00.879766\n", "")]
+    fn test_arg_tdb_model(
+        #[case] options: &[&str],
+        #[case] expected: &str,
+        #[case] expected_err: &str,
+    ) {
+        let mut args = vec![EXE_NAME, "--dt-fmt", "%Y-%m-%dT%H:%M:%S%.6f"];
+        args.extend_from_slice(options);
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(String::from_utf8_lossy(&stdout_buf), expected);
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), expected_err);
+    }
+
+    /// Test errors about the week notation.
+    #[rstest]
+    #[case(&["--from", "utc", "--to", "gps", "--in-repr", "week", "1930:17.5"], 1, "The time scale UTC has no week number")]
+    #[case(&["--from", "gps", "--to", "tt", "--out-repr", "week", "2017-01-01T00:00:00"], 1, "The time scale TT has no week number")]
+    #[case(&["--from", "utc", "--to", "glonass", "--out-repr", "week", "2017-01-01T00:00:00"], 1, "The time scale GLONASS has no week number")]
+    #[case(&["--from", "utc", "--to", "gps", "--out-repr", "n4nt", "2017-01-01T00:00:00"], 1, "The time scale GPS has no four-year interval number N4; only GLONASS time has it")]
+    #[case(&["--from", "glonass", "--to", "utc", "--in-repr", "n4nt", "6:1462:0"], 2, "Cannot parse the datetime: 6:1462:0")]
+    #[case(&["--from", "utc", "--to", "glonass", "--out-repr", "n4nt", "2016-12-31T23:59:60.5"], 2, "The leap second cannot be represented as a number of seconds or days: 2017-01-01 02:59:60.500")]
+    #[case(&["--from", "utc", "--to", "glonass", "--out-repr", "n4nt", "1995-12-31T20:59:59"], 2, "The datetime is too low: 1995-12-31 20:59:59")]
+    #[case(&["--from", "bdt", "--to", "utc", "--in-repr", "week", "--week-rollover-ref", "2010-01-01T00:00:00", "8192:0"], 2, "Cannot parse the datetime: 8192:0")]
+    #[case(&["--from", "gps", "--to", "utc", "--in-repr", "week", "--week-rollover-ref", "2000-01-01", "906:17.5"], 1, "Cannot parse the datetime: 2000-01-01")]
+    #[case(&["--from", "gps", "--to", "utc", "--in-repr", "week", "1930-17.5"], 2, "Cannot parse the datetime: 1930-17.5")]
+    #[case(&["--from", "gps", "--to", "tai", "--in-repr", "week", "99999999999:0"], 2, "Cannot parse the datetime: 99999999999:0")]
+    #[case(&["--from", "utc", "--to", "tai", "--in-repr", "mjd", "2017-01-01T00:00:00"], 2, "Cannot parse the datetime: 2017-01-01T00:00:00")]
+    #[case(&["--from", "utc", "--to", "tai", "--in-repr", "mjd", "99999999999999999"], 2, "Cannot parse the datetime: 99999999999999999")]
+    #[case(&["--from", "utc", "--to", "tai", "--in-repr", "jd", "9999999999999"], 2, "Cannot parse the datetime: 9999999999999")]
+    #[case(&["--from", "utc", "--to", "tai", "--in-repr", "excel", "9999999999999"], 2, "Cannot parse the datetime: 9999999999999")]
+    #[case(&["--from", "tai", "--to", "unix", "2017-01-01T00:00:36.5"], 2, "The leap second cannot be represented as a number of seconds or days: 2016-12-31 23:59:60.500")]
+    #[case(&["--from", "utc", "--to", "gps", "--out-repr", "week", "1979-12-31T23:59:59"], 2, "The datetime is too low: 1979-12-31 23:59:59")]
+    #[case(&["--from", "tai", "--to", "ntp", "2017-01-01T00:00:36"], 2, "The leap second cannot be represented as a number of seconds or days: 2016-12-31 23:59:60")]
+    #[case(&["--from", "ntp", "--to", "utc", "dc12c500"], 2, "Cannot parse the datetime: dc12c500")]
+    #[case(&["--from", "ntp", "--to", "utc", "--ntp-pivot", "1900", "dc12c500.00000000"], 1, "Cannot parse the datetime: 1900")]
+    fn test_arg_repr_week_error(
+        #[case] options: &[&str],
+        #[case] expected_exec_code: i32,
+        #[case] expected_err: &str,
+    ) {
+        let mut args = vec![EXE_NAME];
+        args.extend_from_slice(options);
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, expected_exec_code);
+        assert_eq!(String::from_utf8_lossy(&stdout_buf), "");
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            format!("{}: {}\n", exe::exe_name(), expected_err)
+        );
+    }
+    /// Test the conversion from/to UT1 with the EOP table.
+    #[rstest]
+    #[case(testmod::FINALS2000A, false)]
+    #[case(testmod::FINALS2000A, true)]
+    #[case(&[
+        "      MJD      x    error     y    error   UT1-UTC   error",
+        "       2017  1  1  57754 0.04878 .00003 0.26653 .00003  0.592565 0.000009",
+        "     2017  1  2  57755       0.0488   0.2665   0.591803",
+    ], false)]
+    fn test_eop_table(#[case] eop_table: &[&str], #[case] by_env: bool) {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let eop_table_path = testmod::tmp_text_file(&test_dir, "eop.txt", eop_table).unwrap();
+        let eop_table_path = eop_table_path.to_str().unwrap();
+
+        let mut args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "ut1",
+            "--dt-fmt",
+            "%Y-%m-%dT%H:%M:%S%.6f",
+            "2017-01-01T12:00:00.000000",
+        ];
+        let mut env_vars = HashMap::<&str, &str>::from([]);
+        if by_env {
+            env_vars.insert("EOP_TABLE", eop_table_path);
+        } else {
+            args.extend_from_slice(&["--eop-table", eop_table_path]);
+        }
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            "2017-01-01T12:00:00.592184\n"
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test errors about the EOP table.
+    #[rstest]
+    #[case(&["--from", "ut1", "--to", "utc", "2017-01-01T12:00:00"], 1, "The EOP table is required to convert from/to UT1")]
+    #[case(&["--from", "utc", "--to", "ut1", "--eop-table", "/tmp/dummy/not_exists.txt", "2017-01-01T12:00:00"], 1, "The EOP table file isn't available: /tmp/dummy/not_exists.txt")]
+    fn test_eop_table_error(
+        #[case] options: &[&str],
+        #[case] expected_exec_code: i32,
+        #[case] expected_err: &str,
+    ) {
+        let mut args = vec![EXE_NAME];
+        args.extend_from_slice(options);
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, expected_exec_code);
+        assert_eq!(String::from_utf8_lossy(&stdout_buf), "");
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            format!("{}: {}\n", exe::exe_name(), expected_err)
+        );
+    }
+
+    /// Test the sidereal times and ERA in output.
+    #[rstest]
+    #[case(&["--from", "utc", "--to", "gmst", "2017-01-01T12:00:00"], "18:45:19.977458")]
+    #[case(&["--from", "utc", "--to", "gast", "2017-01-01T12:00:00"], "18:45:19.583478")]
+    #[case(&["--from", "utc", "--to", "era", "2017-01-01T12:00:00"], "18:44:27.696381")]
+    #[case(&["--from", "utc", "--to", "ut1", "--out-repr", "era", "2017-01-01T12:00:00"], "18:44:27.696381")]
+    #[case(&["--from", "utc", "--to", "lst", "--longitude", "139.7", "2017-01-01T12:00:00"], "04:04:07.583478")]
+    #[case(&["--from", "utc", "--to", "lst", "--longitude", "-60", "2017-01-01T12:00:00"], "14:45:19.583478")]
+    #[case(&["--from", "utc", "--to", "gmst", "2016-12-31T23:59:60"], "06:43:20.697418")]
+    fn test_arg_repr_sidereal(#[case] options: &[&str], #[case] expected: &str) {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let eop_table_path =
+            testmod::tmp_text_file(&test_dir, "finals2000A.all", testmod::FINALS2000A).unwrap();
+
+        let mut args = vec![EXE_NAME, "--eop-table", eop_table_path.to_str().unwrap()];
+        args.extend_from_slice(options);
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            format!("{}\n", expected)
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test errors about the sidereal times and ERA.
+    #[rstest]
+    #[case(&["--from", "utc", "--to", "lst", "2017-01-01T12:00:00"], "The longitude is required to calculate the local sidereal time")]
+    #[case(&["--from", "utc", "--to", "lst", "--longitude", "east", "2017-01-01T12:00:00"], "Cannot parse the longitude: east")]
+    #[case(&["--from", "utc", "--to", "tt", "--out-repr", "gmst", "2017-01-01T12:00:00"], "Sidereal time and ERA are calculated only from UT1, not TT")]
+    #[case(&["--from", "ut1", "--in-repr", "gmst", "--to", "utc", "2017-01-01T12:00:00"], "The representation gmst can be used only for output")]
+    fn test_arg_repr_sidereal_error(#[case] options: &[&str], #[case] expected_err: &str) {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let eop_table_path =
+            testmod::tmp_text_file(&test_dir, "finals2000A.all", testmod::FINALS2000A).unwrap();
+
+        let mut args = vec![EXE_NAME, "--eop-table", eop_table_path.to_str().unwrap()];
+        args.extend_from_slice(options);
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 1);
+        assert_eq!(String::from_utf8_lossy(&stdout_buf), "");
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            format!("{}: {}\n", exe::exe_name(), expected_err)
+        );
+    }
+
+    /// Test error when the datetime is out of the coverage of the EOP table.
+    #[test]
+    fn test_eop_table_out_of_coverage() {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let eop_table_path =
+            testmod::tmp_text_file(&test_dir, "finals2000A.all", testmod::FINALS2000A).unwrap();
+
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "ut1",
+            "--eop-table",
+            eop_table_path.to_str().unwrap(),
+            "2017-01-01T00:00:00",
+            "2017-01-03T00:00:00",
+        ];
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 2);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            "2017-01-01T00:00:00.592\n"
+        );
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            format!(
+                "{}: The datetime is out of the coverage of the EOP table: 2017-01-03 00:00:00\n",
+                exe::exe_name()
+            )
+        );
+    }
+
+    /// Test the conversion with the model of ΔT.
+    #[rstest]
+    #[case(&["--from", "utc", "--to", "tt", "--delta-t", "espenak-meeus", "1900-01-01T00:00:00.000"], false, "1899-12-31T23:59:57.210 (delta-t: espenak-meeus)")]
+    #[case(&["--from", "tt", "--to", "utc", "--delta-t", "Espenak-Meeus", "1899-12-31T23:59:57.210"], false, "1900-01-01T00:00:00.000 (delta-t: espenak-meeus)")]
+    #[case(&["--from", "utc", "--to", "tt", "--delta-t", "espenak-meeus", "2017-01-01T12:00:00.000"], false, "2017-01-01T12:01:09.184")]
+    #[case(&["--from", "utc", "--to", "ut1", "--delta-t", "espenak-meeus", "2017-01-01T12:00:00.000"], false, "2017-01-01T11:59:59.171 (delta-t: espenak-meeus)")]
+    #[case(&["--from", "utc", "--to", "tt", "1800-01-01T12:00:00.000"], false, "1800-01-01T12:00:13.800 (delta-t: table)")]
+    #[case(&["--from", "utc", "--to", "tt", "1800-01-01T12:00:00.000"], true, "1800-01-01T12:00:13.800 (delta-t: table)")]
+    #[case(&["--from", "utc", "--to", "tt", "--delta-t", "espenak-meeus", "1800-01-01T12:00:00.000"], true, "1800-01-01T12:00:13.719 (delta-t: espenak-meeus)")]
+    fn test_arg_delta_t(#[case] options: &[&str], #[case] by_env: bool, #[case] expected: &str) {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let delta_t_table_path = testmod::tmp_text_file(
+            &test_dir,
+            "deltat.data",
+            &["1800 1 1 13.7", "1800 1 2 13.9"],
+        )
+        .unwrap();
+        let delta_t_table_path = delta_t_table_path.to_str().unwrap();
+
+        let mut args = vec![EXE_NAME];
+        let mut env_vars = HashMap::<&str, &str>::new();
+        // The ΔT table is used only if the model is not specified.
+        if by_env {
+            env_vars.insert("DELTA_T_TABLE", delta_t_table_path);
+        } else if !options.contains(&"--delta-t") {
+            args.extend_from_slice(&["--delta-t-table", delta_t_table_path]);
+        }
+        args.extend_from_slice(options);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 0);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            format!("{}\n", expected)
+        );
+        assert_eq!(String::from_utf8_lossy(&stderr_buf), "");
+    }
+
+    /// Test errors about the model of ΔT.
+    #[rstest]
+    #[case(&["--from", "utc", "--to", "tt", "--delta-t", "table", "1800-01-01T12:00:00"], 1, "The ΔT table file is required for the ΔT model \"table\"")]
+    #[case(&["--from", "utc", "--to", "tt", "--delta-t-table", "/tmp/dummy/not_exists.txt", "1800-01-01T12:00:00"], 1, "The ΔT table file isn't available: /tmp/dummy/not_exists.txt")]
+    #[case(&["--from", "utc", "--to", "tt", "--delta-t-table", "/tmp/dummy/not_exists.txt", "--delta-t", "espenak-meeus", "1800-01-01T12:00:00"], 0, "")]
+    fn test_arg_delta_t_error(
+        #[case] options: &[&str],
+        #[case] expected_exec_code: i32,
+        #[case] expected_err: &str,
+    ) {
+        let mut args = vec![EXE_NAME];
+        args.extend_from_slice(options);
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, expected_exec_code);
+        if expected_exec_code != 0 {
+            assert_eq!(String::from_utf8_lossy(&stdout_buf), "");
+            assert_eq!(
+                String::from_utf8_lossy(&stderr_buf),
+                format!("{}: {}\n", exe::exe_name(), expected_err)
+            );
+        }
+    }
+
+    /// Test error when the datetime is out of the coverage of the ΔT table.
+    #[test]
+    fn test_delta_t_table_out_of_coverage() {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let delta_t_table_path = testmod::tmp_text_file(
+            &test_dir,
+            "deltat.data",
+            &["1800 1 1 13.7", "1800 1 2 13.9"],
+        )
+        .unwrap();
+
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tt",
+            "--delta-t-table",
+            delta_t_table_path.to_str().unwrap(),
+            "1900-01-01T00:00:00",
+            "1800-01-01T12:00:00",
+        ];
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 2);
+        assert_eq!(
+            String::from_utf8_lossy(&stdout_buf),
+            "1800-01-01T12:00:13.800 (delta-t: table)\n"
+        );
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            format!(
+                "{}: The datetime is out of the coverage of the ΔT table: 1900-01-01 00:00:00\n",
+                exe::exe_name()
+            )
+        );
+    }
+
+    /// Test the policies for the datetimes beyond the expiration date of the TAI-UTC table.
+    #[rstest]
+    #[case(&["--from", "utc", "--to", "tai", "2030-01-01T00:00:00"], 0, "2030-01-01T00:00:37.000\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "--beyond-table", "assume-constant", "2030-01-01T00:00:00"], 0, "2030-01-01T00:00:37.000\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "--beyond-table", "warn", "2024-06-27T23:59:59", "2030-01-01T00:00:00"], 0, "2024-06-28T00:00:36.000\n2030-01-01T00:00:37.000\n", "The datetime is beyond the expiration of the TAI-UTC table (2024-06-28 00:00:00), so leap seconds after it are unknown: 2030-01-01 00:00:00\n")]
+    #[case(&["--from", "tt", "--to", "utc", "--beyond-table", "Warn", "2030-01-01T00:01:09.184"], 0, "2030-01-01T00:00:00.000\n", "The datetime is beyond the expiration of the TAI-UTC table (2024-06-28 00:00:00), so leap seconds after it are unknown: 2030-01-01 00:01:09.184\n")]
+    #[case(&["--from", "tai", "--to", "tt", "--beyond-table", "warn", "2030-01-01T00:00:00"], 0, "2030-01-01T00:00:32.184\n", "")]
+    #[case(&["--from", "utc", "--to", "tt", "--beyond-table", "error", "2024-06-27T23:59:59", "2030-01-01T00:00:00"], 2, "2024-06-28T00:01:08.184\n", "The datetime is too high: 2030-01-01 00:00:00\n")]
+    #[case(&["--from", "utc", "--to", "tt", "--beyond-table", "error", "--delta-t", "espenak-meeus", "2030-01-01T00:00:00"], 0, "2030-01-01T00:01:17.615 (delta-t: espenak-meeus)\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "--beyond-table", "error", "--tai-utc-table-expiration", "2035-01-01T00:00:00", "2030-01-01T00:00:00"], 0, "2030-01-01T00:00:37.000\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "--beyond-table", "error", "--tai-utc-table-expiration", "2020-01-01T00:00:00", "2023-01-01T00:00:00"], 2, "", "The datetime is too high: 2023-01-01 00:00:00\n")]
+    fn test_arg_beyond_table(
+        #[case] options: &[&str],
+        #[case] expected_exec_code: i32,
+        #[case] expected_stdout: &str,
+        #[case] expected_stderr: &str,
+    ) {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let tai_utc_table_path = testmod::tmp_tai_utc_table(
+            &test_dir,
+            &[
+                "#\tFile expires on:  28 June 2024",
+                "#$\t 3676924800",
+                "#@\t 3928521600",
+                "3644697600\t36\t# 1 Jul 2015",
+                "3692217600\t37\t# 1 Jan 2017",
+                "#h\t7e3e2943 3fa11956 e4e658de c4dca0e6 bf6622c9",
+            ],
+        )
+        .unwrap();
+
+        let mut args = vec![
+            EXE_NAME,
+            "--tai-utc-table",
+            tai_utc_table_path.to_str().unwrap(),
+        ];
+        args.extend_from_slice(options);
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, expected_exec_code);
+        assert_eq!(String::from_utf8_lossy(&stdout_buf), expected_stdout);
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            expected_stderr
+                .lines()
+                .map(|line| format!("{}: {}\n", exe::exe_name(), line))
+                .collect::<String>()
+        );
+    }
+
+    /// Test the leap seconds of two seconds and the negative leap seconds.
+    #[rstest]
+    #[case(&["--from", "utc", "--to", "tai", "2018-12-31T23:59:60", "2018-12-31T23:59:61.5", "2019-01-01T00:00:00"], 0, "2019-01-01T00:00:36.000\n2019-01-01T00:00:37.500\n2019-01-01T00:00:38.000\n", "")]
+    #[case(&["--from", "tai", "--to", "utc", "2019-01-01T00:00:36", "2019-01-01T00:00:37.5", "2019-01-01T00:00:38"], 0, "2018-12-31T23:59:60.000\n2018-12-31T23:59:61.500\n2019-01-01T00:00:00.000\n", "")]
+    #[case(&["--from", "utc", "--to", "utc", "--dt-fmt", "%Y%m%d%H%M%S", "20181231235961"], 0, "20181231235961\n", "")]
+    #[case(&["--from", "gps", "--to", "utc", "2019-01-01T00:00:18"], 0, "2018-12-31T23:59:61.000\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "2017-12-31T23:59:58", "2017-12-31T23:59:59", "2018-01-01T00:00:00"], 2, "2018-01-01T00:00:35.000\n2018-01-01T00:00:36.000\n", "The datetime does not exist in UTC because of the leap seconds: 2017-12-31 23:59:59\n")]
+    #[case(&["--from", "utc", "--to", "tai", "--lenient", "2017-12-31T23:59:61"], 2, "", "The datetime does not exist in UTC because of the leap seconds: 2017-12-31 23:59:61\n")]
+    #[case(&["--from", "utc", "--to", "tai", "2016-12-31T23:59:61"], 2, "", "The datetime is too low: 2016-12-31 23:59:61\n")]
+    #[case(&["--from", "tai", "--to", "utc", "--out-leap", "repeat-59", "2019-01-01T00:00:37.25"], 0, "2018-12-31T23:59:59.250 (leap: repeat-59)\n", "")]
+    #[case(&["--from", "tai", "--to", "utc", "--out-repr", "unix", "2019-01-01T00:00:37.25"], 2, "", "The second 61 or more cannot be represented as a datetime without leap seconds: 2018-12-31 23:59:61.250\n")]
+    #[case(&["--from", "tai", "--to", "utc", "--out-repr", "unix", "--out-leap", "roll", "2019-01-01T00:00:37.25"], 0, "1546300800 (leap: roll)\n", "")]
+    fn test_multi_second_and_negative_leap(
+        #[case] options: &[&str],
+        #[case] expected_exec_code: i32,
+        #[case] expected_stdout: &str,
+        #[case] expected_stderr: &str,
+    ) {
+        let test_dir = testmod::tmp_dir(Some("")).unwrap();
+        let tai_utc_table_path = testmod::tmp_tai_utc_table(
+            &test_dir,
+            &[
+                "2017-01-01T00:00:00 37",
+                "2018-01-01T00:00:00 36",
+                "2019-01-01T00:00:00 38",
+            ],
+        )
+        .unwrap();
+
+        let mut args = vec![
+            EXE_NAME,
+            "--tai-utc-table",
+            tai_utc_table_path.to_str().unwrap(),
+        ];
+        args.extend_from_slice(options);
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, expected_exec_code);
+        assert_eq!(String::from_utf8_lossy(&stdout_buf), expected_stdout);
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            expected_stderr
+                .lines()
+                .map(|line| format!("{}: {}\n", exe::exe_name(), line))
+                .collect::<String>()
+        );
+    }
+
+    /// Test the validation of the second 60 and an argument --lenient.
+    #[rstest]
+    #[case(&["--from", "utc", "--to", "tai", "2016-12-31T23:59:60", "2016-06-30T23:59:60", "2017-01-01T00:00:00"], 2, "2017-01-01T00:00:36.000\n2017-01-01T00:00:37.000\n", "The datetime is not a leap second of the TAI-UTC table; the nearest leap second is 2016-12-31 23:59:60: 2016-06-30 23:59:60\n")]
+    #[case(&["--from", "utc", "--to", "tai", "--lenient", "2016-12-31T23:59:60", "2016-06-30T23:59:60", "2017-01-01T00:00:00"], 0, "2017-01-01T00:00:36.000\n2016-07-01T00:00:36.000\n2017-01-01T00:00:37.000\n", "")]
+    #[case(&["--from", "utc", "--to", "tt", "2015-06-30T23:59:60.5"], 0, "2015-07-01T00:01:07.684\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "2015-12-31T23:59:60"], 2, "", "The datetime is not a leap second of the TAI-UTC table; the nearest leap second is 2015-06-30 23:59:60: 2015-12-31 23:59:60\n")]
+    #[case(&["--from", "tai", "--to", "utc", "2016-07-01T00:00:36"], 0, "2016-07-01T00:00:00.000\n", "")]
+    fn test_arg_lenient(
+        #[case] options: &[&str],
+        #[case] expected_exec_code: i32,
+        #[case] expected_stdout: &str,
+        #[case] expected_stderr: &str,
+    ) {
+        let args = [&[EXE_NAME], options].concat();
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, expected_exec_code);
+        assert_eq!(String::from_utf8_lossy(&stdout_buf), expected_stdout);
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            expected_stderr
+                .lines()
+                .map(|line| format!("{}: {}\n", exe::exe_name(), line))
+                .collect::<String>()
+        );
+    }
+
+    /// Test the policies for output datetimes in leap seconds.
+    #[rstest]
+    #[case(&["--from", "tai", "--to", "utc", "--out-leap", "keep", "2017-01-01T00:00:36.5", "2017-01-01T00:00:37"], 0, "2016-12-31T23:59:60.500\n2017-01-01T00:00:00.000\n", "")]
+    #[case(&["--from", "tai", "--to", "utc", "--out-leap", "repeat-59", "2017-01-01T00:00:36.5", "2017-01-01T00:00:37"], 0, "2016-12-31T23:59:59.500 (leap: repeat-59)\n2017-01-01T00:00:00.000\n", "")]
+    #[case(&["--from", "tai", "--to", "utc", "--out-leap", "Freeze", "--dt-fmt", "%Y-%m-%dT%H:%M:%S%.9f", "2017-01-01T00:00:36.500000000"], 0, "2016-12-31T23:59:59.999999999 (leap: freeze)\n", "")]
+    #[case(&["--from", "tai", "--to", "utc", "--out-leap", "roll", "2017-01-01T00:00:36.5"], 0, "2017-01-01T00:00:00.000 (leap: roll)\n", "")]
+    #[case(&["--from", "tai", "--to", "utc", "--out-leap", "error", "2017-01-01T00:00:36.5", "2017-01-01T00:00:37"], 2, "2017-01-01T00:00:00.000\n", "The output datetime is in a leap second: 2016-12-31 23:59:60.500\n")]
+    #[case(&["--from", "tt", "--to", "utc", "--out-leap", "repeat-59", "2017-01-01T00:01:08.684"], 0, "2016-12-31T23:59:59.500 (leap: repeat-59)\n", "")]
+    #[case(&["--from", "tai", "--to", "tai", "--out-leap", "error", "2017-01-01T00:00:36.5"], 0, "2017-01-01T00:00:36.500\n", "")]
+    // 表現形式によらず同じ方針を適用する
+    #[case(&["--from", "tai", "--to", "utc", "--out-repr", "unix", "2017-01-01T00:00:36.5"], 2, "", "The leap second cannot be represented as a number of seconds or days: 2016-12-31 23:59:60.500\n")]
+    #[case(&["--from", "tai", "--to", "utc", "--out-repr", "unix", "--out-leap", "repeat-59", "2017-01-01T00:00:36.5"], 0, "1483228799.5 (leap: repeat-59)\n", "")]
+    #[case(&["--from", "tai", "--to", "utc", "--out-repr", "mjd", "--out-leap", "roll", "2017-01-01T00:00:36.5"], 0, "57754 (leap: roll)\n", "")]
+    #[case(&["--from", "tai", "--to", "utc", "--out-repr", "unix", "--out-leap", "error", "2017-01-01T00:00:36.5"], 2, "", "The output datetime is in a leap second: 2016-12-31 23:59:60.500\n")]
+    fn test_arg_out_leap(
+        #[case] options: &[&str],
+        #[case] expected_exec_code: i32,
+        #[case] expected_stdout: &str,
+        #[case] expected_stderr: &str,
+    ) {
+        let args = [&[EXE_NAME], options].concat();
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, expected_exec_code);
+        assert_eq!(String::from_utf8_lossy(&stdout_buf), expected_stdout);
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            expected_stderr
+                .lines()
+                .map(|line| format!("{}: {}\n", exe::exe_name(), line))
+                .collect::<String>()
+        );
+    }
+
+    /// Test the UTC offsets of input datetimes and arguments --out-tz and --zoneinfo-dir.
+    #[rstest]
+    #[case(&["--from", "tai", "--to", "utc", "--out-tz", "+09:00", "2017-01-01T00:00:36.5", "2017-01-01T00:00:37"], &[], 0, "2017-01-01T08:59:60.500\n2017-01-01T09:00:00.000\n", "")]
+    #[case(&["--from", "tai", "--to", "utc", "--out-tz", "JST-9", "--dt-fmt", "%Y-%m-%dT%H:%M:%S%:z", "2017-01-01T00:00:36+00:00"], &[], 0, "2017-01-01T08:59:60+09:00\n", "")]
+    #[case(&["--from", "tai", "--to", "utc", "--out-tz", "EST5EDT,M3.2.0,M11.1.0", "--dt-fmt", "%Y-%m-%dT%H:%M:%S%z", "2017-01-01T00:00:36+0000", "2017-07-01T00:00:37+0000"], &[], 0, "2016-12-31T18:59:60-0500\n2017-06-30T20:00:00-0400\n", "")]
+    #[case(&["--from", "utc", "--to", "utc", "--out-tz", "-05:00", "--out-leap", "repeat-59", "2017-01-01T08:59:60.5+09:00"], &[], 0, "2016-12-31T18:59:59.500 (leap: repeat-59)\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "2017-01-01T08:59:60+09:00", "2016-12-31T23:59:60Z", "2016-12-31T18:59:60.5-0500"], &[], 0, "2017-01-01T00:00:36.000\n2017-01-01T00:00:36.000\n2017-01-01T00:00:36.500\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "2017-01-01T09:59:60+09:00"], &[], 2, "", "The datetime is not a leap second of the TAI-UTC table; the nearest leap second is 2016-12-31 23:59:60: 2017-01-01 00:59:60\n")]
+    #[case(&["--from", "utc", "--to", "tai", "2017-01-01T09:00:00+09:00", "2017-01-01T00:00:00Z"], &[], 0, "2017-01-01T00:00:37.000\n2017-01-01T00:00:37.000\n", "")]
+    // The trailing UTC offset is not parsed by the other formats without %z.
+    #[case(&["--from", "utc", "--to", "tai", "--dt-fmt", "%Y/%m/%d %H:%M:%S", "2017/01/01 09:00:00+09:00"], &[], 2, "", "Cannot parse the datetime: 2017/01/01 09:00:00+09:00\n")]
+    #[case(&["--from", "utc", "--to", "tai", "--dt-fmt", "%Y-%m-%d %H:%M:%S %z %Z", "2017-01-01 08:59:60 +0900 JST"], &[], 0, "2017-01-01 00:00:36 +0000 TAI\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "--dt-fmt", "%Y-%m-%d %H:%M:%S %z", "2017-01-01 08:59:60 +0900"], &[], 0, "2017-01-01 00:00:36 +0000\n", "")]
+    #[case(&["--from", "tai", "--to", "tt", "--out-tz", "+09:00", "2017-01-01T00:00:00"], &[], 1, "", "The time zone can be used only for the output datetimes of UTC, not TT\n")]
+    #[case(&["--from", "tai", "--to", "utc", "--out-repr", "unix", "--out-tz", "+09:00", "2017-01-01T00:00:00"], &[], 1, "", "The time zone can be used only for the output datetimes of UTC, not unix\n")]
+    #[case(&["--from", "tai", "--to", "utc", "--out-tz", "Asia/Tokyo", "2017-01-01T00:00:00"], &[("TZDIR", "/nonexistent-zoneinfo")], 1, "", "The zoneinfo directory /nonexistent-zoneinfo is not found; the time zone Asia/Tokyo requires the TZif files of the IANA time zone database\n")]
+    #[case(&["--from", "tai", "--to", "utc", "--out-tz", "Asia/Tokyo", "--zoneinfo-dir", "/nonexistent-zoneinfo", "2017-01-01T00:00:00"], &[], 1, "", "The zoneinfo directory /nonexistent-zoneinfo is not found; the time zone Asia/Tokyo requires the TZif files of the IANA time zone database\n")]
+    fn test_arg_out_tz(
+        #[case] options: &[&str],
+        #[case] env_vars: &[(&str, &str)],
+        #[case] expected_exec_code: i32,
+        #[case] expected_stdout: &str,
+        #[case] expected_stderr: &str,
+    ) {
+        let args = [&[EXE_NAME], options].concat();
+        let env_vars = env_vars
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect::<HashMap<_, _>>();
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, expected_exec_code);
+        assert_eq!(String::from_utf8_lossy(&stdout_buf), expected_stdout);
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            expected_stderr
+                .lines()
+                .map(|line| format!("{}: {}\n", exe::exe_name(), line))
+                .collect::<String>()
+        );
+    }
+
+    /// Test ISO 8601 datetimes by the argument --dt-fmt.
+    #[rstest]
+    #[case(&["--from", "utc", "--to", "tai", "--dt-fmt", "iso8601", "2016-12-31T23:59:60", "2016-366T23:59:60,5", "2016W526T235960.250", "2017-01-01T08:59:60+09:00"], 0, "2017-01-01T00:00:36\n2017-001T00:00:36,5\n2016W527T000036.250\n2017-01-01T00:00:36\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "--dt-fmt", "iso8601", "2016-12-31", "2016-12-31T24:00:00", "2016-12-31T23:59,5"], 0, "2016-12-31T00:00:36\n2017-01-01T00:00:37\n2017-01-01T00:00:06\n", "")]
+    #[case(&["--from", "tai", "--to", "utc", "--dt-fmt", "iso8601-week", "2017-01-01T00:00:36.5Z"], 0, "2016-W52-6T23:59:60.5Z\n", "")]
+    #[case(&["--from", "tai", "--to", "utc", "--dt-fmt", "ISO8601-ordinal-basic", "--out-tz", "+09:00", "2017-01-01T00:00:36"], 0, "2017001T085960+0900\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "--dt-fmt", "iso8601", "--out-repr", "unix", "2017-001T00:00:00Z"], 0, "1483228837\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "--dt-fmt", "iso8601", "2017-12-31T23:59:60"], 2, "", "The datetime is not a leap second of the TAI-UTC table; the nearest leap second is 2016-12-31 23:59:60: 2017-12-31 23:59:60\n")]
+    #[case(&["--from", "utc", "--to", "tai", "--dt-fmt", "iso8601", "2016-12-31 23:59:60"], 2, "", "Cannot parse the datetime: 2016-12-31 23:59:60\n")]
+    #[case(&["--from", "utc", "--to", "tai", "--dt-fmt", "iso8601", "--ntp-pivot", "1968-01-20 03:14:08", "2016-12-31"], 1, "", "Cannot parse the datetime: 1968-01-20 03:14:08\n")]
+    fn test_arg_dt_fmt_iso8601(
+        #[case] options: &[&str],
+        #[case] expected_exec_code: i32,
+        #[case] expected_stdout: &str,
+        #[case] expected_stderr: &str,
+    ) {
+        let args = [&[EXE_NAME], options].concat();
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, expected_exec_code);
+        assert_eq!(String::from_utf8_lossy(&stdout_buf), expected_stdout);
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            expected_stderr
+                .lines()
+                .map(|line| format!("{}: {}\n", exe::exe_name(), line))
+                .collect::<String>()
+        );
+    }
+
+    /// Test two or more formats by the argument --dt-fmt and the environment variable DT_FMT, and the argument --out-dt-fmt.
+    #[rstest]
+    #[case(&["--from", "utc", "--to", "tai", "--dt-fmt", "%Y-%m-%dT%H:%M:%S%.3f", "--dt-fmt", "%Y/%m/%d %H:%M:%S", "--dt-fmt", "iso8601", "2016-12-31T23:59:60.500", "2016/12/31 23:59:60", "2016-366T23:59:60Z"], &[], 0, "2017-01-01T00:00:36.500\n2017/01/01 00:00:36\n2017-001T00:00:36\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "2016-12-31T23:59:60.500", "20161231235960", "2016-W52-6T23:59:60"], &[("DT_FMT", "%Y-%m-%dT%H:%M:%S%.3f|%Y%m%d%H%M%S|iso8601")], 0, "2017-01-01T00:00:36.500\n20170101000036\n2016-W52-7T00:00:36\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "--dt-fmt", "%Y%m%d%H%M%S", "20161231235960"], &[("DT_FMT", "%Y-%m-%dT%H:%M:%S%.3f|iso8601")], 0, "20170101000036\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "--dt-fmt", "%Y%m%d%H%M%S", "--dt-fmt", "iso8601", "--out-dt-fmt", "%Y-%m-%d %H:%M:%S", "20161231235960", "2016-366T23:59:60"], &[], 0, "2017-01-01 00:00:36\n2017-01-01 00:00:36\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "--dt-fmt", "%Y%m%d%H%M%S", "--dt-fmt", "iso8601", "--out-dt-fmt", "iso8601-ordinal", "20161231235960", "2017-01-01T08:59:60,5+09:00"], &[], 0, "2017-001T00:00:36\n2017-001T00:00:36,5\n", "")]
+    #[case(&["--from", "tai", "--to", "utc", "--in-repr", "unix", "--dt-fmt", "%Y%m%d%H%M%S", "--dt-fmt", "iso8601", "1483228836"], &[], 0, "20161231235960\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "--dt-fmt", "%Y%m%d%H%M%S", "--dt-fmt", "iso8601", "2016/12/31 23:59:60"], &[], 2, "", "Cannot parse the datetime along any of the formats \"%Y%m%d%H%M%S\", \"iso8601\": 2016/12/31 23:59:60\n")]
+    #[case(&["--from", "utc", "--to", "tai", "2016/12/31 23:59:60"], &[("DT_FMT", "%Y%m%d%H%M%S|iso8601")], 2, "", "Cannot parse the datetime along any of the formats \"%Y%m%d%H%M%S\", \"iso8601\": 2016/12/31 23:59:60\n")]
+    #[case(&["--from", "utc", "--to", "tai", "--dt-fmt", "%Y%m%d%H%M%S", "--dt-fmt", "iso8601", "--ntp-pivot", "1968/01/20", "2016-12-31"], &[], 1, "", "Cannot parse the datetime along any of the formats \"%Y%m%d%H%M%S\", \"iso8601\": 1968/01/20\n")]
+    fn test_arg_dt_fmt_list(
+        #[case] options: &[&str],
+        #[case] env_vars: &[(&str, &str)],
+        #[case] expected_exec_code: i32,
+        #[case] expected_stdout: &str,
+        #[case] expected_stderr: &str,
+    ) {
+        let args = [&[EXE_NAME], options].concat();
+        let env_vars = env_vars
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect::<HashMap<_, _>>();
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, expected_exec_code);
+        assert_eq!(String::from_utf8_lossy(&stdout_buf), expected_stdout);
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            expected_stderr
+                .lines()
+                .map(|line| format!("{}: {}\n", exe::exe_name(), line))
+                .collect::<String>()
+        );
+    }
+
+    /// Test arguments --in-dt-fmt and --out-dt-fmt, environment variables IN_DT_FMT and OUT_DT_FMT, and their precedence.
+    #[rstest]
+    #[case(&["--from", "utc", "--to", "tai", "--in-dt-fmt", "%Y%m%d%H%M%S", "--out-dt-fmt", "%Y-%m-%d %H:%M:%S", "20161231235960"], &[], 0, "2017-01-01 00:00:36\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "--in-dt-fmt", "%Y%m%d%H%M%S", "20161231235960"], &[], 0, "20170101000036\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "--in-dt-fmt", "%Y%m%d%H%M%S", "--in-dt-fmt", "iso8601", "20161231235960", "2016-366T23:59:60"], &[], 0, "20170101000036\n2017-001T00:00:36\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "--in-dt-fmt", "%Y%m%d%H%M%S", "--dt-fmt", "%Y/%m/%d %H:%M:%S", "20161231235960"], &[], 0, "2017/01/01 00:00:36\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "--out-dt-fmt", "%Y%m%d%H%M%S", "--dt-fmt", "%Y/%m/%d %H:%M:%S", "2016/12/31 23:59:60"], &[], 0, "20170101000036\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "20161231235960"], &[("IN_DT_FMT", "%Y%m%d%H%M%S"), ("OUT_DT_FMT", "iso8601-ordinal")], 0, "2017-001T00:00:36\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "20161231235960", "2016-366T23:59:60"], &[("IN_DT_FMT", "%Y%m%d%H%M%S|iso8601"), ("DT_FMT", "%Y/%m/%d %H:%M:%S")], 0, "2017/01/01 00:00:36\n2017/01/01 00:00:36\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "2016/12/31 23:59:60"], &[("DT_FMT", "%Y/%m/%d %H:%M:%S"), ("OUT_DT_FMT", "%Y%m%d%H%M%S")], 0, "20170101000036\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "--dt-fmt", "%Y%m%d%H%M%S", "20161231235960"], &[("IN_DT_FMT", "iso8601"), ("OUT_DT_FMT", "iso8601")], 0, "20170101000036\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "--dt-fmt", "%Y%m%d%H%M%S", "--dt-fmt", "iso8601", "2016-366T23:59:60"], &[("OUT_DT_FMT", "%Y%m%d%H%M%S")], 0, "2017-001T00:00:36\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "--in-dt-fmt", "%Y%m%d%H%M%S", "2016-12-31T23:59:60"], &[], 2, "", "Cannot parse the datetime: 2016-12-31T23:59:60\n")]
+    fn test_arg_in_out_dt_fmt(
+        #[case] options: &[&str],
+        #[case] env_vars: &[(&str, &str)],
+        #[case] expected_exec_code: i32,
+        #[case] expected_stdout: &str,
+        #[case] expected_stderr: &str,
+    ) {
+        let args = [&[EXE_NAME], options].concat();
+        let env_vars = env_vars
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect::<HashMap<_, _>>();
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, expected_exec_code);
+        assert_eq!(String::from_utf8_lossy(&stdout_buf), expected_stdout);
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            expected_stderr
+                .lines()
+                .map(|line| format!("{}: {}\n", exe::exe_name(), line))
+                .collect::<String>()
+        );
+    }
+
+    /// Test the extended specifiers in the datetime formats.
+    #[rstest]
+    #[case(&["--from", "gps", "--to", "utc", "--in-dt-fmt", "%{gpsweek}_%{sow:.1}", "--out-dt-fmt", "%Y-%{doy:.6} %{mjd:.6} dAT=%{dAT}", "1930_17.5", "1930_18"], &[], 0, "2016-366.999994 57753.999994 dAT=36\n2017-001.000000 57754.000000 dAT=37\n", "")]
+    #[case(&["--from", "utc", "--to", "gps", "--dt-fmt", "%{gpsweek}_%{sow:.3}_%{scale}", "1930_0.000_UTC"], &[], 0, "1930_18.000_GPS\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "--dt-fmt", "%Y-%m-%dT%H:%M:%S %{dAT}", "1965-06-01T00:00:00 0", "2017-01-01T00:00:00 0"], &[], 0, "1965-06-01T00:00:03 3.835826\n2017-01-01T00:00:37 37\n", "")]
+    #[case(&["--from", "utc", "--to", "gps", "--dt-fmt", "%{gpsweek}_%{sow:.3}_%{scale}", "1930_0.000_gps"], &[], 2, "", "Cannot parse the datetime: 1930_0.000_gps\n")]
+    #[case(&["--from", "utc", "--to", "tai", "--dt-fmt", "%Y%{doy}%H%M%S", "2016366235960"], &[], 0, "2017001000036\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "--in-dt-fmt", "%Y-%{doy}T%H:%M:%S%z", "--out-dt-fmt", "%Y-%m-%dT%H:%M:%S %{scale}", "2017-001T08:59:60+0900"], &[], 0, "2017-01-01T00:00:36 TAI\n", "")]
+    #[case(&["--from", "tai", "--to", "utc", "--out-dt-fmt", "%{mjd:.6}", "--out-tz", "Asia/Tokyo", "2017-01-01T00:00:36.000"], &[], 0, "57753.999988\n", "")]
+    #[case(&["--from", "tai", "--to", "tt", "--in-dt-fmt", "%Y-%m-%dT%H:%M:%S%.3f", "--out-dt-fmt", "%{dAT}", "1950-01-01T00:00:00.000"], &[], 2, "", "The datetime is too low: 1950-01-01 00:00:32.184\n")]
+    #[case(&["--from", "tai", "--to", "utc", "--dt-fmt", "%{mjd:.15}", "57754.5"], &[], 1, "", "Unknown or illegal extended specifier in the datetime format: %{mjd:.15}\n")]
+    #[case(&["--from", "tai", "--to", "utc", "--out-dt-fmt", "%{foo}", "2017-01-01T00:00:00.000"], &[], 1, "", "Unknown or illegal extended specifier in the datetime format: %{foo}\n")]
+    #[case(&["--from", "tai", "--to", "utc", "2017-01-01T00:00:00.000"], &[("OUT_DT_FMT", "%{sow:.x}")], 1, "", "Unknown or illegal extended specifier in the datetime format: %{sow:.x}\n")]
+    fn test_arg_dt_fmt_extended(
+        #[case] options: &[&str],
+        #[case] env_vars: &[(&str, &str)],
+        #[case] expected_exec_code: i32,
+        #[case] expected_stdout: &str,
+        #[case] expected_stderr: &str,
+    ) {
+        let args = [&[EXE_NAME], options].concat();
+        let env_vars = env_vars
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect::<HashMap<_, _>>();
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, expected_exec_code);
+        assert_eq!(String::from_utf8_lossy(&stdout_buf), expected_stdout);
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            expected_stderr
+                .lines()
+                .map(|line| format!("{}: {}\n", exe::exe_name(), line))
+                .collect::<String>()
+        );
+    }
+
+    /// Test smeared UTC and arguments --smear and --from-smear.
+    #[rstest]
+    #[case(&["--from", "tai", "--to", "utc", "--smear", "google", "2016-12-31T12:00:36", "2017-01-01T00:00:36.5", "2017-01-01T12:00:37"], 0, "2016-12-31T12:00:00.000\n2017-01-01T00:00:00.000\n2017-01-01T12:00:00.000\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "--from-smear", "2016-12-31T12:00:00", "2017-01-01T00:00:00", "2017-01-01T12:00:00"], 0, "2016-12-31T12:00:36.000\n2017-01-01T00:00:36.500\n2017-01-01T12:00:37.000\n", "")]
+    #[case(&["--from", "utc", "--to", "tai", "--from-smear", "2016-12-31T23:59:60"], 2, "", "The datetime does not exist in UTC because of the leap seconds: 2016-12-31 23:59:60\n")]
+    #[case(&["--from", "smeared-utc", "--to", "tai", "2016-12-31T23:59:60.5"], 2, "", "The datetime does not exist in UTC because of the leap seconds: 2016-12-31 23:59:60.500\n")]
+    #[case(&["--from", "tai", "--to", "smeared-utc", "2017-01-01T00:00:36.5"], 0, "2017-01-01T00:00:00.000\n", "")]
+    #[case(&["--from", "tai", "--to", "utc", "--smear", "utc-sls", "2016-12-31T23:52:16.5"], 0, "2016-12-31T23:51:40.000\n", "")]
+    #[case(&["--from", "utc", "--to", "utc", "--smear", "AWS", "2016-12-31T23:59:60"], 0, "2016-12-31T23:59:59.500\n", "")]
+    #[case(&["--from", "tai", "--to", "utc", "--smear", "custom:-10,20,cosine", "2017-01-01T00:00:36.5"], 0, "2017-01-01T00:00:00.000\n", "")]
+    #[case(&["--from", "tai", "--to", "utc", "--smear", "custom:10,20", "2017-01-01T00:00:36.5"], 1, "", "Unknown or illegal shape of smearing: custom:10,20\n")]
+    fn test_arg_smear(
+        #[case] options: &[&str],
+        #[case] expected_exec_code: i32,
+        #[case] expected_stdout: &str,
+        #[case] expected_stderr: &str,
+    ) {
+        let args = [&[EXE_NAME], options].concat();
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, expected_exec_code);
+        assert_eq!(String::from_utf8_lossy(&stdout_buf), expected_stdout);
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            expected_stderr
+                .lines()
+                .map(|line| format!("{}: {}\n", exe::exe_name(), line))
+                .collect::<String>()
+        );
+    }
+
+    /// Test error when the expiration date of the TAI-UTC table is illegal.
+    #[test]
+    fn test_arg_tai_utc_table_expiration_illegal() {
+        let args = vec![
+            EXE_NAME,
+            "--from",
+            "utc",
+            "--to",
+            "tai",
+            "--tai-utc-table-expiration",
+            "2024-06-28",
+            "2017-01-01T00:00:00",
+        ];
+        let env_vars = HashMap::<String, String>::from([]);
+        let stdin_buf = b"";
+        let mut stdout_buf = Vec::<u8>::new();
+        let mut stderr_buf = Vec::<u8>::new();
+
+        // Run the target.
+        let exec_code = main_inner(
+            args,
+            env_vars,
+            &mut &stdin_buf[..],
+            &mut stdout_buf,
+            &mut stderr_buf,
+        );
+
+        assert_eq!(exec_code, 1);
+        assert_eq!(String::from_utf8_lossy(&stdout_buf), "");
+        assert_eq!(
+            String::from_utf8_lossy(&stderr_buf),
+            format!(
+                "{}: Cannot parse the datetime: 2024-06-28\n",
+                exe::exe_name()
+            )
+        );
+    }
+}
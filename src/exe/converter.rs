@@ -1,9 +1,665 @@
 use super::error::Error;
 use super::*;
+use crate::ccsds::{self, format_hex, parse_hex, TimeCode};
+use crate::convgraph::{convert_dt, convert_dt_marked, route, BeyondTable, ConvTables};
+use crate::convtbl::TaiUtcTable;
+use crate::epoch::{
+    epoch_1958, format_excel, format_seconds, j2000_epoch, parse_excel, parse_seconds, unix_epoch,
+};
+use crate::extfmt::ExtFormat;
+use crate::iso8601::{self, Iso8601Datetime, Iso8601Format, Iso8601Style, OffsetForm};
+use crate::julian::{
+    format_jd, format_mjd, format_two_part_jd, parse_jd, parse_mjd, parse_two_part_jd,
+};
+use crate::n4nt::{format_n4nt, parse_n4nt};
+use crate::ntp::{
+    default_pivot, format_ntp, format_ntp_repeating_leap_second, leap_indicator, parse_ntp,
+};
+use crate::ptp::PtpRecord;
+use crate::sidereal::{era, format_hms, gast, gmst, lst};
+use crate::week::{format_week, parse_week};
+use crate::zoneinfo::TimeZone;
+use crate::{check_leap_second, tai2utc_label, utc2tai_label, LeapPolicy, UtcLabel};
+use chrono::NaiveDateTime;
 use std::io::{BufRead, Write};
 
 pub trait Converter {
     fn convert(&self, datetime: &str) -> Result<String, crate::error::Error>;
+
+    /// Check the input for problems which do not prevent the conversion; for example, the metadata which disagrees with the tables.
+    ///
+    /// The problems are reported and make the exit code non-zero.
+    fn check(&self, _datetime: &str) -> Vec<crate::error::Error> {
+        vec![]
+    }
+
+    /// Check the input for warnings; they are reported, but the exit code is not affected.
+    fn warn(&self, _datetime: &str) -> Vec<crate::error::Error> {
+        vec![]
+    }
+}
+
+/// Converter between the time scales specified by `--from` and `--to`
+pub struct ScaleConverter<'a> {
+    from: TimeScale,
+    to: TimeScale,
+    tables: ConvTables,
+    dt_fmts: Vec<&'a str>,
+    out_dt_fmt: Option<&'a str>,
+    in_repr: Repr,
+    out_repr: Repr,
+    week_rollover_ref: Option<NaiveDateTime>,
+    ccsds_pfield: Option<Vec<u8>>,
+    ccsds_implicit_pfield: bool,
+    ccsds_epoch: Option<NaiveDateTime>,
+    ntp_pivot: Option<NaiveDateTime>,
+    ntp_leap_indicator: bool,
+    ptp_record_offset: bool,
+    lenient: bool,
+    out_leap: LeapPolicy,
+    out_tz: Option<TimeZone>,
+    longitude: Option<f64>,
+}
+
+/// The format of an output datetime in [`Repr::Datetime`]
+struct OutputFormat<'a> {
+    dt_fmt: &'a str,
+    /// The parsed input if it is in ISO 8601; its style is kept in the output.
+    in_iso8601: Option<Iso8601Datetime>,
+}
+
+impl<'a> ScaleConverter<'a> {
+    /// Create a converter.
+    ///
+    /// # Arguments
+    /// * `dt_fmts` - The formats of input datetimes, which are tried in order; each output datetime is in the format which the input matched.
+    pub fn new(
+        from: TimeScale,
+        to: TimeScale,
+        tables: ConvTables,
+        dt_fmts: &[&'a str],
+    ) -> ScaleConverter<'a> {
+        ScaleConverter {
+            from,
+            to,
+            tables,
+            dt_fmts: dt_fmts.to_vec(),
+            out_dt_fmt: None,
+            in_repr: Repr::Datetime,
+            out_repr: Repr::Datetime,
+            week_rollover_ref: None,
+            ccsds_pfield: None,
+            ccsds_implicit_pfield: false,
+            ccsds_epoch: None,
+            ntp_pivot: None,
+            ntp_leap_indicator: false,
+            ptp_record_offset: false,
+            lenient: false,
+            out_leap: LeapPolicy::Keep,
+            out_tz: None,
+            longitude: None,
+        }
+    }
+
+    /// Set the format of output datetimes.
+    ///
+    /// # Arguments
+    /// * `out_dt_fmt` - The format of output datetimes; if it is `None`, the format which the input matched is used.
+    pub fn with_out_dt_fmt(self, out_dt_fmt: Option<&'a str>) -> Self {
+        ScaleConverter { out_dt_fmt, ..self }
+    }
+
+    /// Set the representations of input/output datetimes.
+    ///
+    /// # Arguments
+    /// * `in_repr` - The representation of input datetimes
+    /// * `out_repr` - The representation of output datetimes
+    /// * `week_rollover_ref` - If it is specified, week numbers are treated as truncated ones; see [`parse_week`].
+    ///
+    /// # Returns
+    /// Returns [`Error`](crate::error::Error) if the time scale has no week number but [`Repr::Week`] is specified,
+    /// the time scale is not GLONASS time but [`Repr::N4Nt`] is specified,
+    /// the time scale is not TAI but [`Repr::Ptp`] is specified,
+    /// or a sidereal representation such as [`Repr::Gmst`] is specified for input or for output in the time scale except UT1.
+    pub fn with_repr(
+        self,
+        in_repr: Repr,
+        out_repr: Repr,
+        week_rollover_ref: Option<NaiveDateTime>,
+    ) -> Result<Self, crate::error::Error> {
+        for (repr, scale) in [(in_repr, self.from), (out_repr, self.to)] {
+            if repr == Repr::Week && scale.week_epoch().is_none() {
+                Err(crate::error::Error::WeekNotSupportedError(
+                    scale.to_string(),
+                ))?;
+            }
+            if repr == Repr::N4Nt && scale != TimeScale::Glonass {
+                Err(crate::error::Error::N4NtNotSupportedError(
+                    scale.to_string(),
+                ))?;
+            }
+            if repr == Repr::Ptp && scale != TimeScale::Tai {
+                Err(crate::error::Error::PtpNotSupportedError(scale.to_string()))?;
+            }
+        }
+        if in_repr.is_sidereal() {
+            Err(crate::error::Error::OutputOnlyReprError(
+                in_repr.name().to_string(),
+            ))?;
+        }
+        if out_repr.is_sidereal() && self.to != TimeScale::Ut1 {
+            Err(crate::error::Error::SiderealNotSupportedError(
+                self.to.to_string(),
+            ))?;
+        }
+        Ok(ScaleConverter {
+            in_repr,
+            out_repr,
+            week_rollover_ref,
+            ..self
+        })
+    }
+
+    /// Set the parameters of the CCSDS time codes.
+    ///
+    /// # Arguments
+    /// * `pfield` - The P-field used for the side whose representation is the time code which the P-field defines.
+    ///   The other side uses [`TimeCode::default_pfield`].
+    /// * `implicit_pfield` - If it is true, input time codes have no P-field and output time codes omit it.
+    /// * `epoch` - The agency-defined epoch. If it is specified, the default P-fields use it.
+    ///
+    /// # Returns
+    /// Returns [`Error`](crate::error::Error) if `pfield` defines neither the input time code nor the output one.
+    pub fn with_ccsds(
+        self,
+        pfield: Option<Vec<u8>>,
+        implicit_pfield: bool,
+        epoch: Option<NaiveDateTime>,
+    ) -> Result<Self, crate::error::Error> {
+        if let Some(pfield) = &pfield {
+            let code = TimeCode::of_pfield(pfield);
+            if code.is_none()
+                || (code != self.in_repr.ccsds_code() && code != self.out_repr.ccsds_code())
+            {
+                Err(crate::error::Error::CcsdsPFieldError(format_hex(pfield)))?;
+            }
+        }
+        Ok(ScaleConverter {
+            ccsds_pfield: pfield,
+            ccsds_implicit_pfield: implicit_pfield,
+            ccsds_epoch: epoch,
+            ..self
+        })
+    }
+
+    /// Set the parameters of NTP timestamps.
+    ///
+    /// # Arguments
+    /// * `pivot` - The pivot to resolve the eras of input NTP timestamps; see [`parse_ntp`]. If it is `None`, [`default_pivot`] is used.
+    /// * `leap_indicator` - If it is true, the leap indicator at the datetime is appended to each output; see [`leap_indicator`].
+    pub fn with_ntp(self, pivot: Option<NaiveDateTime>, leap_indicator: bool) -> Self {
+        ScaleConverter {
+            ntp_pivot: pivot,
+            ntp_leap_indicator: leap_indicator,
+            ..self
+        }
+    }
+
+    /// Set the source of UTC - TAI for input PTP records.
+    ///
+    /// # Arguments
+    /// * `record_offset` - If it is true, `currentUtcOffset` in each record is used instead of the TAI-UTC table;
+    ///   the records without it are converted with the table. See [`PtpRecord::utc`].
+    pub fn with_ptp(self, record_offset: bool) -> Self {
+        ScaleConverter {
+            ptp_record_offset: record_offset,
+            ..self
+        }
+    }
+
+    /// Set whether to accept the second 60 which is not a leap second.
+    ///
+    /// # Arguments
+    /// * `lenient` - If it is false, input UTC datetimes with the second 60 are checked with [`check_leap_second`].
+    pub fn with_lenient(self, lenient: bool) -> Self {
+        ScaleConverter { lenient, ..self }
+    }
+
+    /// Set the policy for output UTC datetimes in leap seconds.
+    ///
+    /// # Arguments
+    /// * `out_leap` - The policy applied to the output labels of UTC before formatting them along `--out-repr`; see [`UtcLabel::with_leap_policy`].
+    pub fn with_out_leap(self, out_leap: LeapPolicy) -> Self {
+        ScaleConverter { out_leap, ..self }
+    }
+
+    /// Set the time zone of output UTC datetimes.
+    ///
+    /// # Arguments
+    /// * `out_tz` - The time zone; see [`TimeZone::format`]. If it is `None`, the datetimes are output in UTC.
+    ///
+    /// # Returns
+    /// Returns [`Error`](crate::error::Error) if the time zone is specified but the output is not UTC in [`Repr::Datetime`].
+    pub fn with_out_tz(self, out_tz: Option<TimeZone>) -> Result<Self, crate::error::Error> {
+        if out_tz.is_some() && self.to != TimeScale::Utc {
+            Err(crate::error::Error::TimeZoneNotSupportedError(
+                self.to.to_string(),
+            ))?;
+        }
+        if out_tz.is_some() && self.out_repr != Repr::Datetime {
+            Err(crate::error::Error::TimeZoneNotSupportedError(
+                self.out_repr.name().to_string(),
+            ))?;
+        }
+        Ok(ScaleConverter { out_tz, ..self })
+    }
+
+    /// Set the longitude for the local sidereal time.
+    ///
+    /// # Arguments
+    /// * `longitude` - The longitude in degrees, positive to the east
+    ///
+    /// # Returns
+    /// Returns [`Error`](crate::error::Error) if the output representation is [`Repr::Lst`] but `longitude` is not specified.
+    pub fn with_longitude(self, longitude: Option<f64>) -> Result<Self, crate::error::Error> {
+        if self.out_repr == Repr::Lst && longitude.is_none() {
+            Err(crate::error::Error::LongitudeNotSpecifiedError())?;
+        }
+        Ok(ScaleConverter {
+            longitude: longitude.map(f64::to_radians),
+            ..self
+        })
+    }
+
+    /// Calculate the angle of the Earth rotation for the sidereal representation.
+    ///
+    /// # Arguments
+    /// * `ut1` - The output datetime in UT1
+    /// * `in_dt` - The input datetime in the time scale `from`, which is used to obtain TT
+    fn sidereal(
+        &self,
+        ut1: &NaiveDateTime,
+        in_dt: &NaiveDateTime,
+        from: TimeScale,
+    ) -> Result<String, crate::error::Error> {
+        let tt = || convert_dt(in_dt, from, TimeScale::Tt, &self.tables);
+        let angle = match self.out_repr {
+            Repr::Era => era(ut1),
+            Repr::Gmst => gmst(ut1, &tt()?),
+            Repr::Gast => gast(ut1, &tt()?),
+            _ => lst(ut1, &tt()?, self.longitude.unwrap()),
+        };
+        Ok(format_hms(angle))
+    }
+
+    /// The P-field of the CCSDS time code; the specified one if it defines `code`, otherwise the default one.
+    fn ccsds_pfield(&self, code: TimeCode) -> Vec<u8> {
+        match &self.ccsds_pfield {
+            Some(pfield) if TimeCode::of_pfield(pfield) == Some(code) => pfield.clone(),
+            _ => code.default_pfield(self.ccsds_epoch.is_some()),
+        }
+    }
+
+    fn decode_ccsds(
+        &self,
+        code: TimeCode,
+        datetime: &str,
+    ) -> Result<NaiveDateTime, crate::error::Error> {
+        let bytes = if code.is_binary() {
+            parse_hex(datetime)?
+        } else {
+            datetime.as_bytes().to_vec()
+        };
+        let pfield = self.ccsds_pfield(code);
+        ccsds::decode(
+            code,
+            &bytes,
+            self.ccsds_implicit_pfield.then(|| &pfield[..]),
+            self.ccsds_epoch.as_ref(),
+        )
+        .map_err(|e| match e {
+            // Report the input as it is, not normalized hexadecimal.
+            crate::error::Error::DatetimeParseError(_) => {
+                crate::error::Error::DatetimeParseError(datetime.to_string())
+            }
+            e => e,
+        })
+    }
+
+    fn encode_ccsds(
+        &self,
+        code: TimeCode,
+        datetime: &NaiveDateTime,
+    ) -> Result<String, crate::error::Error> {
+        let bytes = ccsds::encode(
+            code,
+            datetime,
+            &self.ccsds_pfield(code),
+            !self.ccsds_implicit_pfield,
+            self.ccsds_epoch.as_ref(),
+        )?;
+        if code.is_binary() {
+            Ok(format_hex(&bytes))
+        } else {
+            Ok(String::from_utf8(bytes).unwrap())
+        }
+    }
+
+    /// Parse the input datetime.
+    ///
+    /// # Returns
+    /// Returns the datetime and its time scale; it may differ from `--from`, for example, when a PTP record has currentUtcOffset.
+    /// The last is the format of the output datetime.
+    fn parse_input(
+        &self,
+        datetime: &str,
+    ) -> Result<(NaiveDateTime, TimeScale, OutputFormat<'a>), crate::error::Error> {
+        let mut from = self.from;
+        let mut out_format = OutputFormat {
+            dt_fmt: self.out_dt_fmt.unwrap_or(self.dt_fmts[0]),
+            in_iso8601: None,
+        };
+        let in_dt = match self.in_repr {
+            Repr::Datetime => {
+                let ((label, in_iso8601), dt_fmt) =
+                    parse_along_dt_fmts(datetime, &self.dt_fmts, |dt_fmt| {
+                        // 拡張指定子が不正な書式には一致しないものとする
+                        let ext_format = ExtFormat::from_dt_fmt(dt_fmt).ok()?;
+                        match (Iso8601Format::from_dt_fmt(dt_fmt), ext_format) {
+                            (Some(_), _) => iso8601::parse(datetime)
+                                .ok()
+                                .map(|parsed| (parsed.label(), Some(parsed))),
+                            (None, Some(ext_format)) => ext_format
+                                .parse(datetime, self.from, self.julian_table(self.from))
+                                .ok()
+                                .map(|(dt, offset)| {
+                                    let label = UtcLabel::from(&dt);
+                                    match self.from {
+                                        TimeScale::Utc => (label.shifted(-i64::from(offset)), None),
+                                        _ => (label, None),
+                                    }
+                                }),
+                            (None, None) if self.from == TimeScale::Utc => {
+                                UtcLabel::parse_from_str(datetime, dt_fmt)
+                                    .ok()
+                                    .map(|label| (label, None))
+                            }
+                            (None, None) => NaiveDateTime::parse_from_str(datetime, dt_fmt)
+                                .ok()
+                                .map(|dt| (UtcLabel::from(&dt), None)),
+                        }
+                        // 秒が61以上のラベルは UTC でのみ受け付ける
+                        .filter(|(label, _)| {
+                            self.from == TimeScale::Utc || label.to_naive().is_some()
+                        })
+                    })?;
+                out_format = OutputFormat {
+                    dt_fmt: self.out_dt_fmt.unwrap_or(dt_fmt),
+                    in_iso8601,
+                };
+                if self.from == TimeScale::Utc && !self.lenient {
+                    check_leap_second(&label, self.tables.tai_utc_table())?;
+                }
+                match label.to_naive() {
+                    Some(in_dt) => in_dt,
+                    // 秒が61以上のラベルは NaiveDateTime で表せないため、先に TAI へ変換する
+                    None => {
+                        from = TimeScale::Tai;
+                        utc2tai_label(&label, self.tables.tai_utc_table())?
+                    }
+                }
+            }
+            Repr::Week => parse_week(
+                datetime,
+                &self.from.week_epoch().unwrap(),
+                self.week_rollover_ref
+                    .as_ref()
+                    .map(|r| (self.from.week_rollover().unwrap(), r)),
+            )?,
+            Repr::N4Nt => parse_n4nt(datetime)?,
+            Repr::Jd => parse_jd(datetime, self.julian_table(self.from))?,
+            Repr::Mjd => parse_mjd(datetime, self.julian_table(self.from))?,
+            Repr::TwoPartJd => parse_two_part_jd(datetime, self.julian_table(self.from))?,
+            Repr::Unix => parse_seconds(datetime, &unix_epoch())?,
+            Repr::SecondsSince1958 => parse_seconds(datetime, &epoch_1958())?,
+            Repr::J2000Seconds => parse_seconds(datetime, &j2000_epoch())?,
+            Repr::Excel => parse_excel(datetime)?,
+            Repr::Ntp => parse_ntp(datetime, &self.ntp_pivot.unwrap_or_else(default_pivot))?,
+            Repr::Ptp => {
+                let record = PtpRecord::parse(datetime)?;
+                match record.utc().filter(|_| self.ptp_record_offset) {
+                    Some(utc) => {
+                        from = TimeScale::Utc;
+                        utc
+                    }
+                    None => record.tai,
+                }
+            }
+            Repr::Cuc | Repr::Cds | Repr::Ccs | Repr::AsciiA | Repr::AsciiB => {
+                self.decode_ccsds(self.in_repr.ccsds_code().unwrap(), datetime)?
+            }
+            Repr::Era | Repr::Gmst | Repr::Gast | Repr::Lst => Err(
+                crate::error::Error::OutputOnlyReprError(self.in_repr.name().to_string()),
+            )?,
+        };
+        Ok((in_dt, from, out_format))
+    }
+
+    /// Check whether the conversion of the input depends on the TAI-UTC table after its expiration date.
+    fn check_beyond_table(&self, datetime: &str) -> Option<crate::error::Error> {
+        let expiration = self.tables.tai_utc_table().expiration()?;
+        let (in_dt, from, _) = self.parse_input(datetime).ok()?;
+        let conv_route = route(from, self.to).ok()?;
+        if !(conv_route.contains(&TimeScale::Utc) && conv_route.contains(&TimeScale::Tai)) {
+            return None;
+        }
+        // The errors of conversion are reported by `convert`.
+        let utc = convert_dt(&in_dt, from, TimeScale::Utc, &self.tables).ok()?;
+        self.tables.tai_utc_table().is_expired_at(&utc).then(|| {
+            crate::error::Error::TaiUtcTableExpiredError(in_dt.to_string(), expiration.to_string())
+        })
+    }
+
+    /// Format the output datetime along `--out-repr`.
+    fn format_output(
+        &self,
+        out_dt: &NaiveDateTime,
+        in_dt: &NaiveDateTime,
+        from: TimeScale,
+        out_format: &OutputFormat,
+    ) -> Result<String, crate::error::Error> {
+        match self.out_repr {
+            Repr::Datetime => self.format_datetime(&UtcLabel::from(out_dt), out_format),
+            Repr::Week => format_week(
+                out_dt,
+                &self.to.week_epoch().unwrap(),
+                self.week_rollover_ref.and(self.to.week_rollover()),
+            )
+            .map_err(|_e| crate::error::Error::DatetimeTooLowError(in_dt.to_string())),
+            Repr::N4Nt => format_n4nt(out_dt).map_err(|e| match e {
+                crate::error::Error::DatetimeTooLowError(_) => {
+                    crate::error::Error::DatetimeTooLowError(in_dt.to_string())
+                }
+                e => e,
+            }),
+            Repr::Jd => Ok(format_jd(out_dt, self.julian_table(self.to))),
+            Repr::Mjd => Ok(format_mjd(out_dt, self.julian_table(self.to))),
+            Repr::TwoPartJd => Ok(format_two_part_jd(out_dt, self.julian_table(self.to))),
+            Repr::Unix => format_seconds(out_dt, &unix_epoch()),
+            Repr::SecondsSince1958 => format_seconds(out_dt, &epoch_1958()),
+            Repr::J2000Seconds => format_seconds(out_dt, &j2000_epoch()),
+            Repr::Excel => format_excel(out_dt),
+            // うるう秒はうるう秒指示子とともに、直前の1秒のタイムスタンプを繰り返して表す
+            Repr::Ntp if self.ntp_leap_indicator => format_ntp_repeating_leap_second(out_dt),
+            Repr::Ntp => format_ntp(out_dt),
+            Repr::Ptp => PtpRecord::from_table(out_dt, &self.tables)
+                .map(|record| record.to_string())
+                .map_err(|_e| crate::error::Error::DatetimeTooLowError(in_dt.to_string())),
+            Repr::Cuc | Repr::Cds | Repr::Ccs | Repr::AsciiA | Repr::AsciiB => {
+                self.encode_ccsds(self.out_repr.ccsds_code().unwrap(), out_dt)
+            }
+            Repr::Era | Repr::Gmst | Repr::Gast | Repr::Lst => self.sidereal(out_dt, in_dt, from),
+        }
+    }
+
+    /// Format the output datetime in the representation [`Repr::Datetime`].
+    ///
+    /// Without `--out-tz`, `%z` and `%Z` in the format are the UTC offset 0 and the name of the time scale; for example, `TAI`.
+    ///
+    /// In ISO 8601, the output keeps the style of the input in ISO 8601, including its UTC offset only if the output is UTC;
+    /// with `--out-tz`, the offset of the time zone is written instead.
+    ///
+    /// The extended specifiers such as `%{gpsweek}` are the fields of the label in the time scale, not in the time zone of `--out-tz`.
+    fn format_datetime(
+        &self,
+        label: &UtcLabel,
+        out_format: &OutputFormat,
+    ) -> Result<String, crate::error::Error> {
+        let dt_fmt = out_format.dt_fmt;
+        if let Some(format) = Iso8601Format::from_dt_fmt(dt_fmt) {
+            let in_iso8601 = out_format.in_iso8601.as_ref();
+            let style = format.style(&in_iso8601.map(|i| i.style).unwrap_or_default());
+            return Ok(match &self.out_tz {
+                Some(out_tz) => {
+                    let (local, local_time_type) = out_tz.localize(label);
+                    let offset_form = style.offset_form.or(Some(OffsetForm::HoursMinutes));
+                    Iso8601Style {
+                        offset_form,
+                        ..style
+                    }
+                    .format(&local, local_time_type.offset)
+                }
+                None if self.to == TimeScale::Utc => {
+                    let offset = in_iso8601.map_or(0, |i| i.offset);
+                    style.format(&label.shifted(i64::from(offset)), offset)
+                }
+                // UTC オフセットは UTC 以外の時刻系では意味をなさない
+                None => Iso8601Style {
+                    offset_form: None,
+                    ..style
+                }
+                .format(label, 0),
+            });
+        }
+        let format_strftime = |dt_fmt: &str| match &self.out_tz {
+            Some(out_tz) => out_tz.format(label, dt_fmt),
+            None => TimeZone::fixed(0, &self.to.name().to_uppercase()).format(label, dt_fmt),
+        };
+        match ExtFormat::from_dt_fmt(dt_fmt)? {
+            Some(ext_format) => ext_format.format(label, self.to, &self.tables, format_strftime),
+            None => Ok(format_strftime(dt_fmt)),
+        }
+    }
+
+    /// Format the output label of UTC along `--out-leap`, `--out-repr` and `--out-tz`.
+    ///
+    /// # Returns
+    /// Returns the output and whether the label was adjusted by `--out-leap`.
+    fn format_utc_output(
+        &self,
+        utc: UtcLabel,
+        in_dt: &NaiveDateTime,
+        from: TimeScale,
+        out_format: &OutputFormat,
+    ) -> Result<(String, bool), crate::error::Error> {
+        let adjusted = utc.with_leap_policy(self.out_leap)?;
+        let output = match (self.out_repr, adjusted.to_naive()) {
+            // 秒が61以上のラベルは NaiveDateTime で表せないため、ラベルとして整形する
+            (Repr::Datetime, _) => self.format_datetime(&adjusted, out_format)?,
+            (_, Some(out_dt)) => self.format_output(&out_dt, in_dt, from, out_format)?,
+            (_, None) => Err(crate::error::Error::UtcLabelNotRepresentableError(
+                adjusted.to_string(),
+            ))?,
+        };
+        Ok((output, adjusted != utc))
+    }
+
+    /// The TAI-UTC table to stretch the days containing leap seconds in Julian dates; it is used only for UTC.
+    fn julian_table(&self, scale: TimeScale) -> Option<&TaiUtcTable> {
+        (scale == TimeScale::Utc).then(|| self.tables.tai_utc_table())
+    }
+}
+
+impl Converter for ScaleConverter<'_> {
+    fn convert(&self, datetime: &str) -> Result<String, crate::error::Error> {
+        let (in_dt, from, out_format) = self.parse_input(datetime)?;
+        let converted = convert_dt_marked(&in_dt, from, self.to, &self.tables);
+        let (output, model_derived, leap_adjusted) = if self.to == TimeScale::Utc {
+            let (utc, model_derived) = match converted {
+                // 秒が61以上のラベルは NaiveDateTime で表せないため、TAI からラベルとして求める
+                Err(crate::error::Error::UtcLabelNotRepresentableError(_)) => {
+                    let tai = convert_dt(&in_dt, from, TimeScale::Tai, &self.tables)?;
+                    (tai2utc_label(&tai, self.tables.utc_tai_table())?, false)
+                }
+                converted => {
+                    let (out_dt, model_derived) = converted?;
+                    (UtcLabel::from(&out_dt), model_derived)
+                }
+            };
+            let (output, leap_adjusted) = self.format_utc_output(utc, &in_dt, from, &out_format)?;
+            (output, model_derived, leap_adjusted)
+        } else {
+            let (out_dt, model_derived) = converted?;
+            (
+                self.format_output(&out_dt, &in_dt, from, &out_format)?,
+                model_derived,
+                false,
+            )
+        };
+
+        let output = if self.ntp_leap_indicator {
+            let utc = convert_dt(&in_dt, from, TimeScale::Utc, &self.tables)?;
+            let leap_indicator = leap_indicator(&utc, self.tables.tai_utc_table());
+            format!("{} LI={}", output, leap_indicator)
+        } else {
+            output
+        };
+
+        // Mark the results whose labels are adjusted because of leap seconds.
+        let output = if leap_adjusted {
+            format!("{} (leap: {})", output, self.out_leap.name())
+        } else {
+            output
+        };
+
+        // Mark the results which are approximated by the model of ΔT.
+        match self.tables.delta_t().filter(|_| model_derived) {
+            Some(delta_t) => Ok(format!("{} (delta-t: {})", output, delta_t.name())),
+            None => Ok(output),
+        }
+    }
+
+    fn check(&self, datetime: &str) -> Vec<crate::error::Error> {
+        match self.in_repr {
+            // The errors of parsing are reported by `convert`.
+            Repr::Ptp => PtpRecord::parse(datetime)
+                .map(|record| record.audit(&self.tables))
+                .unwrap_or_default()
+                .into_iter()
+                // 正規化されたレコードではなく、入力された文字列を報告する
+                .map(|e| match e {
+                    crate::error::Error::PtpUtcOffsetMismatchError(_, expected) => {
+                        crate::error::Error::PtpUtcOffsetMismatchError(
+                            datetime.to_string(),
+                            expected,
+                        )
+                    }
+                    crate::error::Error::PtpLeapFlagMismatchError(_, flag) => {
+                        crate::error::Error::PtpLeapFlagMismatchError(datetime.to_string(), flag)
+                    }
+                    e => e,
+                })
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    fn warn(&self, datetime: &str) -> Vec<crate::error::Error> {
+        match self.tables.beyond_table() {
+            BeyondTable::Warn => self.check_beyond_table(datetime).into_iter().collect(),
+            _ => vec![],
+        }
+    }
 }
 
 pub fn main_convertion<C: Converter>(
@@ -14,7 +670,7 @@ pub fn main_convertion<C: Converter>(
     stderr: &mut impl Write,
 ) -> Result<(), Error> {
     // function for output to stdout
-    let print_line = get_print_line(&params);
+    let print_line = get_print_line(params);
 
     // Chooses input datetimes stream
     let dt_stream: Box<dyn Iterator<Item = Result<String, _>>> = match params.get_datetimes() {
@@ -39,6 +695,13 @@ pub fn main_convertion<C: Converter>(
         };
 
         let out_dt = converter.convert(&in_dt);
+        for problem in converter.check(&in_dt) {
+            someone_is_err = true;
+            print_err(stderr, &problem);
+        }
+        for warning in converter.warn(&in_dt) {
+            print_err(stderr, &warning);
+        }
 
         match out_dt {
             Err(e) => {
@@ -49,9 +712,9 @@ pub fn main_convertion<C: Converter>(
         }
     }
 
-    return if someone_is_err {
+    if someone_is_err {
         Err(Error::FailedSomeConvertionError())
     } else {
         Ok(())
-    };
+    }
 }
@@ -0,0 +1,160 @@
+use crate::ccsds::TimeCode;
+use crate::error::Error;
+use std::str::FromStr;
+
+/// Representations of input/output datetimes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Repr {
+    /// A datetime formatted along `--dt-fmt`
+    #[default]
+    Datetime,
+    /// `week:seconds-of-week`; see [`week`](crate::week)
+    Week,
+    /// `N4:NT:seconds-of-day` of GLONASS time; see [`n4nt`](crate::n4nt)
+    N4Nt,
+    /// Julian date; see [`julian`](crate::julian)
+    Jd,
+    /// Modified Julian date; see [`julian`](crate::julian)
+    Mjd,
+    /// Two-part Julian date; see [`julian`](crate::julian)
+    TwoPartJd,
+    /// Unix time; see [`epoch`](crate::epoch)
+    Unix,
+    /// Seconds since 1958-01-01T00:00:00; see [`epoch`](crate::epoch)
+    SecondsSince1958,
+    /// Seconds since J2000.0; see [`epoch`](crate::epoch)
+    J2000Seconds,
+    /// Serial days of Excel and LibreOffice; see [`epoch`](crate::epoch)
+    Excel,
+    /// NTP timestamp; see [`ntp`](crate::ntp)
+    Ntp,
+    /// PTP record in TAI; see [`ptp`](crate::ptp)
+    Ptp,
+    /// CCSDS Unsegmented time Code in hexadecimal; see [`ccsds`](crate::ccsds)
+    Cuc,
+    /// CCSDS Day Segmented time code in hexadecimal; see [`ccsds`](crate::ccsds)
+    Cds,
+    /// CCSDS Calendar Segmented time code in hexadecimal; see [`ccsds`](crate::ccsds)
+    Ccs,
+    /// CCSDS ASCII time code A; see [`ccsds`](crate::ccsds)
+    AsciiA,
+    /// CCSDS ASCII time code B; see [`ccsds`](crate::ccsds)
+    AsciiB,
+    /// Earth Rotation Angle; output only, see [`era`](crate::era)
+    Era,
+    /// Greenwich mean sidereal time; output only, see [`gmst`](crate::gmst)
+    Gmst,
+    /// Greenwich apparent sidereal time; output only, see [`gast`](crate::gast)
+    Gast,
+    /// Local apparent sidereal time; output only, see [`lst`](crate::lst)
+    Lst,
+}
+
+impl Repr {
+    /// All representations
+    pub const ALL: [Repr; 21] = [
+        Repr::Datetime,
+        Repr::Week,
+        Repr::N4Nt,
+        Repr::Jd,
+        Repr::Mjd,
+        Repr::TwoPartJd,
+        Repr::Unix,
+        Repr::SecondsSince1958,
+        Repr::J2000Seconds,
+        Repr::Excel,
+        Repr::Ntp,
+        Repr::Ptp,
+        Repr::Cuc,
+        Repr::Cds,
+        Repr::Ccs,
+        Repr::AsciiA,
+        Repr::AsciiB,
+        Repr::Era,
+        Repr::Gmst,
+        Repr::Gast,
+        Repr::Lst,
+    ];
+
+    /// The names of [`Repr::ALL`] in the same order
+    pub const NAMES: [&'static str; 21] = [
+        "datetime",
+        "week",
+        "n4nt",
+        "jd",
+        "mjd",
+        "two-part-jd",
+        "unix",
+        "seconds-since-1958",
+        "j2000-seconds",
+        "excel",
+        "ntp",
+        "ptp",
+        "cuc",
+        "cds",
+        "ccs",
+        "ascii-a",
+        "ascii-b",
+        "era",
+        "gmst",
+        "gast",
+        "lst",
+    ];
+
+    /// The name of the representation used in command arguments; for example, `"week"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Repr::Datetime => "datetime",
+            Repr::Week => "week",
+            Repr::N4Nt => "n4nt",
+            Repr::Jd => "jd",
+            Repr::Mjd => "mjd",
+            Repr::TwoPartJd => "two-part-jd",
+            Repr::Unix => "unix",
+            Repr::SecondsSince1958 => "seconds-since-1958",
+            Repr::J2000Seconds => "j2000-seconds",
+            Repr::Excel => "excel",
+            Repr::Ntp => "ntp",
+            Repr::Ptp => "ptp",
+            Repr::Cuc => "cuc",
+            Repr::Cds => "cds",
+            Repr::Ccs => "ccs",
+            Repr::AsciiA => "ascii-a",
+            Repr::AsciiB => "ascii-b",
+            Repr::Era => "era",
+            Repr::Gmst => "gmst",
+            Repr::Gast => "gast",
+            Repr::Lst => "lst",
+        }
+    }
+
+    /// The CCSDS time code of the representation; it is `None` unless the representation is a CCSDS time code.
+    pub fn ccsds_code(&self) -> Option<TimeCode> {
+        match self {
+            Repr::Cuc => Some(TimeCode::Cuc),
+            Repr::Cds => Some(TimeCode::Cds),
+            Repr::Ccs => Some(TimeCode::Ccs),
+            Repr::AsciiA => Some(TimeCode::AsciiA),
+            Repr::AsciiB => Some(TimeCode::AsciiB),
+            _ => None,
+        }
+    }
+
+    /// Whether the representation is an angle of the Earth rotation, which can be used only for output in UT1.
+    pub fn is_sidereal(&self) -> bool {
+        matches!(self, Repr::Era | Repr::Gmst | Repr::Gast | Repr::Lst)
+    }
+}
+
+impl FromStr for Repr {
+    type Err = Error;
+
+    /// Parse the name of a representation. The case of letters is ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Repr::ALL
+            .iter()
+            .find(|repr| repr.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| Error::UnknownReprError(s.to_string()))
+    }
+}
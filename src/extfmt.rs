@@ -0,0 +1,727 @@
+//! Extended format specifiers `%{...}` for the fields which strftime cannot express.
+//!
+//! They can be mixed with the specifiers of [strftime](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html)
+//! in the datetime formats, both to parse and to format datetimes; for example, `%{gpsweek}_%{sow}` or `%Y-%{doy:.3}`.
+//!
+//! | Specifier | Field | Example |
+//! |---|---|---|
+//! | `%{gpsweek}` | The number of weeks since the epoch of GPS time, 1980-01-06T00:00:00 | `1930` |
+//! | `%{sow}` | The seconds of the week of `%{gpsweek}` | `18` |
+//! | `%{doy}` | The day of the year, 1 at 0h of January 1, in three digits at least | `001` |
+//! | `%{mjd}` | The modified Julian date | `57754` |
+//! | `%{scale}` | The name of the time scale in upper case, as `%Z` | `GPS` |
+//! | `%{dAT}` | The difference TAI - UTC in seconds at the datetime | `37` |
+//!
+//! `%{sow}`, `%{doy}` and `%{mjd}` are integers by default, and `%{dAT}` has as many digits as it needs;
+//! for example, `37`, or `3.835826` at 1965-06-01. The number of digits after the decimal point
+//! is specified as `%{mjd:.6}`, up to 9 for `%{sow}` and `%{dAT}` and 14 for `%{doy}` and `%{mjd}`. The digits are truncated.
+//!
+//! The fields are counted on the label of the datetime in its own time scale;
+//! for example, `%{gpsweek}` and `%{sow}` of a datetime in GPS time are the GPS week and seconds of week.
+//! In UTC, the days containing leap seconds are stretched in `%{doy}` and `%{mjd}`, as in [`julian`](crate::julian).
+//!
+//! When a datetime is parsed:
+//! - `%{gpsweek}` and `%{sow}` must be used together, and they decide the datetime.
+//! - `%{mjd}` and `%{doy}` decide the date, and also the time of day if they have the fractional part;
+//!   otherwise the time of day is taken from the strftime specifiers, or 0h without them. `%{doy}` takes the year from `%Y`.
+//! - `%{scale}` must be the name of the time scale of the datetime, ignoring the case of letters.
+//! - `%{dAT}` is read but ignored, since it follows from the datetime and the TAI-UTC table.
+//! - The UTC offset of `%z` is returned only if the time of day is taken from the strftime specifiers.
+use crate::convgraph::{convert_dt, ConvTables};
+use crate::convtbl::TaiUtcTable;
+use crate::epoch::mjd_epoch;
+use crate::error::Error;
+use crate::julian::{format_days_since, parse_days_since};
+use crate::{normalize_leap, parse_decimal_nanos, TimeScale, UtcLabel};
+use chrono::format::{self, Parsed, StrftimeItems};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime};
+
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+const NANOS_PER_WEEK: i64 = 604_800 * NANOS_PER_SECOND;
+
+/// Fields of the extended format specifiers
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Specifier {
+    /// `%{gpsweek}`; the number of weeks since 1980-01-06T00:00:00
+    GpsWeek,
+    /// `%{sow}`; the seconds of the week
+    Sow,
+    /// `%{doy}`; the day of the year
+    Doy,
+    /// `%{mjd}`; the modified Julian date
+    Mjd,
+    /// `%{scale}`; the name of the time scale
+    Scale,
+    /// `%{dAT}`; the difference TAI - UTC in seconds
+    Dat,
+}
+
+impl Specifier {
+    /// All specifiers in the order of [`Specifier::NAMES`]
+    pub const ALL: [Specifier; 6] = [
+        Specifier::GpsWeek,
+        Specifier::Sow,
+        Specifier::Doy,
+        Specifier::Mjd,
+        Specifier::Scale,
+        Specifier::Dat,
+    ];
+
+    /// The names of the specifiers in the order of the variants
+    pub const NAMES: [&'static str; 6] = ["gpsweek", "sow", "doy", "mjd", "scale", "dAT"];
+
+    /// The name of the specifier in the braces; for example, `"mjd"` of `%{mjd:.6}`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Specifier::GpsWeek => "gpsweek",
+            Specifier::Sow => "sow",
+            Specifier::Doy => "doy",
+            Specifier::Mjd => "mjd",
+            Specifier::Scale => "scale",
+            Specifier::Dat => "dAT",
+        }
+    }
+
+    /// The maximum number of digits after the decimal point; `None` if the field has no fractional part.
+    fn max_precision(&self) -> Option<usize> {
+        match self {
+            Specifier::Sow | Specifier::Dat => Some(9),
+            Specifier::Doy | Specifier::Mjd => Some(14),
+            Specifier::GpsWeek | Specifier::Scale => None,
+        }
+    }
+
+    /// Whether the value is a legal number for the field.
+    fn accepts(&self, value: &str) -> bool {
+        match self {
+            Specifier::GpsWeek => !value.is_empty() && value.chars().all(|c| c.is_ascii_digit()),
+            Specifier::Sow => parse_decimal_nanos(value).is_some_and(|sow| sow >= 0),
+            Specifier::Dat => parse_decimal_nanos(value).is_some(),
+            // 日番号は %j と同じく3桁まで
+            Specifier::Doy => {
+                value.split('.').next().is_some_and(|day| day.len() <= 3)
+                    && parse_days_since(value, &mjd_epoch(), None).is_some()
+            }
+            Specifier::Mjd => parse_days_since(value, &mjd_epoch(), None).is_some(),
+            Specifier::Scale => true,
+        }
+    }
+}
+
+/// An extended format specifier with its number of digits after the decimal point
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct ExtSpec {
+    specifier: Specifier,
+    /// `None` if it is not specified
+    precision: Option<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Item<'a> {
+    Strftime(&'a str),
+    Ext(ExtSpec),
+}
+
+/// The state of parsing; the fields of strftime and the values of the extended specifiers which are read
+#[derive(Clone)]
+struct ParseState<'s> {
+    parsed: Parsed,
+    values: Vec<(Specifier, &'s str)>,
+}
+
+/// A datetime format with the extended specifiers
+///
+/// # Examples
+/// ```
+/// use convdate::convgraph::ConvTables;
+/// use convdate::convtbl::TaiUtcTable;
+/// use convdate::extfmt::ExtFormat;
+/// use convdate::{TimeScale, UtcLabel};
+/// use chrono::NaiveDate;
+///
+/// let tai_utc_table = TaiUtcTable::from_lines(vec!["2017-01-01T00:00:00 37"], "%Y-%m-%dT%H:%M:%S").unwrap();
+/// let tables = ConvTables::new(tai_utc_table);
+///
+/// let format = ExtFormat::from_dt_fmt("%{gpsweek}_%{sow:.1}_%{scale}").unwrap().unwrap();
+/// let (datetime, _offset) = format.parse("1930_18.5_GPS", TimeScale::Gps, None).unwrap();
+/// assert_eq!(datetime, NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 18, 500));
+///
+/// let format = ExtFormat::from_dt_fmt("%Y-%{doy:.3} %{mjd} dAT=%{dAT}").unwrap().unwrap();
+/// let label = UtcLabel::from(&datetime);
+/// assert_eq!(
+///     format.format(&label, TimeScale::Gps, &tables, |dt_fmt| label.format(dt_fmt)),
+///     Ok("2017-001.000 57754 dAT=37".to_string()),
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtFormat<'a> {
+    items: Vec<Item<'a>>,
+}
+
+impl<'a> ExtFormat<'a> {
+    /// Split the datetime format into strftime formats and the extended specifiers.
+    ///
+    /// # Arguments
+    /// * `dt_fmt` - A datetime format, which may have the extended specifiers
+    ///
+    /// # Returns
+    /// Returns the format, or `None` if `dt_fmt` has no extended specifiers; it is a plain strftime format.
+    ///
+    /// Returns [`Error`](crate::error::Error) if `dt_fmt` has an unknown or illegal extended specifier.
+    pub fn from_dt_fmt(dt_fmt: &'a str) -> Result<Option<ExtFormat<'a>>, Error> {
+        let mut items = vec![];
+        let push_strftime = |items: &mut Vec<Item<'a>>, strftime: &'a str| {
+            if !strftime.is_empty() {
+                items.push(Item::Strftime(strftime));
+            }
+        };
+        let bytes = dt_fmt.as_bytes();
+        let (mut start, mut i) = (0, 0);
+        while i < bytes.len() {
+            if bytes[i] != b'%' {
+                i += 1;
+                continue;
+            }
+            match bytes.get(i + 1) {
+                Some(b'{') => {
+                    let err = |spec: &str| Error::DtFmtSpecifierError(spec.to_string());
+                    let close = dt_fmt[i..]
+                        .find('}')
+                        .map(|close| i + close)
+                        .ok_or_else(|| err(&dt_fmt[i..]))?;
+                    let spec =
+                        parse_spec(&dt_fmt[i + 2..close]).ok_or_else(|| err(&dt_fmt[i..=close]))?;
+                    push_strftime(&mut items, &dt_fmt[start..i]);
+                    items.push(Item::Ext(spec));
+                    i = close + 1;
+                    start = i;
+                }
+                // "%%" などの strftime の指定子は読み飛ばす
+                Some(_) => i += 2,
+                None => i += 1,
+            }
+        }
+        if !items.iter().any(|item| matches!(item, Item::Ext(_))) {
+            return Ok(None);
+        }
+        push_strftime(&mut items, &dt_fmt[start..]);
+        Ok(Some(ExtFormat { items }))
+    }
+
+    /// Parse a datetime along the format.
+    ///
+    /// # Arguments
+    /// * `s` - A datetime
+    /// * `scale` - The time scale of `s`, which `%{scale}` must match
+    /// * `tai_utc_table` - The TAI-UTC table if `s` is in UTC; it is used to stretch days containing leap seconds.
+    ///
+    /// # Returns
+    /// Returns the datetime and the UTC offset in seconds, which is parsed by `%z` or 0 without it.
+    ///
+    /// Returns [`Error`](crate::error::Error) if it fail to parse.
+    pub fn parse(
+        &self,
+        s: &str,
+        scale: TimeScale,
+        tai_utc_table: Option<&TaiUtcTable>,
+    ) -> Result<(NaiveDateTime, i32), Error> {
+        let state = ParseState {
+            parsed: Parsed::new(),
+            values: vec![],
+        };
+        self.parse_items(s, 0, 0, state, scale, tai_utc_table)
+            .ok_or_else(|| Error::DatetimeParseError(s.to_string()))
+    }
+
+    /// Read the items from `index` at the position `pos` of `s`, trying the longest length of each item first,
+    /// until the whole of `s` is read and the datetime is decided.
+    fn parse_items<'s>(
+        &self,
+        s: &'s str,
+        index: usize,
+        pos: usize,
+        state: ParseState<'s>,
+        scale: TimeScale,
+        tai_utc_table: Option<&TaiUtcTable>,
+    ) -> Option<(NaiveDateTime, i32)> {
+        let item = match self.items.get(index) {
+            Some(item) => item,
+            None if pos == s.len() => return resolve(&state, tai_utc_table),
+            None => return None,
+        };
+        let ends: Vec<usize> = match item {
+            Item::Strftime(_) => (pos..=s.len())
+                .rev()
+                .filter(|end| s.is_char_boundary(*end))
+                .collect(),
+            Item::Ext(spec) if spec.specifier == Specifier::Scale => {
+                let name = scale.name();
+                s.get(pos..pos + name.len())
+                    .filter(|value| value.eq_ignore_ascii_case(name))
+                    .map(|_| vec![pos + name.len()])
+                    .unwrap_or_default()
+            }
+            Item::Ext(_) => {
+                let bytes = s.as_bytes();
+                let mut end = pos;
+                if matches!(bytes.get(end), Some(b'+') | Some(b'-')) {
+                    end += 1;
+                }
+                while end < bytes.len() && (bytes[end].is_ascii_digit() || bytes[end] == b'.') {
+                    end += 1;
+                }
+                (pos + 1..=end).rev().collect()
+            }
+        };
+        for end in ends {
+            let value = &s[pos..end];
+            let mut state = state.clone();
+            let accepted = match item {
+                Item::Strftime(strftime) => {
+                    format::parse(&mut state.parsed, value, StrftimeItems::new(strftime)).is_ok()
+                }
+                Item::Ext(spec) => {
+                    state.values.push((spec.specifier, value));
+                    spec.specifier.accepts(value)
+                }
+            };
+            if !accepted {
+                continue;
+            }
+            if let Some(parsed) = self.parse_items(s, index + 1, end, state, scale, tai_utc_table) {
+                return Some(parsed);
+            }
+        }
+        None
+    }
+
+    /// Format a datetime along the format.
+    ///
+    /// # Arguments
+    /// * `label` - A datetime; its second may be 60 or more if it is in UTC.
+    /// * `scale` - The time scale of `label`
+    /// * `tables` - The tables to calculate `%{dAT}` and to stretch days containing leap seconds in UTC
+    /// * `format_strftime` - The function to format `label` along the strftime formats between the extended specifiers;
+    ///   for example, `|dt_fmt| label.format(dt_fmt)`.
+    ///
+    /// # Returns
+    /// Returns the formatted datetime.
+    ///
+    /// Returns [`Error`](crate::error::Error) if `label` is before the epoch of `%{gpsweek}`,
+    /// or it cannot be converted to UTC to calculate `%{dAT}`.
+    pub fn format(
+        &self,
+        label: &UtcLabel,
+        scale: TimeScale,
+        tables: &ConvTables,
+        format_strftime: impl Fn(&str) -> String,
+    ) -> Result<String, Error> {
+        let datetime = label.to_naive_saturating();
+        let tai_utc_table = (scale == TimeScale::Utc).then_some(tables.tai_utc_table());
+        let mut formatted = String::new();
+        for item in &self.items {
+            let spec = match item {
+                Item::Strftime(strftime) => {
+                    formatted.push_str(&format_strftime(strftime));
+                    continue;
+                }
+                Item::Ext(spec) => spec,
+            };
+            let value = match spec.specifier {
+                Specifier::GpsWeek | Specifier::Sow => {
+                    let nanos = (label.normalized() - gps_epoch())
+                        .num_nanoseconds()
+                        .filter(|nanos| *nanos >= 0)
+                        .ok_or_else(|| Error::DatetimeTooLowError(label.to_string()))?;
+                    match spec.specifier {
+                        Specifier::GpsWeek => nanos.div_euclid(NANOS_PER_WEEK).to_string(),
+                        _ => format_nanos(
+                            nanos.rem_euclid(NANOS_PER_WEEK),
+                            spec.precision.unwrap_or(0),
+                        ),
+                    }
+                }
+                Specifier::Doy => {
+                    let value = format_days_since(
+                        &datetime,
+                        &year_origin(datetime.year()),
+                        tai_utc_table,
+                        spec.precision.unwrap_or(0),
+                    );
+                    let width = match spec.precision.unwrap_or(0) {
+                        0 => 3,
+                        precision => 4 + precision,
+                    };
+                    format!("{:0>width$}", value, width = width)
+                }
+                Specifier::Mjd => format_days_since(
+                    &datetime,
+                    &mjd_epoch(),
+                    tai_utc_table,
+                    spec.precision.unwrap_or(0),
+                ),
+                Specifier::Scale => scale.to_string(),
+                Specifier::Dat => {
+                    let utc = match scale {
+                        TimeScale::Utc => datetime,
+                        _ => convert_dt(&datetime, scale, TimeScale::Utc, tables)?,
+                    };
+                    let tai_utc = tables
+                        .tai_utc_table()
+                        .pick_dominant_row(&utc)?
+                        .diff_at(&normalize_leap(&utc));
+                    // 1972年以前のドリフトは秒の端数になりうる
+                    let nanos = tai_utc
+                        .num_nanoseconds()
+                        .ok_or_else(|| Error::DatetimeTooHighError(label.to_string()))?;
+                    match spec.precision {
+                        Some(precision) => format_nanos(nanos, precision),
+                        // 桁数の指定がなければ、必要なだけの桁数で書く
+                        None => format_nanos(nanos, 9)
+                            .trim_end_matches('0')
+                            .trim_end_matches('.')
+                            .to_string(),
+                    }
+                }
+            };
+            formatted.push_str(&value);
+        }
+        Ok(formatted)
+    }
+}
+
+/// Parse the inside of the braces of an extended specifier; for example, `mjd:.6`.
+fn parse_spec(spec: &str) -> Option<ExtSpec> {
+    let (name, precision) = match spec.split_once(':') {
+        Some((name, precision)) => {
+            let digits = precision.strip_prefix('.')?;
+            if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            (name, Some(digits.parse::<usize>().ok()?))
+        }
+        None => (spec, None),
+    };
+    let specifier = *Specifier::ALL.iter().find(|s| s.name() == name)?;
+    let precision = match (precision, specifier.max_precision()) {
+        (None, _) => None,
+        (Some(precision), Some(max)) if precision <= max => Some(precision),
+        _ => return None,
+    };
+    Some(ExtSpec {
+        specifier,
+        precision,
+    })
+}
+
+/// Decide the datetime from the fields which are read.
+fn resolve(
+    state: &ParseState,
+    tai_utc_table: Option<&TaiUtcTable>,
+) -> Option<(NaiveDateTime, i32)> {
+    let parsed = &state.parsed;
+    let value = |specifier: Specifier| {
+        state
+            .values
+            .iter()
+            .find(|(s, _)| *s == specifier)
+            .map(|(_, value)| *value)
+    };
+    // UTC オフセットは時刻を strftime の指定子から読んだときのみ考慮する
+    let offset = parsed.offset.unwrap_or(0);
+
+    match (value(Specifier::GpsWeek), value(Specifier::Sow)) {
+        (Some(week), Some(sow)) => {
+            // Duration::weeks は範囲外の週番号でパニックするため、先に範囲を確かめる
+            let week = week
+                .parse::<i64>()
+                .ok()
+                .filter(|week| *week <= Duration::max_value().num_weeks())?;
+            let sow = parse_decimal_nanos(sow).filter(|sow| *sow < NANOS_PER_WEEK)?;
+            let datetime = gps_epoch()
+                .checked_add_signed(Duration::weeks(week))?
+                .checked_add_signed(Duration::nanoseconds(sow))?;
+            Some((datetime, 0))
+        }
+        (None, None) => match (value(Specifier::Mjd), value(Specifier::Doy)) {
+            (Some(mjd), _) if mjd.contains('.') => {
+                Some((parse_days_since(mjd, &mjd_epoch(), tai_utc_table)?, 0))
+            }
+            (Some(mjd), _) => Some((parse_day(mjd, mjd_epoch(), parsed)?, offset)),
+            (None, Some(doy)) => {
+                let year = parsed.year?;
+                let (datetime, offset) = if doy.contains('.') {
+                    (parse_days_since(doy, &year_origin(year), tai_utc_table)?, 0)
+                } else {
+                    (parse_day(doy, year_origin(year), parsed)?, offset)
+                };
+                // 年をまたぐ日番号は受け付けない
+                (datetime.year() == year).then_some((datetime, offset))
+            }
+            (None, None) => Some((parsed.to_naive_datetime_with_offset(0).ok()?, offset)),
+        },
+        _ => None,
+    }
+}
+
+/// Parse the integer number of days since `origin`, taking the time of day from `parsed`.
+fn parse_day(value: &str, origin: NaiveDate, parsed: &Parsed) -> Option<NaiveDateTime> {
+    let max_days = Duration::max_value().num_days();
+    let days = value
+        .parse::<i64>()
+        .ok()
+        .filter(|days| (-max_days..=max_days).contains(days))?;
+    let date = origin.checked_add_signed(Duration::days(days))?;
+    let has_time = parsed.hour_mod_12.is_some()
+        || parsed.minute.is_some()
+        || parsed.second.is_some()
+        || parsed.nanosecond.is_some();
+    let time = if has_time {
+        parsed.to_naive_time().ok()?
+    } else {
+        NaiveTime::from_hms(0, 0, 0)
+    };
+    Some(date.and_time(time))
+}
+
+/// The epoch of `%{gpsweek}`
+fn gps_epoch() -> NaiveDateTime {
+    TimeScale::Gps.week_epoch().unwrap()
+}
+
+/// The day before January 1 of the year, so that January 1 is the day 1 of `%{doy}`.
+fn year_origin(year: i32) -> NaiveDate {
+    NaiveDate::from_ymd(year, 1, 1).pred()
+}
+
+/// Format nanoseconds as seconds, truncated to `precision` digits after the decimal point.
+fn format_nanos(nanos: i64, precision: usize) -> String {
+    let sign = if nanos < 0 { "-" } else { "" };
+    let (seconds, subsec) = (
+        nanos.abs() / NANOS_PER_SECOND,
+        nanos.abs() % NANOS_PER_SECOND,
+    );
+    match precision {
+        0 => format!("{}{}", sign, seconds),
+        _ => format!(
+            "{}{}.{:0width$}",
+            sign,
+            seconds,
+            subsec / 10_i64.pow(9 - precision as u32),
+            width = precision
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convtbl::DiffTaiUtc;
+    use crate::testmod;
+    use rstest::*;
+
+    const DT_FMT: &str = "%Y-%m-%dT%H:%M:%S%.9f";
+
+    fn tai_utc_table() -> TaiUtcTable {
+        let mut rows = TaiUtcTable::from_tai_utc_dat(testmod::TAI_UTC_DAT)
+            .unwrap()
+            .to_vec();
+        rows.extend(
+            TaiUtcTable::from_lines(
+                vec!["2015-07-01T00:00:00 36", "2017-01-01T00:00:00 37"],
+                "%Y-%m-%dT%H:%M:%S",
+            )
+            .unwrap()
+            .iter()
+            .cloned(),
+        );
+        TaiUtcTable::from(rows.into_iter().collect::<Vec<DiffTaiUtc>>())
+    }
+
+    #[rstest]
+    #[case("%Y-%m-%dT%H:%M:%S", Ok(None))]
+    #[case("%%{mjd}", Ok(None))]
+    #[case("%{mjd:.6}", Ok(Some(vec![Item::Ext(ExtSpec { specifier: Specifier::Mjd, precision: Some(6) })])))]
+    #[case("gps%{gpsweek}_%{sow}.dat", Ok(Some(vec![
+        Item::Strftime("gps"),
+        Item::Ext(ExtSpec { specifier: Specifier::GpsWeek, precision: None }),
+        Item::Strftime("_"),
+        Item::Ext(ExtSpec { specifier: Specifier::Sow, precision: None }),
+        Item::Strftime(".dat"),
+    ])))]
+    #[case("%%%{scale}%{dAT:.3}", Ok(Some(vec![
+        Item::Strftime("%%"),
+        Item::Ext(ExtSpec { specifier: Specifier::Scale, precision: None }),
+        Item::Ext(ExtSpec { specifier: Specifier::Dat, precision: Some(3) }),
+    ])))]
+    #[case("%Y-%{foo}", Err(Error::DtFmtSpecifierError("%{foo}".to_string())))]
+    #[case("%{DAT}", Err(Error::DtFmtSpecifierError("%{DAT}".to_string())))]
+    #[case("%{mjd:6}", Err(Error::DtFmtSpecifierError("%{mjd:6}".to_string())))]
+    #[case("%{mjd:.}", Err(Error::DtFmtSpecifierError("%{mjd:.}".to_string())))]
+    #[case("%{mjd:.15}", Err(Error::DtFmtSpecifierError("%{mjd:.15}".to_string())))]
+    #[case("%{sow:.10}", Err(Error::DtFmtSpecifierError("%{sow:.10}".to_string())))]
+    #[case("%{gpsweek:.1}", Err(Error::DtFmtSpecifierError("%{gpsweek:.1}".to_string())))]
+    #[case("%{mjd %H", Err(Error::DtFmtSpecifierError("%{mjd %H".to_string())))]
+    fn test_from_dt_fmt(#[case] dt_fmt: &str, #[case] expected: Result<Option<Vec<Item>>, Error>) {
+        let format = ExtFormat::from_dt_fmt(dt_fmt);
+
+        assert_eq!(
+            format,
+            expected.map(|items| items.map(|items| ExtFormat { items }))
+        );
+    }
+
+    #[rstest]
+    // GPS week and seconds of week
+    #[case(TimeScale::Gps, "%{gpsweek}_%{sow}", "1930_18", Some(("2017-01-01T00:00:18.000000000", 0)))]
+    #[case(TimeScale::Gps, "%{gpsweek}:%{sow}", "1930:18.123456789", Some(("2017-01-01T00:00:18.123456789", 0)))]
+    #[case(TimeScale::Gps, "%{gpsweek}_%{sow}", "1930_604799.5", Some(("2017-01-07T23:59:59.500000000", 0)))]
+    #[case(TimeScale::Gps, "%{gpsweek}_%{sow}", "1930_604800", None)]
+    #[case(TimeScale::Gps, "%{gpsweek}_%{sow}", "1930_-1", None)]
+    #[case(TimeScale::Gps, "%{gpsweek}", "1930", None)]
+    #[case(TimeScale::Gps, "%{sow}", "18", None)]
+    #[case(TimeScale::Gps, "%{gpsweek}_%{sow}", "99999999999_0", None)]
+    #[case(TimeScale::Tai, "%{mjd}", "999999999999", None)]
+    // the name of the time scale
+    #[case(TimeScale::Gps, "%{gpsweek}_%{sow}_%{scale}", "1930_18_gps", Some(("2017-01-01T00:00:18.000000000", 0)))]
+    #[case(TimeScale::Gps, "%{gpsweek}_%{sow}_%{scale}", "1930_18_GPS", Some(("2017-01-01T00:00:18.000000000", 0)))]
+    #[case(TimeScale::Gps, "%{gpsweek}_%{sow}_%{scale}", "1930_18_tai", None)]
+    #[case(TimeScale::Tai, "%Y-%m-%dT%H:%M:%S %{scale}", "2017-01-01T00:00:00 TAI", Some(("2017-01-01T00:00:00.000000000", 0)))]
+    // day of year
+    #[case(TimeScale::Tai, "%Y%{doy}%H%M%S", "2017001000036", Some(("2017-01-01T00:00:36.000000000", 0)))]
+    #[case(TimeScale::Tai, "%Y-%{doy}", "2016-366", Some(("2016-12-31T00:00:00.000000000", 0)))]
+    #[case(TimeScale::Tai, "%Y-%{doy:.3}", "2016-366.500", Some(("2016-12-31T12:00:00.000000000", 0)))]
+    #[case(TimeScale::Tai, "%Y-%{doy}", "2017-366", None)]
+    #[case(TimeScale::Tai, "%{doy}", "001", None)]
+    #[case(TimeScale::Utc, "%Y-%{doy:.6}", "2016-366.999994", Some(("2016-12-31T23:59:60.481594000", 0)))]
+    // modified Julian date
+    #[case(TimeScale::Tai, "%{mjd}", "57754", Some(("2017-01-01T00:00:00.000000000", 0)))]
+    #[case(TimeScale::Tai, "%{mjd} %H:%M", "57754 12:30", Some(("2017-01-01T12:30:00.000000000", 0)))]
+    #[case(TimeScale::Tai, "%{mjd:.6}", "57754.25", Some(("2017-01-01T06:00:00.000000000", 0)))]
+    #[case(TimeScale::Tai, "%{mjd}", "57754x", None)]
+    #[case(TimeScale::Utc, "%{mjd:.5}", "57753.99999", Some(("2016-12-31T23:59:60.135990000", 0)))]
+    // UTC offsets
+    #[case(TimeScale::Utc, "%H:%M:%S%z %{mjd}", "08:59:60+0900 57754", Some(("2017-01-01T08:59:60.000000000", 32400)))]
+    #[case(TimeScale::Utc, "%{mjd:.1} %z", "57754.5 +0900", Some(("2017-01-01T12:00:00.000000000", 0)))]
+    // TAI - UTC is ignored
+    #[case(TimeScale::Tai, "%{dAT} %Y-%m-%dT%H:%M:%S", "37 2017-01-01T00:00:00", Some(("2017-01-01T00:00:00.000000000", 0)))]
+    #[case(
+        TimeScale::Tai,
+        "%{dAT} %Y-%m-%dT%H:%M:%S",
+        "x 2017-01-01T00:00:00",
+        None
+    )]
+    fn test_parse(
+        #[case] scale: TimeScale,
+        #[case] dt_fmt: &str,
+        #[case] s: &str,
+        #[case] expected: Option<(&str, i32)>,
+    ) {
+        let tai_utc_table = tai_utc_table();
+        let format = ExtFormat::from_dt_fmt(dt_fmt).unwrap().unwrap();
+
+        let parsed = format.parse(
+            s,
+            scale,
+            (scale == TimeScale::Utc).then_some(&tai_utc_table),
+        );
+
+        match expected {
+            Some((datetime, offset)) => assert_eq!(
+                parsed.map(|(dt, offset)| (dt.format(DT_FMT).to_string(), offset)),
+                Ok((datetime.to_string(), offset))
+            ),
+            None => assert_eq!(parsed, Err(Error::DatetimeParseError(s.to_string()))),
+        }
+    }
+
+    #[rstest]
+    // GPS week and seconds of week
+    #[case(
+        TimeScale::Gps,
+        "%{gpsweek}_%{sow}",
+        "2017-01-01T00:00:18.5",
+        Some("1930_18")
+    )]
+    #[case(
+        TimeScale::Gps,
+        "%{gpsweek}_%{sow:.3}",
+        "2017-01-01T00:00:18.5",
+        Some("1930_18.500")
+    )]
+    #[case(
+        TimeScale::Gps,
+        "%{gpsweek}_%{sow:.9}",
+        "2017-01-07T23:59:59.999999999",
+        Some("1930_604799.999999999")
+    )]
+    #[case(TimeScale::Gps, "%{gpsweek}", "1980-01-05T23:59:59", None)]
+    // day of year
+    #[case(TimeScale::Tai, "%Y-%{doy}", "2016-12-31T12:00:00", Some("2016-366"))]
+    #[case(
+        TimeScale::Tai,
+        "%Y-%{doy:.3}",
+        "2017-01-01T12:00:00",
+        Some("2017-001.500")
+    )]
+    #[case(
+        TimeScale::Utc,
+        "%{doy:.6}",
+        "2016-12-31T23:59:60.5",
+        Some("366.999994")
+    )]
+    // modified Julian date
+    #[case(
+        TimeScale::Tai,
+        "%{mjd:.6}",
+        "2017-01-01T06:00:00",
+        Some("57754.250000")
+    )]
+    #[case(TimeScale::Tai, "%{mjd}", "2017-01-01T23:59:59.999", Some("57754"))]
+    #[case(TimeScale::Tai, "%{mjd:.1}", "1858-11-16T12:00:00", Some("-0.5"))]
+    #[case(
+        TimeScale::Utc,
+        "%{mjd:.5}",
+        "2016-12-31T23:59:60",
+        Some("57753.99998")
+    )]
+    // the name of the time scale
+    #[case(TimeScale::Tai, "%{scale}:%H", "2017-01-01T00:00:00", Some("TAI:00"))]
+    #[case(
+        TimeScale::SmearedUtc,
+        "%{scale}",
+        "2017-01-01T00:00:00",
+        Some("SMEARED-UTC")
+    )]
+    // TAI - UTC
+    #[case(TimeScale::Tai, "%{dAT}", "2017-01-01T00:00:36", Some("36"))]
+    #[case(TimeScale::Tai, "%{dAT}", "2017-01-01T00:00:37", Some("37"))]
+    #[case(
+        TimeScale::Utc,
+        "%H:%M:%S %{dAT}",
+        "2016-12-31T23:59:60",
+        Some("23:59:60 36")
+    )]
+    #[case(TimeScale::Utc, "%{dAT:.6}", "1970-01-01T00:00:00", Some("8.000082"))]
+    #[case(TimeScale::Utc, "%{dAT}", "1970-01-01T00:00:00", Some("8.000082"))]
+    #[case(TimeScale::Utc, "%{dAT}", "1965-06-01T00:00:00", Some("3.46197"))]
+    #[case(TimeScale::Utc, "%{dAT:.0}", "1965-06-01T00:00:00", Some("3"))]
+    #[case(TimeScale::Tai, "%{dAT}", "1950-01-01T00:00:00", None)]
+    fn test_format(
+        #[case] scale: TimeScale,
+        #[case] dt_fmt: &str,
+        #[case] datetime: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        let tables = ConvTables::new(tai_utc_table());
+        let label = UtcLabel::parse_from_str(datetime, "%Y-%m-%dT%H:%M:%S%.f").unwrap();
+        let format = ExtFormat::from_dt_fmt(dt_fmt).unwrap().unwrap();
+
+        let formatted = format.format(&label, scale, &tables, |dt_fmt| label.format(dt_fmt));
+
+        match expected {
+            Some(expected) => assert_eq!(formatted, Ok(expected.to_string())),
+            None => assert!(formatted.is_err()),
+        }
+    }
+}
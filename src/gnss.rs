@@ -0,0 +1,395 @@
+//! Conversion between TAI or UTC and the system times of GNSS except GPS.
+//!
+//! See [`gps2tai`](crate::gps2tai) and [`tai2gps`](crate::tai2gps) about GPS time.
+use crate::error::Error;
+use crate::DtFmts;
+use chrono::{Duration, NaiveDateTime, Timelike};
+
+const D_TAI_GST_S: i64 = 19;
+const D_TAI_BDT_S: i64 = 33;
+const D_TAI_QZSS_S: i64 = 19;
+const D_GLONASS_UTC_S: i64 = 3 * 60 * 60;
+
+/// Convert datetime
+/// from [Galileo System Time](https://en.wikipedia.org/wiki/Galileo_(satellite_navigation))
+/// to [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in GST.
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
+///
+/// # Returns
+/// Returns the datetime in TAI.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # Examples
+/// ```
+/// use convdate;
+///
+/// let tai = convdate::gst2tai(
+///     "2017-01-01T12:00:00.000",
+///     "%Y-%m-%dT%H:%M:%S%.3f");
+///
+/// assert_eq!(tai, Ok("2017-01-01T12:00:19.000".to_string()));
+/// ```
+pub fn gst2tai<'a>(datetime: &str, dt_fmt: impl Into<DtFmts<'a>>) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
+        .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
+    let tai = gst2tai_dt(&datetime);
+    Ok(tai.format(dt_fmt.format).to_string())
+}
+
+/// Convert datetime
+/// from [Galileo System Time](https://en.wikipedia.org/wiki/Galileo_(satellite_navigation))
+/// to [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in GST.
+///
+/// # Returns
+/// Returns the datetime in TAI.
+///
+/// # See also
+/// * [`gst2tai`] - It is same as `gst2tai_dt`, except that the argument and the result are [`str`] and [`String`].
+pub fn gst2tai_dt(datetime: &NaiveDateTime) -> NaiveDateTime {
+    *datetime + Duration::seconds(D_TAI_GST_S)
+}
+
+/// Convert datetime
+/// from [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time)
+/// to [Galileo System Time](https://en.wikipedia.org/wiki/Galileo_(satellite_navigation)).
+///
+/// # Arguments
+/// * `datetime` - Datetime in TAI.
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
+///
+/// # Returns
+/// Returns the datetime in GST.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # Examples
+/// ```
+/// use convdate;
+///
+/// let gst = convdate::tai2gst(
+///     "2017-01-01T12:00:19.000",
+///     "%Y-%m-%dT%H:%M:%S%.3f");
+///
+/// assert_eq!(gst, Ok("2017-01-01T12:00:00.000".to_string()));
+/// ```
+pub fn tai2gst<'a>(datetime: &str, dt_fmt: impl Into<DtFmts<'a>>) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
+        .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
+    let gst = tai2gst_dt(&datetime);
+    Ok(gst.format(dt_fmt.format).to_string())
+}
+
+/// Convert datetime
+/// from [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time)
+/// to [Galileo System Time](https://en.wikipedia.org/wiki/Galileo_(satellite_navigation)).
+///
+/// # Arguments
+/// * `datetime` - Datetime in TAI.
+///
+/// # Returns
+/// Returns the datetime in GST.
+///
+/// # See also
+/// * [`tai2gst`] - It is same as `tai2gst_dt`, except that the argument and the result are [`str`] and [`String`].
+pub fn tai2gst_dt(datetime: &NaiveDateTime) -> NaiveDateTime {
+    *datetime - Duration::seconds(D_TAI_GST_S)
+}
+
+/// Convert datetime
+/// from [BeiDou Time](https://en.wikipedia.org/wiki/BeiDou)
+/// to [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in BDT.
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
+///
+/// # Returns
+/// Returns the datetime in TAI.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # Examples
+/// ```
+/// use convdate;
+///
+/// let tai = convdate::bdt2tai(
+///     "2017-01-01T12:00:00.000",
+///     "%Y-%m-%dT%H:%M:%S%.3f");
+///
+/// assert_eq!(tai, Ok("2017-01-01T12:00:33.000".to_string()));
+/// ```
+pub fn bdt2tai<'a>(datetime: &str, dt_fmt: impl Into<DtFmts<'a>>) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
+        .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
+    let tai = bdt2tai_dt(&datetime);
+    Ok(tai.format(dt_fmt.format).to_string())
+}
+
+/// Convert datetime
+/// from [BeiDou Time](https://en.wikipedia.org/wiki/BeiDou)
+/// to [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in BDT.
+///
+/// # Returns
+/// Returns the datetime in TAI.
+///
+/// # See also
+/// * [`bdt2tai`] - It is same as `bdt2tai_dt`, except that the argument and the result are [`str`] and [`String`].
+pub fn bdt2tai_dt(datetime: &NaiveDateTime) -> NaiveDateTime {
+    *datetime + Duration::seconds(D_TAI_BDT_S)
+}
+
+/// Convert datetime
+/// from [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time)
+/// to [BeiDou Time](https://en.wikipedia.org/wiki/BeiDou).
+///
+/// # Arguments
+/// * `datetime` - Datetime in TAI.
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
+///
+/// # Returns
+/// Returns the datetime in BDT.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # Examples
+/// ```
+/// use convdate;
+///
+/// let bdt = convdate::tai2bdt(
+///     "2017-01-01T12:00:33.000",
+///     "%Y-%m-%dT%H:%M:%S%.3f");
+///
+/// assert_eq!(bdt, Ok("2017-01-01T12:00:00.000".to_string()));
+/// ```
+pub fn tai2bdt<'a>(datetime: &str, dt_fmt: impl Into<DtFmts<'a>>) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
+        .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
+    let bdt = tai2bdt_dt(&datetime);
+    Ok(bdt.format(dt_fmt.format).to_string())
+}
+
+/// Convert datetime
+/// from [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time)
+/// to [BeiDou Time](https://en.wikipedia.org/wiki/BeiDou).
+///
+/// # Arguments
+/// * `datetime` - Datetime in TAI.
+///
+/// # Returns
+/// Returns the datetime in BDT.
+///
+/// # See also
+/// * [`tai2bdt`] - It is same as `tai2bdt_dt`, except that the argument and the result are [`str`] and [`String`].
+pub fn tai2bdt_dt(datetime: &NaiveDateTime) -> NaiveDateTime {
+    *datetime - Duration::seconds(D_TAI_BDT_S)
+}
+
+/// Convert datetime
+/// from [QZSS time](https://en.wikipedia.org/wiki/Quasi-Zenith_Satellite_System)
+/// to [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in QZSST.
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
+///
+/// # Returns
+/// Returns the datetime in TAI.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # Examples
+/// ```
+/// use convdate;
+///
+/// let tai = convdate::qzss2tai(
+///     "2017-01-01T12:00:00.000",
+///     "%Y-%m-%dT%H:%M:%S%.3f");
+///
+/// assert_eq!(tai, Ok("2017-01-01T12:00:19.000".to_string()));
+/// ```
+pub fn qzss2tai<'a>(datetime: &str, dt_fmt: impl Into<DtFmts<'a>>) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
+        .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
+    let tai = qzss2tai_dt(&datetime);
+    Ok(tai.format(dt_fmt.format).to_string())
+}
+
+/// Convert datetime
+/// from [QZSS time](https://en.wikipedia.org/wiki/Quasi-Zenith_Satellite_System)
+/// to [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in QZSST.
+///
+/// # Returns
+/// Returns the datetime in TAI.
+///
+/// # See also
+/// * [`qzss2tai`] - It is same as `qzss2tai_dt`, except that the argument and the result are [`str`] and [`String`].
+pub fn qzss2tai_dt(datetime: &NaiveDateTime) -> NaiveDateTime {
+    *datetime + Duration::seconds(D_TAI_QZSS_S)
+}
+
+/// Convert datetime
+/// from [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time)
+/// to [QZSS time](https://en.wikipedia.org/wiki/Quasi-Zenith_Satellite_System).
+///
+/// # Arguments
+/// * `datetime` - Datetime in TAI.
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
+///
+/// # Returns
+/// Returns the datetime in QZSST.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # Examples
+/// ```
+/// use convdate;
+///
+/// let qzss = convdate::tai2qzss(
+///     "2017-01-01T12:00:19.000",
+///     "%Y-%m-%dT%H:%M:%S%.3f");
+///
+/// assert_eq!(qzss, Ok("2017-01-01T12:00:00.000".to_string()));
+/// ```
+pub fn tai2qzss<'a>(datetime: &str, dt_fmt: impl Into<DtFmts<'a>>) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
+        .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
+    let qzss = tai2qzss_dt(&datetime);
+    Ok(qzss.format(dt_fmt.format).to_string())
+}
+
+/// Convert datetime
+/// from [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time)
+/// to [QZSS time](https://en.wikipedia.org/wiki/Quasi-Zenith_Satellite_System).
+///
+/// # Arguments
+/// * `datetime` - Datetime in TAI.
+///
+/// # Returns
+/// Returns the datetime in QZSST.
+///
+/// # See also
+/// * [`tai2qzss`] - It is same as `tai2qzss_dt`, except that the argument and the result are [`str`] and [`String`].
+pub fn tai2qzss_dt(datetime: &NaiveDateTime) -> NaiveDateTime {
+    *datetime - Duration::seconds(D_TAI_QZSS_S)
+}
+
+/// Convert datetime
+/// from [GLONASS time](https://en.wikipedia.org/wiki/GLONASS)
+/// to [UTC](https://en.wikipedia.org/wiki/Coordinated_Universal_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in GLONASS time.
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
+///
+/// # Returns
+/// Returns the datetime in UTC.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # Examples
+/// ```
+/// use convdate;
+///
+/// let utc = convdate::glonass2utc(
+///     "2016-12-31T02:59:60.500",
+///     "%Y-%m-%dT%H:%M:%S%.3f");
+///
+/// assert_eq!(utc, Ok("2016-12-30T23:59:60.500".to_string()));
+/// ```
+pub fn glonass2utc<'a>(datetime: &str, dt_fmt: impl Into<DtFmts<'a>>) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
+        .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
+    let utc = glonass2utc_dt(&datetime);
+    Ok(utc.format(dt_fmt.format).to_string())
+}
+
+/// Convert datetime
+/// from [GLONASS time](https://en.wikipedia.org/wiki/GLONASS)
+/// to [UTC](https://en.wikipedia.org/wiki/Coordinated_Universal_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in GLONASS time.
+///
+/// # Returns
+/// Returns the datetime in UTC.
+///
+/// # See also
+/// * [`glonass2utc`] - It is same as `glonass2utc_dt`, except that the argument and the result are [`str`] and [`String`].
+pub fn glonass2utc_dt(datetime: &NaiveDateTime) -> NaiveDateTime {
+    // GLONASS time follows the leap seconds of UTC, so the leap second is kept.
+    NaiveDateTime::from_timestamp(
+        datetime.timestamp() - D_GLONASS_UTC_S,
+        datetime.nanosecond(),
+    )
+}
+
+/// Convert datetime
+/// from [UTC](https://en.wikipedia.org/wiki/Coordinated_Universal_Time)
+/// to [GLONASS time](https://en.wikipedia.org/wiki/GLONASS).
+///
+/// # Arguments
+/// * `datetime` - Datetime in UTC.
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
+///
+/// # Returns
+/// Returns the datetime in GLONASS time.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # Examples
+/// ```
+/// use convdate;
+///
+/// let glonass = convdate::utc2glonass(
+///     "2016-12-30T23:59:60.500",
+///     "%Y-%m-%dT%H:%M:%S%.3f");
+///
+/// assert_eq!(glonass, Ok("2016-12-31T02:59:60.500".to_string()));
+/// ```
+pub fn utc2glonass<'a>(datetime: &str, dt_fmt: impl Into<DtFmts<'a>>) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
+        .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
+    let glonass = utc2glonass_dt(&datetime);
+    Ok(glonass.format(dt_fmt.format).to_string())
+}
+
+/// Convert datetime
+/// from [UTC](https://en.wikipedia.org/wiki/Coordinated_Universal_Time)
+/// to [GLONASS time](https://en.wikipedia.org/wiki/GLONASS).
+///
+/// # Arguments
+/// * `datetime` - Datetime in UTC.
+///
+/// # Returns
+/// Returns the datetime in GLONASS time.
+///
+/// # See also
+/// * [`utc2glonass`] - It is same as `utc2glonass_dt`, except that the argument and the result are [`str`] and [`String`].
+pub fn utc2glonass_dt(datetime: &NaiveDateTime) -> NaiveDateTime {
+    // GLONASS time follows the leap seconds of UTC, so the leap second is kept.
+    NaiveDateTime::from_timestamp(
+        datetime.timestamp() + D_GLONASS_UTC_S,
+        datetime.nanosecond(),
+    )
+}
@@ -0,0 +1,99 @@
+use crate::error::Error;
+use crate::DtFmts;
+use chrono::{Duration, NaiveDateTime};
+
+const D_TAI_GPS_S: i64 = 19;
+
+/// Convert datetime
+/// from [GPS time](https://en.wikipedia.org/wiki/Global_Positioning_System#Timekeeping)
+/// to [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in GPS time.
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
+///
+/// # Returns
+/// Returns the datetime in TAI.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # Examples
+/// ```
+/// use convdate;
+///
+/// let tai = convdate::gps2tai(
+///     "2017-01-01T12:00:00.000",
+///     "%Y-%m-%dT%H:%M:%S%.3f");
+///
+/// assert_eq!(tai, Ok("2017-01-01T12:00:19.000".to_string()));
+/// ```
+pub fn gps2tai<'a>(datetime: &str, dt_fmt: impl Into<DtFmts<'a>>) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
+        .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
+    let tai = gps2tai_dt(&datetime);
+    Ok(tai.format(dt_fmt.format).to_string())
+}
+
+/// Convert datetime
+/// from [GPS time](https://en.wikipedia.org/wiki/Global_Positioning_System#Timekeeping)
+/// to [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in GPS time.
+///
+/// # Returns
+/// Returns the datetime in TAI.
+///
+/// # See also
+/// * [`gps2tai`] - It is same as `gps2tai_dt`, except that the argument and the result are [`str`] and [`String`].
+pub fn gps2tai_dt(datetime: &NaiveDateTime) -> NaiveDateTime {
+    *datetime + Duration::seconds(D_TAI_GPS_S)
+}
+
+/// Convert datetime
+/// from [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time)
+/// to [GPS time](https://en.wikipedia.org/wiki/Global_Positioning_System#Timekeeping).
+///
+/// # Arguments
+/// * `datetime` - Datetime in TAI.
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
+///
+/// # Returns
+/// Returns the datetime in GPS time.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # Examples
+/// ```
+/// use convdate;
+///
+/// let gps = convdate::tai2gps(
+///     "2017-01-01T12:00:19.000",
+///     "%Y-%m-%dT%H:%M:%S%.3f");
+///
+/// assert_eq!(gps, Ok("2017-01-01T12:00:00.000".to_string()));
+/// ```
+pub fn tai2gps<'a>(datetime: &str, dt_fmt: impl Into<DtFmts<'a>>) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
+        .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
+    let gps = tai2gps_dt(&datetime);
+    Ok(gps.format(dt_fmt.format).to_string())
+}
+
+/// Convert datetime
+/// from [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time)
+/// to [GPS time](https://en.wikipedia.org/wiki/Global_Positioning_System#Timekeeping).
+///
+/// # Arguments
+/// * `datetime` - Datetime in TAI.
+///
+/// # Returns
+/// Returns the datetime in GPS time.
+///
+/// # See also
+/// * [`tai2gps`] - It is same as `tai2gps_dt`, except that the argument and the result are [`str`] and [`String`].
+pub fn tai2gps_dt(datetime: &NaiveDateTime) -> NaiveDateTime {
+    *datetime - Duration::seconds(D_TAI_GPS_S)
+}
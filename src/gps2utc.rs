@@ -0,0 +1,166 @@
+use crate::convtbl::UtcTaiTable;
+use crate::error::Error;
+use crate::gps::gps2tai_dt;
+use crate::tai2utc::tai2utc_label;
+use crate::DtFmts;
+use crate::UtcLabel;
+use chrono::NaiveDateTime;
+
+/// Convert datetime
+/// from [GPS time](https://en.wikipedia.org/wiki/Global_Positioning_System#Timekeeping)
+/// to the label of [UTC](https://en.wikipedia.org/wiki/Coordinated_Universal_Time).
+///
+/// This function takes leap seconds into account along the argument `utc_tai_table`.
+///
+/// # Arguments
+/// * `datetime` - Datetime in GPS time.
+/// * `utc_tai_table` - The conversion table of UTC - TAI
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
+///
+/// # Returns
+/// Returns the label of UTC; it may have the second 61 or more if two or more leap seconds are inserted at once.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # Examples
+/// ```
+/// use convdate;
+/// use convdate::convtbl::TaiUtcTable;
+///
+/// // Usually, lines read from the file are used as the argument of `from_lines`.
+/// let tai_utc_table = TaiUtcTable::from_lines(vec!["2017-01-01T00:00:00 37"], "%Y-%m-%dT%H:%M:%S").unwrap();
+/// let utc_tai_table = From::from(&tai_utc_table);
+///
+/// let utc = convdate::gps2utc(
+///     "2017-01-01T12:00:18.000",
+///     &utc_tai_table,
+///     "%Y-%m-%dT%H:%M:%S%.3f");
+///
+/// assert_eq!(utc, Ok("2017-01-01T12:00:00.000".to_string()));
+/// ```
+///
+/// # See also
+/// * [`gps2utc`](../gps2utc/index.html) (Binary crate) - The executable program which do same conversion.
+pub fn gps2utc<'a>(
+    datetime: &str,
+    utc_tai_table: &UtcTaiTable,
+    dt_fmt: impl Into<DtFmts<'a>>,
+) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
+        .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
+    let utc = gps2utc_label(&datetime, utc_tai_table)?;
+    Ok(utc.format(dt_fmt.format))
+}
+
+/// Convert datetime
+/// from [GPS time](https://en.wikipedia.org/wiki/Global_Positioning_System#Timekeeping)
+/// to the label of [UTC](https://en.wikipedia.org/wiki/Coordinated_Universal_Time).
+///
+/// This function takes leap seconds into account along the argument `utc_tai_table`.
+///
+/// # Arguments
+/// * `datetime` - Datetime in GPS time.
+/// * `utc_tai_table` - The conversion table of UTC - TAI
+///
+/// # Returns
+/// Returns the label of UTC; it may have the second 61 or more if two or more leap seconds are inserted at once.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # See also
+/// * [`gps2utc`] - It is same as `gps2utc_label`, except that the argument and the result are [`str`] and [`String`].
+/// * [`gps2utc`](../gps2utc/index.html) (Binary crate) - The executable program which do same conversion.
+pub fn gps2utc_label(
+    datetime: &NaiveDateTime,
+    utc_tai_table: &UtcTaiTable,
+) -> Result<UtcLabel, Error> {
+    let tai = gps2tai_dt(datetime);
+
+    match tai2utc_label(&tai, utc_tai_table) {
+        Err(Error::DatetimeTooLowError(_)) => {
+            // 多段階で変換を行う場合、中間の日時文字列がエラーメッセージに使われている場合があるため、入力された日時文字列に置き換える。
+            Err(Error::DatetimeTooLowError(datetime.to_string()))
+        }
+        Err(e) => Err(e),
+        Ok(utc) => Ok(utc),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convtbl::{DiffTaiUtc, TaiUtcTable};
+    use crate::testmod;
+    use chrono::NaiveDate;
+    use rstest::*;
+
+    const DT_FMT: &str = "%Y-%m-%dT%H:%M:%S%.3f";
+
+    #[rstest]
+    // Error when the input datetime is too low.
+    #[case("2015-07-01T00:00:16.999", None, Some(Error::DatetimeTooLowError("2015-07-01 00:00:16.999".to_string())))]
+    #[case("2015-07-01T00:00:17.000", Some("2015-07-01T00:00:00.000"), None)]
+    // regular cases
+    #[case("2017-01-02T11:22:51.000", Some("2017-01-02T11:22:33.000"), None)]
+    #[case("2017-01-02T11:22:51.123", Some("2017-01-02T11:22:33.123"), None)]
+    // うるう秒が挿入される瞬間のテスト
+    #[case("2017-01-01T00:00:16.000", Some("2016-12-31T23:59:59.000"), None)]
+    #[case("2017-01-01T00:00:17.000", Some("2016-12-31T23:59:60.000"), None)]
+    #[case("2017-01-01T00:00:17.123", Some("2016-12-31T23:59:60.123"), None)]
+    #[case("2017-01-01T00:00:18.000", Some("2017-01-01T00:00:00.000"), None)]
+    // うるう秒が削除される瞬間のテスト
+    #[case("2018-01-01T00:00:16.000", Some("2017-12-31T23:59:58.000"), None)]
+    #[case("2018-01-01T00:00:16.123", Some("2017-12-31T23:59:58.123"), None)]
+    #[case("2018-01-01T00:00:17.000", Some("2018-01-01T00:00:00.000"), None)]
+    // うるう秒が2秒挿入される瞬間のテスト
+    #[case("2019-01-01T00:00:16.000", Some("2018-12-31T23:59:59.000"), None)]
+    #[case("2019-01-01T00:00:17.000", Some("2018-12-31T23:59:60.000"), None)]
+    #[case("2019-01-01T00:00:18.000", Some("2018-12-31T23:59:61.000"), None)]
+    #[case("2019-01-01T00:00:19.000", Some("2019-01-01T00:00:00.000"), None)]
+    // うるう秒が2秒削除される瞬間のテスト
+    #[case("2020-01-01T00:00:16.000", Some("2019-12-31T23:59:57.000"), None)]
+    #[case("2020-01-01T00:00:17.000", Some("2020-01-01T00:00:00.000"), None)]
+    // Error when the input datetime is illegal format.
+    #[case("2019-12-31 23:59:57.000", None, Some(Error::DatetimeParseError(gps.to_string())))]
+    fn test_gps2utc(
+        #[case] gps: &str,
+        #[case] expected_ok: Option<&str>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok.map(ToString::to_string), expected_err);
+
+        let tai_utc_table: TaiUtcTable = vec![
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 0),
+                diff_seconds: 36,
+                drift: None,
+            },
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0),
+                diff_seconds: 37,
+                drift: None,
+            },
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2018, 1, 1).and_hms(0, 0, 0),
+                diff_seconds: 36,
+                drift: None,
+            },
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2019, 1, 1).and_hms(0, 0, 0),
+                diff_seconds: 38,
+                drift: None,
+            },
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2020, 1, 1).and_hms(0, 0, 0),
+                diff_seconds: 36,
+                drift: None,
+            },
+        ]
+        .into();
+        let utc_tai_table = From::from(&tai_utc_table);
+        let utc = gps2utc(gps, &utc_tai_table, DT_FMT);
+
+        assert_eq!(utc, expected);
+    }
+}
@@ -0,0 +1,747 @@
+//! ISO 8601 representations of datetimes, which can be used instead of strftime formats.
+//!
+//! [`parse`] accepts the whole grammar of ISO 8601 for datetimes: the basic and extended formats,
+//! calendar dates (`2016-12-31`), ordinal dates (`2016-366`), week dates (`2016-W52-6`), reduced precision (`2016-12`, `2016-12-31T23:59`),
+//! the decimal fraction of the lowest order component with any number of digits, the comma as the decimal sign,
+//! the second 60 of leap seconds and UTC offsets.
+//!
+//! The result has the [`Iso8601Style`] of the input, so that the output can be written in the same style.
+use crate::error::Error;
+use crate::UtcLabel;
+use chrono::{NaiveDate, Weekday};
+
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+const NANOS_PER_MINUTE: u64 = 60 * NANOS_PER_SECOND;
+
+/// The keyword of the datetime format which selects ISO 8601 instead of strftime formats
+pub const ISO8601: &str = "iso8601";
+
+/// Forms of dates in ISO 8601
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DateForm {
+    /// The year, the month and the day; for example, `2016-12-31`.
+    Calendar,
+    /// The year and the day of the year; for example, `2016-366`.
+    Ordinal,
+    /// The week-numbering year, the week and the day of the week; for example, `2016-W52-6`.
+    Week,
+}
+
+impl DateForm {
+    /// All forms in the order of [`DateForm::NAMES`]
+    pub const ALL: [DateForm; 3] = [DateForm::Calendar, DateForm::Ordinal, DateForm::Week];
+
+    /// The names of the forms in the order of the variants
+    pub const NAMES: [&'static str; 3] = ["calendar", "ordinal", "week"];
+
+    /// The name of the form used in the datetime format; for example, `"week"` of `iso8601-week`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DateForm::Calendar => "calendar",
+            DateForm::Ordinal => "ordinal",
+            DateForm::Week => "week",
+        }
+    }
+
+    /// The strftime format of the date
+    fn strftime(&self, basic: bool) -> &'static str {
+        match (self, basic) {
+            (DateForm::Calendar, false) => "%Y-%m-%d",
+            (DateForm::Calendar, true) => "%Y%m%d",
+            (DateForm::Ordinal, false) => "%Y-%j",
+            (DateForm::Ordinal, true) => "%Y%j",
+            (DateForm::Week, false) => "%G-W%V-%u",
+            (DateForm::Week, true) => "%GW%V%u",
+        }
+    }
+}
+
+/// Forms of UTC offsets in ISO 8601
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OffsetForm {
+    /// `Z`, which is the UTC offset 0
+    Z,
+    /// The hours; for example, `+09`.
+    Hours,
+    /// The hours and the minutes; for example, `+09:00` in the extended format and `+0900` in the basic format.
+    HoursMinutes,
+}
+
+/// The style of an ISO 8601 representation
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Iso8601Style {
+    /// The form of the date
+    pub date_form: DateForm,
+    /// Whether it is the basic format (`20161231T235960`) or the extended format (`2016-12-31T23:59:60`)
+    pub basic: bool,
+    /// The number of the digits of the decimal fraction of the second
+    pub fraction_digits: usize,
+    /// Whether the decimal sign is the comma or the full stop
+    pub decimal_comma: bool,
+    /// The form of the UTC offset; `None` if the datetime has no UTC offset.
+    pub offset_form: Option<OffsetForm>,
+}
+
+impl Default for Iso8601Style {
+    /// The style of `2016-12-31T23:59:60`
+    fn default() -> Self {
+        Iso8601Style {
+            date_form: DateForm::Calendar,
+            basic: false,
+            fraction_digits: 0,
+            decimal_comma: false,
+            offset_form: None,
+        }
+    }
+}
+
+impl Iso8601Style {
+    /// Format the datetime in the style.
+    ///
+    /// The time is always written down to the second, even if the style is of an input with reduced precision;
+    /// the conversion between time scales usually moves datetimes by fractions of a minute.
+    ///
+    /// # Arguments
+    /// * `local` - The label of the datetime in the local time
+    /// * `offset` - The UTC offset of `local` in seconds; it is written only if the style has `offset_form`.
+    ///
+    /// # Examples
+    /// ```
+    /// use convdate::iso8601::{self, DateForm};
+    ///
+    /// let parsed = iso8601::parse("2017-001T08:59:60,5+09").unwrap();
+    /// assert_eq!(parsed.style.date_form, DateForm::Ordinal);
+    /// assert_eq!(parsed.style.format(&parsed.local, parsed.offset), "2017-001T08:59:60,5+09");
+    /// ```
+    pub fn format(&self, local: &UtcLabel, offset: i32) -> String {
+        let time_fmt = if self.basic { "T%H%M%S" } else { "T%H:%M:%S" };
+        let mut formatted = local.format(&format!(
+            "{}{}",
+            self.date_form.strftime(self.basic),
+            time_fmt
+        ));
+        if self.fraction_digits > 0 {
+            let nanos = format!("{:09}", local.subsec_nanos());
+            formatted.push(if self.decimal_comma { ',' } else { '.' });
+            formatted.push_str(&format!(
+                "{:0<width$}",
+                &nanos[..self.fraction_digits.min(9)],
+                width = self.fraction_digits
+            ));
+        }
+        if let Some(offset_form) = self.offset_form {
+            formatted.push_str(&format_offset(offset_form, offset, self.basic));
+        }
+        formatted
+    }
+}
+
+/// The ISO 8601 format specified as the datetime format; for example, `iso8601-week-basic`.
+///
+/// `iso8601` keeps the style of the input in the output; the suffixes override a part of it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub struct Iso8601Format {
+    /// The form of output dates; if it is `None`, the form of the input is kept.
+    pub date_form: Option<DateForm>,
+    /// Whether output datetimes are in the basic format; if it is `None`, the format of the input is kept.
+    pub basic: Option<bool>,
+}
+
+impl Iso8601Format {
+    /// Recognize the ISO 8601 format in the datetime format.
+    ///
+    /// The ISO 8601 format is `iso8601`, optionally followed by `-calendar`, `-ordinal` or `-week`
+    /// and by `-basic` or `-extended`; for example, `iso8601-ordinal-basic`. It is case-insensitive.
+    ///
+    /// # Arguments
+    /// * `dt_fmt` - The datetime format
+    ///
+    /// # Returns
+    /// Returns `None` if `dt_fmt` is not an ISO 8601 format; it is a strftime format.
+    ///
+    /// # Examples
+    /// ```
+    /// use convdate::iso8601::{DateForm, Iso8601Format};
+    ///
+    /// let format = Iso8601Format::from_dt_fmt("iso8601-week").unwrap();
+    /// assert_eq!(format.date_form, Some(DateForm::Week));
+    /// assert_eq!(format.basic, None);
+    /// assert_eq!(Iso8601Format::from_dt_fmt("%Y-%m-%dT%H:%M:%S"), None);
+    /// ```
+    pub fn from_dt_fmt(dt_fmt: &str) -> Option<Iso8601Format> {
+        let dt_fmt = dt_fmt.to_lowercase();
+        let mut words = dt_fmt.split('-');
+        if words.next() != Some(ISO8601) {
+            return None;
+        }
+        let mut format = Iso8601Format::default();
+        for word in words {
+            match word {
+                "basic" | "extended" if format.basic.is_none() => {
+                    format.basic = Some(word == "basic");
+                }
+                _ if format.date_form.is_none() && format.basic.is_none() => {
+                    let index = DateForm::NAMES.iter().position(|name| *name == word)?;
+                    format.date_form = Some(DateForm::ALL[index]);
+                }
+                _ => return None,
+            }
+        }
+        Some(format)
+    }
+
+    /// The style of output datetimes.
+    ///
+    /// # Arguments
+    /// * `input` - The style of the input datetime
+    pub fn style(&self, input: &Iso8601Style) -> Iso8601Style {
+        Iso8601Style {
+            date_form: self.date_form.unwrap_or(input.date_form),
+            basic: self.basic.unwrap_or(input.basic),
+            ..*input
+        }
+    }
+}
+
+/// A datetime parsed from an ISO 8601 representation
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Iso8601Datetime {
+    /// The label of the datetime in the local time; it may have the second 60.
+    pub local: UtcLabel,
+    /// The UTC offset in seconds; 0 if the datetime has no UTC offset.
+    pub offset: i32,
+    /// The style of the representation
+    pub style: Iso8601Style,
+}
+
+impl Iso8601Datetime {
+    /// The label of the datetime at the UTC offset 0.
+    pub fn label(&self) -> UtcLabel {
+        self.local.shifted(-i64::from(self.offset))
+    }
+}
+
+/// Parse an ISO 8601 representation of a datetime.
+///
+/// The omitted components are regarded as their beginning; for example, `2016-12` is `2016-12-01T00:00:00`.
+/// `24:00` is regarded as the beginning of the next day.
+///
+/// # Arguments
+/// * `s` - The representation of the datetime
+///
+/// # Returns
+/// Returns the datetime with its style.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to parse.
+///
+/// # Examples
+/// ```
+/// use convdate::iso8601::{self, DateForm};
+///
+/// let parsed = iso8601::parse("2016W526T235960,25").unwrap();
+/// assert_eq!(parsed.label().format("%Y-%m-%dT%H:%M:%S%.3f"), "2016-12-31T23:59:60.250");
+/// assert_eq!(parsed.style.date_form, DateForm::Week);
+/// assert!(parsed.style.basic);
+/// assert_eq!(parsed.style.fraction_digits, 2);
+/// ```
+pub fn parse(s: &str) -> Result<Iso8601Datetime, Error> {
+    parse_opt(s).ok_or_else(|| Error::DatetimeParseError(s.to_string()))
+}
+
+fn parse_opt(s: &str) -> Option<Iso8601Datetime> {
+    let (date, time) = match s.find(['T', 't']) {
+        Some(pos) => (&s[..pos], Some(&s[pos + 1..])),
+        None => (s, None),
+    };
+    let (date, date_form, date_basic) = parse_date(date)?;
+    let time = match time {
+        Some(time) => parse_time(time)?,
+        None => Time::default(),
+    };
+
+    let minute = if time.hour == 24 {
+        date.succ_opt()?.and_hms(0, 0, 0)
+    } else {
+        date.and_hms(time.hour, time.minute, 0)
+    };
+    let (offset, offset_form) = match time.offset {
+        Some((offset, form)) => (offset, Some(form)),
+        None => (0, None),
+    };
+    Some(Iso8601Datetime {
+        local: UtcLabel::from(&minute).stretched(time.nanos_of_minute),
+        offset,
+        style: Iso8601Style {
+            date_form,
+            basic: date_basic.or(time.basic).unwrap_or(false),
+            fraction_digits: time.fraction_digits,
+            decimal_comma: time.decimal_comma,
+            offset_form,
+        },
+    })
+}
+
+/// Parse the date.
+///
+/// # Returns
+/// Returns the date, its form and whether it is in the basic format; the last is `None` if only the year is specified.
+fn parse_date(s: &str) -> Option<(NaiveDate, DateForm, Option<bool>)> {
+    // 符号付きの年は4桁を超えてもよいが、区切りなしの形式では桁数が定まらないため拡張形式に限る
+    let signed = s.starts_with(['+', '-']);
+    let (year, rest) = if signed {
+        let end = s[1..]
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(s.len(), |pos| pos + 1);
+        if end < 5 {
+            return None;
+        }
+        (s[..end].parse::<i32>().ok()?, &s[end..])
+    } else {
+        (digits(s.get(..4)?, 4)? as i32, &s[4..])
+    };
+
+    let (rest, basic) = match rest.strip_prefix('-') {
+        Some(rest) => (rest, false),
+        None if rest.is_empty() => {
+            return Some((
+                NaiveDate::from_ymd_opt(year, 1, 1)?,
+                DateForm::Calendar,
+                None,
+            ));
+        }
+        None if signed => return None,
+        None => (rest, true),
+    };
+
+    if let Some(week) = rest.strip_prefix('W') {
+        let (week, day) = match (basic, week.len()) {
+            (_, 2) => (week, None),
+            (true, 3) => (week.get(..2)?, week.get(2..)),
+            (false, 4) => (week.get(..2)?, week.get(2..)?.strip_prefix('-')),
+            _ => return None,
+        };
+        let day = match day {
+            Some(day) => digits(day, 1)?,
+            None => 1,
+        };
+        let weekday = *[
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ]
+        .get((day as usize).checked_sub(1)?)?;
+        let date = NaiveDate::from_isoywd_opt(year, digits(week, 2)?, weekday)?;
+        return Some((date, DateForm::Week, Some(basic)));
+    }
+
+    let date = match (basic, rest.len()) {
+        (_, 3) => {
+            let date = NaiveDate::from_yo_opt(year, digits(rest, 3)?)?;
+            return Some((date, DateForm::Ordinal, Some(basic)));
+        }
+        // 年月のみの形式は拡張形式に限る (YYYYMM は認められない)
+        (false, 2) => NaiveDate::from_ymd_opt(year, digits(rest, 2)?, 1)?,
+        (false, 5) if rest.get(2..3) == Some("-") => {
+            NaiveDate::from_ymd_opt(year, digits(&rest[..2], 2)?, digits(&rest[3..], 2)?)?
+        }
+        (true, 4) => NaiveDate::from_ymd_opt(year, digits(&rest[..2], 2)?, digits(&rest[2..], 2)?)?,
+        _ => return None,
+    };
+    Some((date, DateForm::Calendar, Some(basic)))
+}
+
+/// The time of day parsed from ISO 8601
+#[derive(Default)]
+struct Time {
+    hour: u32,
+    minute: u32,
+    /// The elapsed time from the beginning of the minute; it may be 60 seconds or more in leap seconds.
+    nanos_of_minute: u64,
+    /// Whether it is in the basic format; `None` if only the hour is specified.
+    basic: Option<bool>,
+    fraction_digits: usize,
+    decimal_comma: bool,
+    offset: Option<(i32, OffsetForm)>,
+}
+
+/// Parse the time of day following `T`.
+fn parse_time(s: &str) -> Option<Time> {
+    let (s, offset) = split_offset(s)?;
+    let (s, fraction) = match s.find([',', '.']) {
+        Some(pos) => {
+            let fraction = &s[pos + 1..];
+            if fraction.is_empty() || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            (&s[..pos], Some((fraction, s[pos..].starts_with(','))))
+        }
+        None => (s, None),
+    };
+
+    let (components, basic) = if s.contains(':') {
+        let components = s
+            .split(':')
+            .map(|component| digits(component, 2))
+            .collect::<Option<Vec<_>>>()?;
+        (components, Some(false))
+    } else {
+        if s.len() % 2 != 0 || !s.is_ascii() {
+            return None;
+        }
+        let components = (0..s.len())
+            .step_by(2)
+            .map(|i| digits(&s[i..i + 2], 2))
+            .collect::<Option<Vec<_>>>()?;
+        (components, (s.len() > 2).then_some(true))
+    };
+
+    // 小数は最下位の要素の端数とみなす
+    let fraction_nanos = |unit: u64| {
+        fraction.map_or(0, |(fraction, _)| {
+            let fraction = &fraction[..fraction.len().min(18)];
+            let value: u128 = fraction.parse().unwrap();
+            (value * u128::from(unit) / 10u128.pow(fraction.len() as u32)) as u64
+        })
+    };
+    let (hour, minute, nanos_of_minute) = match components.as_slice() {
+        [hour] => {
+            let nanos = fraction_nanos(60 * NANOS_PER_MINUTE);
+            (
+                *hour,
+                (nanos / NANOS_PER_MINUTE) as u32,
+                nanos % NANOS_PER_MINUTE,
+            )
+        }
+        [hour, minute] => (*hour, *minute, fraction_nanos(NANOS_PER_MINUTE)),
+        [hour, minute, second] => (
+            *hour,
+            *minute,
+            u64::from(*second) * NANOS_PER_SECOND + fraction_nanos(NANOS_PER_SECOND),
+        ),
+        _ => return None,
+    };
+    if hour > 24 || minute > 59 || (hour == 24 && (minute > 0 || nanos_of_minute > 0)) {
+        return None;
+    }
+
+    Some(Time {
+        hour,
+        minute,
+        nanos_of_minute,
+        basic,
+        fraction_digits: match (components.len(), fraction) {
+            (3, Some((fraction, _))) => fraction.len(),
+            _ => 0,
+        },
+        decimal_comma: fraction.is_some_and(|(_, comma)| comma),
+        offset,
+    })
+}
+
+/// Split the time into the time of day and the UTC offset.
+fn split_offset(s: &str) -> Option<(&str, Option<(i32, OffsetForm)>)> {
+    if let Some(rest) = s.strip_suffix(['Z', 'z']) {
+        return Some((rest, Some((0, OffsetForm::Z))));
+    }
+    let pos = match s.rfind(['+', '-']) {
+        Some(pos) => pos,
+        None => return Some((s, None)),
+    };
+    let designator = &s[pos + 1..];
+    let (hours, minutes, form) = match designator.len() {
+        2 => (digits(designator, 2)?, 0, OffsetForm::Hours),
+        4 => (
+            digits(&designator[..2], 2)?,
+            digits(&designator[2..], 2)?,
+            OffsetForm::HoursMinutes,
+        ),
+        5 if designator.get(2..3) == Some(":") => (
+            digits(&designator[..2], 2)?,
+            digits(&designator[3..], 2)?,
+            OffsetForm::HoursMinutes,
+        ),
+        _ => return None,
+    };
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    let offset = (hours * 3600 + minutes * 60) as i32;
+    let offset = if s[pos..].starts_with('-') {
+        -offset
+    } else {
+        offset
+    };
+    Some((&s[..pos], Some((offset, form))))
+}
+
+/// Format the UTC offset; `Z` is used only for the offset 0, and the hours only for a whole number of hours.
+fn format_offset(form: OffsetForm, offset: i32, basic: bool) -> String {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let minutes = offset.abs() / 60;
+    match form {
+        OffsetForm::Z if offset == 0 => "Z".to_string(),
+        OffsetForm::Hours if minutes % 60 == 0 => format!("{}{:02}", sign, minutes / 60),
+        _ if basic => format!("{}{:02}{:02}", sign, minutes / 60, minutes % 60),
+        _ => format!("{}{:02}:{:02}", sign, minutes / 60, minutes % 60),
+    }
+}
+
+/// Parse the number which has exactly `n` digits.
+fn digits(s: &str, n: usize) -> Option<u32> {
+    if s.len() != n || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    const DT_FMT: &str = "%Y-%m-%dT%H:%M:%S%.9f";
+
+    #[rstest]
+    // calendar dates
+    #[case("2016-12-31T23:59:59", Some(("2016-12-31T23:59:59.000000000", 0)))]
+    #[case("20161231T235959", Some(("2016-12-31T23:59:59.000000000", 0)))]
+    #[case("2016-12-31", Some(("2016-12-31T00:00:00.000000000", 0)))]
+    #[case("20161231", Some(("2016-12-31T00:00:00.000000000", 0)))]
+    #[case("2016-12", Some(("2016-12-01T00:00:00.000000000", 0)))]
+    #[case("2016", Some(("2016-01-01T00:00:00.000000000", 0)))]
+    #[case("201612", None)]
+    #[case("2016-1231", None)]
+    #[case("2016-13-01", None)]
+    #[case("2016-02-30", None)]
+    // ordinal dates
+    #[case("2016-366T23:59:60", Some(("2016-12-31T23:59:60.000000000", 0)))]
+    #[case("2016366T235960", Some(("2016-12-31T23:59:60.000000000", 0)))]
+    #[case("2017-366", None)]
+    #[case("2016-000", None)]
+    // week dates
+    #[case("2016-W52-6T23:59:60", Some(("2016-12-31T23:59:60.000000000", 0)))]
+    #[case("2016W526T235960", Some(("2016-12-31T23:59:60.000000000", 0)))]
+    #[case("2016-W52", Some(("2016-12-26T00:00:00.000000000", 0)))]
+    #[case("2016W52", Some(("2016-12-26T00:00:00.000000000", 0)))]
+    #[case("2009-W01-1", Some(("2008-12-29T00:00:00.000000000", 0)))]
+    #[case("2015-W53-7", Some(("2016-01-03T00:00:00.000000000", 0)))]
+    #[case("2016-W53-1", None)]
+    #[case("2016-W52-8", None)]
+    #[case("2016-W52-0", None)]
+    #[case("2016-W526", None)]
+    // expanded years
+    #[case("+10000-01-01T00:00:00", Some(("+10000-01-01T00:00:00.000000000", 0)))]
+    #[case("-0001-12-31", Some(("-0001-12-31T00:00:00.000000000", 0)))]
+    #[case("+100000101", None)]
+    // reduced precision of the time and decimal fractions
+    #[case("2016-12-31T23:59", Some(("2016-12-31T23:59:00.000000000", 0)))]
+    #[case("2016-12-31T23", Some(("2016-12-31T23:00:00.000000000", 0)))]
+    #[case("20161231T2359", Some(("2016-12-31T23:59:00.000000000", 0)))]
+    #[case("2016-12-31T23:59:59.5", Some(("2016-12-31T23:59:59.500000000", 0)))]
+    #[case("2016-12-31T23:59:59,123456789012", Some(("2016-12-31T23:59:59.123456789", 0)))]
+    #[case("2016-12-31T23:59:60,999", Some(("2016-12-31T23:59:60.999000000", 0)))]
+    #[case("2016-12-31T23:59.5", Some(("2016-12-31T23:59:30.000000000", 0)))]
+    #[case("2016-12-31T23,25", Some(("2016-12-31T23:15:00.000000000", 0)))]
+    #[case("2016-12-31T12.999999999999", Some(("2016-12-31T12:59:59.999999996", 0)))]
+    #[case("2016-12-31T23:59:59.", None)]
+    #[case("2016-12-31T23:59:59.5.5", None)]
+    #[case("2016-12-31T23:5", None)]
+    #[case("2016-12-31T23:60", None)]
+    #[case("2016-12-31T23:59:59:59", None)]
+    // the end of the day
+    #[case("2016-12-31T24:00:00", Some(("2017-01-01T00:00:00.000000000", 0)))]
+    #[case("2016-12-31T24", Some(("2017-01-01T00:00:00.000000000", 0)))]
+    #[case("2016-12-31T24:00:01", None)]
+    #[case("2016-12-31T25:00:00", None)]
+    // two or more leap seconds
+    #[case("2018-12-31T23:59:61.5", Some(("2018-12-31T23:59:61.500000000", 0)))]
+    // UTC offsets
+    #[case("2016-12-31T23:59:60Z", Some(("2016-12-31T23:59:60.000000000", 0)))]
+    #[case("2017-01-01T08:59:60+09:00", Some(("2016-12-31T23:59:60.000000000", 32400)))]
+    #[case("20170101T085960+0900", Some(("2016-12-31T23:59:60.000000000", 32400)))]
+    #[case("2017-001T08:59:60,5+09", Some(("2016-12-31T23:59:60.500000000", 32400)))]
+    #[case("2016-12-31T18:59:60-05:00", Some(("2016-12-31T23:59:60.000000000", -18000)))]
+    #[case("2016-12-31T23:59:60+9", None)]
+    #[case("2016-12-31T23:59:60+24:00", None)]
+    #[case("2016-12-31Z", None)]
+    // illegal formats
+    #[case("", None)]
+    #[case("2016-12-31 23:59:59", None)]
+    #[case("16-12-31", None)]
+    #[case("2016-12-31T", None)]
+    #[case("２０１６-12-31", None)]
+    fn test_parse(#[case] s: &str, #[case] expected: Option<(&str, i32)>) {
+        let parsed = parse(s);
+
+        match expected {
+            Some((label, offset)) => {
+                let parsed = parsed.unwrap();
+                assert_eq!(parsed.label().format(DT_FMT), label);
+                assert_eq!(parsed.offset, offset);
+            }
+            None => assert_eq!(parsed, Err(Error::DatetimeParseError(s.to_string()))),
+        }
+    }
+
+    #[rstest]
+    #[case("2016-12-31T23:59:60", DateForm::Calendar, false, 0, false, None)]
+    #[case("20161231T235960.123", DateForm::Calendar, true, 3, false, None)]
+    #[case("2016-366", DateForm::Ordinal, false, 0, false, None)]
+    #[case(
+        "2016W526T235960,5Z",
+        DateForm::Week,
+        true,
+        1,
+        true,
+        Some(OffsetForm::Z)
+    )]
+    // The format of the time is used if the date has only the year.
+    #[case("2016T235960", DateForm::Calendar, true, 0, false, None)]
+    #[case("2016", DateForm::Calendar, false, 0, false, None)]
+    // The fraction of the hour or the minute is not the fraction of the second.
+    #[case("2016-12-31T23:59,5", DateForm::Calendar, false, 0, true, None)]
+    #[case(
+        "2016-12-31T23:59:60+09",
+        DateForm::Calendar,
+        false,
+        0,
+        false,
+        Some(OffsetForm::Hours)
+    )]
+    #[case(
+        "2016-12-31T23:59:60-0500",
+        DateForm::Calendar,
+        false,
+        0,
+        false,
+        Some(OffsetForm::HoursMinutes)
+    )]
+    fn test_parse_style(
+        #[case] s: &str,
+        #[case] date_form: DateForm,
+        #[case] basic: bool,
+        #[case] fraction_digits: usize,
+        #[case] decimal_comma: bool,
+        #[case] offset_form: Option<OffsetForm>,
+    ) {
+        let expected = Iso8601Style {
+            date_form,
+            basic,
+            fraction_digits,
+            decimal_comma,
+            offset_form,
+        };
+
+        assert_eq!(parse(s).unwrap().style, expected);
+    }
+
+    #[rstest]
+    // The style of the input is kept.
+    #[case("2016-12-31T23:59:60", "2016-12-31T23:59:60")]
+    #[case("20161231T235960.123", "20161231T235960.123")]
+    #[case("2016-366T23:59:60,000000000000", "2016-366T23:59:60,000000000000")]
+    #[case("2016W526T235960.25", "2016W526T235960.25")]
+    #[case("2017-01-01T08:59:60+09:00", "2017-01-01T08:59:60+09:00")]
+    #[case("20170101T085960+0900", "20170101T085960+0900")]
+    #[case("2016-12-31T23:59:60Z", "2016-12-31T23:59:60Z")]
+    #[case("2018-12-31T23:59:61.5", "2018-12-31T23:59:61.5")]
+    // The time is written down to the second.
+    #[case("2016-12-31", "2016-12-31T00:00:00")]
+    #[case("2016-W52", "2016-W52-1T00:00:00")]
+    #[case("2016-12-31T23:59,5", "2016-12-31T23:59:30")]
+    #[case("2016-12-31T24:00", "2017-01-01T00:00:00")]
+    #[case("+10000-01-01", "+10000-01-01T00:00:00")]
+    fn test_format(#[case] s: &str, #[case] expected: &str) {
+        let parsed = parse(s).unwrap();
+
+        assert_eq!(parsed.style.format(&parsed.local, parsed.offset), expected);
+    }
+
+    #[rstest]
+    // The week-numbering year differs from the calendar year.
+    #[case(DateForm::Week, false, None, 0, "2015-W53-5T00:00:00")]
+    #[case(DateForm::Ordinal, true, None, 0, "2016001T000000")]
+    #[case(
+        DateForm::Calendar,
+        false,
+        Some(OffsetForm::Z),
+        0,
+        "2016-01-01T00:00:00Z"
+    )]
+    // `Z` and the hours cannot express the offset.
+    #[case(
+        DateForm::Calendar,
+        false,
+        Some(OffsetForm::Z),
+        3600,
+        "2016-01-01T00:00:00+01:00"
+    )]
+    #[case(
+        DateForm::Calendar,
+        false,
+        Some(OffsetForm::Hours),
+        19800,
+        "2016-01-01T00:00:00+05:30"
+    )]
+    #[case(DateForm::Calendar, true, Some(OffsetForm::Hours), -3600, "20160101T000000-01")]
+    #[case(DateForm::Calendar, true, Some(OffsetForm::HoursMinutes), -3600, "20160101T000000-0100")]
+    fn test_format_style(
+        #[case] date_form: DateForm,
+        #[case] basic: bool,
+        #[case] offset_form: Option<OffsetForm>,
+        #[case] offset: i32,
+        #[case] expected: &str,
+    ) {
+        let style = Iso8601Style {
+            date_form,
+            basic,
+            offset_form,
+            ..Iso8601Style::default()
+        };
+        let local = parse("2016-01-01T00:00:00").unwrap().local;
+
+        assert_eq!(style.format(&local, offset), expected);
+    }
+
+    #[rstest]
+    #[case("iso8601", Some((None, None)))]
+    #[case("ISO8601", Some((None, None)))]
+    #[case("iso8601-week", Some((Some(DateForm::Week), None)))]
+    #[case("iso8601-basic", Some((None, Some(true))))]
+    #[case("iso8601-ordinal-extended", Some((Some(DateForm::Ordinal), Some(false))))]
+    #[case("iso8601-calendar-basic", Some((Some(DateForm::Calendar), Some(true))))]
+    #[case("iso8601-basic-week", None)]
+    #[case("iso8601-week-week", None)]
+    #[case("iso8601-basic-extended", None)]
+    #[case("iso8601-", None)]
+    #[case("iso8601week", None)]
+    #[case("%Y-%m-%dT%H:%M:%S", None)]
+    fn test_iso8601_format_from_dt_fmt(
+        #[case] dt_fmt: &str,
+        #[case] expected: Option<(Option<DateForm>, Option<bool>)>,
+    ) {
+        let expected = expected.map(|(date_form, basic)| Iso8601Format { date_form, basic });
+
+        assert_eq!(Iso8601Format::from_dt_fmt(dt_fmt), expected);
+    }
+
+    #[test]
+    fn test_iso8601_format_style() {
+        let input = parse("2016-366T23:59:60,5Z").unwrap().style;
+        let format = Iso8601Format::from_dt_fmt("iso8601-week-basic").unwrap();
+
+        assert_eq!(
+            format.style(&input),
+            Iso8601Style {
+                date_form: DateForm::Week,
+                basic: true,
+                ..input
+            }
+        );
+        assert_eq!(Iso8601Format::default().style(&input), input);
+    }
+}
@@ -0,0 +1,493 @@
+//! Julian dates of datetimes; JD, MJD and two-part JD.
+//!
+//! A [Julian date](https://en.wikipedia.org/wiki/Julian_day) (JD) is the number of days since
+//! -4712-01-01T12:00:00 (Julian calendar), and a modified Julian date (MJD) is JD - 2400000.5;
+//! for example, `2000-01-01T12:00:00` is JD `2451545` and MJD `51544.5`.
+//! A two-part JD is a pair of numbers whose sum is JD; in this module, it is written as
+//! JD at 0h of the day and the fraction of the day separated by a space, such as `2451544.5 0.5`.
+//!
+//! The numbers are read and written as decimal strings without floating point numbers,
+//! so that no nanoseconds are lost.
+//! They are written with 14 digits after the decimal point at most, which are enough to express nanoseconds.
+//!
+//! In UTC, a day containing a leap second is not 86400 seconds long.
+//! Following the convention of [SOFA](http://www.iausofa.org/), the fraction of such a day is stretched over the day;
+//! for example, `2016-12-31T23:59:60` is MJD 57753 + 86400/86401.
+//! To take it into account, the functions in this module take the TAI-UTC table if the datetimes are in UTC.
+use crate::convtbl::TaiUtcTable;
+use crate::epoch::mjd_epoch;
+use crate::error::Error;
+use crate::utc2tai::utc2tai_dt;
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use std::convert::TryFrom;
+
+/// JD - MJD
+const MJD_OFFSET_DAYS: &str = "2400000.5";
+
+/// The number of nanoseconds in a day without leap seconds
+const NANOS_PER_DAY: i128 = 86_400_000_000_000;
+
+/// The number of digits after the decimal point in the output
+const OUT_DIGITS: u32 = 14;
+
+/// The number of digits after the decimal point which are read; the following digits are ignored.
+const IN_DIGITS: u32 = 18;
+
+/// Parse a Julian date.
+///
+/// # Arguments
+/// * `datetime` - A Julian date; for example, `2451545.25`
+/// * `tai_utc_table` - The TAI-UTC table if the Julian date is in UTC; it is used to stretch days containing leap seconds.
+///
+/// # Returns
+/// Returns the datetime.
+///
+/// Returns [`Error`](crate::error::Error) if `datetime` is illegal.
+///
+/// # Examples
+/// ```
+/// use convdate::julian::parse_jd;
+/// use chrono::NaiveDate;
+///
+/// assert_eq!(
+///     parse_jd("2451545.25", None),
+///     Ok(NaiveDate::from_ymd(2000, 1, 1).and_hms(18, 0, 0)),
+/// );
+/// ```
+pub fn parse_jd(
+    datetime: &str,
+    tai_utc_table: Option<&TaiUtcTable>,
+) -> Result<NaiveDateTime, Error> {
+    let parse_err = || Error::DatetimeParseError(datetime.to_string());
+    let jd = parse_days(datetime).ok_or_else(parse_err)?;
+    from_mjd_units(jd - parse_days(MJD_OFFSET_DAYS).unwrap(), tai_utc_table).ok_or_else(parse_err)
+}
+
+/// Parse a modified Julian date.
+///
+/// # Arguments
+/// * `datetime` - A modified Julian date; for example, `57754.5`
+/// * `tai_utc_table` - The TAI-UTC table if the modified Julian date is in UTC; it is used to stretch days containing leap seconds.
+///
+/// # Returns
+/// Returns the datetime.
+///
+/// Returns [`Error`](crate::error::Error) if `datetime` is illegal.
+///
+/// # Examples
+/// ```
+/// use convdate::convtbl::TaiUtcTable;
+/// use convdate::julian::parse_mjd;
+/// use chrono::NaiveDate;
+///
+/// assert_eq!(
+///     parse_mjd("57754.5", None),
+///     Ok(NaiveDate::from_ymd(2017, 1, 1).and_hms(12, 0, 0)),
+/// );
+///
+/// // The day 2016-12-31 (UTC) is 86401 seconds long.
+/// let tai_utc_table = TaiUtcTable::from_lines(
+///     vec!["2015-07-01T00:00:00 36", "2017-01-01T00:00:00 37"],
+///     "%Y-%m-%dT%H:%M:%S",
+/// ).unwrap();
+/// assert_eq!(
+///     parse_mjd("57753.99998842605988", Some(&tai_utc_table)),
+///     Ok(NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1000)),
+/// );
+/// ```
+pub fn parse_mjd(
+    datetime: &str,
+    tai_utc_table: Option<&TaiUtcTable>,
+) -> Result<NaiveDateTime, Error> {
+    let parse_err = || Error::DatetimeParseError(datetime.to_string());
+    let mjd = parse_days(datetime).ok_or_else(parse_err)?;
+    from_mjd_units(mjd, tai_utc_table).ok_or_else(parse_err)
+}
+
+/// Parse a two-part Julian date.
+///
+/// # Arguments
+/// * `datetime` - Two numbers separated by whitespaces, whose sum is a Julian date; for example, `2451544.5 0.75`
+/// * `tai_utc_table` - The TAI-UTC table if the Julian date is in UTC; it is used to stretch days containing leap seconds.
+///
+/// # Returns
+/// Returns the datetime.
+///
+/// Returns [`Error`](crate::error::Error) if `datetime` is illegal.
+///
+/// # Examples
+/// ```
+/// use convdate::julian::parse_two_part_jd;
+/// use chrono::NaiveDate;
+///
+/// assert_eq!(
+///     parse_two_part_jd("2451544.5 0.75", None),
+///     Ok(NaiveDate::from_ymd(2000, 1, 1).and_hms(18, 0, 0)),
+/// );
+/// assert_eq!(
+///     parse_two_part_jd("2451545 0.25", None),
+///     Ok(NaiveDate::from_ymd(2000, 1, 1).and_hms(18, 0, 0)),
+/// );
+/// ```
+pub fn parse_two_part_jd(
+    datetime: &str,
+    tai_utc_table: Option<&TaiUtcTable>,
+) -> Result<NaiveDateTime, Error> {
+    let parse_err = || Error::DatetimeParseError(datetime.to_string());
+    let parts: Vec<&str> = datetime.split_whitespace().collect();
+    let (jd1, jd2) = match parts[..] {
+        [jd1, jd2] => (jd1, jd2),
+        _ => Err(parse_err())?,
+    };
+    let jd = parse_days(jd1).ok_or_else(parse_err)? + parse_days(jd2).ok_or_else(parse_err)?;
+    from_mjd_units(jd - parse_days(MJD_OFFSET_DAYS).unwrap(), tai_utc_table).ok_or_else(parse_err)
+}
+
+/// Format the datetime as a Julian date.
+///
+/// The Julian date has the fractional part only if it is needed.
+///
+/// # Arguments
+/// * `datetime` - A datetime
+/// * `tai_utc_table` - The TAI-UTC table if `datetime` is in UTC; it is used to stretch days containing leap seconds.
+///
+/// # Returns
+/// Returns the Julian date.
+///
+/// # Examples
+/// ```
+/// use convdate::julian::format_jd;
+/// use chrono::NaiveDate;
+///
+/// let datetime = NaiveDate::from_ymd(2000, 1, 1).and_hms(18, 0, 0);
+///
+/// assert_eq!(format_jd(&datetime, None), "2451545.25");
+/// ```
+pub fn format_jd(datetime: &NaiveDateTime, tai_utc_table: Option<&TaiUtcTable>) -> String {
+    let (day, fraction) = to_mjd_parts(datetime, tai_utc_table);
+    format_days(day * 10_i128.pow(OUT_DIGITS) + fraction + out_mjd_offset())
+}
+
+/// Format the datetime as a modified Julian date.
+///
+/// The modified Julian date has the fractional part only if it is needed.
+///
+/// # Arguments
+/// * `datetime` - A datetime
+/// * `tai_utc_table` - The TAI-UTC table if `datetime` is in UTC; it is used to stretch days containing leap seconds.
+///
+/// # Returns
+/// Returns the modified Julian date.
+///
+/// # Examples
+/// ```
+/// use convdate::convtbl::TaiUtcTable;
+/// use convdate::julian::format_mjd;
+/// use chrono::NaiveDate;
+///
+/// let datetime = NaiveDate::from_ymd(2017, 1, 1).and_hms(12, 0, 0);
+/// assert_eq!(format_mjd(&datetime, None), "57754.5");
+///
+/// // The day 2016-12-31 (UTC) is 86401 seconds long.
+/// let tai_utc_table = TaiUtcTable::from_lines(
+///     vec!["2015-07-01T00:00:00 36", "2017-01-01T00:00:00 37"],
+///     "%Y-%m-%dT%H:%M:%S",
+/// ).unwrap();
+/// let datetime = NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1000);
+/// assert_eq!(format_mjd(&datetime, Some(&tai_utc_table)), "57753.99998842605988");
+/// ```
+pub fn format_mjd(datetime: &NaiveDateTime, tai_utc_table: Option<&TaiUtcTable>) -> String {
+    let (day, fraction) = to_mjd_parts(datetime, tai_utc_table);
+    format_days(day * 10_i128.pow(OUT_DIGITS) + fraction)
+}
+
+/// Format the datetime as a two-part Julian date.
+///
+/// The first part is the Julian date at 0h of the day and the second part is the fraction of the day.
+///
+/// # Arguments
+/// * `datetime` - A datetime
+/// * `tai_utc_table` - The TAI-UTC table if `datetime` is in UTC; it is used to stretch days containing leap seconds.
+///
+/// # Returns
+/// Returns the two-part Julian date.
+///
+/// # Examples
+/// ```
+/// use convdate::julian::format_two_part_jd;
+/// use chrono::NaiveDate;
+///
+/// let datetime = NaiveDate::from_ymd(2000, 1, 1).and_hms(18, 0, 0);
+///
+/// assert_eq!(format_two_part_jd(&datetime, None), "2451544.5 0.75");
+/// ```
+pub fn format_two_part_jd(datetime: &NaiveDateTime, tai_utc_table: Option<&TaiUtcTable>) -> String {
+    let (day, fraction) = to_mjd_parts(datetime, tai_utc_table);
+    format!(
+        "{} {}",
+        format_days(day * 10_i128.pow(OUT_DIGITS) + out_mjd_offset()),
+        format_days(fraction)
+    )
+}
+
+/// Format the number of days since 0h of `origin`, truncated to `digits` digits after the decimal point.
+///
+/// It is used by the extended format specifiers such as `%{mjd:.6}`; see [`extfmt`](crate::extfmt).
+pub(crate) fn format_days_since(
+    datetime: &NaiveDateTime,
+    origin: &NaiveDate,
+    tai_utc_table: Option<&TaiUtcTable>,
+    digits: usize,
+) -> String {
+    let digits = digits.min(OUT_DIGITS as usize);
+    let (day, fraction) = to_mjd_parts(datetime, tai_utc_table);
+    let day = day - i128::from((*origin - mjd_epoch()).num_days());
+    let value =
+        (day * 10_i128.pow(OUT_DIGITS) + fraction) / 10_i128.pow(OUT_DIGITS - digits as u32);
+    let sign = if value < 0 { "-" } else { "" };
+    let scale = 10_i128.pow(digits as u32);
+    match digits {
+        0 => format!("{}{}", sign, value.abs()),
+        _ => format!(
+            "{}{}.{:0width$}",
+            sign,
+            value.abs() / scale,
+            value.abs() % scale,
+            width = digits
+        ),
+    }
+}
+
+/// Parse the decimal number of days since 0h of `origin`.
+///
+/// It is used by the extended format specifiers such as `%{mjd:.6}`; see [`extfmt`](crate::extfmt).
+pub(crate) fn parse_days_since(
+    value: &str,
+    origin: &NaiveDate,
+    tai_utc_table: Option<&TaiUtcTable>,
+) -> Option<NaiveDateTime> {
+    let days = parse_days(value)?;
+    let origin = i128::from((*origin - mjd_epoch()).num_days()) * 10_i128.pow(IN_DIGITS);
+    from_mjd_units(origin + days, tai_utc_table)
+}
+
+/// JD - MJD in 10<sup>-[`OUT_DIGITS`]</sup> days
+fn out_mjd_offset() -> i128 {
+    parse_days(MJD_OFFSET_DAYS).unwrap() / 10_i128.pow(IN_DIGITS - OUT_DIGITS)
+}
+
+/// The length of the day in nanoseconds.
+///
+/// If `tai_utc_table` is specified, the day is a day of UTC and it is stretched by leap seconds;
+/// the drift of TAI - UTC before 1972 is not regarded as a leap, as in SOFA.
+fn day_nanos(date: &NaiveDate, tai_utc_table: Option<&TaiUtcTable>) -> i128 {
+    let tai_utc_table = match tai_utc_table {
+        Some(tai_utc_table) => tai_utc_table,
+        None => return NANOS_PER_DAY,
+    };
+    let tai_utc = |utc: NaiveDateTime| {
+        utc2tai_dt(&utc, tai_utc_table)
+            .map(|tai| i128::from((tai - utc).num_nanoseconds().unwrap()))
+    };
+    let start = date.and_hms(0, 0, 0);
+    match (
+        tai_utc(start),
+        tai_utc(start + Duration::hours(12)),
+        tai_utc(start + Duration::days(1)),
+    ) {
+        (Ok(dat0), Ok(dat12), Ok(dat24)) => {
+            // The drift is truncated to nanoseconds, so the leap is rounded to milliseconds.
+            let leap = dat24 - (2 * dat12 - dat0);
+            NANOS_PER_DAY + (leap + 500_000).div_euclid(1_000_000) * 1_000_000
+        }
+        // Leap seconds are unknown out of the table.
+        _ => NANOS_PER_DAY,
+    }
+}
+
+/// The MJD of the datetime; the day number and the fraction of the day in 10<sup>-[`OUT_DIGITS`]</sup> days.
+fn to_mjd_parts(datetime: &NaiveDateTime, tai_utc_table: Option<&TaiUtcTable>) -> (i128, i128) {
+    let date = datetime.date();
+    let day = i128::from((date - mjd_epoch()).num_days());
+    // The nanoseconds may exceed 1_000_000_000 to represent leap seconds.
+    let nanos_of_day = i128::from(datetime.num_seconds_from_midnight()) * 1_000_000_000
+        + i128::from(datetime.nanosecond());
+    let day_nanos = day_nanos(&date, tai_utc_table);
+    let scale = 10_i128.pow(OUT_DIGITS);
+    let fraction = (nanos_of_day * scale * 2 + day_nanos).div_euclid(day_nanos * 2);
+    (day, fraction)
+}
+
+/// The datetime of the MJD in 10<sup>-[`IN_DIGITS`]</sup> days
+fn from_mjd_units(mjd: i128, tai_utc_table: Option<&TaiUtcTable>) -> Option<NaiveDateTime> {
+    let scale = 10_i128.pow(IN_DIGITS);
+    // Duration::days は範囲外の日数でパニックするため、先に範囲を確かめる
+    let max_days = Duration::max_value().num_days();
+    let day = i64::try_from(mjd.div_euclid(scale))
+        .ok()
+        .filter(|day| (-max_days..=max_days).contains(day))?;
+    let fraction = mjd.rem_euclid(scale);
+    let date = mjd_epoch().checked_add_signed(Duration::days(day))?;
+    let day_nanos = day_nanos(&date, tai_utc_table);
+    let nanos_of_day = (fraction * day_nanos * 2 + scale).div_euclid(scale * 2);
+
+    if nanos_of_day >= day_nanos {
+        // It is rounded up to the next day.
+        date.succ_opt().map(|date| date.and_hms(0, 0, 0))
+    } else if nanos_of_day >= NANOS_PER_DAY - 1_000_000_000 {
+        // The last second of the day, which may be a leap second
+        let nanos = u32::try_from(nanos_of_day - (NANOS_PER_DAY - 1_000_000_000)).ok()?;
+        NaiveTime::from_hms_nano_opt(23, 59, 59, nanos).map(|time| date.and_time(time))
+    } else {
+        let seconds = u32::try_from(nanos_of_day / 1_000_000_000).ok()?;
+        let nanos = u32::try_from(nanos_of_day % 1_000_000_000).ok()?;
+        NaiveTime::from_num_seconds_from_midnight_opt(seconds, nanos)
+            .map(|time| date.and_time(time))
+    }
+}
+
+/// Parse a decimal number of days into 10<sup>-[`IN_DIGITS`]</sup> days.
+fn parse_days(value: &str) -> Option<i128> {
+    let value = value.trim();
+    let (sign, value) = match value.strip_prefix('-') {
+        Some(value) => (-1, value),
+        None => (1, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let (int_part, frac_part) = value.split_once('.').unwrap_or((value, ""));
+    let all_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if int_part.is_empty() && frac_part.is_empty()
+        || !all_digits(int_part)
+        || !all_digits(frac_part)
+    {
+        return None;
+    }
+    let int_part: i128 = if int_part.is_empty() {
+        0
+    } else {
+        int_part.parse().ok()?
+    };
+    let frac_part = &frac_part[..frac_part.len().min(IN_DIGITS as usize)];
+    let frac_part: i128 = format!("{:0<width$}", frac_part, width = IN_DIGITS as usize)
+        .parse()
+        .ok()?;
+    Some(sign * (int_part.checked_mul(10_i128.pow(IN_DIGITS))? + frac_part))
+}
+
+/// Format 10<sup>-[`OUT_DIGITS`]</sup> days as a decimal number of days.
+fn format_days(value: i128) -> String {
+    let scale = 10_i128.pow(OUT_DIGITS);
+    let sign = if value < 0 { "-" } else { "" };
+    let int_part = value.abs() / scale;
+    let frac_part = value.abs() % scale;
+    if frac_part == 0 {
+        format!("{}{}", sign, int_part)
+    } else {
+        let fraction = format!("{:0width$}", frac_part, width = OUT_DIGITS as usize);
+        format!("{}{}.{}", sign, int_part, fraction.trim_end_matches('0'))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testmod;
+    use rstest::*;
+
+    fn tai_utc_table() -> TaiUtcTable {
+        TaiUtcTable::from_lines(
+            vec!["2015-07-01T00:00:00 36", "2017-01-01T00:00:00 37"],
+            "%Y-%m-%dT%H:%M:%S",
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    #[case(NaiveDate::from_ymd(2000, 1, 1).and_hms(12, 0, 0), false, "51544.5", "2451545", "2451544.5 0.5")]
+    #[case(NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0), false, "57754", "2457754.5", "2457754.5 0")]
+    #[case(NaiveDate::from_ymd(2017, 1, 1).and_hms_nano(0, 0, 0, 1), false, "57754.00000000000001", "2457754.50000000000001", "2457754.5 0.00000000000001")]
+    #[case(NaiveDate::from_ymd(1858, 11, 16).and_hms(6, 0, 0), false, "-0.75", "2399999.75", "2399999.5 0.25")]
+    // うるう秒が挿入される日は 86401 秒
+    #[case(NaiveDate::from_ymd(2016, 12, 31).and_hms(12, 0, 0), true, "57753.49999421302994", "2457753.99999421302994", "2457753.5 0.49999421302994")]
+    #[case(NaiveDate::from_ymd(2016, 12, 31).and_hms(23, 59, 59), true, "57753.99997685211977", "2457754.49997685211977", "2457753.5 0.99997685211977")]
+    #[case(NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1000), true, "57753.99998842605988", "2457754.49998842605988", "2457753.5 0.99998842605988")]
+    #[case(NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1500), true, "57753.99999421302994", "2457754.49999421302994", "2457753.5 0.99999421302994")]
+    // The day is 86400 seconds long in the other time scales.
+    #[case(NaiveDate::from_ymd(2016, 12, 31).and_hms(12, 0, 0), false, "57753.5", "2457754", "2457753.5 0.5")]
+    fn test_julian(
+        #[case] datetime: NaiveDateTime,
+        #[case] is_utc: bool,
+        #[case] mjd: &str,
+        #[case] jd: &str,
+        #[case] two_part_jd: &str,
+    ) {
+        let tai_utc_table = tai_utc_table();
+        let tai_utc_table = is_utc.then_some(&tai_utc_table);
+
+        assert_eq!(format_mjd(&datetime, tai_utc_table), mjd);
+        assert_eq!(format_jd(&datetime, tai_utc_table), jd);
+        assert_eq!(format_two_part_jd(&datetime, tai_utc_table), two_part_jd);
+        assert_eq!(parse_mjd(mjd, tai_utc_table), Ok(datetime));
+        assert_eq!(parse_jd(jd, tai_utc_table), Ok(datetime));
+        assert_eq!(parse_two_part_jd(two_part_jd, tai_utc_table), Ok(datetime));
+    }
+
+    #[test]
+    fn test_julian_rubber_seconds() {
+        let tai_utc_table = TaiUtcTable::from_tai_utc_dat(testmod::TAI_UTC_DAT).unwrap();
+        // TAI - UTC decreases by 0.05 seconds at 1961-08-01, so the day before is 86399.95 seconds long.
+        let datetime = NaiveDate::from_ymd(1961, 7, 31).and_hms(12, 0, 0);
+
+        assert_eq!(
+            format_mjd(&datetime, Some(&tai_utc_table)),
+            "37511.50000028935202"
+        );
+        assert_eq!(
+            parse_mjd("37511.50000028935202", Some(&tai_utc_table)),
+            Ok(datetime)
+        );
+    }
+
+    #[rstest]
+    #[case("57754.5", Some(NaiveDate::from_ymd(2017, 1, 1).and_hms(12, 0, 0)), None)]
+    #[case("+57754.5", Some(NaiveDate::from_ymd(2017, 1, 1).and_hms(12, 0, 0)), None)]
+    #[case(" 57754.", Some(NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0)), None)]
+    // The digits after the 18th are ignored.
+    #[case("57754.5000000000000000009", Some(NaiveDate::from_ymd(2017, 1, 1).and_hms(12, 0, 0)), None)]
+    #[case("57754.99999999999999", Some(NaiveDate::from_ymd(2017, 1, 1).and_hms_nano(23, 59, 59, 999_999_999)), None)]
+    #[case("57754.999999999999999", Some(NaiveDate::from_ymd(2017, 1, 2).and_hms(0, 0, 0)), None)]
+    // Illegal notations
+    #[case(".", None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("57754.5.1", None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("5.7754e4", None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("2017-01-01T00:00:00", None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("99999999999999999999", None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    // Out of the range of datetimes
+    #[case("99999999999999999", None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("-99999999999999999", None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("99999999999", None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    fn test_parse_mjd(
+        #[case] datetime: &str,
+        #[case] expected_ok: Option<NaiveDateTime>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok, expected_err);
+
+        assert_eq!(parse_mjd(datetime, None), expected);
+    }
+
+    #[rstest]
+    #[case("2457754.5 0.5", Some(NaiveDate::from_ymd(2017, 1, 1).and_hms(12, 0, 0)), None)]
+    #[case("2457754  1", Some(NaiveDate::from_ymd(2017, 1, 1).and_hms(12, 0, 0)), None)]
+    #[case("2457754.5", None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("2457754.5 0.5 0", None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("9999999999999 0", None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    fn test_parse_two_part_jd(
+        #[case] datetime: &str,
+        #[case] expected_ok: Option<NaiveDateTime>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok, expected_err);
+
+        assert_eq!(parse_two_part_jd(datetime, None), expected);
+    }
+}
@@ -1,8 +1,14 @@
-//! convdate is a set of tools for converting datetime UTC <=> TAI <=> TT.
+//! convdate is a set of tools for converting datetime UTC <=> TAI <=> TT, GPS and the other GNSS system times, TCG, TCB, TDB, UT1 and smeared UTC.
+//! UTC can also be read with UTC offsets and written in time zones; see [`zoneinfo`].
+//! Datetimes can be written in ISO 8601 as well as strftime formats; see [`iso8601`].
+//! The strftime formats can have extended specifiers such as `%{gpsweek}` and `%{mjd:.6}`; see [`extfmt`].
 //!
 //! If you want **to know about executables, look for below documents** of binary crates:
+//! - [convdate](../convdate/index.html)
+//! - [gps2utc](../gps2utc/index.html)
 //! - [tai2utc](../tai2utc/index.html)
 //! - [tt2utc](../tt2utc/index.html)
+//! - [utc2gps](../utc2gps/index.html)
 //! - [utc2tai](../utc2tai/index.html)
 //! - [utc2tt](../utc2tt/index.html)
 //!
@@ -11,21 +17,62 @@
 //! # Caution
 //! *This library crate is being adjusted. There are plans to make disruptive changes in future updates.*
 
+pub mod ccsds;
+pub mod convgraph;
 pub mod convtbl;
+pub mod deltat;
+mod dtfmt;
+pub mod epoch;
 pub mod error;
 #[doc(hidden)]
 pub mod exe;
+pub mod extfmt;
+mod gnss;
+mod gps;
+mod gps2utc;
+pub mod iso8601;
+pub mod julian;
+pub mod n4nt;
+pub mod ntp;
+pub mod ptp;
+mod scale;
+mod sidereal;
+pub mod smear;
 mod tai2utc;
+mod tcb;
+mod tcg;
+mod tdb;
 mod tt;
 mod tt2utc;
+mod ut1;
+mod utc2gps;
 mod utc2tai;
 mod utc2tt;
+mod utclabel;
+pub mod week;
+pub mod zoneinfo;
 use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Timelike};
-pub use tai2utc::tai2utc;
+pub use convgraph::{convert, convert_dt, convert_dt_marked};
+pub use dtfmt::DtFmts;
+pub use gnss::{
+    bdt2tai, bdt2tai_dt, glonass2utc, glonass2utc_dt, gst2tai, gst2tai_dt, qzss2tai, qzss2tai_dt,
+    tai2bdt, tai2bdt_dt, tai2gst, tai2gst_dt, tai2qzss, tai2qzss_dt, utc2glonass, utc2glonass_dt,
+};
+pub use gps::{gps2tai, gps2tai_dt, tai2gps, tai2gps_dt};
+pub use gps2utc::gps2utc;
+pub use scale::TimeScale;
+pub use sidereal::{equation_of_equinoxes, era, format_hms, gast, gmst, lst};
+pub use tai2utc::{tai2utc, tai2utc_label};
+pub use tcb::{tcb2tdb_dt, tcb2tt, tcb2tt_dt, tdb2tcb_dt, tt2tcb, tt2tcb_dt};
+pub use tcg::{tcg2tt, tcg2tt_dt, tt2tcg, tt2tcg_dt};
+pub use tdb::{tdb2tt, tdb2tt_dt, tt2tdb, tt2tdb_dt, TdbModel};
 pub use tt::{tai2tt, tt2tai};
 pub use tt2utc::tt2utc;
-pub use utc2tai::utc2tai;
+pub use ut1::{tai2ut1, tai2ut1_dt, ut12tai, ut12tai_dt};
+pub use utc2gps::utc2gps;
+pub use utc2tai::{check_leap_second, utc2tai, utc2tai_label};
 pub use utc2tt::utc2tt;
+pub use utclabel::{LeapPolicy, UtcLabel};
 
 #[cfg(test)]
 mod testmod;
@@ -42,9 +89,64 @@ const DT_FMT: &str = "%Y-%m-%dT%H:%M:%S%.3f";
 /// # Returns
 /// A datetime without leaps.
 fn normalize_leap(datetime: &NaiveDateTime) -> NaiveDateTime {
-    return NaiveDate::from_ymd(datetime.year(), datetime.month(), datetime.day()).and_hms(
+    NaiveDate::from_ymd(datetime.year(), datetime.month(), datetime.day()).and_hms(
         datetime.hour(),
         datetime.minute(),
         datetime.second(),
-    ) + Duration::nanoseconds(datetime.nanosecond().into());
+    ) + Duration::nanoseconds(datetime.nanosecond().into())
+}
+
+/// The length of the duration in nanoseconds, which does not overflow.
+pub(crate) fn nanos(duration: Duration) -> i128 {
+    let seconds = duration.num_seconds();
+    let subsec = (duration - Duration::seconds(seconds))
+        .num_nanoseconds()
+        .unwrap();
+    i128::from(seconds) * 1_000_000_000 + i128::from(subsec)
+}
+
+/// Parse a decimal number such as `1.4228180` or `37300.` into the number multiplied by 10<sup>9</sup>.
+///
+/// It does not use floating point numbers in order not to lose precision.
+pub(crate) fn parse_decimal_nanos(value: &str) -> Option<i64> {
+    let value = value.trim();
+    let (sign, value) = match value.strip_prefix('-') {
+        Some(value) => (-1, value),
+        None => (1, value),
+    };
+    let (int_part, frac_part) = value.split_once('.').unwrap_or((value, ""));
+    if int_part.is_empty() && frac_part.is_empty() || frac_part.len() > 9 {
+        return None;
+    }
+    let all_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if !all_digits(int_part) || !all_digits(frac_part) {
+        return None;
+    }
+    let int_part: i64 = if int_part.is_empty() {
+        0
+    } else {
+        int_part.parse().ok()?
+    };
+    let frac_part: i64 = format!("{:0<9}", frac_part).parse().ok()?;
+    Some(sign * (int_part.checked_mul(1_000_000_000)? + frac_part))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case("1.4228180", Some(1_422_818_000))]
+    #[case("37300.", Some(37_300_000_000_000))]
+    #[case(" 0.0011232", Some(1_123_200))]
+    #[case("-0.5", Some(-500_000_000))]
+    #[case(".5", Some(500_000_000))]
+    #[case("0.0000000001", None)]
+    #[case(".", None)]
+    #[case("1.2.3", None)]
+    #[case("", None)]
+    fn test_parse_decimal_nanos(#[case] value: &str, #[case] expected: Option<i64>) {
+        assert_eq!(parse_decimal_nanos(value), expected);
+    }
 }
@@ -0,0 +1,184 @@
+//! Day notation of GLONASS time; `N4:NT:seconds-of-day`.
+//!
+//! GLONASS time does not count weeks. Instead, its navigation messages carry
+//! the number of the four-year interval `N4`, which is 1 for 1996-1999,
+//! and the calendar day number `NT` within the four-year interval, which is 1 for January 1 of the leap year.
+//! In the day notation, a datetime is written as them and the number of seconds since the beginning of the day;
+//! for example, `2017-01-01T03:00:00` in GLONASS time is `6:367:10800`.
+//!
+//! As GLONASS time follows the leap seconds of UTC, its leap seconds cannot be represented in this notation
+//! as well as in the seconds of the Unix time.
+use crate::epoch::reject_leap_second;
+use crate::error::Error;
+use crate::parse_decimal_nanos;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Timelike};
+use std::convert::TryFrom;
+
+/// The first year of the four-year interval whose number is 1
+const N4_EPOCH_YEAR: i32 = 1996;
+
+const NANOS_PER_DAY: i64 = 86_400_000_000_000;
+
+/// The first day of the four-year interval
+fn interval_start(n4: i64) -> Option<NaiveDate> {
+    let year = i64::from(N4_EPOCH_YEAR) + 4 * (n4 - 1);
+    NaiveDate::from_ymd_opt(i32::try_from(year).ok()?, 1, 1)
+}
+
+/// Parse the day notation of GLONASS time.
+///
+/// # Arguments
+/// * `datetime` - A datetime in the day notation; for example, `6:367:10800.5`
+///
+/// # Returns
+/// Returns the datetime in GLONASS time.
+///
+/// Returns [`Error`](crate::error::Error) if `datetime` is illegal.
+///
+/// # Examples
+/// ```
+/// use convdate::n4nt::parse_n4nt;
+/// use chrono::NaiveDate;
+///
+/// assert_eq!(
+///     parse_n4nt("6:367:10800.5"),
+///     Ok(NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(3, 0, 0, 500)),
+/// );
+/// ```
+pub fn parse_n4nt(datetime: &str) -> Result<NaiveDateTime, Error> {
+    let parse_err = || Error::DatetimeParseError(datetime.to_string());
+    let mut fields = datetime.trim().splitn(3, ':');
+    let mut next_number = || {
+        fields
+            .next()
+            .filter(|s| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()))
+            .and_then(|s| s.parse::<i64>().ok())
+            .ok_or_else(parse_err)
+    };
+    let n4 = next_number()?;
+    let nt = next_number()?;
+    let sod = fields
+        .next()
+        .and_then(parse_decimal_nanos)
+        .filter(|sod| (0..NANOS_PER_DAY).contains(sod))
+        .ok_or_else(parse_err)?;
+
+    if n4 < 1 {
+        Err(parse_err())?;
+    }
+    let start = interval_start(n4).ok_or_else(parse_err)?;
+    let end = interval_start(n4 + 1).ok_or_else(parse_err)?;
+    if !(1..=(end - start).num_days()).contains(&nt) {
+        Err(parse_err())?;
+    }
+
+    Ok(start.and_hms(0, 0, 0) + Duration::days(nt - 1) + Duration::nanoseconds(sod))
+}
+
+/// Format the datetime in the day notation of GLONASS time.
+///
+/// The seconds of day has the fractional part only if it is needed.
+///
+/// # Arguments
+/// * `datetime` - A datetime in GLONASS time
+///
+/// # Returns
+/// Returns the datetime in the day notation.
+///
+/// Returns [`Error`](crate::error::Error) if `datetime` is before 1996-01-01T00:00:00 or in a leap second.
+///
+/// # Examples
+/// ```
+/// use convdate::n4nt::format_n4nt;
+/// use chrono::NaiveDate;
+///
+/// let datetime = NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(3, 0, 0, 500);
+///
+/// assert_eq!(format_n4nt(&datetime), Ok("6:367:10800.5".to_string()));
+/// ```
+pub fn format_n4nt(datetime: &NaiveDateTime) -> Result<String, Error> {
+    reject_leap_second(datetime)?;
+    if datetime.year() < N4_EPOCH_YEAR {
+        Err(Error::DatetimeTooLowError(datetime.to_string()))?;
+    }
+    let n4 = i64::from(datetime.year() - N4_EPOCH_YEAR) / 4 + 1;
+    let nt = (datetime.date() - interval_start(n4).unwrap()).num_days() + 1;
+    let sod_seconds = datetime.num_seconds_from_midnight();
+    let sod_nanos = datetime.nanosecond();
+
+    if sod_nanos == 0 {
+        Ok(format!("{}:{}:{}", n4, nt, sod_seconds))
+    } else {
+        let fraction = format!("{:09}", sod_nanos);
+        Ok(format!(
+            "{}:{}:{}.{}",
+            n4,
+            nt,
+            sod_seconds,
+            fraction.trim_end_matches('0')
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testmod;
+    use rstest::*;
+
+    #[rstest]
+    #[case("1:1:0", Some(NaiveDate::from_ymd(1996, 1, 1).and_hms(0, 0, 0)), None)]
+    #[case("6:367:10800", Some(NaiveDate::from_ymd(2017, 1, 1).and_hms(3, 0, 0)), None)]
+    #[case("6:366:86399.123456789", Some(NaiveDate::from_ymd(2016, 12, 31).and_hms_nano(23, 59, 59, 123_456_789)), None)]
+    #[case("6:1461:0", Some(NaiveDate::from_ymd(2019, 12, 31).and_hms(0, 0, 0)), None)]
+    #[case("7:60:0", Some(NaiveDate::from_ymd(2020, 2, 29).and_hms(0, 0, 0)), None)]
+    // 2100 is not a leap year, so the four-year interval has 1460 days.
+    #[case("27:1460:0", Some(NaiveDate::from_ymd(2103, 12, 31).and_hms(0, 0, 0)), None)]
+    #[case("27:1461:0", None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    // Illegal notations
+    #[case("0:1:0", None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("6:0:0", None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("6:1462:0", None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("6:367:86400", None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("6:367:-1", None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("6:367", None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("-6:367:0", None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("2017-01-01T00:00:00", None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    fn test_parse_n4nt(
+        #[case] datetime: &str,
+        #[case] expected_ok: Option<NaiveDateTime>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok, expected_err);
+
+        assert_eq!(parse_n4nt(datetime), expected);
+    }
+
+    #[rstest]
+    #[case(NaiveDate::from_ymd(1996, 1, 1).and_hms(0, 0, 0), Some("1:1:0"), None)]
+    #[case(NaiveDate::from_ymd(2017, 1, 1).and_hms(3, 0, 0), Some("6:367:10800"), None)]
+    #[case(NaiveDate::from_ymd(2017, 1, 1).and_hms_nano(3, 0, 0, 120_000_000), Some("6:367:10800.12"), None)]
+    #[case(NaiveDate::from_ymd(2019, 12, 31).and_hms(23, 59, 59), Some("6:1461:86399"), None)]
+    #[case(NaiveDate::from_ymd(2020, 1, 1).and_hms(0, 0, 0), Some("7:1:0"), None)]
+    #[case(NaiveDate::from_ymd(1995, 12, 31).and_hms(23, 59, 59), None, Some(Error::DatetimeTooLowError("1995-12-31 23:59:59".to_string())))]
+    // 2017-01-01T02:59:60.5 in GLONASS time, which is the leap second
+    #[case(NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(2, 59, 59, 1500), None, Some(Error::LeapSecondNotRepresentableError("2017-01-01 02:59:60.500".to_string())))]
+    fn test_format_n4nt(
+        #[case] datetime: NaiveDateTime,
+        #[case] expected_ok: Option<&str>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok.map(ToString::to_string), expected_err);
+
+        assert_eq!(format_n4nt(&datetime), expected);
+    }
+
+    #[rstest]
+    #[case("6:367:10800.5")]
+    #[case("27:1460:86399.999999999")]
+    fn test_round_trip(#[case] datetime: &str) {
+        let parsed = parse_n4nt(datetime).unwrap();
+
+        assert_eq!(format_n4nt(&parsed), Ok(datetime.to_string()));
+    }
+}
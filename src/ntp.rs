@@ -0,0 +1,359 @@
+//! NTP timestamps; 64-bit fixed-point seconds since 1900-01-01T00:00:00 ([RFC 5905](https://www.rfc-editor.org/rfc/rfc5905)).
+//!
+//! A timestamp is written as `SSSSSSSS.FFFFFFFF`, 32 bits of seconds and 32 bits of the fraction in hexadecimal as `ntpq` shows.
+//! The seconds roll over every 2<sup>32</sup> seconds (about 136 years); the first rollover is at 2036-02-07T06:28:16.
+//! Each period is called an era, and it can be written before the timestamp as `era:SSSSSSSS.FFFFFFFF`.
+//! If the era is omitted, it is resolved by a pivot datetime; see [`parse_ntp`].
+//!
+//! NTP timestamps are in UTC, but they count every day as 86400 seconds like Unix time.
+//! Therefore, an inserted leap second cannot be represented, and [`format_ntp`] returns an error for it.
+//! Instead, an NTP server advertises the coming leap second by the leap indicator; see [`leap_indicator`].
+//! During the leap second, the server repeats the timestamps of the last second before it; see [`format_ntp_repeating_leap_second`].
+use crate::convtbl::TaiUtcTable;
+use crate::epoch::reject_leap_second;
+use crate::error::Error;
+use crate::nanos;
+use chrono::{Duration, NaiveDate, NaiveDateTime, Timelike};
+use std::convert::TryFrom;
+use std::fmt;
+
+/// The length of an era in seconds
+const ERA_SECONDS: i128 = 1 << 32;
+
+/// The epoch of NTP timestamps; 1900-01-01T00:00:00
+pub fn ntp_epoch() -> NaiveDateTime {
+    NaiveDate::from_ymd(1900, 1, 1).and_hms(0, 0, 0)
+}
+
+/// The pivot used when it is not specified; 1968-01-20T03:14:08
+///
+/// As [RFC 4330](https://www.rfc-editor.org/rfc/rfc4330), the timestamps whose most significant bit is 1 are in 1968-2036,
+/// and the others are in 2036-2104.
+pub fn default_pivot() -> NaiveDateTime {
+    ntp_epoch() + Duration::seconds(1 << 31)
+}
+
+/// The leap indicator (LI) in the header of NTP packets
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LeapIndicator {
+    /// 0: no warning
+    NoWarning,
+    /// 1: the last minute of the day has 61 seconds
+    InsertSecond,
+    /// 2: the last minute of the day has 59 seconds
+    DeleteSecond,
+}
+
+impl LeapIndicator {
+    /// The 2 bits of the leap indicator
+    pub fn bits(&self) -> u8 {
+        match self {
+            LeapIndicator::NoWarning => 0,
+            LeapIndicator::InsertSecond => 1,
+            LeapIndicator::DeleteSecond => 2,
+        }
+    }
+}
+
+impl fmt::Display for LeapIndicator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+
+/// Parse the NTP timestamp.
+///
+/// # Arguments
+/// * `datetime` - The NTP timestamp `SSSSSSSS.FFFFFFFF` or `era:SSSSSSSS.FFFFFFFF`; for example, `dc12c500.80000000`
+/// * `pivot` - If the era is omitted, the timestamp is resolved to the first datetime not before it; for example, [`default_pivot`].
+///
+/// # Returns
+/// Returns the datetime.
+///
+/// Returns [`Error`](crate::error::Error) if `datetime` is illegal.
+///
+/// # Examples
+/// ```
+/// use convdate::ntp::{default_pivot, parse_ntp};
+/// use chrono::NaiveDate;
+///
+/// assert_eq!(
+///     parse_ntp("dc12c500.80000000", &default_pivot()),
+///     Ok(NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 0, 500)),
+/// );
+///
+/// // The timestamps after the rollover in 2036
+/// assert_eq!(
+///     parse_ntp("00000000.00000000", &default_pivot()),
+///     Ok(NaiveDate::from_ymd(2036, 2, 7).and_hms(6, 28, 16)),
+/// );
+/// assert_eq!(
+///     parse_ntp("0:00000000.00000000", &default_pivot()),
+///     Ok(NaiveDate::from_ymd(1900, 1, 1).and_hms(0, 0, 0)),
+/// );
+/// ```
+pub fn parse_ntp(datetime: &str, pivot: &NaiveDateTime) -> Result<NaiveDateTime, Error> {
+    let parse_err = || Error::DatetimeParseError(datetime.to_string());
+    let trimmed = datetime.trim();
+    let (era, timestamp) = match trimmed.split_once(':') {
+        Some((era, timestamp)) => (
+            Some(era.parse::<i32>().map_err(|_| parse_err())?),
+            timestamp,
+        ),
+        None => (None, trimmed),
+    };
+    let (seconds, fraction) = timestamp.split_once('.').ok_or_else(parse_err)?;
+    let seconds = parse_hex32(seconds).ok_or_else(parse_err)?;
+    let fraction = parse_hex32(fraction).ok_or_else(parse_err)?;
+
+    // 最も近いナノ秒に丸める
+    let nanos_in_era = i128::from(seconds) * 1_000_000_000
+        + ((i128::from(fraction) * 1_000_000_000 + (1 << 31)) >> 32);
+    let era = match era {
+        Some(era) => i128::from(era),
+        None => {
+            // the first era where the datetime is not before the pivot
+            let pivot_nanos = nanos(*pivot - ntp_epoch());
+            let era_nanos = ERA_SECONDS * 1_000_000_000;
+            -(nanos_in_era - pivot_nanos).div_euclid(era_nanos)
+        }
+    };
+
+    let elapsed = era * ERA_SECONDS * 1_000_000_000 + nanos_in_era;
+    let elapsed = i64::try_from(elapsed).map_err(|_| parse_err())?;
+    ntp_epoch()
+        .checked_add_signed(Duration::nanoseconds(elapsed))
+        .ok_or_else(parse_err)
+}
+
+/// Format the datetime as the NTP timestamp.
+///
+/// The era is written only if it is not 0, that is, the datetime is not in 1900-2036.
+///
+/// # Arguments
+/// * `datetime` - A datetime
+///
+/// # Returns
+/// Returns the NTP timestamp; for example, `dc12c500.80000000`.
+///
+/// Returns [`Error`](crate::error::Error) if `datetime` is in a leap second.
+///
+/// # Examples
+/// ```
+/// use convdate::ntp::format_ntp;
+/// use chrono::NaiveDate;
+///
+/// let datetime = NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 0, 500);
+/// assert_eq!(format_ntp(&datetime), Ok("dc12c500.80000000".to_string()));
+///
+/// let datetime = NaiveDate::from_ymd(2036, 2, 7).and_hms(6, 28, 17);
+/// assert_eq!(format_ntp(&datetime), Ok("1:00000001.00000000".to_string()));
+/// ```
+pub fn format_ntp(datetime: &NaiveDateTime) -> Result<String, Error> {
+    reject_leap_second(datetime)?;
+    let elapsed = nanos(*datetime - ntp_epoch());
+    let seconds = elapsed.div_euclid(1_000_000_000);
+    let era = seconds.div_euclid(ERA_SECONDS);
+    let seconds = seconds.rem_euclid(ERA_SECONDS);
+    // The fraction is truncated.
+    let fraction = (elapsed.rem_euclid(1_000_000_000) << 32) / 1_000_000_000;
+
+    if era == 0 {
+        Ok(format!("{:08x}.{:08x}", seconds, fraction))
+    } else {
+        Ok(format!("{}:{:08x}.{:08x}", era, seconds, fraction))
+    }
+}
+
+/// Format the datetime as the NTP timestamp, repeating the last second before an inserted leap second for the leap second itself.
+///
+/// It is same as [`format_ntp`] except for the leap second;
+/// an NTP server sends such timestamps during the leap second with the leap indicator 1, since the timestamps cannot represent it.
+///
+/// # Arguments
+/// * `datetime` - A datetime
+///
+/// # Returns
+/// Returns the NTP timestamp; for example, `dc12c4ff.80000000` for 2016-12-31T23:59:60.5.
+///
+/// # Examples
+/// ```
+/// use convdate::ntp::format_ntp_repeating_leap_second;
+/// use chrono::NaiveDate;
+///
+/// let datetime = NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1500);
+/// assert_eq!(format_ntp_repeating_leap_second(&datetime), Ok("dc12c4ff.80000000".to_string()));
+///
+/// let datetime = NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 500);
+/// assert_eq!(format_ntp_repeating_leap_second(&datetime), Ok("dc12c4ff.80000000".to_string()));
+/// ```
+pub fn format_ntp_repeating_leap_second(datetime: &NaiveDateTime) -> Result<String, Error> {
+    match datetime.with_nanosecond(datetime.nanosecond() % 1_000_000_000) {
+        Some(repeated) => format_ntp(&repeated),
+        None => format_ntp(datetime),
+    }
+}
+
+/// The leap indicator which an NTP server should advertise at the UTC datetime.
+///
+/// The leap indicator warns of the leap second throughout the day at the end of which it is inserted or deleted,
+/// including the inserted leap second itself.
+///
+/// # Arguments
+/// * `utc` - A datetime in UTC
+/// * `tai_utc_table` - The TAI-UTC table which has the leap seconds
+///
+/// # Examples
+/// ```
+/// use convdate::convtbl::{DiffTaiUtc, TaiUtcTable};
+/// use convdate::ntp::{leap_indicator, LeapIndicator};
+/// use chrono::NaiveDate;
+///
+/// let tai_utc_table = TaiUtcTable::from(vec![
+///     DiffTaiUtc {
+///         datetime: NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 0),
+///         diff_seconds: 36,
+///         drift: None,
+///     },
+///     DiffTaiUtc {
+///         datetime: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0),
+///         diff_seconds: 37,
+///         drift: None,
+///     },
+/// ]);
+///
+/// let utc = NaiveDate::from_ymd(2016, 12, 31).and_hms(0, 0, 0);
+/// assert_eq!(leap_indicator(&utc, &tai_utc_table), LeapIndicator::InsertSecond);
+///
+/// let utc = NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0);
+/// assert_eq!(leap_indicator(&utc, &tai_utc_table), LeapIndicator::NoWarning);
+/// ```
+pub fn leap_indicator(utc: &NaiveDateTime, tai_utc_table: &TaiUtcTable) -> LeapIndicator {
+    let next_day = match utc.date().succ_opt() {
+        Some(next_day) => next_day.and_hms(0, 0, 0),
+        None => return LeapIndicator::NoWarning,
+    };
+    let (current, next) = match (
+        tai_utc_table.pick_dominant_row(utc),
+        tai_utc_table.iter().find(|row| row.datetime == next_day),
+    ) {
+        (Ok(current), Some(next)) => (current, next),
+        _ => return LeapIndicator::NoWarning,
+    };
+    match next.diff_seconds.cmp(&current.diff_seconds) {
+        std::cmp::Ordering::Greater => LeapIndicator::InsertSecond,
+        std::cmp::Ordering::Less => LeapIndicator::DeleteSecond,
+        std::cmp::Ordering::Equal => LeapIndicator::NoWarning,
+    }
+}
+
+/// Parse 8 hexadecimal digits.
+fn parse_hex32(value: &str) -> Option<u32> {
+    if value.len() != 8 || !value.bytes().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(value, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convtbl::DiffTaiUtc;
+    use crate::testmod;
+    use rstest::*;
+
+    #[rstest]
+    #[case("dc12c500.80000000", default_pivot(), Some(NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 0, 500)), None)]
+    #[case("dc12c4ff.ffffffff", default_pivot(), Some(NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0)), None)]
+    #[case("DC12C500.00000004", default_pivot(), Some(NaiveDate::from_ymd(2017, 1, 1).and_hms_nano(0, 0, 0, 1)), None)]
+    // the rollover in 2036
+    #[case("ffffffff.00000000", default_pivot(), Some(NaiveDate::from_ymd(2036, 2, 7).and_hms(6, 28, 15)), None)]
+    #[case("00000000.00000000", default_pivot(), Some(NaiveDate::from_ymd(2036, 2, 7).and_hms(6, 28, 16)), None)]
+    #[case("80000000.00000000", default_pivot(), Some(NaiveDate::from_ymd(1968, 1, 20).and_hms(3, 14, 8)), None)]
+    #[case("7fffffff.00000000", default_pivot(), Some(NaiveDate::from_ymd(2104, 2, 26).and_hms(9, 42, 23)), None)]
+    #[case("00000000.00000000", NaiveDate::from_ymd(1900, 1, 1).and_hms(0, 0, 0), Some(NaiveDate::from_ymd(1900, 1, 1).and_hms(0, 0, 0)), None)]
+    #[case("dc12c500.00000000", NaiveDate::from_ymd(2040, 1, 1).and_hms(0, 0, 0), Some(NaiveDate::from_ymd(2153, 2, 7).and_hms(6, 28, 16)), None)]
+    // the explicit era
+    #[case("0:00000000.00000000", default_pivot(), Some(NaiveDate::from_ymd(1900, 1, 1).and_hms(0, 0, 0)), None)]
+    #[case("1:dc12c500.00000000", default_pivot(), Some(NaiveDate::from_ymd(2153, 2, 7).and_hms(6, 28, 16)), None)]
+    #[case("-1:ffffffff.00000000", default_pivot(), Some(NaiveDate::from_ymd(1899, 12, 31).and_hms(23, 59, 59)), None)]
+    // illegal notations
+    #[case("dc12c500", default_pivot(), None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("dc12c500.8", default_pivot(), None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("0xdc12c50.80000000", default_pivot(), None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("a:dc12c500.80000000", default_pivot(), None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    fn test_parse_ntp(
+        #[case] datetime: &str,
+        #[case] pivot: NaiveDateTime,
+        #[case] expected_ok: Option<NaiveDateTime>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok, expected_err);
+
+        assert_eq!(parse_ntp(datetime, &pivot), expected);
+    }
+
+    #[rstest]
+    #[case(NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 0, 500), Some("dc12c500.80000000"), None)]
+    #[case(NaiveDate::from_ymd(2017, 1, 1).and_hms_nano(0, 0, 0, 1), Some("dc12c500.00000004"), None)]
+    #[case(NaiveDate::from_ymd(1900, 1, 1).and_hms(0, 0, 0), Some("00000000.00000000"), None)]
+    #[case(NaiveDate::from_ymd(2036, 2, 7).and_hms(6, 28, 15), Some("ffffffff.00000000"), None)]
+    #[case(NaiveDate::from_ymd(2036, 2, 7).and_hms(6, 28, 16), Some("1:00000000.00000000"), None)]
+    #[case(NaiveDate::from_ymd(1899, 12, 31).and_hms_milli(23, 59, 59, 500), Some("-1:ffffffff.80000000"), None)]
+    // NTP タイムスタンプはうるう秒を表せない
+    #[case(NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1000), None, Some(Error::LeapSecondNotRepresentableError("2016-12-31 23:59:60".to_string())))]
+    fn test_format_ntp(
+        #[case] datetime: NaiveDateTime,
+        #[case] expected_ok: Option<&str>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok.map(ToString::to_string), expected_err);
+
+        assert_eq!(format_ntp(&datetime), expected);
+    }
+
+    #[rstest]
+    #[case(NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 0), "dc12c4ff.00000000")]
+    #[case(NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1000), "dc12c4ff.00000000")]
+    #[case(NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1999), "dc12c4ff.ffbe76c8")]
+    #[case(NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 0, 500), "dc12c500.80000000")]
+    fn test_format_ntp_repeating_leap_second(
+        #[case] datetime: NaiveDateTime,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(
+            format_ntp_repeating_leap_second(&datetime),
+            Ok(expected.to_string())
+        );
+    }
+
+    #[rstest]
+    #[case(NaiveDate::from_ymd(2016, 12, 30).and_hms(23, 59, 59), LeapIndicator::NoWarning)]
+    #[case(NaiveDate::from_ymd(2016, 12, 31).and_hms(0, 0, 0), LeapIndicator::InsertSecond)]
+    #[case(NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1999), LeapIndicator::InsertSecond)]
+    #[case(NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0), LeapIndicator::NoWarning)]
+    #[case(NaiveDate::from_ymd(2017, 6, 30).and_hms(12, 0, 0), LeapIndicator::DeleteSecond)]
+    #[case(NaiveDate::from_ymd(1900, 1, 1).and_hms(0, 0, 0), LeapIndicator::NoWarning)]
+    fn test_leap_indicator(#[case] utc: NaiveDateTime, #[case] expected: LeapIndicator) {
+        // 2017-07-01 の負のうるう秒は架空のもの
+        let tai_utc_table = TaiUtcTable::from(vec![
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 0),
+                diff_seconds: 36,
+                drift: None,
+            },
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0),
+                diff_seconds: 37,
+                drift: None,
+            },
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2017, 7, 1).and_hms(0, 0, 0),
+                diff_seconds: 36,
+                drift: None,
+            },
+        ]);
+
+        assert_eq!(leap_indicator(&utc, &tai_utc_table), expected);
+    }
+}
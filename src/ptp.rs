@@ -0,0 +1,370 @@
+//! PTP timestamps of IEEE 1588; seconds and nanoseconds since 1970-01-01T00:00:00 TAI.
+//!
+//! A PTP record is written as `SECONDS.NANOSECONDS [OFFSET [leap61|leap59]]`; for example, `1483228836.500000000 36 leap61`.
+//! - `SECONDS.NANOSECONDS` - The timestamp in TAI, which does not have leap seconds
+//! - `OFFSET` - `currentUtcOffset`, that is, TAI - UTC in seconds which the grandmaster announces
+//! - `leap61`, `leap59` - The flags which announce that the last minute of the current UTC day has 61 or 59 seconds
+//!
+//! The optional fields are used to audit grandmaster clocks against the TAI-UTC table; see [`PtpRecord::audit`].
+use crate::convgraph::ConvTables;
+use crate::error::Error;
+use crate::ntp::{leap_indicator, LeapIndicator};
+use crate::parse_decimal_nanos;
+use crate::tai2utc::tai2utc_dt;
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use std::fmt;
+
+/// The epoch of PTP timestamps; 1970-01-01T00:00:00 in TAI
+pub fn ptp_epoch() -> NaiveDateTime {
+    NaiveDate::from_ymd(1970, 1, 1).and_hms(0, 0, 0)
+}
+
+/// The flags of PTP which announce a leap second at the end of the current UTC day
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LeapFlag {
+    /// The last minute of the day has 61 seconds.
+    Leap61,
+    /// The last minute of the day has 59 seconds.
+    Leap59,
+}
+
+impl LeapFlag {
+    /// The name of the flag; for example, `"leap61"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            LeapFlag::Leap61 => "leap61",
+            LeapFlag::Leap59 => "leap59",
+        }
+    }
+}
+
+/// A PTP timestamp with the UTC information which the grandmaster announces
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PtpRecord {
+    /// The timestamp in TAI
+    pub tai: NaiveDateTime,
+    /// `currentUtcOffset`; TAI - UTC in seconds
+    pub current_utc_offset: Option<i64>,
+    /// `leap61` or `leap59` if it is set
+    pub leap_flag: Option<LeapFlag>,
+}
+
+impl PtpRecord {
+    /// Parse the PTP record.
+    ///
+    /// # Arguments
+    /// * `record` - The PTP record; for example, `1483228836.5 36 leap61`
+    ///
+    /// # Returns
+    /// Returns the PTP record.
+    ///
+    /// Returns [`Error`](crate::error::Error) if `record` is illegal.
+    ///
+    /// # Examples
+    /// ```
+    /// use convdate::ptp::{LeapFlag, PtpRecord};
+    /// use chrono::NaiveDate;
+    ///
+    /// assert_eq!(
+    ///     PtpRecord::parse("1483228836.500000000 36 leap61"),
+    ///     Ok(PtpRecord {
+    ///         tai: NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 36, 500),
+    ///         current_utc_offset: Some(36),
+    ///         leap_flag: Some(LeapFlag::Leap61),
+    ///     }),
+    /// );
+    /// ```
+    pub fn parse(record: &str) -> Result<PtpRecord, Error> {
+        let parse_err = || Error::DatetimeParseError(record.to_string());
+        let fields: Vec<&str> = record.split_whitespace().collect();
+        let (timestamp, offset, flag) = match fields.as_slice() {
+            [timestamp] => (timestamp, None, None),
+            [timestamp, offset] => (timestamp, Some(offset), None),
+            [timestamp, offset, flag] => (timestamp, Some(offset), Some(flag)),
+            _ => Err(parse_err())?,
+        };
+
+        // PTP タイムスタンプは符号なし
+        if timestamp.starts_with('-') {
+            Err(parse_err())?;
+        }
+        let nanos = parse_decimal_nanos(timestamp).ok_or_else(parse_err)?;
+        let tai = ptp_epoch()
+            .checked_add_signed(Duration::nanoseconds(nanos))
+            .ok_or_else(parse_err)?;
+        let current_utc_offset = offset
+            .map(|offset| offset.parse::<i64>().map_err(|_| parse_err()))
+            .transpose()?;
+        let leap_flag = match flag {
+            None => None,
+            Some(flag) if flag.eq_ignore_ascii_case("leap61") => Some(LeapFlag::Leap61),
+            Some(flag) if flag.eq_ignore_ascii_case("leap59") => Some(LeapFlag::Leap59),
+            Some(_) => Err(parse_err())?,
+        };
+
+        Ok(PtpRecord {
+            tai,
+            current_utc_offset,
+            leap_flag,
+        })
+    }
+
+    /// Construct the PTP record which a grandmaster should announce along the TAI-UTC table.
+    ///
+    /// The leap flag is set throughout the day at the end of which the leap second is inserted or deleted.
+    ///
+    /// # Arguments
+    /// * `tai` - A datetime in TAI
+    /// * `tables` - The tables which have the TAI-UTC table
+    ///
+    /// # Returns
+    /// Returns [`Error`](crate::error::Error) if `tai` is before 1970-01-01 or the TAI-UTC table does not cover it.
+    pub fn from_table(tai: &NaiveDateTime, tables: &ConvTables) -> Result<PtpRecord, Error> {
+        if *tai < ptp_epoch() {
+            Err(Error::DatetimeTooLowError(tai.to_string()))?;
+        }
+        let utc = tai2utc_dt(tai, tables.utc_tai_table())?;
+        let current_utc_offset = tables.tai_utc_table().pick_dominant_row(&utc)?.diff_seconds;
+        let leap_flag = match leap_indicator(&utc, tables.tai_utc_table()) {
+            LeapIndicator::InsertSecond => Some(LeapFlag::Leap61),
+            LeapIndicator::DeleteSecond => Some(LeapFlag::Leap59),
+            LeapIndicator::NoWarning => None,
+        };
+        Ok(PtpRecord {
+            tai: *tai,
+            current_utc_offset: Some(current_utc_offset),
+            leap_flag,
+        })
+    }
+
+    /// The UTC datetime which `currentUtcOffset` and the leap flag of the record indicate.
+    ///
+    /// It is `None` if the record has no `currentUtcOffset`.
+    ///
+    /// During the inserted leap second, `currentUtcOffset` is not updated yet and `leap61` is still set,
+    /// so TAI - `currentUtcOffset` at 00:00:00 to 00:00:01 of the next day means the leap second 23:59:60.
+    ///
+    /// # Examples
+    /// ```
+    /// use convdate::ptp::PtpRecord;
+    /// use chrono::NaiveDate;
+    ///
+    /// let record = PtpRecord::parse("1483228836.5 36 leap61").unwrap();
+    /// assert_eq!(
+    ///     record.utc(),
+    ///     Some(NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1500)),
+    /// );
+    /// ```
+    pub fn utc(&self) -> Option<NaiveDateTime> {
+        let utc = self.tai - Duration::seconds(self.current_utc_offset?);
+        match self.leap_flag {
+            Some(LeapFlag::Leap61) if utc.time() < NaiveTime::from_hms(0, 0, 1) => {
+                let last_second = utc - Duration::seconds(1);
+                last_second.with_nanosecond(last_second.nanosecond() + 1_000_000_000)
+            }
+            // 23:59:59 は存在しないため、既に翌日になっている
+            Some(LeapFlag::Leap59) if utc.time() >= NaiveTime::from_hms(23, 59, 59) => {
+                Some(utc + Duration::seconds(1))
+            }
+            _ => Some(utc),
+        }
+    }
+
+    /// Check `currentUtcOffset` and the leap flag of the record against the TAI-UTC table.
+    ///
+    /// The missing fields are not checked. Also, a leap flag which is not set is checked only in the inserted leap second,
+    /// since grandmasters may set it only in the last hours of the day.
+    ///
+    /// # Arguments
+    /// * `tables` - The tables which have the TAI-UTC table
+    ///
+    /// # Returns
+    /// Returns the mismatches as errors. It is empty if the record agrees with the table.
+    ///
+    /// # Examples
+    /// ```
+    /// use convdate::convgraph::ConvTables;
+    /// use convdate::convtbl::{DiffTaiUtc, TaiUtcTable};
+    /// use convdate::error::Error;
+    /// use convdate::ptp::PtpRecord;
+    /// use chrono::NaiveDate;
+    ///
+    /// let tables = ConvTables::new(TaiUtcTable::from(vec![
+    ///     DiffTaiUtc {
+    ///         datetime: NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 0),
+    ///         diff_seconds: 36,
+    ///         drift: None,
+    ///     },
+    ///     DiffTaiUtc {
+    ///         datetime: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0),
+    ///         diff_seconds: 37,
+    ///         drift: None,
+    ///     },
+    /// ]));
+    ///
+    /// let record = PtpRecord::parse("1483228837.000000000 37").unwrap();
+    /// assert_eq!(record.audit(&tables), vec![]);
+    ///
+    /// let record = PtpRecord::parse("1483228837.000000000 36").unwrap();
+    /// assert_eq!(
+    ///     record.audit(&tables),
+    ///     vec![Error::PtpUtcOffsetMismatchError(
+    ///         "1483228837.000000000 36".to_string(),
+    ///         37,
+    ///     )],
+    /// );
+    /// ```
+    pub fn audit(&self, tables: &ConvTables) -> Vec<Error> {
+        let expected = match PtpRecord::from_table(&self.tai, tables) {
+            Ok(expected) => expected,
+            // The table cannot tell the answer.
+            Err(_) => return vec![],
+        };
+
+        let expected_offset = expected.current_utc_offset.unwrap();
+
+        let mut mismatches = vec![];
+        if let Some(offset) = self.current_utc_offset {
+            if offset != expected_offset {
+                mismatches.push(Error::PtpUtcOffsetMismatchError(
+                    self.to_string(),
+                    expected_offset,
+                ));
+            } else if self.leap_flag.is_none() && self.utc() != expected.utc() {
+                // 挿入されたうるう秒の間に leap61 が立っていない
+                mismatches.push(Error::PtpLeapFlagMismatchError(
+                    self.to_string(),
+                    expected.leap_flag.unwrap().name().to_string(),
+                ));
+            }
+        }
+        if let Some(leap_flag) = self.leap_flag {
+            if expected.leap_flag != Some(leap_flag) {
+                mismatches.push(Error::PtpLeapFlagMismatchError(
+                    self.to_string(),
+                    leap_flag.name().to_string(),
+                ));
+            }
+        }
+        mismatches
+    }
+}
+
+impl fmt::Display for PtpRecord {
+    /// Write the record as `SECONDS.NANOSECONDS [OFFSET [leap61|leap59]]`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let elapsed = self.tai - ptp_epoch();
+        let seconds = elapsed.num_seconds();
+        let nanos = (elapsed - Duration::seconds(seconds))
+            .num_nanoseconds()
+            .unwrap();
+        write!(f, "{}.{:09}", seconds, nanos)?;
+        if let Some(offset) = self.current_utc_offset {
+            write!(f, " {}", offset)?;
+            if let Some(leap_flag) = self.leap_flag {
+                write!(f, " {}", leap_flag.name())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convtbl::{DiffTaiUtc, TaiUtcTable};
+    use crate::testmod;
+    use rstest::*;
+
+    fn tables() -> ConvTables {
+        ConvTables::new(TaiUtcTable::from(vec![
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 0),
+                diff_seconds: 36,
+                drift: None,
+            },
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0),
+                diff_seconds: 37,
+                drift: None,
+            },
+            // 架空の負のうるう秒
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2017, 7, 1).and_hms(0, 0, 0),
+                diff_seconds: 36,
+                drift: None,
+            },
+        ]))
+    }
+
+    #[rstest]
+    #[case("1483228837", Some(PtpRecord { tai: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 37), current_utc_offset: None, leap_flag: None }), None)]
+    #[case("1483228837.5 37", Some(PtpRecord { tai: NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 37, 500), current_utc_offset: Some(37), leap_flag: None }), None)]
+    #[case(" 1483228836.000000001 36 LEAP61 ", Some(PtpRecord { tai: NaiveDate::from_ymd(2017, 1, 1).and_hms_nano(0, 0, 36, 1), current_utc_offset: Some(36), leap_flag: Some(LeapFlag::Leap61) }), None)]
+    #[case("1483228836 36 leap59", Some(PtpRecord { tai: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 36), current_utc_offset: Some(36), leap_flag: Some(LeapFlag::Leap59) }), None)]
+    // illegal records
+    #[case("-1.5", None, Some(Error::DatetimeParseError(record.to_string())))]
+    #[case("1483228837 3.5", None, Some(Error::DatetimeParseError(record.to_string())))]
+    #[case("1483228837 37 leap60", None, Some(Error::DatetimeParseError(record.to_string())))]
+    #[case("1483228837 37 leap61 1", None, Some(Error::DatetimeParseError(record.to_string())))]
+    #[case("", None, Some(Error::DatetimeParseError(record.to_string())))]
+    fn test_parse(
+        #[case] record: &str,
+        #[case] expected_ok: Option<PtpRecord>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok, expected_err);
+
+        assert_eq!(PtpRecord::parse(record), expected);
+    }
+
+    #[rstest]
+    #[case(NaiveDate::from_ymd(2016, 12, 31).and_hms(0, 0, 36), Some("1483142436.000000000 36 leap61"), None)]
+    #[case(NaiveDate::from_ymd(2017, 1, 1).and_hms_nano(0, 0, 36, 1), Some("1483228836.000000001 36 leap61"), None)]
+    #[case(NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 37), Some("1483228837.000000000 37"), None)]
+    #[case(NaiveDate::from_ymd(2017, 6, 30).and_hms(12, 0, 0), Some("1498824000.000000000 37 leap59"), None)]
+    #[case(NaiveDate::from_ymd(1969, 12, 31).and_hms(0, 0, 0), None, Some(Error::DatetimeTooLowError("1969-12-31 00:00:00".to_string())))]
+    #[case(NaiveDate::from_ymd(2015, 6, 30).and_hms(0, 0, 0), None, Some(Error::DatetimeTooLowError("2015-06-30 00:00:00".to_string())))]
+    fn test_from_table(
+        #[case] tai: NaiveDateTime,
+        #[case] expected_ok: Option<&str>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok.map(ToString::to_string), expected_err);
+
+        let result = PtpRecord::from_table(&tai, &tables());
+
+        assert_eq!(result.map(|record| record.to_string()), expected);
+    }
+
+    #[rstest]
+    #[case("1483228837", None)]
+    #[case("1483228837 37", Some(NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0)))]
+    #[case("1483228835.5 36 leap61", Some(NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 500)))]
+    #[case("1483228836.5 36 leap61", Some(NaiveDate::from_ymd(2016, 12, 31).and_hms_milli(23, 59, 59, 1500)))]
+    #[case("1483228836.5 36", Some(NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 0, 500)))]
+    #[case("1498867235.5 37 leap59", Some(NaiveDate::from_ymd(2017, 6, 30).and_hms_milli(23, 59, 58, 500)))]
+    #[case("1498867236.5 37 leap59", Some(NaiveDate::from_ymd(2017, 7, 1).and_hms_milli(0, 0, 0, 500)))]
+    fn test_utc(#[case] record: &str, #[case] expected: Option<NaiveDateTime>) {
+        assert_eq!(PtpRecord::parse(record).unwrap().utc(), expected);
+    }
+
+    #[rstest]
+    #[case("1483228837", vec![])]
+    #[case("1483228837 37", vec![])]
+    #[case("1483228836.5 36 leap61", vec![])]
+    #[case("1483228837.5 37", vec![])]
+    #[case("1483228837 36", vec![Error::PtpUtcOffsetMismatchError("1483228837.000000000 36".to_string(), 37)])]
+    // うるう秒の間に leap61 を立てていない
+    #[case("1483228836.5 36", vec![Error::PtpLeapFlagMismatchError("1483228836.500000000 36".to_string(), "leap61".to_string())])]
+    #[case("1483228837 37 leap61", vec![Error::PtpLeapFlagMismatchError("1483228837.000000000 37 leap61".to_string(), "leap61".to_string())])]
+    #[case("1483142436 35 leap59", vec![
+        Error::PtpUtcOffsetMismatchError("1483142436.000000000 35 leap59".to_string(), 36),
+        Error::PtpLeapFlagMismatchError("1483142436.000000000 35 leap59".to_string(), "leap59".to_string()),
+    ])]
+    // The table does not cover it.
+    #[case("1000000000 36", vec![])]
+    fn test_audit(#[case] record: &str, #[case] expected: Vec<Error>) {
+        assert_eq!(PtpRecord::parse(record).unwrap().audit(&tables()), expected);
+    }
+}
@@ -0,0 +1,187 @@
+use crate::error::Error;
+use crate::week::{BDT_WEEK_ROLLOVER, GPS_WEEK_ROLLOVER, GST_WEEK_ROLLOVER};
+use chrono::{NaiveDate, NaiveDateTime};
+use std::fmt;
+use std::str::FromStr;
+
+/// Time scales which convdate can convert between
+///
+/// # Examples
+/// ```
+/// use convdate::TimeScale;
+///
+/// let scale: TimeScale = "utc".parse().unwrap();
+/// assert_eq!(scale, TimeScale::Utc);
+/// assert_eq!(scale.to_string(), "UTC");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TimeScale {
+    /// [UTC](https://en.wikipedia.org/wiki/Coordinated_Universal_Time)
+    Utc,
+    /// [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time)
+    Tai,
+    /// [TT](https://en.wikipedia.org/wiki/Terrestrial_Time)
+    Tt,
+    /// [GPS time](https://en.wikipedia.org/wiki/Global_Positioning_System#Timekeeping)
+    Gps,
+    /// [Galileo System Time](https://en.wikipedia.org/wiki/Galileo_(satellite_navigation)); TAI - 19 s, same as GPS time
+    Gst,
+    /// [BeiDou Time](https://en.wikipedia.org/wiki/BeiDou); TAI - 33 s, which equals to UTC at 2006-01-01
+    Bdt,
+    /// [GLONASS time](https://en.wikipedia.org/wiki/GLONASS); UTC(SU) + 3 h, which follows leap seconds
+    Glonass,
+    /// [QZSS time](https://en.wikipedia.org/wiki/Quasi-Zenith_Satellite_System); TAI - 19 s, same as GPS time
+    Qzss,
+    /// [TCG](https://en.wikipedia.org/wiki/Geocentric_Coordinate_Time); it runs faster than TT at the rate L<sub>G</sub>
+    Tcg,
+    /// [TCB](https://en.wikipedia.org/wiki/Barycentric_Coordinate_Time); it runs faster than TDB at the rate L<sub>B</sub>
+    Tcb,
+    /// [TDB](https://en.wikipedia.org/wiki/Barycentric_Dynamical_Time); it differs from TT periodically, see [`TdbModel`](crate::TdbModel)
+    Tdb,
+    /// [UT1](https://en.wikipedia.org/wiki/Universal_Time); it follows the rotation of the Earth, see [`Ut1UtcTable`](crate::convtbl::Ut1UtcTable)
+    Ut1,
+    /// Smeared UTC, which spreads leap seconds over a window; see [`Smear`](crate::smear::Smear)
+    SmearedUtc,
+}
+
+impl TimeScale {
+    /// All time scales which convdate supports
+    pub const ALL: [TimeScale; 13] = [
+        TimeScale::Utc,
+        TimeScale::Tai,
+        TimeScale::Tt,
+        TimeScale::Gps,
+        TimeScale::Gst,
+        TimeScale::Bdt,
+        TimeScale::Glonass,
+        TimeScale::Qzss,
+        TimeScale::Tcg,
+        TimeScale::Tcb,
+        TimeScale::Tdb,
+        TimeScale::Ut1,
+        TimeScale::SmearedUtc,
+    ];
+
+    /// The names of [`TimeScale::ALL`] in the same order
+    pub const NAMES: [&'static str; 13] = [
+        "utc",
+        "tai",
+        "tt",
+        "gps",
+        "gst",
+        "bdt",
+        "glonass",
+        "qzss",
+        "tcg",
+        "tcb",
+        "tdb",
+        "ut1",
+        "smeared-utc",
+    ];
+
+    /// The name of the time scale used in command arguments; for example, `"utc"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TimeScale::Utc => "utc",
+            TimeScale::Tai => "tai",
+            TimeScale::Tt => "tt",
+            TimeScale::Gps => "gps",
+            TimeScale::Gst => "gst",
+            TimeScale::Bdt => "bdt",
+            TimeScale::Glonass => "glonass",
+            TimeScale::Qzss => "qzss",
+            TimeScale::Tcg => "tcg",
+            TimeScale::Tcb => "tcb",
+            TimeScale::Tdb => "tdb",
+            TimeScale::Ut1 => "ut1",
+            TimeScale::SmearedUtc => "smeared-utc",
+        }
+    }
+
+    /// The epoch of the week number of the time scale, in the time scale itself
+    ///
+    /// It returns `None` if the time scale does not count weeks; GLONASS time counts days in four-year intervals instead,
+    /// which are written in the notation of [`n4nt`](crate::n4nt).
+    ///
+    /// # See also
+    /// * [`week`](crate::week) - The week notation `week:seconds-of-week`
+    pub fn week_epoch(&self) -> Option<NaiveDateTime> {
+        match self {
+            TimeScale::Gps | TimeScale::Qzss => {
+                Some(NaiveDate::from_ymd(1980, 1, 6).and_hms(0, 0, 0))
+            }
+            TimeScale::Gst => Some(NaiveDate::from_ymd(1999, 8, 22).and_hms(0, 0, 0)),
+            TimeScale::Bdt => Some(NaiveDate::from_ymd(2006, 1, 1).and_hms(0, 0, 0)),
+            _ => None,
+        }
+    }
+
+    /// The number of weeks after which the week number broadcast by the system rolls over to 0
+    ///
+    /// It returns `None` if the time scale does not count weeks.
+    ///
+    /// # See also
+    /// * [`parse_week`](crate::week::parse_week) - Resolution of the truncated week numbers
+    pub fn week_rollover(&self) -> Option<i64> {
+        match self {
+            TimeScale::Gps | TimeScale::Qzss => Some(GPS_WEEK_ROLLOVER),
+            TimeScale::Gst => Some(GST_WEEK_ROLLOVER),
+            TimeScale::Bdt => Some(BDT_WEEK_ROLLOVER),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for TimeScale {
+    type Err = Error;
+
+    /// Parse the name of a time scale. The case of letters is ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TimeScale::ALL
+            .iter()
+            .find(|scale| scale.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| Error::UnknownTimeScaleError(s.to_string()))
+    }
+}
+
+impl fmt::Display for TimeScale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name().to_uppercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testmod;
+    use rstest::*;
+
+    #[rstest]
+    #[case("utc", Some(TimeScale::Utc), None)]
+    #[case("TAI", Some(TimeScale::Tai), None)]
+    #[case("Tt", Some(TimeScale::Tt), None)]
+    #[case("GPS", Some(TimeScale::Gps), None)]
+    #[case("Glonass", Some(TimeScale::Glonass), None)]
+    #[case("TDB", Some(TimeScale::Tdb), None)]
+    #[case("Smeared-UTC", Some(TimeScale::SmearedUtc), None)]
+    #[case("gal", None, Some(Error::UnknownTimeScaleError("gal".to_string())))]
+    #[case("", None, Some(Error::UnknownTimeScaleError("".to_string())))]
+    fn test_from_str(
+        #[case] name: &str,
+        #[case] expected_ok: Option<TimeScale>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok, expected_err);
+
+        assert_eq!(name.parse::<TimeScale>(), expected);
+    }
+
+    #[test]
+    fn test_name_round_trip() {
+        for (scale, name) in TimeScale::ALL.iter().zip(TimeScale::NAMES) {
+            assert_eq!(scale.name(), name);
+            assert_eq!(name.parse::<TimeScale>(), Ok(*scale));
+        }
+    }
+}
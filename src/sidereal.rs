@@ -0,0 +1,256 @@
+//! Earth Rotation Angle and sidereal times.
+//!
+//! All angles are in radians and normalized into [0, 2π).
+//! They depend on UT1 and, except ERA, on TT; both of them can be obtained by [`convert_dt`](crate::convert_dt).
+use crate::epoch::j2000_epoch;
+use chrono::{Duration, NaiveDateTime};
+use std::f64::consts::TAU;
+
+/// Arcseconds to radians
+const ARCSEC_TO_RAD: f64 = TAU / 1_296_000.0;
+
+/// The number of days in a Julian century
+const DAYS_PER_JULIAN_CENTURY: f64 = 36_525.0;
+
+/// The leading terms of the lunisolar nutation in longitude of IAU 2000B.
+///
+/// Each term is (multipliers of *l*, *l'*, *F*, *D*, Ω, the coefficient of sin, the rate of it per Julian century, the coefficient of cos);
+/// the coefficients are in 0.1 microarcseconds as `iauNut00b` of [SOFA](http://www.iausofa.org/).
+const NUTATION_TERMS: [([f64; 5], f64, f64, f64); 20] = [
+    ([0.0, 0.0, 0.0, 0.0, 1.0], -172064161.0, -174666.0, 33386.0),
+    ([0.0, 0.0, 2.0, -2.0, 2.0], -13170906.0, -1675.0, -13696.0),
+    ([0.0, 0.0, 2.0, 0.0, 2.0], -2276413.0, -234.0, 2796.0),
+    ([0.0, 0.0, 0.0, 0.0, 2.0], 2074554.0, 207.0, -698.0),
+    ([0.0, 1.0, 0.0, 0.0, 0.0], 1475877.0, -3633.0, 11817.0),
+    ([0.0, 1.0, 2.0, -2.0, 2.0], -516821.0, 1226.0, -524.0),
+    ([1.0, 0.0, 0.0, 0.0, 0.0], 711159.0, 73.0, -872.0),
+    ([0.0, 0.0, 2.0, 0.0, 1.0], -387298.0, -367.0, 380.0),
+    ([1.0, 0.0, 2.0, 0.0, 2.0], -301461.0, -36.0, 816.0),
+    ([0.0, -1.0, 2.0, -2.0, 2.0], 215829.0, -494.0, 111.0),
+    ([0.0, 0.0, 2.0, -2.0, 1.0], 128227.0, 137.0, 181.0),
+    ([-1.0, 0.0, 2.0, 0.0, 2.0], 123457.0, 11.0, 19.0),
+    ([-1.0, 0.0, 0.0, 2.0, 0.0], 156994.0, 10.0, -168.0),
+    ([1.0, 0.0, 0.0, 0.0, 1.0], 63110.0, 63.0, 27.0),
+    ([-1.0, 0.0, 0.0, 0.0, 1.0], -57976.0, -63.0, -189.0),
+    ([-1.0, 0.0, 2.0, 2.0, 2.0], -59641.0, -11.0, 149.0),
+    ([1.0, 0.0, 2.0, 0.0, 1.0], -51613.0, -42.0, 129.0),
+    ([-2.0, 0.0, 2.0, 0.0, 1.0], 45893.0, 50.0, 31.0),
+    ([0.0, 0.0, 0.0, 2.0, 0.0], 63384.0, 11.0, -150.0),
+    ([0.0, 0.0, 2.0, 2.0, 2.0], -38571.0, -1.0, 158.0),
+];
+
+/// The fixed offset of IAU 2000B in place of the planetary nutation, in arcseconds
+const NUTATION_PLANETARY_OFFSET: f64 = -0.135e-3;
+
+/// Split the time since J2000.0 into the whole days and the fraction of the day.
+fn days_since_j2000(datetime: &NaiveDateTime) -> (f64, f64) {
+    let elapsed = *datetime - j2000_epoch();
+    let days = elapsed.num_days();
+    let rest = elapsed - Duration::days(days);
+    let fraction = rest.num_nanoseconds().unwrap() as f64 / 86_400e9;
+    (days as f64, fraction)
+}
+
+/// Julian centuries since J2000.0
+fn centuries_since_j2000(datetime: &NaiveDateTime) -> f64 {
+    let (days, fraction) = days_since_j2000(datetime);
+    (days + fraction) / DAYS_PER_JULIAN_CENTURY
+}
+
+/// Normalize the angle into [0, 2π).
+fn normalize(angle: f64) -> f64 {
+    let angle = angle.rem_euclid(TAU);
+    // rem_euclid may return TAU itself because of rounding.
+    if angle >= TAU {
+        0.0
+    } else {
+        angle
+    }
+}
+
+/// Calculate the Earth Rotation Angle (IAU 2000).
+///
+/// # Arguments
+/// * `ut1` - Datetime in UT1
+///
+/// # Returns
+/// Returns ERA in radians.
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+///
+/// let era = convdate::era(&NaiveDate::from_ymd(2000, 1, 1).and_hms(12, 0, 0));
+/// assert!((era - 4.894961212823756).abs() < 1e-12);
+/// ```
+pub fn era(ut1: &NaiveDateTime) -> f64 {
+    let (days, fraction) = days_since_j2000(ut1);
+    // The whole days are separated to keep the precision of the fraction.
+    normalize(TAU * (fraction + 0.7790572732640 + 0.00273781191135448 * (days + fraction)))
+}
+
+/// Calculate the Greenwich mean sidereal time (IAU 2006).
+///
+/// # Arguments
+/// * `ut1` - Datetime in UT1
+/// * `tt` - The same instant in TT
+///
+/// # Returns
+/// Returns GMST in radians.
+pub fn gmst(ut1: &NaiveDateTime, tt: &NaiveDateTime) -> f64 {
+    let t = centuries_since_j2000(tt);
+    let polynomial = 0.014506
+        + (4612.156534
+            + (1.3915817 + (-0.00000044 + (-0.000029956 + -0.0000000368 * t) * t) * t) * t)
+            * t;
+    normalize(era(ut1) + polynomial * ARCSEC_TO_RAD)
+}
+
+/// Calculate the equation of the equinoxes, that is, GAST - GMST.
+///
+/// The nutation in longitude is calculated with the leading 20 terms of IAU 2000B,
+/// and the complementary terms with the leading ones of IAU 2000;
+/// the error is within about 10 milliarcseconds (less than a millisecond of time) around the present.
+///
+/// # Arguments
+/// * `tt` - Datetime in TT
+///
+/// # Returns
+/// Returns the equation of the equinoxes in radians; it is not normalized, so it may be negative.
+pub fn equation_of_equinoxes(tt: &NaiveDateTime) -> f64 {
+    let t = centuries_since_j2000(tt);
+    let argument =
+        |constant: f64, rate: f64| (constant + rate * t).rem_euclid(1_296_000.0) * ARCSEC_TO_RAD;
+    let l = argument(485868.249036, 1717915923.2178);
+    let lp = argument(1287104.79305, 129596581.0481);
+    let f = argument(335779.526232, 1739527262.8478);
+    let d = argument(1072260.70369, 1602961601.2090);
+    let om = argument(450160.398036, -6962890.5431);
+
+    let dpsi = NUTATION_TERMS
+        .iter()
+        .map(|(multipliers, sin, sin_rate, cos)| {
+            let arg = multipliers
+                .iter()
+                .zip([l, lp, f, d, om])
+                .map(|(n, a)| n * a)
+                .sum::<f64>();
+            (sin + sin_rate * t) * arg.sin() + cos * arg.cos()
+        })
+        .sum::<f64>()
+        * 1e-7
+        + NUTATION_PLANETARY_OFFSET;
+
+    // The mean obliquity of the ecliptic (IAU 2006)
+    let obliquity = 84381.406
+        + (-46.836769
+            + (-0.0001831 + (0.00200340 + (-0.000000576 + -0.0000000434 * t) * t) * t) * t)
+            * t;
+    let complementary = 2640.96e-6 * om.sin() - 0.39e-6 * om.cos() + 63.52e-6 * (2.0 * om).sin()
+        - 0.87e-6 * t * om.sin();
+
+    (dpsi * (obliquity * ARCSEC_TO_RAD).cos() + complementary) * ARCSEC_TO_RAD
+}
+
+/// Calculate the Greenwich apparent sidereal time; GMST plus [the equation of the equinoxes](equation_of_equinoxes).
+///
+/// # Arguments
+/// * `ut1` - Datetime in UT1
+/// * `tt` - The same instant in TT
+///
+/// # Returns
+/// Returns GAST in radians.
+pub fn gast(ut1: &NaiveDateTime, tt: &NaiveDateTime) -> f64 {
+    normalize(gmst(ut1, tt) + equation_of_equinoxes(tt))
+}
+
+/// Calculate the local apparent sidereal time.
+///
+/// # Arguments
+/// * `ut1` - Datetime in UT1
+/// * `tt` - The same instant in TT
+/// * `longitude` - The longitude in radians, positive to the east
+///
+/// # Returns
+/// Returns the local sidereal time in radians.
+pub fn lst(ut1: &NaiveDateTime, tt: &NaiveDateTime, longitude: f64) -> f64 {
+    normalize(gast(ut1, tt) + longitude)
+}
+
+/// Format the angle in hours, minutes and seconds; for example, `06:42:01.637380`.
+///
+/// The angle is normalized into [0h, 24h) and written to microseconds.
+///
+/// # Examples
+/// ```
+/// use std::f64::consts::PI;
+///
+/// assert_eq!(convdate::format_hms(PI / 2.0), "06:00:00.000000");
+/// assert_eq!(convdate::format_hms(-PI / 2.0), "18:00:00.000000");
+/// ```
+pub fn format_hms(angle: f64) -> String {
+    let micros = (normalize(angle) / TAU * 86_400e6).round() as u64 % 86_400_000_000;
+    format!(
+        "{:02}:{:02}:{:02}.{:06}",
+        micros / 3_600_000_000,
+        micros / 60_000_000 % 60,
+        micros / 1_000_000 % 60,
+        micros % 1_000_000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use rstest::*;
+
+    /// The tolerance of the truncated nutation model; 10 milliarcseconds
+    const NUTATION_TOLERANCE: f64 = 0.01 * ARCSEC_TO_RAD;
+
+    // The expected values are taken from the test program of SOFA.
+    #[rstest]
+    #[case(NaiveDate::from_ymd(2007, 10, 15).and_hms(0, 0, 0), 0.4022837240028158)]
+    #[case(NaiveDate::from_ymd(2000, 1, 1).and_hms(12, 0, 0), 4.894961212823756)]
+    fn test_era(#[case] ut1: NaiveDateTime, #[case] expected: f64) {
+        assert!((era(&ut1) - expected).abs() < 1e-12);
+    }
+
+    #[rstest]
+    #[case(NaiveDate::from_ymd(2006, 1, 1).and_hms(0, 0, 0), 1.754174971870091)]
+    fn test_gmst(#[case] datetime: NaiveDateTime, #[case] expected: f64) {
+        assert!((gmst(&datetime, &datetime) - expected).abs() < 1e-12);
+    }
+
+    /// The expected value is calculated by `iauEe06a` of SOFA with the full nutation model.
+    #[rstest]
+    #[case(NaiveDate::from_ymd(2006, 1, 1).and_hms(0, 0, 0), -0.883419507204379e-5)]
+    fn test_equation_of_equinoxes(#[case] tt: NaiveDateTime, #[case] expected: f64) {
+        assert!((equation_of_equinoxes(&tt) - expected).abs() < NUTATION_TOLERANCE);
+    }
+
+    #[rstest]
+    #[case(NaiveDate::from_ymd(2006, 1, 1).and_hms(0, 0, 0), 1.754166137675019)]
+    fn test_gast(#[case] datetime: NaiveDateTime, #[case] expected: f64) {
+        assert!((gast(&datetime, &datetime) - expected).abs() < NUTATION_TOLERANCE);
+    }
+
+    #[rstest]
+    #[case(0.0, 1.754166137675019)]
+    #[case(TAU / 4.0, 1.754166137675019 + TAU / 4.0)]
+    #[case(-TAU / 2.0, 1.754166137675019 + TAU / 2.0)]
+    fn test_lst(#[case] longitude: f64, #[case] expected: f64) {
+        let datetime = NaiveDate::from_ymd(2006, 1, 1).and_hms(0, 0, 0);
+        assert!((lst(&datetime, &datetime, longitude) - expected).abs() < NUTATION_TOLERANCE);
+    }
+
+    #[rstest]
+    #[case(0.0, "00:00:00.000000")]
+    #[case(TAU / 4.0, "06:00:00.000000")]
+    #[case(TAU, "00:00:00.000000")]
+    #[case(-TAU / 86_400e6, "23:59:59.999999")]
+    #[case(1.754174971870091, "06:42:01.637380")]
+    fn test_format_hms(#[case] angle: f64, #[case] expected: &str) {
+        assert_eq!(format_hms(angle), expected);
+    }
+}
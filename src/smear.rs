@@ -0,0 +1,484 @@
+//! Smeared UTC, which spreads leap seconds over a window instead of inserting `23:59:60`.
+//!
+//! Some NTP servers, for example the ones of Google and AWS, serve smeared UTC;
+//! their clocks run slightly slower (or faster for a removed second) during the window around a leap second,
+//! so that the clients never see the second 60.
+//! The windows are placed at the leap seconds of [`TaiUtcTable`]; out of the windows, smeared UTC equals to UTC.
+use crate::convtbl::{TaiUtcTable, UtcTaiTable};
+use crate::error::Error;
+use crate::tai2utc::tai2utc_label;
+use crate::utc2tai::utc2tai_dt;
+use crate::{nanos, parse_decimal_nanos};
+use chrono::{Duration, NaiveDateTime, Timelike};
+use std::f64::consts::PI;
+use std::fmt;
+use std::str::FromStr;
+
+/// Curves of smearing; how the inserted time is spread over the window
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SmearCurve {
+    /// The clock runs at a constant rate through the window.
+    Linear,
+    /// The rate of the clock changes smoothly at the ends of the window along a half cycle of cosine.
+    Cosine,
+}
+
+impl SmearCurve {
+    /// The names of the curves in the order of the variants
+    pub const NAMES: [&'static str; 2] = ["linear", "cosine"];
+
+    /// The name of the curve used in command arguments; for example, `"linear"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SmearCurve::Linear => "linear",
+            SmearCurve::Cosine => "cosine",
+        }
+    }
+
+    /// The ratio of the time smeared until the position `x` (0 to 1) in the window.
+    fn at(&self, x: f64) -> f64 {
+        match self {
+            SmearCurve::Linear => x,
+            SmearCurve::Cosine => (1.0 - (PI * x).cos()) / 2.0,
+        }
+    }
+}
+
+/// The shape of smearing
+///
+/// # Examples
+/// ```
+/// use convdate::smear::{Smear, SmearCurve};
+/// use chrono::Duration;
+///
+/// let smear: Smear = "google".parse().unwrap();
+/// assert_eq!(smear, Smear::google());
+///
+/// let smear: Smear = "custom:-7200,7200,cosine".parse().unwrap();
+/// assert_eq!(smear, Smear::custom(Duration::hours(-2), Duration::hours(2), SmearCurve::Cosine).unwrap());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Smear {
+    name: &'static str,
+    /// The beginning of the window relative to the end of the leap second; it is not positive.
+    start: Duration,
+    /// The length of the window in smeared UTC
+    length: Duration,
+    curve: SmearCurve,
+}
+
+impl Smear {
+    /// The names of the shapes used in command arguments; `custom` is followed by the parameters, see [`FromStr`].
+    pub const NAMES: [&'static str; 4] = ["google", "aws", "utc-sls", "custom"];
+
+    /// The smearing of [Google](https://developers.google.com/time/smear); linear over 24 hours from noon to noon.
+    pub fn google() -> Smear {
+        Smear {
+            name: "google",
+            start: Duration::hours(-12),
+            length: Duration::hours(24),
+            curve: SmearCurve::Linear,
+        }
+    }
+
+    /// The smearing of [AWS](https://aws.amazon.com/about-aws/whats-new/2017/11/introducing-the-amazon-time-sync-service/); the same as [`Smear::google`].
+    pub fn aws() -> Smear {
+        Smear {
+            name: "aws",
+            ..Smear::google()
+        }
+    }
+
+    /// [UTC-SLS](https://www.cl.cam.ac.uk/~mgk25/time/utc-sls/); linear over the last 1000 seconds before the leap second.
+    pub fn utc_sls() -> Smear {
+        Smear {
+            name: "utc-sls",
+            start: Duration::seconds(-1000),
+            length: Duration::seconds(1000),
+            curve: SmearCurve::Linear,
+        }
+    }
+
+    /// The smearing over the specified window.
+    ///
+    /// # Arguments
+    /// * `start` - The beginning of the window relative to the end of the leap second
+    /// * `length` - The length of the window
+    /// * `curve` - The curve of smearing
+    ///
+    /// # Returns
+    /// Returns [`Error`](crate::error::Error) if the window does not contain the leap second.
+    pub fn custom(start: Duration, length: Duration, curve: SmearCurve) -> Result<Smear, Error> {
+        if start > Duration::zero()
+            || start + length < Duration::zero()
+            || length <= Duration::zero()
+        {
+            Err(Error::SmearParseError(format!(
+                "custom:{},{},{}",
+                format_seconds(start),
+                format_seconds(length),
+                curve.name()
+            )))?;
+        }
+        Ok(Smear {
+            name: "custom",
+            start,
+            length,
+            curve,
+        })
+    }
+
+    /// The name of the shape; for example, `"google"`.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The windows of smearing in TAI and in smeared UTC, with the offsets TAI - UTC before and after them.
+    fn windows<'a>(&'a self, tai_utc_table: &'a TaiUtcTable) -> impl Iterator<Item = Window> + 'a {
+        tai_utc_table.windows(2).filter_map(move |rows| {
+            let leap = rows[1].datetime;
+            let (before, after) = (rows[0].diff_at(&leap), rows[1].diff_at(&leap));
+            (before != after).then(|| Window {
+                smeared_start: leap + self.start,
+                tai_start: leap + self.start + before,
+                length: self.length,
+                before,
+                after,
+                curve: self.curve,
+            })
+        })
+    }
+}
+
+impl Default for Smear {
+    fn default() -> Self {
+        Smear::google()
+    }
+}
+
+impl FromStr for Smear {
+    type Err = Error;
+
+    /// Parse the shape; `google`, `aws`, `utc-sls` or `custom:<start>,<length>[,<curve>]`.
+    ///
+    /// For `custom`, `<start>` and `<length>` are in seconds, and `<curve>` is `linear` (default) or `cosine`;
+    /// for example, `custom:-43200,86400,cosine`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_err = || Error::SmearParseError(s.to_string());
+        match s.to_ascii_lowercase().as_str() {
+            "google" => Ok(Smear::google()),
+            "aws" => Ok(Smear::aws()),
+            "utc-sls" => Ok(Smear::utc_sls()),
+            lower => {
+                let params = lower.strip_prefix("custom:").ok_or_else(parse_err)?;
+                let params: Vec<&str> = params.split(',').collect();
+                let seconds = |value: &str| parse_decimal_nanos(value).map(Duration::nanoseconds);
+                let (start, length, curve) = match params.as_slice() {
+                    [start, length] => (seconds(start), seconds(length), Some(SmearCurve::Linear)),
+                    [start, length, curve] => (
+                        seconds(start),
+                        seconds(length),
+                        match curve.trim() {
+                            "linear" => Some(SmearCurve::Linear),
+                            "cosine" => Some(SmearCurve::Cosine),
+                            _ => None,
+                        },
+                    ),
+                    _ => (None, None, None),
+                };
+                match (start, length, curve) {
+                    (Some(start), Some(length), Some(curve)) => {
+                        Smear::custom(start, length, curve).map_err(|_e| parse_err())
+                    }
+                    _ => Err(parse_err()),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Smear {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name {
+            "custom" => write!(
+                f,
+                "custom:{},{},{}",
+                format_seconds(self.start),
+                format_seconds(self.length),
+                self.curve.name()
+            ),
+            name => write!(f, "{}", name),
+        }
+    }
+}
+
+/// A window of smearing around a leap second
+struct Window {
+    smeared_start: NaiveDateTime,
+    tai_start: NaiveDateTime,
+    /// The length of the window in smeared UTC
+    length: Duration,
+    /// TAI - UTC before the leap second
+    before: Duration,
+    /// TAI - UTC after the leap second
+    after: Duration,
+    curve: SmearCurve,
+}
+
+impl Window {
+    /// The length of the window in TAI
+    fn tai_length(&self) -> Duration {
+        self.length + self.after - self.before
+    }
+
+    /// TAI - smeared UTC at the position `x` (0 to 1) in the window.
+    fn offset_at(&self, x: f64) -> Duration {
+        let inserted = nanos(self.after - self.before) as f64;
+        self.before + Duration::nanoseconds((inserted * self.curve.at(x)).round() as i64)
+    }
+}
+
+/// Convert datetime
+/// from [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time)
+/// to smeared UTC.
+///
+/// # Arguments
+/// * `datetime` - Datetime in TAI.
+/// * `tai_utc_table` - The conversion table of TAI - UTC, whose leap seconds are smeared
+/// * `utc_tai_table` - The conversion table of UTC - TAI, used out of the windows of smearing
+/// * `smear` - The shape of smearing
+///
+/// # Returns
+/// Returns the datetime in smeared UTC.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # Examples
+/// ```
+/// use convdate::convtbl::{TaiUtcTable, UtcTaiTable};
+/// use convdate::smear::{tai2smear_dt, Smear};
+/// use chrono::NaiveDate;
+///
+/// let tai_utc_table = TaiUtcTable::from_lines(vec!["2015-07-01T00:00:00 36", "2017-01-01T00:00:00 37"], "%Y-%m-%dT%H:%M:%S").unwrap();
+/// let utc_tai_table = UtcTaiTable::from(&tai_utc_table);
+///
+/// // In the middle of the window, the smeared clock has delayed by a half of the leap second.
+/// let smeared = tai2smear_dt(
+///     &NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 36, 500),
+///     &tai_utc_table,
+///     &utc_tai_table,
+///     &Smear::google());
+/// assert_eq!(smeared, Ok(NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0)));
+/// ```
+pub fn tai2smear_dt(
+    datetime: &NaiveDateTime,
+    tai_utc_table: &TaiUtcTable,
+    utc_tai_table: &UtcTaiTable,
+    smear: &Smear,
+) -> Result<NaiveDateTime, Error> {
+    let window = smear.windows(tai_utc_table).find(|window| {
+        window.tai_start <= *datetime && *datetime < window.tai_start + window.tai_length()
+    });
+    match window {
+        Some(window) => {
+            let x = nanos(*datetime - window.tai_start) as f64 / nanos(window.tai_length()) as f64;
+            Ok(*datetime - window.offset_at(x))
+        }
+        None => Ok(tai2utc_label(datetime, utc_tai_table)?.normalized()),
+    }
+}
+
+/// Convert datetime
+/// from smeared UTC
+/// to [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time).
+///
+/// It is the inverse of [`tai2smear_dt`].
+///
+/// # Arguments
+/// * `datetime` - Datetime in smeared UTC.
+/// * `tai_utc_table` - The conversion table of TAI - UTC, whose leap seconds are smeared
+/// * `smear` - The shape of smearing
+///
+/// # Returns
+/// Returns the datetime in TAI.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert;
+/// the second 60, which smeared UTC never has, results in [`Error::NonexistentUtcError`].
+pub fn smear2tai_dt(
+    datetime: &NaiveDateTime,
+    tai_utc_table: &TaiUtcTable,
+    smear: &Smear,
+) -> Result<NaiveDateTime, Error> {
+    if datetime.nanosecond() >= 1_000_000_000 {
+        Err(Error::NonexistentUtcError(datetime.to_string()))?;
+    }
+    let window = smear.windows(tai_utc_table).find(|window| {
+        window.smeared_start <= *datetime && *datetime < window.smeared_start + window.length
+    });
+    match window {
+        Some(window) => {
+            // smeared = tai_start + x * tai_length - (offset_at(x) - before) は x について単調増加なので、二分法で解く
+            let target = nanos(*datetime - window.smeared_start) as f64;
+            let smeared_at = |x: f64| {
+                x * nanos(window.tai_length()) as f64
+                    - nanos(window.offset_at(x) - window.before) as f64
+            };
+            let (mut low, mut high) = (0.0, 1.0);
+            for _ in 0..64 {
+                let mid = (low + high) / 2.0;
+                if smeared_at(mid) < target {
+                    low = mid;
+                } else {
+                    high = mid;
+                }
+            }
+            Ok(*datetime + window.offset_at(high))
+        }
+        None => utc2tai_dt(datetime, tai_utc_table),
+    }
+}
+
+/// Format the duration in seconds; for example, `-43200` or `0.5`.
+fn format_seconds(duration: Duration) -> String {
+    let nanos = duration.num_nanoseconds().unwrap();
+    let seconds = format!(
+        "{}{}.{:09}",
+        if nanos < 0 { "-" } else { "" },
+        nanos.abs() / 1_000_000_000,
+        nanos.abs() % 1_000_000_000
+    );
+    seconds
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convtbl::DiffTaiUtc;
+    use crate::testmod;
+    use chrono::NaiveDate;
+    use rstest::*;
+
+    const DT_FMT: &str = "%Y-%m-%dT%H:%M:%S%.3f";
+
+    fn tai_utc_table() -> TaiUtcTable {
+        vec![
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 0),
+                diff_seconds: 36,
+                drift: None,
+            },
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0),
+                diff_seconds: 37,
+                drift: None,
+            },
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2018, 1, 1).and_hms(0, 0, 0),
+                diff_seconds: 36,
+                drift: None,
+            },
+        ]
+        .into()
+    }
+
+    #[rstest]
+    #[case("google", Some(Smear::google()), None)]
+    #[case("AWS", Some(Smear::aws()), None)]
+    #[case("utc-sls", Some(Smear::utc_sls()), None)]
+    #[case("custom:-3600,7200", Some(Smear::custom(Duration::hours(-1), Duration::hours(2), SmearCurve::Linear).unwrap()), None)]
+    #[case("custom:-0.5,1,cosine", Some(Smear::custom(Duration::milliseconds(-500), Duration::seconds(1), SmearCurve::Cosine).unwrap()), None)]
+    #[case("custom:0,0", None, Some(Error::SmearParseError("custom:0,0".to_string())))]
+    #[case("custom:-10,5", None, Some(Error::SmearParseError("custom:-10,5".to_string())))]
+    #[case("custom:1,5", None, Some(Error::SmearParseError("custom:1,5".to_string())))]
+    #[case("custom:-10,20,sine", None, Some(Error::SmearParseError("custom:-10,20,sine".to_string())))]
+    #[case("custom:-10", None, Some(Error::SmearParseError("custom:-10".to_string())))]
+    #[case("ntp", None, Some(Error::SmearParseError("ntp".to_string())))]
+    fn test_from_str(
+        #[case] s: &str,
+        #[case] expected_ok: Option<Smear>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        assert_eq!(s.parse(), testmod::result(expected_ok, expected_err));
+    }
+
+    #[rstest]
+    #[case("google", "google")]
+    #[case("custom:-3600,7200", "custom:-3600,7200,linear")]
+    #[case("custom:-0.5,1.25,cosine", "custom:-0.5,1.25,cosine")]
+    fn test_display(#[case] s: &str, #[case] expected: &str) {
+        assert_eq!(s.parse::<Smear>().unwrap().to_string(), expected);
+    }
+
+    #[rstest]
+    // 窓の外では UTC と同じ
+    #[case("google", "2016-12-31T12:00:35.000", "2016-12-31T11:59:59.000")]
+    #[case("google", "2016-12-31T12:00:36.000", "2016-12-31T12:00:00.000")]
+    #[case("google", "2017-01-01T12:00:37.000", "2017-01-01T12:00:00.000")]
+    // Google: 正午から正午まで線形
+    #[case("google", "2017-01-01T00:00:36.500", "2017-01-01T00:00:00.000")]
+    #[case("google", "2016-12-31T18:00:36.250", "2016-12-31T18:00:00.000")]
+    #[case("google", "2017-01-01T06:00:36.750", "2017-01-01T06:00:00.000")]
+    // UTC-SLS: 最後の1000秒
+    #[case("utc-sls", "2016-12-31T23:43:56.000", "2016-12-31T23:43:20.000")]
+    #[case("utc-sls", "2016-12-31T23:52:16.500", "2016-12-31T23:51:40.000")]
+    #[case("utc-sls", "2017-01-01T00:00:37.000", "2017-01-01T00:00:00.000")]
+    // 削除されるうるう秒
+    #[case("google", "2018-01-01T00:00:36.500", "2018-01-01T00:00:00.000")]
+    #[case(
+        "custom:-10,20,cosine",
+        "2017-01-01T00:00:36.500",
+        "2017-01-01T00:00:00.000"
+    )]
+    #[case(
+        "custom:-10,20,cosine",
+        "2016-12-31T23:59:50.000",
+        "2016-12-31T23:59:14.000"
+    )]
+    fn test_tai2smear_dt(#[case] smear: &str, #[case] tai: &str, #[case] expected: &str) {
+        let tai_utc_table = tai_utc_table();
+        let utc_tai_table = UtcTaiTable::from(&tai_utc_table);
+        let smear: Smear = smear.parse().unwrap();
+        let tai = NaiveDateTime::parse_from_str(tai, DT_FMT).unwrap();
+
+        let smeared = tai2smear_dt(&tai, &tai_utc_table, &utc_tai_table, &smear).unwrap();
+        assert_eq!(smeared.format(DT_FMT).to_string(), expected);
+        // 逆変換
+        let restored = smear2tai_dt(&smeared, &tai_utc_table, &smear).unwrap();
+        assert!((restored - tai).num_nanoseconds().unwrap().abs() <= 1);
+    }
+
+    #[test]
+    fn test_tai2smear_dt_too_low() {
+        let tai_utc_table = tai_utc_table();
+        let utc_tai_table = UtcTaiTable::from(&tai_utc_table);
+        let tai = NaiveDate::from_ymd(2015, 1, 1).and_hms(0, 0, 0);
+
+        assert_eq!(
+            tai2smear_dt(&tai, &tai_utc_table, &utc_tai_table, &Smear::google()),
+            Err(Error::DatetimeTooLowError(tai.to_string()))
+        );
+        assert_eq!(
+            smear2tai_dt(&tai, &tai_utc_table, &Smear::google()),
+            Err(Error::DatetimeTooLowError(tai.to_string()))
+        );
+    }
+
+    #[rstest]
+    #[case("google", "2016-12-31T23:59:60.000")]
+    #[case("google", "2016-12-31T23:59:60.500")]
+    #[case("utc-sls", "2016-12-31T23:59:60.000")]
+    // うるう秒のない時刻の60秒
+    #[case("google", "2017-06-30T23:59:60.000")]
+    fn test_smear2tai_dt_second_60(#[case] smear: &str, #[case] smeared: &str) {
+        let smear: Smear = smear.parse().unwrap();
+        let smeared = NaiveDateTime::parse_from_str(smeared, DT_FMT).unwrap();
+
+        assert_eq!(
+            smear2tai_dt(&smeared, &tai_utc_table(), &smear),
+            Err(Error::NonexistentUtcError(smeared.to_string()))
+        );
+    }
+}
@@ -1,6 +1,8 @@
 use crate::convtbl::UtcTaiTable;
 use crate::error::Error;
-use chrono::{Duration, NaiveDateTime, Timelike};
+use crate::DtFmts;
+use crate::UtcLabel;
+use chrono::NaiveDateTime;
 
 /// Convert datetime
 /// from [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time)
@@ -11,7 +13,7 @@ use chrono::{Duration, NaiveDateTime, Timelike};
 /// # Arguments
 /// * `datetime` - Datetime in TAI.
 /// * `utc_tai_table` - The conversion table of UTC - TAI
-/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime`
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
 ///
 /// # Returns
 /// Returns the datetime in UTC.
@@ -37,11 +39,16 @@ use chrono::{Duration, NaiveDateTime, Timelike};
 ///
 /// # See also
 /// * [`tai2utc`](../tai2utc/index.html) (Binary crate) - The executable program which do same conversion.
-pub fn tai2utc(datetime: &str, utc_tai_table: &UtcTaiTable, dt_fmt: &str) -> Result<String, Error> {
-    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt)
+pub fn tai2utc<'a>(
+    datetime: &str,
+    utc_tai_table: &UtcTaiTable,
+    dt_fmt: impl Into<DtFmts<'a>>,
+) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
         .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
-    let utc = tai2utc_dt(&datetime, utc_tai_table)?;
-    Ok(utc.format(dt_fmt).to_string())
+    let utc = tai2utc_label(&datetime, utc_tai_table)?;
+    Ok(utc.format(dt_fmt.format))
 }
 
 /// Convert datetime
@@ -57,7 +64,9 @@ pub fn tai2utc(datetime: &str, utc_tai_table: &UtcTaiTable, dt_fmt: &str) -> Res
 /// # Returns
 /// Returns the datetime in UTC.
 ///
-/// Returns [`Error`](crate::error::Error) if it fail to convert.
+/// Returns [`Error`](crate::error::Error) if it fail to convert,
+/// or if the result is the second 61 or more, which [`NaiveDateTime`] cannot express;
+/// use [`tai2utc_label`] in order to get such a result.
 ///
 /// # See also
 /// * [`tai2utc`] - It is same as `tai2utc_dt`, except that the argument and the result are [`str`] and [`String`].
@@ -66,16 +75,33 @@ pub fn tai2utc_dt(
     datetime: &NaiveDateTime,
     utc_tai_table: &UtcTaiTable,
 ) -> Result<NaiveDateTime, Error> {
-    return utc_tai_table
+    let utc = tai2utc_label(datetime, utc_tai_table)?;
+    utc.to_naive()
+        .ok_or_else(|| Error::UtcLabelNotRepresentableError(utc.to_string()))
+}
+
+/// Convert datetime
+/// from [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time)
+/// to the label of [UTC](https://en.wikipedia.org/wiki/Coordinated_Universal_Time).
+///
+/// Unlike [`tai2utc_dt`], the result can have the second 61 or more
+/// when two or more leap seconds are inserted at once.
+///
+/// # Arguments
+/// * `datetime` - Datetime in TAI.
+/// * `utc_tai_table` - The conversion table of UTC - TAI
+///
+/// # Returns
+/// Returns the label of UTC.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+pub fn tai2utc_label(
+    datetime: &NaiveDateTime,
+    utc_tai_table: &UtcTaiTable,
+) -> Result<UtcLabel, Error> {
+    utc_tai_table
         .pick_dominant_row(datetime)
-        .map(|diff_utc_tai| {
-            let mut datetime_tmp = datetime.clone();
-            datetime_tmp += Duration::seconds(diff_utc_tai.diff_seconds);
-            NaiveDateTime::from_timestamp(
-                datetime_tmp.timestamp(),
-                datetime_tmp.nanosecond() + diff_utc_tai.corr_seconds * 1_000_000_000,
-            )
-        });
+        .map(|diff_utc_tai| diff_utc_tai.apply(datetime))
 }
 
 #[cfg(test)]
@@ -106,8 +132,9 @@ mod tests {
     #[case("2018-01-01T00:00:36.000", Some("2018-01-01T00:00:00.000"), None)]
     // うるう秒が2秒挿入される瞬間のテスト
     #[case("2019-01-01T00:00:35.000", Some("2018-12-31T23:59:59.000"), None)]
-    // #[case("2019-01-01T00:00:36.000", Some("2018-12-31T23:59:60.000"), None)]
-    // #[case("2019-01-01T00:00:37.000", Some("2018-12-31T23:59:61.000"), None)]
+    #[case("2019-01-01T00:00:36.000", Some("2018-12-31T23:59:60.000"), None)]
+    #[case("2019-01-01T00:00:37.000", Some("2018-12-31T23:59:61.000"), None)]
+    #[case("2019-01-01T00:00:37.500", Some("2018-12-31T23:59:61.500"), None)]
     #[case("2019-01-01T00:00:38.000", Some("2019-01-01T00:00:00.000"), None)]
     // うるう秒が2秒削除される瞬間のテスト
     #[case("2020-01-01T00:00:35.000", Some("2019-12-31T23:59:57.000"), None)]
@@ -125,27 +152,32 @@ mod tests {
             DiffTaiUtc {
                 datetime: NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 0),
                 diff_seconds: 36,
+                drift: None,
             },
             DiffTaiUtc {
                 datetime: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0),
                 diff_seconds: 37,
+                drift: None,
             },
             DiffTaiUtc {
                 datetime: NaiveDate::from_ymd(2018, 1, 1).and_hms(0, 0, 0),
                 diff_seconds: 36,
+                drift: None,
             },
             DiffTaiUtc {
                 datetime: NaiveDate::from_ymd(2019, 1, 1).and_hms(0, 0, 0),
                 diff_seconds: 38,
+                drift: None,
             },
             DiffTaiUtc {
                 datetime: NaiveDate::from_ymd(2020, 1, 1).and_hms(0, 0, 0),
                 diff_seconds: 36,
+                drift: None,
             },
         ]
         .into();
         let utc_tai_table = From::from(&tai_utc_table);
-        let utc = tai2utc(&tai, &utc_tai_table, DT_FMT);
+        let utc = tai2utc(tai, &utc_tai_table, DT_FMT);
 
         assert_eq!(utc, expected);
     }
@@ -200,10 +232,76 @@ mod tests {
         let tai_utc_table: TaiUtcTable = vec![DiffTaiUtc {
             datetime: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0),
             diff_seconds: 37,
+            drift: None,
         }]
         .into();
         let utc_tai_table = From::from(&tai_utc_table);
-        let utc = tai2utc(&tai, &utc_tai_table, dt_fmt);
+        let utc = tai2utc(tai, &utc_tai_table, dt_fmt);
+
+        assert_eq!(utc, expected);
+    }
+
+    #[rstest]
+    // Error when the input datetime is too low.
+    #[case("1961-01-01T00:00:01.422817999", None, Some(Error::DatetimeTooLowError("1961-01-01 00:00:01.422817999".to_string())))]
+    #[case(
+        "1961-01-01T00:00:01.422818000",
+        Some("1961-01-01T00:00:00.000000000"),
+        None
+    )]
+    // TAI - UTC drifts day by day
+    #[case(
+        "1970-01-01T00:00:08.000082000",
+        Some("1970-01-01T00:00:00.000000000"),
+        None
+    )]
+    // TAI - UTC decreases by 0.05 seconds; UTC skips 0.05 seconds
+    #[case(
+        "1961-08-01T00:00:00.697569985",
+        Some("1961-07-31T23:59:59.000000000"),
+        None
+    )]
+    #[case(
+        "1961-08-01T00:00:01.647570000",
+        Some("1961-08-01T00:00:00.000000000"),
+        None
+    )]
+    #[case(
+        "1961-08-01T00:00:01.670000000",
+        Some("1961-08-01T00:00:00.022430000"),
+        None
+    )]
+    // TAI - UTC increases by 0.107758 seconds at the end of the era of rubber seconds; UTC inserts 0.107758 seconds
+    #[case(
+        "1972-01-01T00:00:09.891241999",
+        Some("1971-12-31T23:59:59.999000000"),
+        None
+    )]
+    #[case(
+        "1972-01-01T00:00:09.892242000",
+        Some("1971-12-31T23:59:60.000000000"),
+        None
+    )]
+    #[case(
+        "1972-01-01T00:00:09.950000000",
+        Some("1971-12-31T23:59:60.057758000"),
+        None
+    )]
+    #[case(
+        "1972-01-01T00:00:10.000000000",
+        Some("1972-01-01T00:00:00.000000000"),
+        None
+    )]
+    fn test_tai2utc_rubber_seconds(
+        #[case] tai: &str,
+        #[case] expected_ok: Option<&str>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok.map(ToString::to_string), expected_err);
+
+        let tai_utc_table = TaiUtcTable::from_tai_utc_dat(testmod::TAI_UTC_DAT).unwrap();
+        let utc_tai_table = From::from(&tai_utc_table);
+        let utc = tai2utc(tai, &utc_tai_table, "%Y-%m-%dT%H:%M:%S%.9f");
 
         assert_eq!(utc, expected);
     }
@@ -0,0 +1,168 @@
+use crate::error::Error;
+use crate::tcg::scale_elapsed;
+use crate::tdb::{tdb2tt_dt, tt2tdb_dt, TdbModel};
+use crate::DtFmts;
+use chrono::{Duration, NaiveDateTime};
+
+/// The rate of TCB relative to TDB; TDB = TCB - L<sub>B</sub> × (TCB - T<sub>0</sub>) + TDB<sub>0</sub>
+const L_B: f64 = 1.550519768e-8;
+
+/// TDB<sub>0</sub> of the IAU 2006 Resolution B3, in nanoseconds
+const TDB0_NANOS: f64 = -65_500.0;
+
+/// Convert datetime
+/// from [TDB](https://en.wikipedia.org/wiki/Barycentric_Dynamical_Time)
+/// to [TCB](https://en.wikipedia.org/wiki/Barycentric_Coordinate_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in TDB.
+///
+/// # Returns
+/// Returns the datetime in TCB.
+pub fn tdb2tcb_dt(datetime: &NaiveDateTime) -> NaiveDateTime {
+    *datetime + scale_elapsed(datetime, L_B / (1.0 - L_B))
+        - Duration::nanoseconds((TDB0_NANOS / (1.0 - L_B)).round() as i64)
+}
+
+/// Convert datetime
+/// from [TCB](https://en.wikipedia.org/wiki/Barycentric_Coordinate_Time)
+/// to [TDB](https://en.wikipedia.org/wiki/Barycentric_Dynamical_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in TCB.
+///
+/// # Returns
+/// Returns the datetime in TDB.
+pub fn tcb2tdb_dt(datetime: &NaiveDateTime) -> NaiveDateTime {
+    *datetime - scale_elapsed(datetime, L_B) + Duration::nanoseconds(TDB0_NANOS as i64)
+}
+
+/// Convert datetime
+/// from [TT](https://en.wikipedia.org/wiki/Terrestrial_Time)
+/// to [TCB](https://en.wikipedia.org/wiki/Barycentric_Coordinate_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in TT.
+/// * `model` - The model of TDB - TT
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
+///
+/// # Returns
+/// Returns the datetime in TCB.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # Examples
+/// ```
+/// use convdate::{self, TdbModel};
+///
+/// let tcb = convdate::tt2tcb(
+///     "2017-01-01T12:00:00.000000",
+///     TdbModel::FairheadBretagnonTruncated,
+///     "%Y-%m-%dT%H:%M:%S%.6f");
+///
+/// assert_eq!(tcb, Ok("2017-01-01T12:00:19.572973".to_string()));
+/// ```
+pub fn tt2tcb<'a>(
+    datetime: &str,
+    model: TdbModel,
+    dt_fmt: impl Into<DtFmts<'a>>,
+) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
+        .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
+    let tcb = tt2tcb_dt(&datetime, model);
+    Ok(tcb.format(dt_fmt.format).to_string())
+}
+
+/// Convert datetime
+/// from [TT](https://en.wikipedia.org/wiki/Terrestrial_Time)
+/// to [TCB](https://en.wikipedia.org/wiki/Barycentric_Coordinate_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in TT.
+/// * `model` - The model of TDB - TT
+///
+/// # Returns
+/// Returns the datetime in TCB.
+///
+/// # See also
+/// * [`tt2tcb`] - It is same as `tt2tcb_dt`, except that the argument and the result are [`str`] and [`String`].
+pub fn tt2tcb_dt(datetime: &NaiveDateTime, model: TdbModel) -> NaiveDateTime {
+    tdb2tcb_dt(&tt2tdb_dt(datetime, model))
+}
+
+/// Convert datetime
+/// from [TCB](https://en.wikipedia.org/wiki/Barycentric_Coordinate_Time)
+/// to [TT](https://en.wikipedia.org/wiki/Terrestrial_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in TCB.
+/// * `model` - The model of TDB - TT
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
+///
+/// # Returns
+/// Returns the datetime in TT.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # Examples
+/// ```
+/// use convdate::{self, TdbModel};
+///
+/// let tt = convdate::tcb2tt(
+///     "2017-01-01T12:00:19.572974",
+///     TdbModel::FairheadBretagnonTruncated,
+///     "%Y-%m-%dT%H:%M:%S%.6f");
+///
+/// assert_eq!(tt, Ok("2017-01-01T12:00:00.000000".to_string()));
+/// ```
+pub fn tcb2tt<'a>(
+    datetime: &str,
+    model: TdbModel,
+    dt_fmt: impl Into<DtFmts<'a>>,
+) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
+        .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
+    let tt = tcb2tt_dt(&datetime, model);
+    Ok(tt.format(dt_fmt.format).to_string())
+}
+
+/// Convert datetime
+/// from [TCB](https://en.wikipedia.org/wiki/Barycentric_Coordinate_Time)
+/// to [TT](https://en.wikipedia.org/wiki/Terrestrial_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in TCB.
+/// * `model` - The model of TDB - TT
+///
+/// # Returns
+/// Returns the datetime in TT.
+///
+/// # See also
+/// * [`tcb2tt`] - It is same as `tcb2tt_dt`, except that the argument and the result are [`str`] and [`String`].
+pub fn tcb2tt_dt(datetime: &NaiveDateTime, model: TdbModel) -> NaiveDateTime {
+    tdb2tt_dt(&tcb2tdb_dt(datetime), model)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    const DT_FMT: &str = "%Y-%m-%dT%H:%M:%S%.9f";
+
+    #[rstest]
+    // At T0, TCB - TDB is only -TDB0.
+    #[case("1977-01-01T00:00:32.184000000", "1977-01-01T00:00:32.184065500")]
+    #[case("2017-01-01T12:00:00.000000000", "2017-01-01T12:00:19.573008181")]
+    #[case("1900-01-01T00:00:00.000000000", "1899-12-31T23:59:22.323773703")]
+    fn test_tdb2tcb(#[case] tdb: &str, #[case] expected: &str) {
+        let tdb = NaiveDateTime::parse_from_str(tdb, DT_FMT).unwrap();
+
+        let tcb = tdb2tcb_dt(&tdb);
+
+        assert_eq!(tcb.format(DT_FMT).to_string(), expected);
+        assert_eq!(tcb2tdb_dt(&tcb), tdb);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::error::Error;
+use crate::DtFmts;
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+
+/// The rate of TCG relative to TT; TT = TCG - L<sub>G</sub> × (TCG - T<sub>0</sub>)
+const L_G: f64 = 6.969290134e-10;
+
+/// T<sub>0</sub> (1977-01-01T00:00:32.184 TAI), at which TT, TCG and TCB coincide
+pub(crate) fn t0() -> NaiveDateTime {
+    NaiveDate::from_ymd(1977, 1, 1).and_hms_milli(0, 0, 32, 184)
+}
+
+/// Calculate (`datetime` - T<sub>0</sub>) × `rate`.
+pub(crate) fn scale_elapsed(datetime: &NaiveDateTime, rate: f64) -> Duration {
+    let elapsed = *datetime - t0();
+    let seconds = elapsed.num_seconds();
+    let nanos = (elapsed - Duration::seconds(seconds))
+        .num_nanoseconds()
+        .unwrap();
+    let scaled = (seconds as f64 * 1e9 + nanos as f64) * rate;
+    Duration::nanoseconds(scaled.round() as i64)
+}
+
+/// Convert datetime
+/// from [TT](https://en.wikipedia.org/wiki/Terrestrial_Time)
+/// to [TCG](https://en.wikipedia.org/wiki/Geocentric_Coordinate_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in TT.
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
+///
+/// # Returns
+/// Returns the datetime in TCG.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # Examples
+/// ```
+/// use convdate;
+///
+/// let tcg = convdate::tt2tcg(
+///     "2017-01-01T12:00:00.000000",
+///     "%Y-%m-%dT%H:%M:%S%.6f");
+///
+/// assert_eq!(tcg, Ok("2017-01-01T12:00:00.879766".to_string()));
+/// ```
+pub fn tt2tcg<'a>(datetime: &str, dt_fmt: impl Into<DtFmts<'a>>) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
+        .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
+    let tcg = tt2tcg_dt(&datetime);
+    Ok(tcg.format(dt_fmt.format).to_string())
+}
+
+/// Convert datetime
+/// from [TT](https://en.wikipedia.org/wiki/Terrestrial_Time)
+/// to [TCG](https://en.wikipedia.org/wiki/Geocentric_Coordinate_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in TT.
+///
+/// # Returns
+/// Returns the datetime in TCG.
+///
+/// # See also
+/// * [`tt2tcg`] - It is same as `tt2tcg_dt`, except that the argument and the result are [`str`] and [`String`].
+pub fn tt2tcg_dt(datetime: &NaiveDateTime) -> NaiveDateTime {
+    *datetime + scale_elapsed(datetime, L_G / (1.0 - L_G))
+}
+
+/// Convert datetime
+/// from [TCG](https://en.wikipedia.org/wiki/Geocentric_Coordinate_Time)
+/// to [TT](https://en.wikipedia.org/wiki/Terrestrial_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in TCG.
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
+///
+/// # Returns
+/// Returns the datetime in TT.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # Examples
+/// ```
+/// use convdate;
+///
+/// let tt = convdate::tcg2tt(
+///     "2017-01-01T12:00:00.879767",
+///     "%Y-%m-%dT%H:%M:%S%.6f");
+///
+/// assert_eq!(tt, Ok("2017-01-01T12:00:00.000000".to_string()));
+/// ```
+pub fn tcg2tt<'a>(datetime: &str, dt_fmt: impl Into<DtFmts<'a>>) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
+        .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
+    let tt = tcg2tt_dt(&datetime);
+    Ok(tt.format(dt_fmt.format).to_string())
+}
+
+/// Convert datetime
+/// from [TCG](https://en.wikipedia.org/wiki/Geocentric_Coordinate_Time)
+/// to [TT](https://en.wikipedia.org/wiki/Terrestrial_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in TCG.
+///
+/// # Returns
+/// Returns the datetime in TT.
+///
+/// # See also
+/// * [`tcg2tt`] - It is same as `tcg2tt_dt`, except that the argument and the result are [`str`] and [`String`].
+pub fn tcg2tt_dt(datetime: &NaiveDateTime) -> NaiveDateTime {
+    *datetime - scale_elapsed(datetime, L_G)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    const DT_FMT: &str = "%Y-%m-%dT%H:%M:%S%.9f";
+
+    #[rstest]
+    #[case("1977-01-01T00:00:32.184000000", "1977-01-01T00:00:32.184000000")]
+    #[case("2017-01-01T12:00:00.000000000", "2017-01-01T12:00:00.879766367")]
+    #[case("1900-01-01T00:00:00.000000000", "1899-12-31T23:59:58.306522688")]
+    fn test_tt2tcg(#[case] tt: &str, #[case] expected: &str) {
+        let tt = NaiveDateTime::parse_from_str(tt, DT_FMT).unwrap();
+
+        let tcg = tt2tcg_dt(&tt);
+
+        assert_eq!(tcg.format(DT_FMT).to_string(), expected);
+        assert_eq!(tcg2tt_dt(&tcg), tt);
+    }
+}
@@ -0,0 +1,325 @@
+use crate::epoch::j2000_epoch;
+use crate::error::Error;
+use crate::DtFmts;
+use chrono::{Duration, NaiveDateTime};
+use std::fmt;
+use std::str::FromStr;
+
+/// The leading terms of the Fairhead–Bretagnon series; (amplitude in seconds, frequency in radians per Julian millennium, phase in radians).
+///
+/// They are the first 20 of the 474 terms of T<sup>0</sup> in the order of `iauDtdb` of [SOFA](http://www.iausofa.org/);
+/// every omitted term of T<sup>0</sup> is smaller than 0.4 microseconds.
+const FAIRHEAD_T0: [(f64, f64, f64); 20] = [
+    (1656.674564e-6, 6283.075849991, 6.240054195),
+    (22.417471e-6, 5753.384884897, 4.296977442),
+    (13.839792e-6, 12566.151699983, 6.196904410),
+    (4.770086e-6, 529.690965095, 0.444401603),
+    (4.676740e-6, 6069.776754553, 4.021195093),
+    (2.256707e-6, 213.299095438, 5.543113262),
+    (1.694205e-6, -3.523118349, 5.025132748),
+    (1.554905e-6, 77713.771467920, 5.198467090),
+    (1.276839e-6, 7860.419392439, 5.988822341),
+    (1.193379e-6, 5223.693919802, 3.649823730),
+    (1.115322e-6, 3930.209696220, 1.422745069),
+    (0.794185e-6, 11506.769769794, 2.322313077),
+    (0.447061e-6, 26.298319800, 3.615796498),
+    (0.435206e-6, -398.149003408, 4.349338347),
+    (0.600309e-6, 1577.343542448, 2.678271909),
+    (0.496817e-6, 6208.294251424, 5.696701824),
+    (0.486306e-6, 5884.926846583, 0.520007179),
+    (0.432392e-6, 74.781598567, 2.435898309),
+    (0.468597e-6, 6244.942814354, 5.866398759),
+    (0.375510e-6, 5507.553238667, 4.103476804),
+];
+
+/// The leading terms of the Fairhead–Bretagnon series multiplied by T (Julian millennia)
+///
+/// They are the first 2 of the 205 terms of T<sup>1</sup> in the order of `iauDtdb` of SOFA.
+const FAIRHEAD_T1: [(f64, f64, f64); 2] = [
+    (102.156724e-6, 6283.075849991, 4.249032005),
+    (1.706807e-6, 12566.151699983, 4.205904248),
+];
+
+/// The leading term of the Fairhead–Bretagnon series multiplied by T<sup>2</sup> (Julian millennia)
+///
+/// It is the first of the 85 terms of T<sup>2</sup> in the order of `iauDtdb` of SOFA.
+const FAIRHEAD_T2: [(f64, f64, f64); 1] = [(4.322990e-6, 6283.075849991, 2.642893748)];
+
+const SECONDS_PER_JULIAN_MILLENNIUM: f64 = 365_250.0 * 86_400.0;
+
+/// Models of TDB - TT
+///
+/// # Examples
+/// ```
+/// use convdate::TdbModel;
+///
+/// let model: TdbModel = "simplified".parse().unwrap();
+/// assert_eq!(model, TdbModel::Simplified);
+/// assert_eq!(TdbModel::default(), TdbModel::FairheadBretagnonTruncated);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TdbModel {
+    /// The Fairhead–Bretagnon series (1990) truncated to the first 23 of its 787 terms in the order of `iauDtdb` of SOFA;
+    /// 20 terms of T<sup>0</sup>, 2 terms of T<sup>1</sup> and 1 term of T<sup>2</sup>, omitting the terms of T<sup>3</sup> and T<sup>4</sup>.
+    ///
+    /// The omitted terms of T<sup>0</sup> are smaller than 0.4 microseconds each, but the omitted terms are not negligible in total;
+    /// the error from the full series is estimated at up to about 5 microseconds between 1600 and 2200,
+    /// and the topocentric terms are not included.
+    /// Use the full series, such as `iauDtdb` of SOFA, when an accuracy of a microsecond or better is required.
+    #[default]
+    FairheadBretagnonTruncated,
+    /// TDB - TT = 0.001657 sin *g* + 0.000014 sin 2*g*, where *g* is the mean anomaly of the Earth; the error is up to about 40 microseconds.
+    Simplified,
+}
+
+impl TdbModel {
+    /// All models of TDB - TT which convdate supports
+    pub const ALL: [TdbModel; 2] = [TdbModel::FairheadBretagnonTruncated, TdbModel::Simplified];
+
+    /// The names of [`TdbModel::ALL`] in the same order
+    pub const NAMES: [&'static str; 2] = ["fairhead-bretagnon-truncated", "simplified"];
+
+    /// The name of the model used in command arguments; for example, `"simplified"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TdbModel::FairheadBretagnonTruncated => "fairhead-bretagnon-truncated",
+            TdbModel::Simplified => "simplified",
+        }
+    }
+
+    /// Calculate TDB - TT in seconds.
+    ///
+    /// # Arguments
+    /// * `datetime` - Datetime in TT; TDB can be also used since the difference does not matter.
+    fn diff_seconds(&self, datetime: &NaiveDateTime) -> f64 {
+        let elapsed = *datetime - j2000_epoch();
+        let elapsed = elapsed.num_seconds() as f64
+            + (elapsed - Duration::seconds(elapsed.num_seconds()))
+                .num_nanoseconds()
+                .unwrap() as f64
+                * 1e-9;
+        match self {
+            TdbModel::FairheadBretagnonTruncated => {
+                let t = elapsed / SECONDS_PER_JULIAN_MILLENNIUM;
+                let series = |terms: &[(f64, f64, f64)]| -> f64 {
+                    terms
+                        .iter()
+                        .map(|(amplitude, frequency, phase)| {
+                            amplitude * (frequency * t + phase).sin()
+                        })
+                        .sum()
+                };
+                series(&FAIRHEAD_T0) + t * series(&FAIRHEAD_T1) + t * t * series(&FAIRHEAD_T2)
+            }
+            TdbModel::Simplified => {
+                let g = (357.53 + 0.98560028 * elapsed / 86_400.0).to_radians();
+                0.001657 * g.sin() + 0.000014 * (2.0 * g).sin()
+            }
+        }
+    }
+
+    /// Calculate TDB - TT.
+    fn diff(&self, datetime: &NaiveDateTime) -> Duration {
+        Duration::nanoseconds((self.diff_seconds(datetime) * 1e9).round() as i64)
+    }
+}
+
+impl FromStr for TdbModel {
+    type Err = Error;
+
+    /// Parse the name of a model. The case of letters is ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TdbModel::ALL
+            .iter()
+            .find(|model| model.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| Error::UnknownTdbModelError(s.to_string()))
+    }
+}
+
+impl fmt::Display for TdbModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Convert datetime
+/// from [TT](https://en.wikipedia.org/wiki/Terrestrial_Time)
+/// to [TDB](https://en.wikipedia.org/wiki/Barycentric_Dynamical_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in TT.
+/// * `model` - The model of TDB - TT
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
+///
+/// # Returns
+/// Returns the datetime in TDB.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # Examples
+/// ```
+/// use convdate::{self, TdbModel};
+///
+/// let tdb = convdate::tt2tdb(
+///     "2017-01-01T12:00:00.000000",
+///     TdbModel::FairheadBretagnonTruncated,
+///     "%Y-%m-%dT%H:%M:%S%.6f");
+///
+/// assert_eq!(tdb, Ok("2017-01-01T11:59:59.999965".to_string()));
+/// ```
+pub fn tt2tdb<'a>(
+    datetime: &str,
+    model: TdbModel,
+    dt_fmt: impl Into<DtFmts<'a>>,
+) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
+        .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
+    let tdb = tt2tdb_dt(&datetime, model);
+    Ok(tdb.format(dt_fmt.format).to_string())
+}
+
+/// Convert datetime
+/// from [TT](https://en.wikipedia.org/wiki/Terrestrial_Time)
+/// to [TDB](https://en.wikipedia.org/wiki/Barycentric_Dynamical_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in TT.
+/// * `model` - The model of TDB - TT
+///
+/// # Returns
+/// Returns the datetime in TDB.
+///
+/// # See also
+/// * [`tt2tdb`] - It is same as `tt2tdb_dt`, except that the argument and the result are [`str`] and [`String`].
+pub fn tt2tdb_dt(datetime: &NaiveDateTime, model: TdbModel) -> NaiveDateTime {
+    *datetime + model.diff(datetime)
+}
+
+/// Convert datetime
+/// from [TDB](https://en.wikipedia.org/wiki/Barycentric_Dynamical_Time)
+/// to [TT](https://en.wikipedia.org/wiki/Terrestrial_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in TDB.
+/// * `model` - The model of TDB - TT
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
+///
+/// # Returns
+/// Returns the datetime in TT.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # Examples
+/// ```
+/// use convdate::{self, TdbModel};
+///
+/// let tt = convdate::tdb2tt(
+///     "2017-01-01T11:59:59.999966",
+///     TdbModel::FairheadBretagnonTruncated,
+///     "%Y-%m-%dT%H:%M:%S%.6f");
+///
+/// assert_eq!(tt, Ok("2017-01-01T12:00:00.000000".to_string()));
+/// ```
+pub fn tdb2tt<'a>(
+    datetime: &str,
+    model: TdbModel,
+    dt_fmt: impl Into<DtFmts<'a>>,
+) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
+        .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
+    let tt = tdb2tt_dt(&datetime, model);
+    Ok(tt.format(dt_fmt.format).to_string())
+}
+
+/// Convert datetime
+/// from [TDB](https://en.wikipedia.org/wiki/Barycentric_Dynamical_Time)
+/// to [TT](https://en.wikipedia.org/wiki/Terrestrial_Time).
+///
+/// # Arguments
+/// * `datetime` - Datetime in TDB.
+/// * `model` - The model of TDB - TT
+///
+/// # Returns
+/// Returns the datetime in TT.
+///
+/// # See also
+/// * [`tdb2tt`] - It is same as `tdb2tt_dt`, except that the argument and the result are [`str`] and [`String`].
+pub fn tdb2tt_dt(datetime: &NaiveDateTime, model: TdbModel) -> NaiveDateTime {
+    // TDB - TT changes so slowly that one correction with the estimated TT is enough.
+    let tt = *datetime - model.diff(datetime);
+    *datetime - model.diff(&tt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testmod;
+    use rstest::*;
+
+    const DT_FMT: &str = "%Y-%m-%dT%H:%M:%S%.9f";
+
+    #[rstest]
+    #[case(
+        "fairhead-bretagnon-truncated",
+        Some(TdbModel::FairheadBretagnonTruncated),
+        None
+    )]
+    #[case("Simplified", Some(TdbModel::Simplified), None)]
+    #[case("fb", None, Some(Error::UnknownTdbModelError("fb".to_string())))]
+    fn test_from_str(
+        #[case] name: &str,
+        #[case] expected_ok: Option<TdbModel>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok, expected_err);
+
+        assert_eq!(name.parse::<TdbModel>(), expected);
+    }
+
+    #[rstest]
+    // iauDtdb of SOFA gives -0.0012804 s at this moment, including the topocentric terms.
+    #[case(TdbModel::FairheadBretagnonTruncated, "1992-11-13T02:57:07.200000000", -0.001_280_0)]
+    #[case(TdbModel::Simplified, "1992-11-13T02:57:07.200000000", -0.001_289_2)]
+    #[case(TdbModel::FairheadBretagnonTruncated, "2000-01-01T12:00:00.000000000", -0.000_099_1)]
+    fn test_diff_seconds(#[case] model: TdbModel, #[case] tt: &str, #[case] expected: f64) {
+        let tt = NaiveDateTime::parse_from_str(tt, DT_FMT).unwrap();
+
+        let diff = model.diff_seconds(&tt);
+
+        assert!((diff - expected).abs() < 1e-7, "{} != {}", diff, expected);
+    }
+
+    #[rstest]
+    // The documented error of each model against iauDtdb of SOFA, which gives -0.001280368005937 s at this moment;
+    // its topocentric terms amount to about -0.4 microseconds here, which the models do not include.
+    #[case(TdbModel::FairheadBretagnonTruncated, 5e-6)]
+    #[case(TdbModel::Simplified, 40e-6)]
+    fn test_diff_seconds_accuracy(#[case] model: TdbModel, #[case] bound: f64) {
+        let tt = NaiveDateTime::parse_from_str("1992-11-13T02:57:07.200000000", DT_FMT).unwrap();
+        let reference = -0.001_280_368_005_937;
+
+        let diff = model.diff_seconds(&tt);
+
+        assert!(
+            (diff - reference).abs() < bound,
+            "{} is not within {} of {}",
+            diff,
+            bound,
+            reference
+        );
+    }
+
+    #[rstest]
+    #[case(TdbModel::FairheadBretagnonTruncated, "2017-01-01T12:00:00.000000000")]
+    #[case(TdbModel::Simplified, "2017-07-01T00:00:00.123456789")]
+    #[case(TdbModel::FairheadBretagnonTruncated, "1850-03-01T00:00:00.000000000")]
+    fn test_round_trip(#[case] model: TdbModel, #[case] tt: &str) {
+        let tdb = tt2tdb(tt, model, DT_FMT).unwrap();
+
+        assert_ne!(tdb, tt);
+        assert_eq!(tdb2tt(&tdb, model, DT_FMT), Ok(tt.to_string()));
+    }
+}
@@ -3,8 +3,24 @@ use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use tempfile::{Builder, TempDir};
 
+/// A part of the USNO tai-utc.dat; it contains the era of "rubber seconds" and its end.
+pub const TAI_UTC_DAT: &[&str] = &[
+    " 1961 JAN  1 =JD 2437300.5  TAI-UTC=   1.4228180 S + (MJD - 37300.) X 0.001296 S",
+    " 1961 AUG  1 =JD 2437512.5  TAI-UTC=   1.3728180 S + (MJD - 37300.) X 0.001296 S",
+    " 1968 FEB  1 =JD 2439887.5  TAI-UTC=   4.2131700 S + (MJD - 39126.) X 0.002592 S",
+    " 1972 JAN  1 =JD 2441317.5  TAI-UTC=  10.0       S + (MJD - 41317.) X 0.0      S",
+];
+
+/// A part of the IERS finals2000A.all; it contains the leap second at 2016-12-31.
+pub const FINALS2000A: &[&str] = &[
+    "161230 57752.00 I  0.048778 0.000031  0.266532 0.000030  I-0.4059124 0.0000088  1.2071 0.0069  I   -93.812    0.300    -3.847    0.300",
+    "161231 57753.00 I  0.048778 0.000031  0.266532 0.000030  I-0.4067321 0.0000088  1.2071 0.0069  I   -93.812    0.300    -3.847    0.300",
+    "17 1 1 57754.00 I  0.048778 0.000031  0.266532 0.000030  I 0.5925650 0.0000088  1.2071 0.0069  I   -93.812    0.300    -3.847    0.300",
+    "17 1 2 57755.00 I  0.048778 0.000031  0.266532 0.000030  I 0.5918034 0.0000088  1.2071 0.0069  I   -93.812    0.300    -3.847    0.300",
+];
+
 pub fn tmp_dir(prefix: Option<&str>) -> io::Result<TempDir> {
-    return Builder::new().prefix(prefix.unwrap_or("")).tempdir();
+    Builder::new().prefix(prefix.unwrap_or("")).tempdir()
 }
 
 pub fn tmp_tai_utc_table<P: AsRef<Path>>(dir: &P, lines: &[&str]) -> io::Result<PathBuf> {
@@ -16,7 +32,7 @@ pub fn tmp_tai_utc_table<P: AsRef<Path>>(dir: &P, lines: &[&str]) -> io::Result<
         writeln!(table_file, "{}", line)?;
     }
 
-    return Ok(table_file_path);
+    Ok(table_file_path)
 }
 
 pub fn tmp_text_file<P: AsRef<Path>>(dir: &P, name: &str, lines: &[&str]) -> io::Result<PathBuf> {
@@ -28,7 +44,7 @@ pub fn tmp_text_file<P: AsRef<Path>>(dir: &P, name: &str, lines: &[&str]) -> io:
         writeln!(file, "{}", line)?;
     }
 
-    return Ok(file_path);
+    Ok(file_path)
 }
 
 /// (Option<T>, Option<E>) -> Result<T, E>
@@ -1,4 +1,5 @@
 use crate::error::Error;
+use crate::DtFmts;
 use chrono::{Duration, NaiveDateTime};
 
 const D_TT_TAI_MS: i64 = 32184;
@@ -9,7 +10,7 @@ const D_TT_TAI_MS: i64 = 32184;
 ///
 /// # Arguments
 /// * `datetime` - Datetime in TT.
-/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime`
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
 ///
 /// # Returns
 /// Returns the datetime in TAI.
@@ -26,11 +27,12 @@ const D_TT_TAI_MS: i64 = 32184;
 ///
 /// assert_eq!(tai, Ok("2017-01-01T11:59:27.816".to_string()));
 /// ```
-pub fn tt2tai(datetime: &str, dt_fmt: &str) -> Result<String, Error> {
-    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt)
+pub fn tt2tai<'a>(datetime: &str, dt_fmt: impl Into<DtFmts<'a>>) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
         .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
     let tai = tt2tai_dt(&datetime);
-    Ok(tai.format(dt_fmt).to_string())
+    Ok(tai.format(dt_fmt.format).to_string())
 }
 
 /// Convert datetime
@@ -48,7 +50,7 @@ pub fn tt2tai(datetime: &str, dt_fmt: &str) -> Result<String, Error> {
 /// # See also
 /// * [`tt2tai`] - It is same as `tt2tai_dt`, except that the argument and the result are [`str`] and [`String`].
 pub fn tt2tai_dt(datetime: &NaiveDateTime) -> NaiveDateTime {
-    return datetime.clone() - Duration::milliseconds(D_TT_TAI_MS);
+    *datetime - Duration::milliseconds(D_TT_TAI_MS)
 }
 
 /// Convert datetime
@@ -57,7 +59,7 @@ pub fn tt2tai_dt(datetime: &NaiveDateTime) -> NaiveDateTime {
 ///
 /// # Arguments
 /// * `datetime` - Datetime in TAI.
-/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime`
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
 ///
 /// # Returns
 /// Returns the datetime in TT.
@@ -74,11 +76,12 @@ pub fn tt2tai_dt(datetime: &NaiveDateTime) -> NaiveDateTime {
 ///
 /// assert_eq!(tai, Ok("2017-01-01T12:00:32.184".to_string()));
 /// ```
-pub fn tai2tt(datetime: &str, dt_fmt: &str) -> Result<String, Error> {
-    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt)
+pub fn tai2tt<'a>(datetime: &str, dt_fmt: impl Into<DtFmts<'a>>) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
         .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
     let tt = tai2tt_dt(&datetime);
-    Ok(tt.format(dt_fmt).to_string())
+    Ok(tt.format(dt_fmt.format).to_string())
 }
 
 /// Convert datetime
@@ -96,5 +99,5 @@ pub fn tai2tt(datetime: &str, dt_fmt: &str) -> Result<String, Error> {
 /// # See also
 /// * [`tai2tt`] - It is same as `tai2tt_dt`, except that the argument and the result are [`str`] and [`String`].
 pub fn tai2tt_dt(datetime: &NaiveDateTime) -> NaiveDateTime {
-    return datetime.clone() + Duration::milliseconds(D_TT_TAI_MS);
+    *datetime + Duration::milliseconds(D_TT_TAI_MS)
 }
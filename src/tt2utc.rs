@@ -1,22 +1,24 @@
 use crate::convtbl::UtcTaiTable;
 use crate::error::Error;
-use crate::tai2utc::tai2utc_dt;
+use crate::tai2utc::tai2utc_label;
 use crate::tt::tt2tai_dt;
+use crate::DtFmts;
+use crate::UtcLabel;
 use chrono::NaiveDateTime;
 
 /// Convert datetime
 /// from [TT](https://en.wikipedia.org/wiki/Terrestrial_Time)
-/// to [UTC](https://en.wikipedia.org/wiki/Coordinated_Universal_Time).
+/// to the label of [UTC](https://en.wikipedia.org/wiki/Coordinated_Universal_Time).
 ///
 /// This function takes leap seconds into account along the argument `utc_tai_table`.
 ///
 /// # Arguments
 /// * `datetime` - Datetime in TT.
 /// * `utc_tai_table` - The conversion table of UTC - TAI
-/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime`
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
 ///
 /// # Returns
-/// Returns the datetime in UTC.
+/// Returns the label of UTC; it may have the second 61 or more if two or more leap seconds are inserted at once.
 ///
 /// Returns [`Error`](crate::error::Error) if it fail to convert.
 ///
@@ -39,16 +41,21 @@ use chrono::NaiveDateTime;
 ///
 /// # See also
 /// * [`tt2utc`](../tt2utc/index.html) (Binary crate) - The executable program which do same conversion.
-pub fn tt2utc(datetime: &str, utc_tai_table: &UtcTaiTable, dt_fmt: &str) -> Result<String, Error> {
-    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt)
+pub fn tt2utc<'a>(
+    datetime: &str,
+    utc_tai_table: &UtcTaiTable,
+    dt_fmt: impl Into<DtFmts<'a>>,
+) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
         .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
-    let tai = tt2utc_dt(&datetime, utc_tai_table)?;
-    Ok(tai.format(dt_fmt).to_string())
+    let utc = tt2utc_label(&datetime, utc_tai_table)?;
+    Ok(utc.format(dt_fmt.format))
 }
 
 /// Convert datetime
 /// from [TT](https://en.wikipedia.org/wiki/Terrestrial_Time)
-/// to [UTC](https://en.wikipedia.org/wiki/Coordinated_Universal_Time).
+/// to the label of [UTC](https://en.wikipedia.org/wiki/Coordinated_Universal_Time).
 ///
 /// This function takes leap seconds into account along the argument `utc_tai_table`.
 ///
@@ -57,20 +64,20 @@ pub fn tt2utc(datetime: &str, utc_tai_table: &UtcTaiTable, dt_fmt: &str) -> Resu
 /// * `utc_tai_table` - The conversion table of UTC - TAI
 ///
 /// # Returns
-/// Returns the datetime in UTC.
+/// Returns the label of UTC; it may have the second 61 or more if two or more leap seconds are inserted at once.
 ///
 /// Returns [`Error`](crate::error::Error) if it fail to convert.
 ///
 /// # See also
-/// * [`tt2utc`] - It is same as `tt2utc_dt`, except that the argument and the result are [`str`] and [`String`].
+/// * [`tt2utc`] - It is same as `tt2utc_label`, except that the argument and the result are [`str`] and [`String`].
 /// * [`tt2utc`](../tt2utc/index.html) (Binary crate) - The executable program which do same conversion.
-pub fn tt2utc_dt(
+pub fn tt2utc_label(
     datetime: &NaiveDateTime,
     utc_tai_table: &UtcTaiTable,
-) -> Result<NaiveDateTime, Error> {
+) -> Result<UtcLabel, Error> {
     let tai = tt2tai_dt(datetime);
 
-    match tai2utc_dt(&tai, utc_tai_table) {
+    match tai2utc_label(&tai, utc_tai_table) {
         Err(Error::DatetimeTooLowError(_)) => {
             // 多段階で変換を行う場合、中間の日時文字列がエラーメッセージに使われている場合があるため、入力された日時文字列に置き換える。
             Err(Error::DatetimeTooLowError(datetime.to_string()))
@@ -108,8 +115,8 @@ mod tests {
     #[case("2018-01-01T00:01:08.184", Some("2018-01-01T00:00:00.000"), None)]
     // うるう秒が2秒挿入される瞬間のテスト
     #[case("2019-01-01T00:01:07.184", Some("2018-12-31T23:59:59.000"), None)]
-    // #[case("2019-01-01T00:01:08.184", Some("2018-12-31T23:59:60.000"), None)]
-    // #[case("2019-01-01T00:01:09.184", Some("2018-12-31T23:59:61.000"), None)]
+    #[case("2019-01-01T00:01:08.184", Some("2018-12-31T23:59:60.000"), None)]
+    #[case("2019-01-01T00:01:09.184", Some("2018-12-31T23:59:61.000"), None)]
     #[case("2019-01-01T00:01:10.184", Some("2019-01-01T00:00:00.000"), None)]
     // うるう秒が2秒削除される瞬間のテスト
     #[case("2020-01-01T00:01:07.184", Some("2019-12-31T23:59:57.000"), None)]
@@ -127,27 +134,32 @@ mod tests {
             DiffTaiUtc {
                 datetime: NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 0),
                 diff_seconds: 36,
+                drift: None,
             },
             DiffTaiUtc {
                 datetime: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0),
                 diff_seconds: 37,
+                drift: None,
             },
             DiffTaiUtc {
                 datetime: NaiveDate::from_ymd(2018, 1, 1).and_hms(0, 0, 0),
                 diff_seconds: 36,
+                drift: None,
             },
             DiffTaiUtc {
                 datetime: NaiveDate::from_ymd(2019, 1, 1).and_hms(0, 0, 0),
                 diff_seconds: 38,
+                drift: None,
             },
             DiffTaiUtc {
                 datetime: NaiveDate::from_ymd(2020, 1, 1).and_hms(0, 0, 0),
                 diff_seconds: 36,
+                drift: None,
             },
         ]
         .into();
         let utc_tai_table = From::from(&tai_utc_table);
-        let utc = tt2utc(&tt, &utc_tai_table, DT_FMT);
+        let utc = tt2utc(tt, &utc_tai_table, DT_FMT);
 
         assert_eq!(utc, expected);
     }
@@ -202,10 +214,11 @@ mod tests {
         let tai_utc_table: TaiUtcTable = vec![DiffTaiUtc {
             datetime: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0),
             diff_seconds: 37,
+            drift: None,
         }]
         .into();
         let utc_tai_table = From::from(&tai_utc_table);
-        let utc = tt2utc(&tt, &utc_tai_table, dt_fmt);
+        let utc = tt2utc(tt, &utc_tai_table, dt_fmt);
 
         assert_eq!(utc, expected);
     }
@@ -0,0 +1,304 @@
+use crate::convtbl::{DiffUt1Utc, TaiUtcTable, Ut1UtcTable};
+use crate::error::Error;
+use crate::utc2tai::utc2tai_dt;
+use crate::DtFmts;
+use chrono::{Duration, NaiveDateTime};
+
+/// Convert datetime
+/// from [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time)
+/// to [UT1](https://en.wikipedia.org/wiki/Universal_Time).
+///
+/// UT1 - UTC is interpolated linearly between the daily values of `ut1_utc_table`.
+/// Since UT1 - UTC jumps at leap seconds, the interpolation is done on UT1 - TAI.
+///
+/// # Arguments
+/// * `datetime` - Datetime in TAI.
+/// * `tai_utc_table` - The conversion table of TAI - UTC
+/// * `ut1_utc_table` - The daily values of UT1 - UTC
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
+///
+/// # Returns
+/// Returns the datetime in UT1.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert;
+/// [`EopOutOfCoverageError`](crate::error::Error::EopOutOfCoverageError) if `ut1_utc_table` does not cover the datetime.
+///
+/// # Examples
+/// ```
+/// use convdate;
+/// use convdate::convtbl::{DiffUt1Utc, TaiUtcTable, Ut1UtcTable};
+/// use chrono::NaiveDate;
+///
+/// let tai_utc_table = TaiUtcTable::from_lines(vec!["2017-01-01T00:00:00 37"], "%Y-%m-%dT%H:%M:%S").unwrap();
+/// // Usually, lines read from the file are used as the argument of `from_finals2000a`.
+/// let ut1_utc_table = Ut1UtcTable::from(vec![
+///     DiffUt1Utc { date: NaiveDate::from_ymd(2017, 1, 1), diff_nanos: 592_565_000 },
+///     DiffUt1Utc { date: NaiveDate::from_ymd(2017, 1, 2), diff_nanos: 591_803_000 },
+/// ]);
+///
+/// let ut1 = convdate::tai2ut1(
+///     "2017-01-01T12:00:37.000",
+///     &tai_utc_table,
+///     &ut1_utc_table,
+///     "%Y-%m-%dT%H:%M:%S%.3f");
+///
+/// assert_eq!(ut1, Ok("2017-01-01T12:00:00.592".to_string()));
+/// ```
+pub fn tai2ut1<'a>(
+    datetime: &str,
+    tai_utc_table: &TaiUtcTable,
+    ut1_utc_table: &Ut1UtcTable,
+    dt_fmt: impl Into<DtFmts<'a>>,
+) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
+        .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
+    let ut1 = tai2ut1_dt(&datetime, tai_utc_table, ut1_utc_table)?;
+    Ok(ut1.format(dt_fmt.format).to_string())
+}
+
+/// Convert datetime
+/// from [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time)
+/// to [UT1](https://en.wikipedia.org/wiki/Universal_Time).
+///
+/// UT1 - UTC is interpolated linearly between the daily values of `ut1_utc_table`.
+/// Since UT1 - UTC jumps at leap seconds, the interpolation is done on UT1 - TAI.
+///
+/// # Arguments
+/// * `datetime` - Datetime in TAI.
+/// * `tai_utc_table` - The conversion table of TAI - UTC
+/// * `ut1_utc_table` - The daily values of UT1 - UTC
+///
+/// # Returns
+/// Returns the datetime in UT1.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert;
+/// [`EopOutOfCoverageError`](crate::error::Error::EopOutOfCoverageError) if `ut1_utc_table` does not cover the datetime.
+///
+/// # See also
+/// * [`tai2ut1`] - It is same as `tai2ut1_dt`, except that the argument and the result are [`str`] and [`String`].
+pub fn tai2ut1_dt(
+    datetime: &NaiveDateTime,
+    tai_utc_table: &TaiUtcTable,
+    ut1_utc_table: &Ut1UtcTable,
+) -> Result<NaiveDateTime, Error> {
+    Ok(*datetime + ut1_tai_at(datetime, tai_utc_table, ut1_utc_table)?)
+}
+
+/// Convert datetime
+/// from [UT1](https://en.wikipedia.org/wiki/Universal_Time)
+/// to [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time).
+///
+/// It is the inverse of [`tai2ut1`].
+///
+/// # Arguments
+/// * `datetime` - Datetime in UT1.
+/// * `tai_utc_table` - The conversion table of TAI - UTC
+/// * `ut1_utc_table` - The daily values of UT1 - UTC
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
+///
+/// # Returns
+/// Returns the datetime in TAI.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert;
+/// [`EopOutOfCoverageError`](crate::error::Error::EopOutOfCoverageError) if `ut1_utc_table` does not cover the datetime.
+///
+/// # Examples
+/// ```
+/// use convdate;
+/// use convdate::convtbl::{DiffUt1Utc, TaiUtcTable, Ut1UtcTable};
+/// use chrono::NaiveDate;
+///
+/// let tai_utc_table = TaiUtcTable::from_lines(vec!["2017-01-01T00:00:00 37"], "%Y-%m-%dT%H:%M:%S").unwrap();
+/// // Usually, lines read from the file are used as the argument of `from_finals2000a`.
+/// let ut1_utc_table = Ut1UtcTable::from(vec![
+///     DiffUt1Utc { date: NaiveDate::from_ymd(2017, 1, 1), diff_nanos: 592_565_000 },
+///     DiffUt1Utc { date: NaiveDate::from_ymd(2017, 1, 2), diff_nanos: 591_803_000 },
+/// ]);
+///
+/// let tai = convdate::ut12tai(
+///     "2017-01-01T12:00:00.593",
+///     &tai_utc_table,
+///     &ut1_utc_table,
+///     "%Y-%m-%dT%H:%M:%S%.3f");
+///
+/// assert_eq!(tai, Ok("2017-01-01T12:00:37.000".to_string()));
+/// ```
+pub fn ut12tai<'a>(
+    datetime: &str,
+    tai_utc_table: &TaiUtcTable,
+    ut1_utc_table: &Ut1UtcTable,
+    dt_fmt: impl Into<DtFmts<'a>>,
+) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt.parse)
+        .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
+    let tai = ut12tai_dt(&datetime, tai_utc_table, ut1_utc_table)?;
+    Ok(tai.format(dt_fmt.format).to_string())
+}
+
+/// Convert datetime
+/// from [UT1](https://en.wikipedia.org/wiki/Universal_Time)
+/// to [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time).
+///
+/// It is the inverse of [`tai2ut1_dt`].
+///
+/// # Arguments
+/// * `datetime` - Datetime in UT1.
+/// * `tai_utc_table` - The conversion table of TAI - UTC
+/// * `ut1_utc_table` - The daily values of UT1 - UTC
+///
+/// # Returns
+/// Returns the datetime in TAI.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert;
+/// [`EopOutOfCoverageError`](crate::error::Error::EopOutOfCoverageError) if `ut1_utc_table` does not cover the datetime.
+///
+/// # See also
+/// * [`ut12tai`] - It is same as `ut12tai_dt`, except that the argument and the result are [`str`] and [`String`].
+pub fn ut12tai_dt(
+    datetime: &NaiveDateTime,
+    tai_utc_table: &TaiUtcTable,
+    ut1_utc_table: &Ut1UtcTable,
+) -> Result<NaiveDateTime, Error> {
+    let out_of_coverage = || Error::EopOutOfCoverageError(datetime.to_string());
+
+    // The first estimation uses UT1 - TAI at the beginning of the day.
+    let row_index = ut1_utc_table
+        .partition_point(|row| row.date <= datetime.date())
+        .checked_sub(1)
+        .ok_or_else(out_of_coverage)?;
+    let (_, ut1_tai) = knot(&ut1_utc_table[row_index], tai_utc_table)?;
+    let mut tai = *datetime - Duration::nanoseconds(ut1_tai as i64);
+
+    // UT1 - TAI changes so slowly that a few corrections are enough.
+    for _ in 0..3 {
+        tai = match ut1_tai_at(&tai, tai_utc_table, ut1_utc_table) {
+            Ok(ut1_tai) => *datetime - ut1_tai,
+            Err(Error::EopOutOfCoverageError(_)) => Err(out_of_coverage())?,
+            Err(e) => Err(e)?,
+        };
+    }
+    Ok(tai)
+}
+
+/// The TAI datetime of the row and UT1 - TAI (nanoseconds) at the datetime
+fn knot(row: &DiffUt1Utc, tai_utc_table: &TaiUtcTable) -> Result<(NaiveDateTime, i128), Error> {
+    let utc = row.date.and_hms(0, 0, 0);
+    let tai = utc2tai_dt(&utc, tai_utc_table)?;
+    let tai_utc = (tai - utc).num_nanoseconds().unwrap();
+    Ok((tai, i128::from(row.diff_nanos) - i128::from(tai_utc)))
+}
+
+/// Calculate UT1 - TAI at the TAI datetime.
+fn ut1_tai_at(
+    datetime: &NaiveDateTime,
+    tai_utc_table: &TaiUtcTable,
+    ut1_utc_table: &Ut1UtcTable,
+) -> Result<Duration, Error> {
+    let out_of_coverage = || Error::EopOutOfCoverageError(datetime.to_string());
+
+    // 二分探索; TAI is ahead of UTC, so the row of the day in TAI may begin after the datetime.
+    let mut index = ut1_utc_table.partition_point(|row| row.date <= datetime.date());
+    let (start, start_value) = loop {
+        index = index.checked_sub(1).ok_or_else(out_of_coverage)?;
+        let (start, start_value) = knot(&ut1_utc_table[index], tai_utc_table)?;
+        if start <= *datetime {
+            break (start, start_value);
+        }
+    };
+    if start == *datetime {
+        return Ok(Duration::nanoseconds(start_value as i64));
+    }
+
+    let (end, end_value) = match ut1_utc_table.get(index + 1) {
+        Some(row) => knot(row, tai_utc_table)?,
+        None => Err(out_of_coverage())?,
+    };
+    let elapsed = i128::from((*datetime - start).num_nanoseconds().unwrap());
+    let span = i128::from((end - start).num_nanoseconds().unwrap());
+    // 四捨五入して ut12tai で元に戻るようにする
+    let value = start_value + ((end_value - start_value) * elapsed * 2 + span).div_euclid(span * 2);
+    Ok(Duration::nanoseconds(value as i64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testmod;
+    use rstest::*;
+
+    const DT_FMT: &str = "%Y-%m-%dT%H:%M:%S%.9f";
+
+    fn tables() -> (TaiUtcTable, Ut1UtcTable) {
+        let tai_utc_table = TaiUtcTable::from_lines(
+            vec!["20150701000000 36", "20170101000000 37"],
+            "%Y%m%d%H%M%S",
+        )
+        .unwrap();
+        let ut1_utc_table = Ut1UtcTable::from_finals2000a(testmod::FINALS2000A).unwrap();
+        (tai_utc_table, ut1_utc_table)
+    }
+
+    #[rstest]
+    // the daily values
+    #[case(
+        "2016-12-31T00:00:36.000000000",
+        Some("2016-12-30T23:59:59.593267900"),
+        None
+    )]
+    #[case(
+        "2017-01-01T00:00:37.000000000",
+        Some("2017-01-01T00:00:00.592565000"),
+        None
+    )]
+    // interpolation over the leap second; the day has 86401 seconds.
+    #[case(
+        "2016-12-31T12:00:36.000000000",
+        Some("2016-12-31T11:59:59.592916454"),
+        None
+    )]
+    #[case(
+        "2017-01-01T00:00:36.500000000",
+        Some("2017-01-01T00:00:00.092565004"),
+        None
+    )]
+    // the end of coverage
+    #[case(
+        "2017-01-02T00:00:37.000000000",
+        Some("2017-01-02T00:00:00.591803400"),
+        None
+    )]
+    // out of coverage
+    #[case("2017-01-02T00:00:37.000000001", None, Some(Error::EopOutOfCoverageError("2017-01-02 00:00:37.000000001".to_string())))]
+    #[case("2016-12-30T00:00:35.999999999", None, Some(Error::EopOutOfCoverageError("2016-12-30 00:00:35.999999999".to_string())))]
+    fn test_tai2ut1(
+        #[case] tai: &str,
+        #[case] expected_ok: Option<&str>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok.map(ToString::to_string), expected_err);
+        let (tai_utc_table, ut1_utc_table) = tables();
+
+        let ut1 = tai2ut1(tai, &tai_utc_table, &ut1_utc_table, DT_FMT);
+
+        assert_eq!(ut1, expected);
+        if let Ok(ut1) = ut1 {
+            assert_eq!(
+                ut12tai(&ut1, &tai_utc_table, &ut1_utc_table, DT_FMT),
+                Ok(tai.to_string())
+            );
+        }
+    }
+
+    #[rstest]
+    #[case("2016-12-29T23:59:59.000000000", Some(Error::EopOutOfCoverageError("2016-12-29 23:59:59".to_string())))]
+    #[case("2017-01-02T00:00:01.000000000", Some(Error::EopOutOfCoverageError("2017-01-02 00:00:01".to_string())))]
+    fn test_ut12tai_out_of_coverage(#[case] ut1: &str, #[case] expected_err: Option<Error>) {
+        let (tai_utc_table, ut1_utc_table) = tables();
+
+        let tai = ut12tai(ut1, &tai_utc_table, &ut1_utc_table, DT_FMT);
+
+        assert_eq!(tai, Err(expected_err.unwrap()));
+    }
+}
@@ -0,0 +1,154 @@
+use crate::convtbl::TaiUtcTable;
+use crate::error::Error;
+use crate::gps::tai2gps_dt;
+use crate::utc2tai::utc2tai_label;
+use crate::DtFmts;
+use crate::UtcLabel;
+use chrono::NaiveDateTime;
+
+/// Convert datetime
+/// from [UTC](https://en.wikipedia.org/wiki/Coordinated_Universal_Time)
+/// to [GPS time](https://en.wikipedia.org/wiki/Global_Positioning_System#Timekeeping).
+///
+/// This function takes leap seconds into account along the argument `tai_utc_table`.
+///
+/// # Arguments
+/// * `datetime` - Label of UTC, which may have the second 61 or more.
+/// * `tai_utc_table` - The conversion table of TAI - UTC
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
+///
+/// # Returns
+/// Returns the datetime in GPS time.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # Examples
+/// ```
+/// use convdate;
+/// use convdate::convtbl::TaiUtcTable;
+///
+/// // Usually, lines read from the file are used as the argument of `from_lines`.
+/// let tai_utc_table = TaiUtcTable::from_lines(vec!["2017-01-01T00:00:00 37"], "%Y-%m-%dT%H:%M:%S").unwrap();
+///
+/// let gps = convdate::utc2gps(
+///     "2017-01-01T12:00:00.000",
+///     &tai_utc_table,
+///     "%Y-%m-%dT%H:%M:%S%.3f");
+///
+/// assert_eq!(gps, Ok("2017-01-01T12:00:18.000".to_string()));
+/// ```
+///
+/// # See also
+/// * [`utc2gps`](../utc2gps/index.html) (Binary crate) - The executable program which do same conversion.
+pub fn utc2gps<'a>(
+    datetime: &str,
+    tai_utc_table: &TaiUtcTable,
+    dt_fmt: impl Into<DtFmts<'a>>,
+) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = UtcLabel::parse_from_str(datetime, dt_fmt.parse)?;
+    let gps = utc2gps_label(&datetime, tai_utc_table)?;
+    Ok(gps.format(dt_fmt.format).to_string())
+}
+
+/// Convert datetime
+/// from [UTC](https://en.wikipedia.org/wiki/Coordinated_Universal_Time)
+/// to [GPS time](https://en.wikipedia.org/wiki/Global_Positioning_System#Timekeeping).
+///
+/// This function takes leap seconds into account along the argument `tai_utc_table`.
+///
+/// # Arguments
+/// * `datetime` - Label of UTC, which may have the second 61 or more.
+/// * `tai_utc_table` - The conversion table of TAI - UTC
+///
+/// # Returns
+/// Returns the datetime in GPS time.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert.
+///
+/// # See also
+/// * [`utc2gps`] - It is same as `utc2gps_label`, except that the argument and the result are [`str`] and [`String`].
+/// * [`utc2gps`](../utc2gps/index.html) (Binary crate) - The executable program which do same conversion.
+pub fn utc2gps_label(
+    datetime: &UtcLabel,
+    tai_utc_table: &TaiUtcTable,
+) -> Result<NaiveDateTime, Error> {
+    let tai = utc2tai_label(datetime, tai_utc_table)?;
+    Ok(tai2gps_dt(&tai))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convtbl::DiffTaiUtc;
+    use crate::testmod;
+    use chrono::NaiveDate;
+    use rstest::*;
+
+    const DT_FMT: &str = "%Y-%m-%dT%H:%M:%S%.3f";
+
+    #[rstest]
+    // Error when the input datetime is too low.
+    #[case("2015-06-30T23:59:60.999", None, Some(Error::DatetimeTooLowError("2015-06-30 23:59:60.999".to_string())))]
+    #[case("2015-07-01T00:00:00.000", Some("2015-07-01T00:00:17.000"), None)]
+    // regular cases
+    #[case("2017-01-02T11:22:33.000", Some("2017-01-02T11:22:51.000"), None)]
+    #[case("2017-01-02T11:22:33.123", Some("2017-01-02T11:22:51.123"), None)]
+    // うるう秒が挿入される瞬間のテスト
+    #[case("2016-12-31T23:59:59.000", Some("2017-01-01T00:00:16.000"), None)]
+    #[case("2016-12-31T23:59:60.000", Some("2017-01-01T00:00:17.000"), None)]
+    #[case("2016-12-31T23:59:60.123", Some("2017-01-01T00:00:17.123"), None)]
+    #[case("2017-01-01T00:00:00.000", Some("2017-01-01T00:00:18.000"), None)]
+    // うるう秒が削除される瞬間のテスト
+    #[case("2017-12-31T23:59:58.000", Some("2018-01-01T00:00:16.000"), None)]
+    #[case("2017-12-31T23:59:58.123", Some("2018-01-01T00:00:16.123"), None)]
+    #[case("2018-01-01T00:00:00.000", Some("2018-01-01T00:00:17.000"), None)]
+    // うるう秒が2秒挿入される瞬間のテスト
+    #[case("2018-12-31T23:59:59.000", Some("2019-01-01T00:00:16.000"), None)]
+    #[case("2018-12-31T23:59:60.000", Some("2019-01-01T00:00:17.000"), None)]
+    #[case("2018-12-31T23:59:61.000", Some("2019-01-01T00:00:18.000"), None)]
+    #[case("2019-01-01T00:00:00.000", Some("2019-01-01T00:00:19.000"), None)]
+    // うるう秒が2秒削除される瞬間のテスト
+    #[case("2019-12-31T23:59:57.000", Some("2020-01-01T00:00:16.000"), None)]
+    #[case("2020-01-01T00:00:00.000", Some("2020-01-01T00:00:17.000"), None)]
+    // Error when the input datetime is illegal format.
+    #[case("2019-12-31 23:59:57.000", None, Some(Error::DatetimeParseError(utc.to_string())))]
+    fn test_utc2gps(
+        #[case] utc: &str,
+        #[case] expected_ok: Option<&str>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok.map(ToString::to_string), expected_err);
+
+        let tai_utc_table = vec![
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 0),
+                diff_seconds: 36,
+                drift: None,
+            },
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0),
+                diff_seconds: 37,
+                drift: None,
+            },
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2018, 1, 1).and_hms(0, 0, 0),
+                diff_seconds: 36,
+                drift: None,
+            },
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2019, 1, 1).and_hms(0, 0, 0),
+                diff_seconds: 38,
+                drift: None,
+            },
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2020, 1, 1).and_hms(0, 0, 0),
+                diff_seconds: 36,
+                drift: None,
+            },
+        ];
+        let gps = utc2gps(utc, &tai_utc_table.into(), DT_FMT);
+
+        assert_eq!(gps, expected);
+    }
+}
@@ -1,5 +1,5 @@
-use crate::convtbl::TaiUtcTable;
-use crate::{error::Error, normalize_leap};
+use crate::convtbl::{DiffTaiUtc, TaiUtcTable};
+use crate::{error::Error, DtFmts, UtcLabel};
 use chrono::{Duration, NaiveDateTime};
 
 /// Convert datetime
@@ -11,7 +11,7 @@ use chrono::{Duration, NaiveDateTime};
 /// # Arguments
 /// * `datetime` - Datetime in UTC.
 /// * `tai_utc_table` - The conversion table of TAI - UTC
-/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime`
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
 ///
 /// # Returns
 /// Returns the datetime in TAI.
@@ -36,11 +36,15 @@ use chrono::{Duration, NaiveDateTime};
 ///
 /// # See also
 /// * [`utc2tai`](../utc2tai/index.html) (Binary crate) - The executable program which do same conversion.
-pub fn utc2tai(datetime: &str, tai_utc_table: &TaiUtcTable, dt_fmt: &str) -> Result<String, Error> {
-    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt)
-        .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
-    let tai = utc2tai_dt(&datetime, tai_utc_table)?;
-    Ok(tai.format(dt_fmt).to_string())
+pub fn utc2tai<'a>(
+    datetime: &str,
+    tai_utc_table: &TaiUtcTable,
+    dt_fmt: impl Into<DtFmts<'a>>,
+) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = UtcLabel::parse_from_str(datetime, dt_fmt.parse)?;
+    let tai = utc2tai_label(&datetime, tai_utc_table)?;
+    Ok(tai.format(dt_fmt.format).to_string())
 }
 
 /// Convert datetime
@@ -65,17 +69,136 @@ pub fn utc2tai_dt(
     datetime: &NaiveDateTime,
     tai_utc_table: &TaiUtcTable,
 ) -> Result<NaiveDateTime, Error> {
-    let datetime_nm = normalize_leap(datetime);
+    utc2tai_label(&UtcLabel::from(datetime), tai_utc_table)
+}
 
-    return tai_utc_table
-        .pick_dominant_row(datetime)
-        .map(|diff_tai_utc| datetime_nm + Duration::seconds(diff_tai_utc.diff_seconds));
+/// Convert the label of [UTC](https://en.wikipedia.org/wiki/Coordinated_Universal_Time)
+/// to [TAI](https://en.wikipedia.org/wiki/International_Atomic_Time).
+///
+/// Unlike [`utc2tai_dt`], the label can have the second 61 or more
+/// when two or more leap seconds are inserted at once.
+///
+/// # Arguments
+/// * `label` - Label of UTC.
+/// * `tai_utc_table` - The conversion table of TAI - UTC
+///
+/// # Returns
+/// Returns the datetime in TAI.
+///
+/// Returns [`Error`](crate::error::Error) if it fail to convert,
+/// or if the label does not exist in UTC; for example, `23:59:59` of the day when a leap second is removed.
+pub fn utc2tai_label(
+    label: &UtcLabel,
+    tai_utc_table: &TaiUtcTable,
+) -> Result<NaiveDateTime, Error> {
+    let datetime_nm = label.normalized();
+    let diff_tai_utc = tai_utc_table
+        .pick_dominant_row(&label.to_naive_saturating())
+        .map_err(|e| match e {
+            Error::DatetimeTooLowError(_) => Error::DatetimeTooLowError(label.to_string()),
+            e => e,
+        })?;
+
+    if !exists(label, diff_tai_utc, tai_utc_table, false) {
+        Err(Error::NonexistentUtcError(label.to_string()))?;
+    }
+    Ok(datetime_nm + diff_tai_utc.diff_at(&datetime_nm))
+}
+
+/// Check that the label with the second 60 or more is a leap second inserted by the TAI-UTC table.
+///
+/// [`utc2tai_label`] accepts any `:60` even if no leap second is inserted at the time; this function is used to refuse such a label.
+///
+/// # Arguments
+/// * `label` - Label of UTC.
+/// * `tai_utc_table` - The conversion table of TAI - UTC
+///
+/// # Returns
+/// Returns [`Error`](crate::error::Error) naming the nearest leap second if the label is not a leap second.
+/// The labels with the second 59 or less always pass.
+///
+/// # Examples
+/// ```
+/// use convdate::{check_leap_second, UtcLabel};
+/// use convdate::convtbl::TaiUtcTable;
+/// use convdate::error::Error;
+///
+/// let tai_utc_table = TaiUtcTable::from_lines(vec!["2015-07-01T00:00:00 36", "2017-01-01T00:00:00 37"], "%Y-%m-%dT%H:%M:%S").unwrap();
+/// let label = UtcLabel::parse_from_str("2016-06-30T23:59:60", "%Y-%m-%dT%H:%M:%S").unwrap();
+///
+/// assert_eq!(
+///     check_leap_second(&label, &tai_utc_table),
+///     Err(Error::NotLeapSecondError("2016-06-30 23:59:60".to_string(), "2016-12-31 23:59:60".to_string())));
+/// ```
+pub fn check_leap_second(label: &UtcLabel, tai_utc_table: &TaiUtcTable) -> Result<(), Error> {
+    if label.leap_seconds() == 0 {
+        return Ok(());
+    }
+    let diff_tai_utc = tai_utc_table
+        .pick_dominant_row(&label.to_naive_saturating())
+        .map_err(|e| match e {
+            Error::DatetimeTooLowError(_) => Error::DatetimeTooLowError(label.to_string()),
+            e => e,
+        })?;
+    if exists(label, diff_tai_utc, tai_utc_table, true) {
+        return Ok(());
+    }
+    match nearest_leap_second(label, tai_utc_table) {
+        Some(nearest) => Err(Error::NotLeapSecondError(
+            label.to_string(),
+            nearest.to_string(),
+        )),
+        None => Err(Error::NonexistentUtcError(label.to_string())),
+    }
+}
+
+/// Whether the label exists in UTC dominated by the row.
+///
+/// The labels just before the next row do not exist if the next row removes seconds,
+/// and the labels `:61` and after exist only if the next row inserts as many seconds.
+/// If `strict` is true, so does `:60`.
+fn exists(
+    label: &UtcLabel,
+    diff_tai_utc: &DiffTaiUtc,
+    tai_utc_table: &TaiUtcTable,
+    strict: bool,
+) -> bool {
+    let datetime_nm = label.normalized();
+    let min_leap_seconds = if strict { 1 } else { 2 };
+    let next = tai_utc_table
+        .iter()
+        .find(|next| diff_tai_utc.datetime < next.datetime);
+    match next {
+        None => label.leap_seconds() < min_leap_seconds,
+        Some(next) => {
+            // 次の行で挿入 (正) または削除 (負) される時間
+            let inserted = next.diff_at(&next.datetime) - diff_tai_utc.diff_at(&next.datetime);
+            if label.leap_seconds() >= min_leap_seconds {
+                label.end_of_minute() == next.datetime && datetime_nm < next.datetime + inserted
+            } else {
+                !(next.datetime + inserted <= datetime_nm && datetime_nm < next.datetime)
+            }
+        }
+    }
+}
+
+/// The first label of the leap second nearest to the label; for example, `2016-12-31 23:59:60`.
+fn nearest_leap_second(label: &UtcLabel, tai_utc_table: &TaiUtcTable) -> Option<UtcLabel> {
+    let datetime_nm = label.normalized();
+    tai_utc_table
+        .windows(2)
+        .filter(|rows| rows[1].diff_at(&rows[1].datetime) > rows[0].diff_at(&rows[1].datetime))
+        .map(|rows| rows[1].datetime)
+        .min_by_key(|datetime| (*datetime - datetime_nm).num_nanoseconds().map(i64::abs))
+        .map(|datetime| {
+            UtcLabel::from(&(datetime - Duration::minutes(1))).stretched(60_000_000_000)
+        })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::convtbl::DiffTaiUtc;
+    use crate::convtbl::{DiffTaiUtc, TaiUtcTable};
     use crate::testmod;
     use chrono::NaiveDate;
     use rstest::*;
@@ -97,15 +220,26 @@ mod tests {
     // うるう秒が削除される瞬間のテスト
     #[case("2017-12-31T23:59:58.000", Some("2018-01-01T00:00:35.000"), None)]
     #[case("2017-12-31T23:59:58.123", Some("2018-01-01T00:00:35.123"), None)]
+    #[case("2017-12-31T23:59:59.000", None, Some(Error::NonexistentUtcError("2017-12-31 23:59:59".to_string())))]
+    #[case("2017-12-31T23:59:59.999", None, Some(Error::NonexistentUtcError("2017-12-31 23:59:59.999".to_string())))]
     #[case("2018-01-01T00:00:00.000", Some("2018-01-01T00:00:36.000"), None)]
     // うるう秒が2秒挿入される瞬間のテスト
     #[case("2018-12-31T23:59:59.000", Some("2019-01-01T00:00:35.000"), None)]
     #[case("2018-12-31T23:59:60.000", Some("2019-01-01T00:00:36.000"), None)]
-    // #[case("2018-12-31T23:59:61.000", Some("2019-01-01T00:00:37.000"), None)]
+    #[case("2018-12-31T23:59:61.000", Some("2019-01-01T00:00:37.000"), None)]
+    #[case("2018-12-31T23:59:61.999", Some("2019-01-01T00:00:37.999"), None)]
+    #[case("2018-12-31T23:59:62.000", None, Some(Error::NonexistentUtcError("2018-12-31 23:59:62".to_string())))]
+    #[case("2016-12-31T23:59:61.000", None, Some(Error::NonexistentUtcError("2016-12-31 23:59:61".to_string())))]
     #[case("2019-01-01T00:00:00.000", Some("2019-01-01T00:00:38.000"), None)]
     // うるう秒が2秒削除される瞬間のテスト
     #[case("2019-12-31T23:59:57.000", Some("2020-01-01T00:00:35.000"), None)]
+    #[case("2019-12-31T23:59:58.000", None, Some(Error::NonexistentUtcError("2019-12-31 23:59:58".to_string())))]
+    #[case("2019-12-31T23:59:59.500", None, Some(Error::NonexistentUtcError("2019-12-31 23:59:59.500".to_string())))]
     #[case("2020-01-01T00:00:00.000", Some("2020-01-01T00:00:36.000"), None)]
+    // UTC オフセット付きの日時は UTC に変換してから扱う
+    #[case("2017-01-01T08:59:60.000+09:00", Some("2017-01-01T00:00:36.000"), None)]
+    #[case("2016-12-31T23:59:60.500Z", Some("2017-01-01T00:00:36.500"), None)]
+    #[case("2017-01-01T08:59:59.000+09:00", Some("2017-01-01T00:00:35.000"), None)]
     // Error when the input datetime is illegal format.
     #[case("2019-12-31 23:59:57.000", None, Some(Error::DatetimeParseError(utc.to_string())))]
     fn test_utc2tai(
@@ -119,29 +253,81 @@ mod tests {
             DiffTaiUtc {
                 datetime: NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 0),
                 diff_seconds: 36,
+                drift: None,
             },
             DiffTaiUtc {
                 datetime: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0),
                 diff_seconds: 37,
+                drift: None,
             },
             DiffTaiUtc {
                 datetime: NaiveDate::from_ymd(2018, 1, 1).and_hms(0, 0, 0),
                 diff_seconds: 36,
+                drift: None,
             },
             DiffTaiUtc {
                 datetime: NaiveDate::from_ymd(2019, 1, 1).and_hms(0, 0, 0),
                 diff_seconds: 38,
+                drift: None,
             },
             DiffTaiUtc {
                 datetime: NaiveDate::from_ymd(2020, 1, 1).and_hms(0, 0, 0),
                 diff_seconds: 36,
+                drift: None,
             },
         ];
-        let tai = utc2tai(&utc, &tai_utc_table.into(), DT_FMT);
+        let tai = utc2tai(utc, &tai_utc_table.into(), DT_FMT);
 
         assert_eq!(tai, expected);
     }
 
+    #[rstest]
+    #[case("2017-01-02T11:22:33.000", None)]
+    #[case("2016-12-31T23:59:60.000", None)]
+    #[case("2016-12-31T23:59:60.999", None)]
+    #[case("2018-12-31T23:59:61.500", None)]
+    #[case("2016-06-30T23:59:60.000", Some(Error::NotLeapSecondError("2016-06-30 23:59:60".to_string(), "2016-12-31 23:59:60".to_string())))]
+    #[case("2017-01-01T23:59:60.000", Some(Error::NotLeapSecondError("2017-01-01 23:59:60".to_string(), "2016-12-31 23:59:60".to_string())))]
+    #[case("2018-06-30T23:59:60.000", Some(Error::NotLeapSecondError("2018-06-30 23:59:60".to_string(), "2018-12-31 23:59:60".to_string())))]
+    #[case("2019-12-31T23:59:60.000", Some(Error::NotLeapSecondError("2019-12-31 23:59:60".to_string(), "2018-12-31 23:59:60".to_string())))]
+    #[case("2015-06-30T23:59:60.000", Some(Error::DatetimeTooLowError("2015-06-30 23:59:60".to_string())))]
+    fn test_check_leap_second(#[case] utc: &str, #[case] expected_err: Option<Error>) {
+        let tai_utc_table: TaiUtcTable = vec![
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 0),
+                diff_seconds: 36,
+                drift: None,
+            },
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0),
+                diff_seconds: 37,
+                drift: None,
+            },
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2018, 1, 1).and_hms(0, 0, 0),
+                diff_seconds: 36,
+                drift: None,
+            },
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2019, 1, 1).and_hms(0, 0, 0),
+                diff_seconds: 38,
+                drift: None,
+            },
+            DiffTaiUtc {
+                datetime: NaiveDate::from_ymd(2020, 1, 1).and_hms(0, 0, 0),
+                diff_seconds: 36,
+                drift: None,
+            },
+        ]
+        .into();
+        let label = UtcLabel::parse_from_str(utc, DT_FMT).unwrap();
+
+        assert_eq!(
+            check_leap_second(&label, &tai_utc_table),
+            testmod::result(Some(()).filter(|_| expected_err.is_none()), expected_err)
+        );
+    }
+
     #[rstest]
     #[case(
         "2017-01-02T11:22:33.000",
@@ -192,8 +378,63 @@ mod tests {
         let tai_utc_table = vec![DiffTaiUtc {
             datetime: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0),
             diff_seconds: 37,
+            drift: None,
         }];
-        let tai = utc2tai(&utc, &tai_utc_table.into(), dt_fmt);
+        let tai = utc2tai(utc, &tai_utc_table.into(), dt_fmt);
+
+        assert_eq!(tai, expected);
+    }
+
+    #[rstest]
+    // Error when the input datetime is too low.
+    #[case("1960-12-31T23:59:59.000000000", None, Some(Error::DatetimeTooLowError("1960-12-31 23:59:59".to_string())))]
+    #[case(
+        "1961-01-01T00:00:00.000000000",
+        Some("1961-01-01T00:00:01.422818000"),
+        None
+    )]
+    // TAI - UTC drifts day by day
+    #[case(
+        "1970-01-01T00:00:00.000000000",
+        Some("1970-01-01T00:00:08.000082000"),
+        None
+    )]
+    // TAI - UTC decreases by 0.05 seconds
+    #[case(
+        "1961-07-31T23:59:59.000000000",
+        Some("1961-08-01T00:00:00.697569985"),
+        None
+    )]
+    #[case(
+        "1961-08-01T00:00:00.000000000",
+        Some("1961-08-01T00:00:01.647570000"),
+        None
+    )]
+    // TAI - UTC increases by 0.107758 seconds at the end of the era of rubber seconds
+    #[case(
+        "1971-12-31T23:59:59.999000000",
+        Some("1972-01-01T00:00:09.891241999"),
+        None
+    )]
+    #[case(
+        "1971-12-31T23:59:60.050000000",
+        Some("1972-01-01T00:00:09.942242001"),
+        None
+    )]
+    #[case(
+        "1972-01-01T00:00:00.000000000",
+        Some("1972-01-01T00:00:10.000000000"),
+        None
+    )]
+    fn test_utc2tai_rubber_seconds(
+        #[case] utc: &str,
+        #[case] expected_ok: Option<&str>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok.map(ToString::to_string), expected_err);
+
+        let tai_utc_table = TaiUtcTable::from_tai_utc_dat(testmod::TAI_UTC_DAT).unwrap();
+        let tai = utc2tai(utc, &tai_utc_table, "%Y-%m-%dT%H:%M:%S%.9f");
 
         assert_eq!(tai, expected);
     }
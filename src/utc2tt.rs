@@ -1,7 +1,9 @@
 use crate::convtbl::TaiUtcTable;
 use crate::error::Error;
 use crate::tt::tai2tt_dt;
-use crate::utc2tai::utc2tai_dt;
+use crate::utc2tai::utc2tai_label;
+use crate::DtFmts;
+use crate::UtcLabel;
 use chrono::NaiveDateTime;
 
 /// Convert datetime
@@ -11,9 +13,9 @@ use chrono::NaiveDateTime;
 /// This function takes leap seconds into account along the argument `tai_utc_table`.
 ///
 /// # Arguments
-/// * `datetime` - Datetime in UTC.
+/// * `datetime` - Label of UTC, which may have the second 61 or more.
 /// * `tai_utc_table` - The conversion table of TAI - UTC
-/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime`
+/// * `dt_fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `datetime` and the result, or a pair of them; see [`DtFmts`](crate::DtFmts)
 ///
 /// # Returns
 /// Returns the datetime in TT.
@@ -38,11 +40,15 @@ use chrono::NaiveDateTime;
 ///
 /// # See also
 /// * [`utc2tt`](../utc2tt/index.html) (Binary crate) - The executable program which do same conversion.
-pub fn utc2tt(datetime: &str, tai_utc_table: &TaiUtcTable, dt_fmt: &str) -> Result<String, Error> {
-    let datetime = NaiveDateTime::parse_from_str(datetime, dt_fmt)
-        .map_err(|_e| Error::DatetimeParseError(datetime.to_string()))?;
-    let tai = utc2tt_dt(&datetime, tai_utc_table)?;
-    Ok(tai.format(dt_fmt).to_string())
+pub fn utc2tt<'a>(
+    datetime: &str,
+    tai_utc_table: &TaiUtcTable,
+    dt_fmt: impl Into<DtFmts<'a>>,
+) -> Result<String, Error> {
+    let dt_fmt = dt_fmt.into();
+    let datetime = UtcLabel::parse_from_str(datetime, dt_fmt.parse)?;
+    let tai = utc2tt_label(&datetime, tai_utc_table)?;
+    Ok(tai.format(dt_fmt.format).to_string())
 }
 
 /// Convert datetime
@@ -52,7 +58,7 @@ pub fn utc2tt(datetime: &str, tai_utc_table: &TaiUtcTable, dt_fmt: &str) -> Resu
 /// This function takes leap seconds into account along the argument `tai_utc_table`.
 ///
 /// # Arguments
-/// * `datetime` - Datetime in UTC.
+/// * `datetime` - Label of UTC, which may have the second 61 or more.
 /// * `tai_utc_table` - The conversion table of TAI - UTC
 ///
 /// # Returns
@@ -61,13 +67,13 @@ pub fn utc2tt(datetime: &str, tai_utc_table: &TaiUtcTable, dt_fmt: &str) -> Resu
 /// Returns [`Error`](crate::error::Error) if it fail to convert.
 ///
 /// # See also
-/// * [`utc2tt`] - It is same as `utc2tt_dt`, except that the argument and the result are [`str`] and [`String`].
+/// * [`utc2tt`] - It is same as `utc2tt_label`, except that the argument and the result are [`str`] and [`String`].
 /// * [`utc2tt`](../utc2tt/index.html) (Binary crate) - The executable program which do same conversion.
-pub fn utc2tt_dt(
-    datetime: &NaiveDateTime,
+pub fn utc2tt_label(
+    datetime: &UtcLabel,
     tai_utc_table: &TaiUtcTable,
 ) -> Result<NaiveDateTime, Error> {
-    let tai = utc2tai_dt(datetime, tai_utc_table)?;
+    let tai = utc2tai_label(datetime, tai_utc_table)?;
     Ok(tai2tt_dt(&tai))
 }
 
@@ -100,7 +106,7 @@ mod tests {
     // うるう秒が2秒挿入される瞬間のテスト
     #[case("2018-12-31T23:59:59.000", Some("2019-01-01T00:01:07.184"), None)]
     #[case("2018-12-31T23:59:60.000", Some("2019-01-01T00:01:08.184"), None)]
-    // #[case("2018-12-31T23:59:61.000", Some("2019-01-01T00:01:09.184"), None)]
+    #[case("2018-12-31T23:59:61.000", Some("2019-01-01T00:01:09.184"), None)]
     #[case("2019-01-01T00:00:00.000", Some("2019-01-01T00:01:10.184"), None)]
     // うるう秒が2秒削除される瞬間のテスト
     #[case("2019-12-31T23:59:57.000", Some("2020-01-01T00:01:07.184"), None)]
@@ -118,25 +124,30 @@ mod tests {
             DiffTaiUtc {
                 datetime: NaiveDate::from_ymd(2015, 7, 1).and_hms(0, 0, 0),
                 diff_seconds: 36,
+                drift: None,
             },
             DiffTaiUtc {
                 datetime: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0),
                 diff_seconds: 37,
+                drift: None,
             },
             DiffTaiUtc {
                 datetime: NaiveDate::from_ymd(2018, 1, 1).and_hms(0, 0, 0),
                 diff_seconds: 36,
+                drift: None,
             },
             DiffTaiUtc {
                 datetime: NaiveDate::from_ymd(2019, 1, 1).and_hms(0, 0, 0),
                 diff_seconds: 38,
+                drift: None,
             },
             DiffTaiUtc {
                 datetime: NaiveDate::from_ymd(2020, 1, 1).and_hms(0, 0, 0),
                 diff_seconds: 36,
+                drift: None,
             },
         ];
-        let tai = utc2tt(&utc, &tai_utc_table.into(), DT_FMT);
+        let tai = utc2tt(utc, &tai_utc_table.into(), DT_FMT);
 
         assert_eq!(tai, expected);
     }
@@ -191,8 +202,9 @@ mod tests {
         let tai_utc_table = vec![DiffTaiUtc {
             datetime: NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 0),
             diff_seconds: 37,
+            drift: None,
         }];
-        let tai = utc2tt(&utc, &tai_utc_table.into(), dt_fmt);
+        let tai = utc2tt(utc, &tai_utc_table.into(), dt_fmt);
 
         assert_eq!(tai, expected);
     }
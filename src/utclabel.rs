@@ -0,0 +1,586 @@
+use crate::error::Error;
+use crate::zoneinfo::parse_utc_offset;
+use chrono::{DateTime, Duration, NaiveDateTime, NaiveTime, Timelike};
+use std::fmt;
+use std::str::FromStr;
+
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+
+/// A label of UTC which may have the second 60, 61, ...
+///
+/// [`NaiveDateTime`] can express only one leap second (`23:59:60`) at the end of a minute.
+/// When two or more leap seconds are inserted at once, the labels `23:59:61` and after are required;
+/// `UtcLabel` expresses them as the minute and the elapsed time from the beginning of the minute.
+///
+/// # Examples
+/// ```
+/// use convdate::UtcLabel;
+///
+/// let label = UtcLabel::parse_from_str("2018-12-31T23:59:61.500", "%Y-%m-%dT%H:%M:%S%.3f").unwrap();
+/// assert_eq!(label.leap_seconds(), 2);
+/// assert_eq!(label.to_naive(), None);
+/// assert_eq!(label.format("%Y-%m-%dT%H:%M:%S%.3f"), "2018-12-31T23:59:61.500");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UtcLabel {
+    /// The beginning of the minute
+    minute: NaiveDateTime,
+    /// The elapsed time from the beginning of the minute in nanoseconds; it may be 60 seconds or more.
+    nanos_of_minute: u64,
+}
+
+impl UtcLabel {
+    /// The number of seconds of the label beyond the second 59; for example, 2 for `23:59:61.500`.
+    pub fn leap_seconds(&self) -> u64 {
+        (self.nanos_of_minute / NANOS_PER_SECOND).saturating_sub(59)
+    }
+
+    /// The fraction of the second of the label in nanoseconds.
+    pub(crate) fn subsec_nanos(&self) -> u32 {
+        (self.nanos_of_minute % NANOS_PER_SECOND) as u32
+    }
+
+    /// Convert the label to [`NaiveDateTime`].
+    ///
+    /// # Returns
+    /// Returns `None` if the label has the second 61 or more, which [`NaiveDateTime`] cannot express.
+    pub fn to_naive(&self) -> Option<NaiveDateTime> {
+        let nanos_of_minute = self.nanos_of_minute;
+        let (sec, nano) = if nanos_of_minute >= 59 * NANOS_PER_SECOND {
+            (59, nanos_of_minute - 59 * NANOS_PER_SECOND)
+        } else {
+            (
+                nanos_of_minute / NANOS_PER_SECOND,
+                nanos_of_minute % NANOS_PER_SECOND,
+            )
+        };
+        let time = NaiveTime::from_hms_nano_opt(
+            self.minute.hour(),
+            self.minute.minute(),
+            sec as u32,
+            nano as u32,
+        )?;
+        Some(self.minute.date().and_time(time))
+    }
+
+    /// Convert the label to [`NaiveDateTime`], replacing the second 61 or more with the end of the second 60.
+    ///
+    /// It is used to pick the row of the TAI-UTC table.
+    pub(crate) fn to_naive_saturating(self) -> NaiveDateTime {
+        self.to_naive().unwrap_or_else(|| {
+            self.minute.date().and_time(NaiveTime::from_hms_nano(
+                self.minute.hour(),
+                self.minute.minute(),
+                59,
+                1_999_999_999,
+            ))
+        })
+    }
+
+    /// The datetime which the label indicates, counting leap seconds as the seconds of the next minute.
+    pub fn normalized(&self) -> NaiveDateTime {
+        self.minute + Duration::nanoseconds(self.nanos_of_minute as i64)
+    }
+
+    /// The beginning of the minute next to the label's minute.
+    pub(crate) fn end_of_minute(&self) -> NaiveDateTime {
+        self.minute + Duration::minutes(1)
+    }
+
+    /// Shift the label by the UTC offset, keeping the leap seconds.
+    ///
+    /// If the offset is not a whole number of minutes, for example the local mean time of the 19th century,
+    /// the label is [`normalized`](UtcLabel::normalized) before shifted.
+    pub(crate) fn shifted(self, seconds: i64) -> UtcLabel {
+        if seconds % 60 == 0 {
+            UtcLabel {
+                minute: self.minute + Duration::seconds(seconds),
+                nanos_of_minute: self.nanos_of_minute,
+            }
+        } else {
+            UtcLabel::from(&(self.normalized() + Duration::seconds(seconds)))
+        }
+    }
+
+    /// Extend the second of the label by the leap seconds.
+    pub(crate) fn stretched(self, nanos: u64) -> UtcLabel {
+        UtcLabel {
+            minute: self.minute,
+            nanos_of_minute: self.nanos_of_minute + nanos,
+        }
+    }
+
+    /// Adjust the label in a leap second along the policy.
+    ///
+    /// # Arguments
+    /// * `policy` - The policy for the labels in leap seconds
+    ///
+    /// # Returns
+    /// Returns the adjusted label; it is same as `self` if the label is not in a leap second.
+    ///
+    /// Returns [`Error`](crate::error::Error) if the label is in a leap second and `policy` is [`LeapPolicy::Error`].
+    pub fn with_leap_policy(self, policy: LeapPolicy) -> Result<UtcLabel, Error> {
+        if self.leap_seconds() == 0 {
+            return Ok(self);
+        }
+        match policy {
+            LeapPolicy::Keep => Ok(self),
+            LeapPolicy::Repeat => Ok(UtcLabel {
+                minute: self.minute,
+                nanos_of_minute: 59 * NANOS_PER_SECOND + self.nanos_of_minute % NANOS_PER_SECOND,
+            }),
+            LeapPolicy::Freeze => Ok(UtcLabel {
+                minute: self.minute,
+                nanos_of_minute: 60 * NANOS_PER_SECOND - 1,
+            }),
+            LeapPolicy::Roll => Ok(UtcLabel::from(&self.end_of_minute())),
+            LeapPolicy::Error => Err(Error::LeapSecondOutputError(self.to_string())),
+        }
+    }
+
+    /// Parse a label of UTC.
+    ///
+    /// In addition to the formats which [`NaiveDateTime`] can parse, the second 61 or more is accepted
+    /// if the format has the two-digit second; for example, `%S` or `%T`.
+    ///
+    /// The datetime with the UTC offset is converted to UTC; the offset is parsed by `%z`, `%:z` or `%#z` in `fmt`,
+    /// or taken from the trailing `Z`, `±HH:MM`, `±HHMM` or `±HH` of `s` if `fmt` is the default format `%Y-%m-%dT%H:%M:%S%.3f`.
+    /// For example, `2017-01-01T08:59:60+09:00` is parsed as `2016-12-31T23:59:60`.
+    /// With the other formats, `s` with the UTC offset fails to parse.
+    ///
+    /// # Arguments
+    /// * `s` - The label of UTC
+    /// * `fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of `s`
+    ///
+    /// # Returns
+    /// Returns the label.
+    ///
+    /// Returns [`Error`](crate::error::Error) if it fail to parse.
+    pub fn parse_from_str(s: &str, fmt: &str) -> Result<UtcLabel, Error> {
+        let (local, offset) = UtcLabel::parse_local(s, fmt)
+            .ok_or_else(|| Error::DatetimeParseError(s.to_string()))?;
+        Ok(local.shifted(-offset))
+    }
+
+    /// Parse a label and its UTC offset in seconds.
+    ///
+    /// If `fmt` has a specifier of the UTC offset, the trailing `Z` of `s` is also accepted as `+00:00`.
+    /// If `fmt` is the default format [`DT_FMT`](crate::DT_FMT) and `s` does not match it,
+    /// the trailing `Z`, `±HH:MM`, `±HHMM` or `±HH` of `s` is regarded as the UTC offset.
+    fn parse_local(s: &str, fmt: &str) -> Option<(UtcLabel, i64)> {
+        if ["%z", "%:z", "%#z"].iter().any(|spec| fmt.contains(spec)) {
+            let parse_aware = |s: &str| {
+                DateTime::parse_from_str(s, fmt)
+                    .ok()
+                    .map(|dt| (dt.naive_local(), i64::from(dt.offset().local_minus_utc())))
+            };
+            return UtcLabel::parse_by(s, parse_aware).or_else(|| {
+                // 末尾の "Z" は UTC オフセット +00:00 とみなす
+                let rest = s.strip_suffix(|c| c == 'Z' || c == 'z')?;
+                UtcLabel::parse_by(&format!("{}+00:00", rest), parse_aware)
+            });
+        }
+        let parse_naive = |s: &str| NaiveDateTime::parse_from_str(s, fmt).ok().map(|dt| (dt, 0));
+        if let Some(parsed) = UtcLabel::parse_by(s, parse_naive) {
+            return Some(parsed);
+        }
+        if fmt != crate::DT_FMT {
+            return None;
+        }
+
+        // 既定の書式では、末尾の "Z" や "+09:00" を UTC オフセットとみなして取り除く
+        let (s, offset) = match s.strip_suffix(|c| c == 'Z' || c == 'z') {
+            Some(rest) => (rest, 0),
+            None => {
+                let pos = s.rfind(['+', '-'])?;
+                (&s[..pos], parse_utc_offset(&s[pos..])?)
+            }
+        };
+        UtcLabel::parse_by(s, parse_naive).map(|(label, _)| (label, offset))
+    }
+
+    /// Parse a label by `parse`, accepting the second 61 or more.
+    fn parse_by(
+        s: &str,
+        parse: impl Fn(&str) -> Option<(NaiveDateTime, i64)>,
+    ) -> Option<(UtcLabel, i64)> {
+        if let Some((datetime, offset)) = parse(s) {
+            return Some((UtcLabel::from(&datetime), offset));
+        }
+
+        // 秒が61以上の場合、秒を59と58に置き換えて解析し、1秒差になる位置を秒の位置とみなす
+        let bytes = s.as_bytes();
+        for i in 0..bytes.len().saturating_sub(1) {
+            let (tens, ones) = (bytes[i], bytes[i + 1]);
+            if !tens.is_ascii_digit() || !ones.is_ascii_digit() {
+                continue;
+            }
+            let second = u64::from(tens - b'0') * 10 + u64::from(ones - b'0');
+            if second < 61 {
+                continue;
+            }
+            let replaced = |digits: &str| format!("{}{}{}", &s[..i], digits, &s[i + 2..]);
+            if let (Some((at59, offset)), Some((at58, _))) =
+                (parse(&replaced("59")), parse(&replaced("58")))
+            {
+                if at59.second() == 59 && at59 - at58 == Duration::seconds(1) {
+                    let label = UtcLabel::from(&at59).stretched((second - 59) * NANOS_PER_SECOND);
+                    return Some((label, offset));
+                }
+            }
+        }
+        None
+    }
+
+    /// Format the label.
+    ///
+    /// If the label has the second 61 or more and the format has no two-digit second,
+    /// the label is formatted as [`normalized`](UtcLabel::normalized).
+    ///
+    /// # Arguments
+    /// * `fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of the result
+    pub fn format(&self, fmt: &str) -> String {
+        if let Some(datetime) = self.to_naive() {
+            return datetime.format(fmt).to_string();
+        }
+
+        // 秒を59と58にして整形し、異なる位置を秒の位置とみなす
+        let at59 = self.minute
+            + Duration::seconds(59)
+            + Duration::nanoseconds((self.nanos_of_minute % NANOS_PER_SECOND) as i64);
+        let at58 = at59 - Duration::seconds(1);
+        let (at59, at58) = (at59.format(fmt).to_string(), at58.format(fmt).to_string());
+        let differ: Vec<usize> = at59
+            .bytes()
+            .zip(at58.bytes())
+            .enumerate()
+            .filter(|(_, (a, b))| a != b)
+            .map(|(i, _)| i)
+            .collect();
+        match differ.as_slice() {
+            [i] if at59.len() == at58.len() && *i >= 1 && &at59[i - 1..=*i] == "59" => format!(
+                "{}{:02}{}",
+                &at59[..i - 1],
+                self.nanos_of_minute / NANOS_PER_SECOND,
+                &at59[i + 1..]
+            ),
+            _ => self.normalized().format(fmt).to_string(),
+        }
+    }
+}
+
+/// Policies for output labels of UTC in leap seconds
+///
+/// Many systems, for example databases and Unix time, cannot accept the second 60;
+/// the policies except [`LeapPolicy::Keep`] replace such labels with ones without leap seconds.
+///
+/// # Examples
+/// ```
+/// use convdate::{LeapPolicy, UtcLabel};
+///
+/// let label = UtcLabel::parse_from_str("2016-12-31T23:59:60.500", "%Y-%m-%dT%H:%M:%S%.3f").unwrap();
+/// let policy: LeapPolicy = "repeat-59".parse().unwrap();
+/// let adjusted = label.with_leap_policy(policy).unwrap();
+/// assert_eq!(adjusted.format("%Y-%m-%dT%H:%M:%S%.3f"), "2016-12-31T23:59:59.500");
+/// assert_eq!(LeapPolicy::default(), LeapPolicy::Keep);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LeapPolicy {
+    /// The label is kept as it is; for example, `23:59:60.500`.
+    #[default]
+    Keep,
+    /// The second 59 is repeated; for example, `23:59:59.500`.
+    Repeat,
+    /// The label is frozen at the end of the second 59; `23:59:59.999999999`.
+    Freeze,
+    /// The label is rolled to the beginning of the next minute; `00:00:00`.
+    Roll,
+    /// The label is refused with [`LeapSecondOutputError`](crate::error::Error::LeapSecondOutputError).
+    Error,
+}
+
+impl LeapPolicy {
+    /// All policies which convdate supports
+    pub const ALL: [LeapPolicy; 5] = [
+        LeapPolicy::Keep,
+        LeapPolicy::Repeat,
+        LeapPolicy::Freeze,
+        LeapPolicy::Roll,
+        LeapPolicy::Error,
+    ];
+
+    /// The names of [`LeapPolicy::ALL`] in the same order
+    pub const NAMES: [&'static str; 5] = ["keep", "repeat-59", "freeze", "roll", "error"];
+
+    /// The name of the policy used in command arguments; for example, `"repeat-59"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            LeapPolicy::Keep => "keep",
+            LeapPolicy::Repeat => "repeat-59",
+            LeapPolicy::Freeze => "freeze",
+            LeapPolicy::Roll => "roll",
+            LeapPolicy::Error => "error",
+        }
+    }
+}
+
+impl FromStr for LeapPolicy {
+    type Err = Error;
+
+    /// Parse the name of a policy. The case of letters is ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LeapPolicy::ALL
+            .iter()
+            .find(|policy| policy.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| Error::UnknownLeapPolicyError(s.to_string()))
+    }
+}
+
+impl fmt::Display for LeapPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl From<&NaiveDateTime> for UtcLabel {
+    fn from(datetime: &NaiveDateTime) -> Self {
+        let minute = datetime
+            .date()
+            .and_hms(datetime.hour(), datetime.minute(), 0);
+        UtcLabel {
+            minute,
+            nanos_of_minute: u64::from(datetime.second()) * NANOS_PER_SECOND
+                + u64::from(datetime.nanosecond()),
+        }
+    }
+}
+
+impl fmt::Display for UtcLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.format("%Y-%m-%d %H:%M:%S%.f"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use rstest::*;
+
+    const DT_FMT: &str = "%Y-%m-%dT%H:%M:%S%.3f";
+    const DT_FMT_WITH_OFFSET: &str = "%Y-%m-%dT%H:%M:%S%.3f%:z";
+
+    #[rstest]
+    #[case("2018-12-31T23:59:59.000", Some("2018-12-31T23:59:59.000"), 0)]
+    #[case("2018-12-31T23:59:60.500", Some("2018-12-31T23:59:60.500"), 1)]
+    #[case("2018-12-31T23:59:61.500", Some("2018-12-31T23:59:61.500"), 2)]
+    #[case("2018-12-31T23:59:62.000", Some("2018-12-31T23:59:62.000"), 3)]
+    #[case("2018-12-31T23:61:00.000", None, 0)]
+    fn test_parse_and_format(
+        #[case] label: &str,
+        #[case] expected: Option<&str>,
+        #[case] expected_leap_seconds: u64,
+    ) {
+        let parsed = UtcLabel::parse_from_str(label, DT_FMT);
+        match expected {
+            Some(expected) => {
+                let parsed = parsed.unwrap();
+                assert_eq!(parsed.format(DT_FMT), expected);
+                assert_eq!(parsed.leap_seconds(), expected_leap_seconds);
+            }
+            None => assert_eq!(parsed, Err(Error::DatetimeParseError(label.to_string()))),
+        }
+    }
+
+    #[rstest]
+    #[case("20181231235961", "%Y%m%d%H%M%S", "20181231235961")]
+    #[case("2018-12-31 23:59:61", "%Y-%m-%d %T", "2018-12-31 23:59:61")]
+    #[case("23:59:61 31/12/2018", "%H:%M:%S %d/%m/%Y", "23:59:61 31/12/2018")]
+    fn test_parse_and_format_other_formats(
+        #[case] label: &str,
+        #[case] dt_fmt: &str,
+        #[case] expected: &str,
+    ) {
+        let parsed = UtcLabel::parse_from_str(label, dt_fmt).unwrap();
+        assert_eq!(parsed.format(dt_fmt), expected);
+    }
+
+    #[rstest]
+    #[case(
+        "2017-01-01T08:59:60.500+09:00",
+        DT_FMT_WITH_OFFSET,
+        Some("2016-12-31T23:59:60.500")
+    )]
+    #[case(
+        "2017-01-01T09:00:00.000+0900",
+        "%Y-%m-%dT%H:%M:%S%.3f%z",
+        Some("2017-01-01T00:00:00.000")
+    )]
+    #[case(
+        "2016-12-31T18:59:60.000-05:00",
+        DT_FMT_WITH_OFFSET,
+        Some("2016-12-31T23:59:60.000")
+    )]
+    #[case(
+        "2016-12-31T23:59:60.000Z",
+        DT_FMT_WITH_OFFSET,
+        Some("2016-12-31T23:59:60.000")
+    )]
+    #[case(
+        "2019-01-01T08:59:61.000+09:00",
+        DT_FMT_WITH_OFFSET,
+        Some("2018-12-31T23:59:61.000")
+    )]
+    #[case(
+        "2017-01-01 08:59:60 +0900",
+        "%Y-%m-%d %H:%M:%S %z",
+        Some("2016-12-31T23:59:60.000")
+    )]
+    #[case(
+        "2017-01-01 08:59:60+09:00",
+        "%Y-%m-%d %H:%M:%S%:z",
+        Some("2016-12-31T23:59:60.000")
+    )]
+    // UTC オフセットが分単位でない場合はうるう秒を表せない
+    #[case(
+        "2017-01-01T09:18:59.000+09:18",
+        DT_FMT_WITH_OFFSET,
+        Some("2017-01-01T00:00:59.000")
+    )]
+    #[case("2017-01-01T09:00:00.000+9:00", DT_FMT_WITH_OFFSET, None)]
+    #[case("2017-01-01 09:00:00", "%Y-%m-%d %H:%M:%S %z", None)]
+    // 既定の書式では、末尾の UTC オフセットも受け付ける
+    #[case(
+        "2017-01-01T08:59:60.500+09:00",
+        DT_FMT,
+        Some("2016-12-31T23:59:60.500")
+    )]
+    #[case(
+        "2017-01-01T09:00:00.000+0900",
+        DT_FMT,
+        Some("2017-01-01T00:00:00.000")
+    )]
+    #[case("2016-12-31T18:59:60.000-05", DT_FMT, Some("2016-12-31T23:59:60.000"))]
+    #[case("2016-12-31T23:59:60.000Z", DT_FMT, Some("2016-12-31T23:59:60.000"))]
+    #[case(
+        "2019-01-01T08:59:61.000+09:00",
+        DT_FMT,
+        Some("2018-12-31T23:59:61.000")
+    )]
+    #[case("2017-01-01T09:00:00.000+9:00", DT_FMT, None)]
+    // その他の書式に UTC オフセットがなければ、UTC オフセット付きの日時は解析できない
+    #[case("2017-01-01 08:59:60+09:00", "%Y-%m-%d %H:%M:%S", None)]
+    #[case("2016-12-31 23:59:60Z", "%Y-%m-%d %H:%M:%S", None)]
+    fn test_parse_with_offset(
+        #[case] label: &str,
+        #[case] dt_fmt: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        let parsed = UtcLabel::parse_from_str(label, dt_fmt).map(|label| label.format(DT_FMT));
+        match expected {
+            Some(expected) => assert_eq!(parsed, Ok(expected.to_string())),
+            None => assert_eq!(parsed, Err(Error::DatetimeParseError(label.to_string()))),
+        }
+    }
+
+    #[rstest]
+    #[case("2018-12-31T23:59:59.000", "2018-12-31 23:59:59")]
+    #[case("2018-12-31T23:59:60.000", "2019-01-01 00:00:00")]
+    #[case("2018-12-31T23:59:61.250", "2019-01-01 00:00:01.250")]
+    fn test_normalized(#[case] label: &str, #[case] expected: &str) {
+        let label = UtcLabel::parse_from_str(label, DT_FMT).unwrap();
+        assert_eq!(label.normalized().to_string(), expected);
+    }
+
+    #[rstest]
+    #[case(NaiveDate::from_ymd(2018, 12, 31).and_hms_milli(23, 59, 59, 1500), "2018-12-31 23:59:60.500")]
+    #[case(NaiveDate::from_ymd(2018, 12, 31).and_hms(12, 34, 56), "2018-12-31 12:34:56")]
+    fn test_from_naive(#[case] datetime: NaiveDateTime, #[case] expected: &str) {
+        let label = UtcLabel::from(&datetime);
+        assert_eq!(label.to_string(), expected);
+        assert_eq!(label.to_naive(), Some(datetime));
+    }
+
+    #[rstest]
+    #[case(
+        "2016-12-31T23:59:60.500",
+        LeapPolicy::Keep,
+        Ok("2016-12-31T23:59:60.500000000")
+    )]
+    #[case(
+        "2016-12-31T23:59:60.500",
+        LeapPolicy::Repeat,
+        Ok("2016-12-31T23:59:59.500000000")
+    )]
+    #[case(
+        "2016-12-31T23:59:60.500",
+        LeapPolicy::Freeze,
+        Ok("2016-12-31T23:59:59.999999999")
+    )]
+    #[case(
+        "2016-12-31T23:59:60.500",
+        LeapPolicy::Roll,
+        Ok("2017-01-01T00:00:00.000000000")
+    )]
+    #[case("2016-12-31T23:59:60.500", LeapPolicy::Error, Err(Error::LeapSecondOutputError("2016-12-31 23:59:60.500".to_string())))]
+    #[case(
+        "2018-12-31T23:59:61.250",
+        LeapPolicy::Repeat,
+        Ok("2018-12-31T23:59:59.250000000")
+    )]
+    #[case(
+        "2018-12-31T23:59:61.250",
+        LeapPolicy::Roll,
+        Ok("2019-01-01T00:00:00.000000000")
+    )]
+    // うるう秒でないラベルはどの方針でも変更しない
+    #[case(
+        "2016-12-31T23:59:59.500",
+        LeapPolicy::Freeze,
+        Ok("2016-12-31T23:59:59.500000000")
+    )]
+    #[case(
+        "2016-12-31T23:59:59.500",
+        LeapPolicy::Error,
+        Ok("2016-12-31T23:59:59.500000000")
+    )]
+    fn test_with_leap_policy(
+        #[case] label: &str,
+        #[case] policy: LeapPolicy,
+        #[case] expected: Result<&str, Error>,
+    ) {
+        let label = UtcLabel::parse_from_str(label, DT_FMT).unwrap();
+        let adjusted = label
+            .with_leap_policy(policy)
+            .map(|label| label.format("%Y-%m-%dT%H:%M:%S%.9f"));
+        assert_eq!(adjusted, expected.map(ToString::to_string));
+    }
+
+    #[rstest]
+    #[case("keep", Ok(LeapPolicy::Keep))]
+    #[case("Repeat-59", Ok(LeapPolicy::Repeat))]
+    #[case("FREEZE", Ok(LeapPolicy::Freeze))]
+    #[case("roll", Ok(LeapPolicy::Roll))]
+    #[case("error", Ok(LeapPolicy::Error))]
+    #[case("repeat", Err(Error::UnknownLeapPolicyError("repeat".to_string())))]
+    fn test_leap_policy_from_str(#[case] s: &str, #[case] expected: Result<LeapPolicy, Error>) {
+        assert_eq!(s.parse::<LeapPolicy>(), expected);
+        if let Ok(policy) = expected {
+            assert_eq!(s.to_ascii_lowercase(), policy.to_string());
+        }
+    }
+
+    #[test]
+    fn test_ord() {
+        let at60 = UtcLabel::parse_from_str("2018-12-31T23:59:60.999", DT_FMT).unwrap();
+        let at61 = UtcLabel::parse_from_str("2018-12-31T23:59:61.000", DT_FMT).unwrap();
+        let next = UtcLabel::parse_from_str("2019-01-01T00:00:00.000", DT_FMT).unwrap();
+        assert!(at60 < at61);
+        assert!(at61 < next);
+        assert_eq!(
+            at61.to_naive_saturating(),
+            NaiveDate::from_ymd(2018, 12, 31).and_hms_nano(23, 59, 59, 1_999_999_999)
+        );
+    }
+}
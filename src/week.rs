@@ -0,0 +1,225 @@
+//! Week notation of datetimes; `week:seconds-of-week`.
+//!
+//! Some time scales, such as GPS time and the other GNSS system times, count weeks from their epochs.
+//! In the week notation, a datetime is written as the number of weeks since the epoch
+//! and the number of seconds since the beginning of the week; for example,
+//! `2017-01-01T00:00:18` in GPS time is `1930:18`.
+//!
+//! See [`TimeScale::week_epoch`](crate::TimeScale::week_epoch) about the epochs
+//! and [`TimeScale::week_rollover`](crate::TimeScale::week_rollover) about the rollover of the broadcast week numbers.
+use crate::error::Error;
+use crate::{normalize_leap, parse_decimal_nanos};
+use chrono::{Duration, NaiveDateTime};
+
+/// The number of weeks which the 10-bit week number of GPS and QZSS can express; it rolls over to 0 after 1023.
+pub const GPS_WEEK_ROLLOVER: i64 = 1024;
+
+/// The number of weeks which the 12-bit week number of Galileo can express; it rolls over to 0 after 4095.
+pub const GST_WEEK_ROLLOVER: i64 = 4096;
+
+/// The number of weeks which the 13-bit week number of BeiDou can express; it rolls over to 0 after 8191.
+pub const BDT_WEEK_ROLLOVER: i64 = 8192;
+
+const NANOS_PER_WEEK: i64 = 604_800_000_000_000;
+
+/// Parse the week notation.
+///
+/// # Arguments
+/// * `datetime` - A datetime in the week notation; for example, `1930:18.5`
+/// * `epoch` - The epoch of the week number
+/// * `rollover` - If it is specified as `(weeks, rollover_ref)`, the week number in `datetime` is treated as a truncated number which rolls over every `weeks` weeks,
+///   and it is resolved to the first week not before the week containing `rollover_ref`.
+///
+/// # Returns
+/// Returns the datetime.
+///
+/// Returns [`Error`](crate::error::Error) if `datetime` is illegal.
+///
+/// # Examples
+/// ```
+/// use convdate::week::{parse_week, GPS_WEEK_ROLLOVER};
+/// use chrono::NaiveDate;
+///
+/// let gps_epoch = NaiveDate::from_ymd(1980, 1, 6).and_hms(0, 0, 0);
+///
+/// assert_eq!(
+///     parse_week("1930:18.5", &gps_epoch, None),
+///     Ok(NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 18, 500)),
+/// );
+///
+/// // The week number 906 after the reference epoch 2000-01-01 is the week 1930.
+/// let rollover_ref = NaiveDate::from_ymd(2000, 1, 1).and_hms(0, 0, 0);
+/// assert_eq!(
+///     parse_week("906:18.5", &gps_epoch, Some((GPS_WEEK_ROLLOVER, &rollover_ref))),
+///     Ok(NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 18, 500)),
+/// );
+/// ```
+pub fn parse_week(
+    datetime: &str,
+    epoch: &NaiveDateTime,
+    rollover: Option<(i64, &NaiveDateTime)>,
+) -> Result<NaiveDateTime, Error> {
+    let parse_err = || Error::DatetimeParseError(datetime.to_string());
+    let (week, sow) = datetime.trim().split_once(':').ok_or_else(parse_err)?;
+    if week.is_empty() || !week.chars().all(|c| c.is_ascii_digit()) {
+        Err(parse_err())?;
+    }
+    let week: i64 = week.parse().map_err(|_| parse_err())?;
+    let sow = parse_decimal_nanos(sow)
+        .filter(|sow| (0..NANOS_PER_WEEK).contains(sow))
+        .ok_or_else(parse_err)?;
+
+    let week = match rollover {
+        Some((weeks, rollover_ref)) => {
+            if week >= weeks {
+                Err(parse_err())?;
+            }
+            let ref_week = (*rollover_ref - *epoch).num_weeks();
+            ref_week + (week - ref_week).rem_euclid(weeks)
+        }
+        None => week,
+    };
+
+    // Duration::weeks は範囲外の週番号でパニックするため、先に範囲を確かめる
+    if week > Duration::max_value().num_weeks() {
+        Err(parse_err())?;
+    }
+    epoch
+        .checked_add_signed(Duration::weeks(week))
+        .and_then(|dt| dt.checked_add_signed(Duration::nanoseconds(sow)))
+        .ok_or_else(parse_err)
+}
+
+/// Format the datetime in the week notation.
+///
+/// The seconds of week has the fractional part only if it is needed.
+///
+/// # Arguments
+/// * `datetime` - A datetime
+/// * `epoch` - The epoch of the week number
+/// * `rollover` - If it is specified, the week number is truncated; it rolls over every `rollover` weeks.
+///
+/// # Returns
+/// Returns the datetime in the week notation.
+///
+/// Returns [`Error`](crate::error::Error) if `datetime` is before `epoch`.
+///
+/// # Examples
+/// ```
+/// use convdate::week::{format_week, GPS_WEEK_ROLLOVER};
+/// use chrono::NaiveDate;
+///
+/// let gps_epoch = NaiveDate::from_ymd(1980, 1, 6).and_hms(0, 0, 0);
+/// let datetime = NaiveDate::from_ymd(2017, 1, 1).and_hms_milli(0, 0, 18, 500);
+///
+/// assert_eq!(format_week(&datetime, &gps_epoch, None), Ok("1930:18.5".to_string()));
+/// assert_eq!(format_week(&datetime, &gps_epoch, Some(GPS_WEEK_ROLLOVER)), Ok("906:18.5".to_string()));
+/// ```
+pub fn format_week(
+    datetime: &NaiveDateTime,
+    epoch: &NaiveDateTime,
+    rollover: Option<i64>,
+) -> Result<String, Error> {
+    let elapsed = normalize_leap(datetime) - *epoch;
+    if elapsed < Duration::zero() {
+        Err(Error::DatetimeTooLowError(datetime.to_string()))?;
+    }
+    let week = elapsed.num_weeks();
+    let sow = elapsed - Duration::weeks(week);
+    let sow_seconds = sow.num_seconds();
+    let sow_nanos = (sow - Duration::seconds(sow_seconds))
+        .num_nanoseconds()
+        .unwrap();
+
+    let week = match rollover {
+        Some(weeks) => week % weeks,
+        None => week,
+    };
+
+    if sow_nanos == 0 {
+        Ok(format!("{}:{}", week, sow_seconds))
+    } else {
+        let fraction = format!("{:09}", sow_nanos);
+        Ok(format!(
+            "{}:{}.{}",
+            week,
+            sow_seconds,
+            fraction.trim_end_matches('0')
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testmod;
+    use chrono::NaiveDate;
+    use rstest::*;
+
+    fn gps_epoch() -> NaiveDateTime {
+        NaiveDate::from_ymd(1980, 1, 6).and_hms(0, 0, 0)
+    }
+
+    #[rstest]
+    #[case("0:0", None, Some(NaiveDate::from_ymd(1980, 1, 6).and_hms(0, 0, 0)), None)]
+    #[case("1930:18", None, Some(NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 18)), None)]
+    #[case("1930:18.123456789", None, Some(NaiveDate::from_ymd(2017, 1, 1).and_hms_nano(0, 0, 18, 123_456_789)), None)]
+    #[case("2047:604799.5", None, Some(NaiveDate::from_ymd(2019, 4, 6).and_hms_milli(23, 59, 59, 500)), None)]
+    // The 10-bit week number is resolved with the reference epoch.
+    #[case("906:18", Some(NaiveDate::from_ymd(2000, 1, 1).and_hms(0, 0, 0)), Some(NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 18)), None)]
+    #[case("0:0", Some(NaiveDate::from_ymd(2019, 4, 7).and_hms(0, 0, 0)), Some(NaiveDate::from_ymd(2019, 4, 7).and_hms(0, 0, 0)), None)]
+    #[case("1023:604799.5", Some(NaiveDate::from_ymd(2019, 4, 7).and_hms(0, 0, 0)), Some(NaiveDate::from_ymd(2038, 11, 20).and_hms_milli(23, 59, 59, 500)), None)]
+    #[case("1023:604799.5", Some(NaiveDate::from_ymd(2000, 1, 1).and_hms(0, 0, 0)), Some(NaiveDate::from_ymd(2019, 4, 6).and_hms_milli(23, 59, 59, 500)), None)]
+    #[case("1024:0", Some(NaiveDate::from_ymd(2000, 1, 1).and_hms(0, 0, 0)), None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    // Illegal notations
+    #[case("1930:604800", None, None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("1930", None, None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("-1:0", None, None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("1930:-1", None, None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("2017-01-01T00:00:00", None, None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    // Out of the range of datetimes
+    #[case("9999999999:0", None, None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("99999999999:0", None, None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    #[case("99999999999999999999:0", None, None, Some(Error::DatetimeParseError(datetime.to_string())))]
+    fn test_parse_week(
+        #[case] datetime: &str,
+        #[case] rollover_ref: Option<NaiveDateTime>,
+        #[case] expected_ok: Option<NaiveDateTime>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok, expected_err);
+
+        let result = parse_week(
+            datetime,
+            &gps_epoch(),
+            rollover_ref.as_ref().map(|r| (GPS_WEEK_ROLLOVER, r)),
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case(NaiveDate::from_ymd(1980, 1, 6).and_hms(0, 0, 0), false, Some("0:0"), None)]
+    #[case(NaiveDate::from_ymd(2017, 1, 1).and_hms(0, 0, 18), false, Some("1930:18"), None)]
+    #[case(NaiveDate::from_ymd(2017, 1, 1).and_hms_nano(0, 0, 18, 120_000_000), false, Some("1930:18.12"), None)]
+    #[case(NaiveDate::from_ymd(2019, 4, 6).and_hms_milli(23, 59, 59, 500), true, Some("1023:604799.5"), None)]
+    #[case(NaiveDate::from_ymd(2019, 4, 7).and_hms(0, 0, 0), false, Some("2048:0"), None)]
+    #[case(NaiveDate::from_ymd(2019, 4, 7).and_hms(0, 0, 0), true, Some("0:0"), None)]
+    #[case(NaiveDate::from_ymd(1980, 1, 5).and_hms(23, 59, 59), false, None, Some(Error::DatetimeTooLowError("1980-01-05 23:59:59".to_string())))]
+    fn test_format_week(
+        #[case] datetime: NaiveDateTime,
+        #[case] rollover: bool,
+        #[case] expected_ok: Option<&str>,
+        #[case] expected_err: Option<Error>,
+    ) {
+        let expected = testmod::result(expected_ok.map(ToString::to_string), expected_err);
+
+        let result = format_week(
+            &datetime,
+            &gps_epoch(),
+            rollover.then_some(GPS_WEEK_ROLLOVER),
+        );
+
+        assert_eq!(result, expected);
+    }
+}
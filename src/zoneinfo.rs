@@ -0,0 +1,952 @@
+//! Time zones of [the IANA time zone database](https://www.iana.org/time-zones).
+//!
+//! A time zone is read from a TZif file ([RFC 8536](https://www.rfc-editor.org/rfc/rfc8536)) in the zoneinfo directory,
+//! for example `/usr/share/zoneinfo/Asia/Tokyo`; see [`TimeZone::load`].
+//! The database is not embedded, so the names of the database require the zoneinfo directory, which the tzdata package installs.
+//! The time zones can also be written without any files as fixed UTC offsets such as `+09:00`
+//! or as POSIX TZ strings such as `JST-9` and `EST5EDT,M3.2.0,M11.1.0`.
+//!
+//! The local time is calculated from the label of UTC, so leap seconds are shown as the second 60
+//! in the same way as the `right/` zones of tzdata; for example, `2017-01-01T08:59:60+09:00` in Asia/Tokyo.
+//! The leap seconds in the TZif files are used only to read the `right/` zones; the leap seconds of the TAI-UTC table are shown instead.
+use crate::error::Error;
+use crate::UtcLabel;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+use std::convert::TryInto;
+use std::fs;
+use std::path::{Component, Path};
+
+/// The zoneinfo directory used when it is not specified
+pub const DEFAULT_ZONEINFO_DIR: &str = "/usr/share/zoneinfo";
+
+/// The transition rules of POSIX TZ strings used when a DST is specified without rules; same as tzcode.
+const DEFAULT_DST_RULES: &str = "M3.2.0,M11.1.0";
+
+/// A local time type; the UTC offset and the abbreviation
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocalTimeType {
+    /// The UTC offset in seconds, positive to the east of Greenwich
+    pub offset: i32,
+    /// Whether it is a daylight saving time or not
+    pub is_dst: bool,
+    /// The abbreviation; for example, `"JST"`
+    pub abbreviation: String,
+}
+
+/// A time zone
+///
+/// # Examples
+/// ```
+/// use convdate::zoneinfo::TimeZone;
+/// use convdate::UtcLabel;
+///
+/// let tz = TimeZone::from_posix("JST-9").unwrap();
+/// let utc = UtcLabel::parse_from_str("2016-12-31T23:59:60.500", "%Y-%m-%dT%H:%M:%S%.3f").unwrap();
+///
+/// assert_eq!(tz.format(&utc, "%Y-%m-%dT%H:%M:%S%.3f%:z %Z"), "2017-01-01T08:59:60.500+09:00 JST");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimeZone {
+    /// The transition times in seconds since 1970-01-01T00:00:00 UTC without leap seconds, and the indices of `types`
+    transitions: Vec<(i64, usize)>,
+    /// The local time types; the first one is used before the first transition.
+    types: Vec<LocalTimeType>,
+    /// The rule used after the last transition
+    rule: Option<PosixTz>,
+}
+
+impl TimeZone {
+    /// Load a time zone.
+    ///
+    /// # Arguments
+    /// * `name` - A fixed UTC offset such as `"+09:00"` or `"Z"`, a name of the IANA time zone database such as `"Asia/Tokyo"`,
+    ///   or a POSIX TZ string such as `"JST-9"`.
+    /// * `zoneinfo_dir` - The zoneinfo directory which contains the TZif files; for example, [`DEFAULT_ZONEINFO_DIR`].
+    ///
+    /// # Returns
+    /// Returns the time zone.
+    ///
+    /// Returns [`Error`](crate::error::Error) if the time zone is unknown, the TZif file is illegal,
+    /// or `zoneinfo_dir` does not exist; the names of the database are not embedded in this library.
+    pub fn load(name: &str, zoneinfo_dir: &Path) -> Result<TimeZone, Error> {
+        if let Some(offset) = parse_utc_offset(name) {
+            return Ok(TimeZone::fixed(offset as i32, name));
+        }
+
+        // ".." などでディレクトリの外を参照させない
+        let relative = Path::new(name);
+        if relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            let path = zoneinfo_dir.join(relative);
+            if let Ok(bytes) = fs::read(&path) {
+                return TimeZone::from_tzif(&bytes)
+                    .map_err(|e| Error::TimeZoneFileError(path.clone(), Box::new(e)));
+            }
+        }
+
+        // POSIX TZ strings always have the UTC offset, unlike the names of the database.
+        if name.contains(|c: char| c.is_ascii_digit()) {
+            if let Ok(tz) = TimeZone::from_posix(name) {
+                return Ok(tz);
+            }
+        }
+        // tzdata が入っていない環境では、名前の誤りと区別して報告する
+        if !zoneinfo_dir.is_dir() {
+            Err(Error::ZoneinfoDirNotFoundError(
+                zoneinfo_dir.to_path_buf(),
+                name.to_string(),
+            ))?;
+        }
+        Err(Error::UnknownTimeZoneError(name.to_string()))
+    }
+
+    /// The time zone whose UTC offset is fixed.
+    ///
+    /// # Arguments
+    /// * `offset` - The UTC offset in seconds, positive to the east of Greenwich
+    /// * `abbreviation` - The abbreviation
+    pub fn fixed(offset: i32, abbreviation: &str) -> TimeZone {
+        TimeZone {
+            transitions: vec![],
+            types: vec![LocalTimeType {
+                offset,
+                is_dst: false,
+                abbreviation: abbreviation.to_string(),
+            }],
+            rule: None,
+        }
+    }
+
+    /// Read a time zone from the TZif data ([RFC 8536](https://www.rfc-editor.org/rfc/rfc8536)).
+    ///
+    /// # Arguments
+    /// * `bytes` - The content of a TZif file
+    ///
+    /// # Returns
+    /// Returns the time zone.
+    ///
+    /// Returns [`Error`](crate::error::Error) if the data is illegal.
+    pub fn from_tzif(bytes: &[u8]) -> Result<TimeZone, Error> {
+        let err = |reason: &str| Error::TimeZoneParseError(reason.to_string());
+        let mut reader = Reader { bytes, pos: 0 };
+
+        let header = TzifHeader::read(&mut reader).ok_or_else(|| err("not TZif"))?;
+        // バージョン2以降は64ビットのデータブロックが続くため、32ビットのデータブロックは読み飛ばす
+        let (header, time_size) = if header.version >= 2 {
+            reader
+                .take(header.block_len(4))
+                .ok_or_else(|| err("truncated data"))?;
+            (
+                TzifHeader::read(&mut reader).ok_or_else(|| err("not TZif"))?,
+                8,
+            )
+        } else {
+            (header, 4)
+        };
+
+        let read_time = |reader: &mut Reader| match time_size {
+            4 => reader.i32().map(i64::from),
+            _ => reader.i64(),
+        };
+        let mut times = Vec::with_capacity(header.timecnt);
+        for _ in 0..header.timecnt {
+            times.push(read_time(&mut reader).ok_or_else(|| err("truncated data"))?);
+        }
+        let indices = reader
+            .take(header.timecnt)
+            .ok_or_else(|| err("truncated data"))?
+            .to_vec();
+        let mut raw_types = Vec::with_capacity(header.typecnt);
+        for _ in 0..header.typecnt {
+            let offset = reader.i32().ok_or_else(|| err("truncated data"))?;
+            let flags = reader.take(2).ok_or_else(|| err("truncated data"))?;
+            raw_types.push((offset, flags[0] != 0, usize::from(flags[1])));
+        }
+        let chars = reader
+            .take(header.charcnt)
+            .ok_or_else(|| err("truncated data"))?;
+        let mut leaps = Vec::with_capacity(header.leapcnt);
+        for _ in 0..header.leapcnt {
+            let occurrence = read_time(&mut reader).ok_or_else(|| err("truncated data"))?;
+            let correction = reader.i32().ok_or_else(|| err("truncated data"))?;
+            leaps.push((occurrence, i64::from(correction)));
+        }
+        reader
+            .take(header.isstdcnt + header.isutcnt)
+            .ok_or_else(|| err("truncated data"))?;
+
+        let types = raw_types
+            .into_iter()
+            .map(|(offset, is_dst, index)| {
+                let abbreviation = chars
+                    .get(index..)
+                    .ok_or_else(|| err("illegal abbreviation"))?;
+                let end = abbreviation
+                    .iter()
+                    .position(|c| *c == 0)
+                    .ok_or_else(|| err("illegal abbreviation"))?;
+                Ok(LocalTimeType {
+                    offset,
+                    is_dst,
+                    abbreviation: String::from_utf8_lossy(&abbreviation[..end]).into_owned(),
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        if types.is_empty() {
+            return Err(err("no local time types"));
+        }
+
+        // right/ のゾーンでは遷移時刻がうるう秒を含めて数えられているため、その分を差し引く
+        let transitions = times
+            .into_iter()
+            .zip(indices)
+            .map(|(time, index)| {
+                let index = usize::from(index);
+                if index >= types.len() {
+                    return Err(err("illegal index of local time types"));
+                }
+                let correction = leaps
+                    .iter()
+                    .take_while(|(occurrence, _)| *occurrence <= time)
+                    .last()
+                    .map_or(0, |(_, correction)| *correction);
+                Ok((time - correction, index))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        // The footer is a POSIX TZ string between newlines.
+        let rule = if header.version >= 2 {
+            let footer = &bytes[reader.pos..];
+            let footer = footer
+                .strip_prefix(b"\n")
+                .and_then(|footer| footer.split(|c| *c == b'\n').next())
+                .ok_or_else(|| err("illegal footer"))?;
+            let footer = std::str::from_utf8(footer).map_err(|_| err("illegal footer"))?;
+            match footer {
+                "" => None,
+                footer => Some(PosixTz::parse(footer)?),
+            }
+        } else {
+            None
+        };
+
+        Ok(TimeZone {
+            transitions,
+            types,
+            rule,
+        })
+    }
+
+    /// Read a time zone from a POSIX TZ string; for example, `"JST-9"` or `"EST5EDT,M3.2.0,M11.1.0"`.
+    ///
+    /// # Returns
+    /// Returns the time zone.
+    ///
+    /// Returns [`Error`](crate::error::Error) if the string is illegal.
+    pub fn from_posix(s: &str) -> Result<TimeZone, Error> {
+        let rule = PosixTz::parse(s)?;
+        Ok(TimeZone {
+            transitions: vec![],
+            types: vec![rule.std.clone()],
+            rule: Some(rule),
+        })
+    }
+
+    /// The local time type at the datetime.
+    ///
+    /// # Arguments
+    /// * `utc` - The datetime in UTC
+    pub fn local_time_type(&self, utc: &NaiveDateTime) -> LocalTimeType {
+        let time = utc.timestamp();
+        let index = self
+            .transitions
+            .partition_point(|(transition, _)| *transition <= time);
+        match (index, &self.rule) {
+            (index, Some(rule)) if index == self.transitions.len() => rule.local_time_type(time),
+            (0, _) => self.types[0].clone(),
+            (index, _) => self.types[self.transitions[index - 1].1].clone(),
+        }
+    }
+
+    /// Convert the label of UTC to the label of the local time.
+    ///
+    /// The leap seconds are kept if the UTC offset is a whole number of minutes.
+    ///
+    /// # Returns
+    /// Returns the label of the local time and its local time type.
+    pub fn localize(&self, utc: &UtcLabel) -> (UtcLabel, LocalTimeType) {
+        let local_time_type = self.local_time_type(&utc.to_naive_saturating());
+        (
+            utc.shifted(i64::from(local_time_type.offset)),
+            local_time_type,
+        )
+    }
+
+    /// Format the label of UTC in the local time.
+    ///
+    /// In addition to the specifiers for [`NaiveDateTime`], `%z`, `%:z` and `%#z` are replaced with the UTC offset such as `+0900` and `+09:00`,
+    /// and `%Z` is replaced with the abbreviation.
+    ///
+    /// # Arguments
+    /// * `utc` - The label of UTC
+    /// * `fmt` - [format](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) of the result
+    pub fn format(&self, utc: &UtcLabel, fmt: &str) -> String {
+        let (local, local_time_type) = self.localize(utc);
+        local.format(&replace_zone_specifiers(fmt, &local_time_type))
+    }
+}
+
+/// Replace `%z`, `%:z`, `%#z` and `%Z` in the format with the UTC offset and the abbreviation.
+///
+/// [`NaiveDateTime`] cannot format them, and the arithmetic of chrono drops leap seconds,
+/// so they are replaced before formatting the local time.
+fn replace_zone_specifiers(fmt: &str, local_time_type: &LocalTimeType) -> String {
+    let offset = local_time_type.offset;
+    let sign = if offset < 0 { '-' } else { '+' };
+    let (hours, minutes) = (offset.abs() / 3600, offset.abs() % 3600 / 60);
+    let specifiers = [
+        ("%%", "%%".to_string()),
+        ("%Z", local_time_type.abbreviation.replace('%', "%%")),
+        ("%:z", format!("{}{:02}:{:02}", sign, hours, minutes)),
+        ("%#z", format!("{}{:02}{:02}", sign, hours, minutes)),
+        ("%z", format!("{}{:02}{:02}", sign, hours, minutes)),
+    ];
+
+    let mut replaced = String::with_capacity(fmt.len());
+    let mut rest = fmt;
+    while let Some(pos) = rest.find('%') {
+        replaced.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        match specifiers.iter().find(|(spec, _)| rest.starts_with(spec)) {
+            Some((spec, value)) => {
+                replaced.push_str(value);
+                rest = &rest[spec.len()..];
+            }
+            None => {
+                replaced.push('%');
+                rest = &rest[1..];
+            }
+        }
+    }
+    replaced.push_str(rest);
+    replaced
+}
+
+/// Parse a fixed UTC offset; `Z`, `UTC`, `GMT`, `±HH`, `±HHMM` or `±HH:MM`.
+///
+/// # Returns
+/// Returns the UTC offset in seconds, positive to the east of Greenwich, or `None` if it is not a UTC offset.
+pub(crate) fn parse_utc_offset(s: &str) -> Option<i64> {
+    if ["Z", "UTC", "GMT"]
+        .iter()
+        .any(|name| name.eq_ignore_ascii_case(s))
+    {
+        return Some(0);
+    }
+    let (sign, rest) = match s.as_bytes().first()? {
+        b'+' => (1, &s[1..]),
+        b'-' => (-1, &s[1..]),
+        _ => return None,
+    };
+    if !rest.chars().all(|c| c.is_ascii_digit() || c == ':') {
+        return None;
+    }
+    let (hours, minutes) = match (rest.len(), rest.find(':')) {
+        (2, None) => (&rest[..2], "0"),
+        (4, None) => (&rest[..2], &rest[2..]),
+        (5, Some(2)) => (&rest[..2], &rest[3..]),
+        _ => return None,
+    };
+    let (hours, minutes): (i64, i64) = (hours.parse().ok()?, minutes.parse().ok()?);
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Reader of big-endian binary data
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let taken = self.bytes.get(self.pos..self.pos.checked_add(len)?)?;
+        self.pos += len;
+        Some(taken)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_be_bytes(self.take(4)?.try_into().ok()?))
+    }
+
+    fn i32(&mut self) -> Option<i32> {
+        Some(i32::from_be_bytes(self.take(4)?.try_into().ok()?))
+    }
+
+    fn i64(&mut self) -> Option<i64> {
+        Some(i64::from_be_bytes(self.take(8)?.try_into().ok()?))
+    }
+}
+
+/// The header of TZif data
+struct TzifHeader {
+    version: u8,
+    isutcnt: usize,
+    isstdcnt: usize,
+    leapcnt: usize,
+    timecnt: usize,
+    typecnt: usize,
+    charcnt: usize,
+}
+
+impl TzifHeader {
+    fn read(reader: &mut Reader) -> Option<TzifHeader> {
+        if reader.take(4)? != b"TZif" {
+            return None;
+        }
+        let version = match reader.take(1)?[0] {
+            0 => 1,
+            version @ b'2'..=b'9' => version - b'0',
+            _ => return None,
+        };
+        reader.take(15)?;
+        let mut count = || reader.u32().map(|count| count as usize);
+        Some(TzifHeader {
+            version,
+            isutcnt: count()?,
+            isstdcnt: count()?,
+            leapcnt: count()?,
+            timecnt: count()?,
+            typecnt: count()?,
+            charcnt: count()?,
+        })
+    }
+
+    /// The length of the data block in bytes
+    fn block_len(&self, time_size: usize) -> usize {
+        self.timecnt * (time_size + 1)
+            + self.typecnt * 6
+            + self.charcnt
+            + self.leapcnt * (time_size + 4)
+            + self.isstdcnt
+            + self.isutcnt
+    }
+}
+
+/// A POSIX TZ string; for example, `EST5EDT,M3.2.0,M11.1.0`
+#[derive(Clone, Debug, PartialEq)]
+struct PosixTz {
+    std: LocalTimeType,
+    /// The DST and the rules of its start and end
+    dst: Option<(LocalTimeType, PosixRule, PosixRule)>,
+}
+
+impl PosixTz {
+    fn parse(s: &str) -> Result<PosixTz, Error> {
+        let err = || Error::TimeZoneParseError(s.to_string());
+        let mut rest = s;
+
+        let std_name = take_abbreviation(&mut rest).ok_or_else(err)?;
+        let std_offset = take_hms(&mut rest).ok_or_else(err)?;
+        let std = LocalTimeType {
+            offset: -std_offset,
+            is_dst: false,
+            abbreviation: std_name,
+        };
+        if rest.is_empty() {
+            return Ok(PosixTz { std, dst: None });
+        }
+
+        let dst_name = take_abbreviation(&mut rest).ok_or_else(err)?;
+        let dst_offset = if rest.starts_with(',') || rest.is_empty() {
+            std_offset - 3600
+        } else {
+            take_hms(&mut rest).ok_or_else(err)?
+        };
+        let dst = LocalTimeType {
+            offset: -dst_offset,
+            is_dst: true,
+            abbreviation: dst_name,
+        };
+        let rules = match rest {
+            "" => DEFAULT_DST_RULES,
+            rest => rest.strip_prefix(',').ok_or_else(err)?,
+        };
+        let (start, end) = match rules.split(',').collect::<Vec<_>>().as_slice() {
+            [start, end] => (
+                PosixRule::parse(start).ok_or_else(err)?,
+                PosixRule::parse(end).ok_or_else(err)?,
+            ),
+            _ => return Err(err()),
+        };
+        Ok(PosixTz {
+            std,
+            dst: Some((dst, start, end)),
+        })
+    }
+
+    /// The local time type at the time in seconds since 1970-01-01T00:00:00 UTC.
+    fn local_time_type(&self, time: i64) -> LocalTimeType {
+        let (dst, start, end) = match &self.dst {
+            Some(dst) => dst,
+            None => return self.std.clone(),
+        };
+        let year = match NaiveDateTime::from_timestamp_opt(time + i64::from(self.std.offset), 0) {
+            Some(local) => local.year(),
+            None => return self.std.clone(),
+        };
+        // 開始時刻は標準時、終了時刻は夏時間で表されている
+        let (start, end) = match (
+            start.timestamp(year, self.std.offset),
+            end.timestamp(year, dst.offset),
+        ) {
+            (Some(start), Some(end)) => (start, end),
+            _ => return self.std.clone(),
+        };
+        let is_dst = if start < end {
+            start <= time && time < end
+        } else {
+            // 南半球では年をまたいで夏時間になる
+            !(end <= time && time < start)
+        };
+        if is_dst {
+            dst.clone()
+        } else {
+            self.std.clone()
+        }
+    }
+}
+
+/// Take an abbreviation of a POSIX TZ string; three or more letters, or any characters in `<` and `>`.
+fn take_abbreviation(rest: &mut &str) -> Option<String> {
+    let (name, len) = match rest.strip_prefix('<') {
+        Some(quoted) => {
+            let end = quoted.find('>')?;
+            (&quoted[..end], end + 2)
+        }
+        None => {
+            let end = rest
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(rest.len());
+            (&rest[..end], end)
+        }
+    };
+    if name.len() < 3 {
+        return None;
+    }
+    let name = name.to_string();
+    *rest = &rest[len..];
+    Some(name)
+}
+
+/// Take a time of a POSIX TZ string; `[+-]hh[:mm[:ss]]`.
+///
+/// # Returns
+/// Returns the time in seconds.
+fn take_hms(rest: &mut &str) -> Option<i32> {
+    let (sign, unsigned) = match rest.as_bytes().first()? {
+        b'+' => (1, &rest[1..]),
+        b'-' => (-1, &rest[1..]),
+        _ => (1, *rest),
+    };
+    let end = unsigned
+        .find(|c: char| !c.is_ascii_digit() && c != ':')
+        .unwrap_or(unsigned.len());
+    let fields: Vec<&str> = unsigned[..end].split(':').collect();
+    if fields.len() > 3
+        || fields
+            .iter()
+            .any(|field| field.is_empty() || field.len() > 3)
+    {
+        return None;
+    }
+    let seconds = fields
+        .iter()
+        .zip([3600, 60, 1])
+        .map(|(field, unit)| field.parse::<i32>().map(|value| value * unit))
+        .sum::<Result<i32, _>>()
+        .ok()?;
+    *rest = &unsigned[end..];
+    Some(sign * seconds)
+}
+
+/// A rule of the start or end of DST in a POSIX TZ string; for example, `M3.2.0/2`
+#[derive(Clone, Debug, PartialEq)]
+struct PosixRule {
+    date: PosixDate,
+    /// The local time of the transition in seconds; it may be negative or over 24 hours.
+    time: i32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum PosixDate {
+    /// `Jn`: the day of the year from 1 to 365, not counting February 29
+    Julian1(u16),
+    /// `n`: the day of the year from 0 to 365, counting February 29
+    Julian0(u16),
+    /// `Mm.w.d`: the day `d` (0 is Sunday) of the week `w` (5 is the last week) of the month `m`
+    MonthWeekDay(u32, u32, u32),
+}
+
+impl PosixRule {
+    fn parse(s: &str) -> Option<PosixRule> {
+        let (date, time) = match s.split_once('/') {
+            Some((date, time)) => {
+                let mut time = time;
+                let seconds = take_hms(&mut time)?;
+                if !time.is_empty() {
+                    return None;
+                }
+                (date, seconds)
+            }
+            None => (s, 2 * 3600),
+        };
+        let date = if let Some(day) = date.strip_prefix('J') {
+            PosixDate::Julian1(day.parse().ok().filter(|day| (1..=365).contains(day))?)
+        } else if let Some(mwd) = date.strip_prefix('M') {
+            let fields = mwd
+                .split('.')
+                .map(str::parse)
+                .collect::<Result<Vec<u32>, _>>()
+                .ok()?;
+            match fields.as_slice() {
+                [m @ 1..=12, w @ 1..=5, d @ 0..=6] => PosixDate::MonthWeekDay(*m, *w, *d),
+                _ => return None,
+            }
+        } else {
+            PosixDate::Julian0(date.parse().ok().filter(|day| *day <= 365)?)
+        };
+        Some(PosixRule { date, time })
+    }
+
+    /// The time of the transition in seconds since 1970-01-01T00:00:00 UTC.
+    ///
+    /// # Arguments
+    /// * `year` - The year of the transition
+    /// * `offset` - The UTC offset in which the rule is written
+    fn timestamp(&self, year: i32, offset: i32) -> Option<i64> {
+        let date = match self.date {
+            PosixDate::Julian1(day) => {
+                let date =
+                    NaiveDate::from_ymd_opt(year, 1, 1)? + Duration::days(i64::from(day) - 1);
+                // 2月29日は数えない
+                match NaiveDate::from_ymd_opt(year, 2, 29) {
+                    Some(leap_day) if date >= leap_day => date.succ_opt()?,
+                    _ => date,
+                }
+            }
+            PosixDate::Julian0(day) => {
+                NaiveDate::from_ymd_opt(year, 1, 1)? + Duration::days(i64::from(day))
+            }
+            PosixDate::MonthWeekDay(month, week, weekday) => {
+                let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+                let first_weekday = first.weekday().num_days_from_sunday();
+                let mut day = 1 + (weekday + 7 - first_weekday) % 7 + (week - 1) * 7;
+                while NaiveDate::from_ymd_opt(year, month, day).is_none() {
+                    day -= 7;
+                }
+                NaiveDate::from_ymd_opt(year, month, day)?
+            }
+        };
+        Some(date.and_hms(0, 0, 0).timestamp() + i64::from(self.time) - i64::from(offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    const DT_FMT: &str = "%Y-%m-%dT%H:%M:%S%.3f";
+
+    #[rstest]
+    #[case("Z", Some(0))]
+    #[case("utc", Some(0))]
+    #[case("+09:00", Some(9 * 3600))]
+    #[case("+0930", Some(9 * 3600 + 30 * 60))]
+    #[case("-05", Some(-5 * 3600))]
+    #[case("+9:00", None)]
+    #[case("+24:00", None)]
+    #[case("Asia/Tokyo", None)]
+    fn test_parse_utc_offset(#[case] s: &str, #[case] expected: Option<i64>) {
+        assert_eq!(parse_utc_offset(s), expected);
+    }
+
+    #[rstest]
+    #[case(
+        "JST-9",
+        "2017-01-01T00:00:00.000",
+        "2017-01-01T09:00:00.000+09:00 JST"
+    )]
+    #[case(
+        "JST-9",
+        "2016-12-31T23:59:60.500",
+        "2017-01-01T08:59:60.500+09:00 JST"
+    )]
+    #[case(
+        "EST5EDT,M3.2.0,M11.1.0",
+        "2017-01-01T00:00:00.000",
+        "2016-12-31T19:00:00.000-05:00 EST"
+    )]
+    #[case(
+        "EST5EDT,M3.2.0,M11.1.0",
+        "2017-07-01T00:00:00.000",
+        "2017-06-30T20:00:00.000-04:00 EDT"
+    )]
+    // 夏時間の開始と終了 (2017-03-12T02:00 EST, 2017-11-05T02:00 EDT)
+    #[case(
+        "EST5EDT",
+        "2017-03-12T06:59:59.000",
+        "2017-03-12T01:59:59.000-05:00 EST"
+    )]
+    #[case(
+        "EST5EDT",
+        "2017-03-12T07:00:00.000",
+        "2017-03-12T03:00:00.000-04:00 EDT"
+    )]
+    #[case(
+        "EST5EDT",
+        "2017-11-05T05:59:59.000",
+        "2017-11-05T01:59:59.000-04:00 EDT"
+    )]
+    #[case(
+        "EST5EDT",
+        "2017-11-05T06:00:00.000",
+        "2017-11-05T01:00:00.000-05:00 EST"
+    )]
+    // 南半球
+    #[case(
+        "AEST-10AEDT,M10.1.0,M4.1.0/3",
+        "2017-01-01T00:00:00.000",
+        "2017-01-01T11:00:00.000+11:00 AEDT"
+    )]
+    #[case(
+        "AEST-10AEDT,M10.1.0,M4.1.0/3",
+        "2017-07-01T00:00:00.000",
+        "2017-07-01T10:00:00.000+10:00 AEST"
+    )]
+    #[case(
+        "<+0330>-3:30",
+        "2017-01-01T00:00:00.000",
+        "2017-01-01T03:30:00.000+03:30 +0330"
+    )]
+    #[case(
+        "CET-1CEST,J60/2,J300/3",
+        "2017-03-01T00:59:59.000",
+        "2017-03-01T01:59:59.000+01:00 CET"
+    )]
+    #[case(
+        "CET-1CEST,J60/2,J300/3",
+        "2017-03-01T01:00:00.000",
+        "2017-03-01T03:00:00.000+02:00 CEST"
+    )]
+    fn test_from_posix(#[case] tz: &str, #[case] utc: &str, #[case] expected: &str) {
+        let tz = TimeZone::from_posix(tz).unwrap();
+        let utc = UtcLabel::parse_from_str(utc, DT_FMT).unwrap();
+        assert_eq!(tz.format(&utc, "%Y-%m-%dT%H:%M:%S%.3f%:z %Z"), expected);
+    }
+
+    #[rstest]
+    #[case("JST")]
+    #[case("J-9")]
+    #[case("JST-9JDT,M3.2.0")]
+    #[case("JST-9JDT,M13.2.0,M11.1.0")]
+    #[case("JST-9x")]
+    fn test_from_posix_error(#[case] tz: &str) {
+        assert_eq!(
+            TimeZone::from_posix(tz),
+            Err(Error::TimeZoneParseError(tz.to_string()))
+        );
+    }
+
+    /// Build TZif data of version 2 with the leap second records.
+    fn tzif(
+        transitions: &[(i64, u8)],
+        types: &[(i32, bool, &str)],
+        leaps: &[(i64, i32)],
+        footer: &str,
+    ) -> Vec<u8> {
+        let mut chars = Vec::<u8>::new();
+        let mut type_bytes = Vec::<u8>::new();
+        for (offset, is_dst, abbreviation) in types {
+            type_bytes.extend_from_slice(&offset.to_be_bytes());
+            type_bytes.push(*is_dst as u8);
+            type_bytes.push(chars.len() as u8);
+            chars.extend_from_slice(abbreviation.as_bytes());
+            chars.push(0);
+        }
+        let header = |bytes: &mut Vec<u8>| {
+            bytes.extend_from_slice(b"TZif2");
+            bytes.extend_from_slice(&[0; 15]);
+            for count in [
+                0,
+                0,
+                leaps.len(),
+                transitions.len(),
+                types.len(),
+                chars.len(),
+            ] {
+                bytes.extend_from_slice(&(count as u32).to_be_bytes());
+            }
+        };
+
+        let mut bytes = Vec::<u8>::new();
+        header(&mut bytes);
+        for (time, _) in transitions {
+            bytes.extend_from_slice(&(*time as i32).to_be_bytes());
+        }
+        bytes.extend(transitions.iter().map(|(_, index)| *index));
+        bytes.extend_from_slice(&type_bytes);
+        bytes.extend_from_slice(&chars);
+        for (occurrence, correction) in leaps {
+            bytes.extend_from_slice(&(*occurrence as i32).to_be_bytes());
+            bytes.extend_from_slice(&correction.to_be_bytes());
+        }
+        header(&mut bytes);
+        for (time, _) in transitions {
+            bytes.extend_from_slice(&time.to_be_bytes());
+        }
+        bytes.extend(transitions.iter().map(|(_, index)| *index));
+        bytes.extend_from_slice(&type_bytes);
+        bytes.extend_from_slice(&chars);
+        for (occurrence, correction) in leaps {
+            bytes.extend_from_slice(&occurrence.to_be_bytes());
+            bytes.extend_from_slice(&correction.to_be_bytes());
+        }
+        bytes.extend_from_slice(format!("\n{}\n", footer).as_bytes());
+        bytes
+    }
+
+    #[rstest]
+    // 最初の遷移より前は最初の型を使う
+    #[case(&[], "2017-01-01T00:00:00.000", "2017-01-01T09:18:59.000+09:18 LMT")]
+    #[case(&[], "2017-01-02T00:00:00.000", "2017-01-02T09:00:00.000+09:00 JST")]
+    #[case(&[], "2017-01-03T00:00:00.000", "2017-01-03T10:00:00.000+10:00 JDT")]
+    #[case(&[], "2017-01-03T23:59:59.000", "2017-01-04T09:59:59.000+10:00 JDT")]
+    #[case(&[], "2017-01-04T00:00:00.000", "2017-01-04T09:00:00.000+09:00 JST")]
+    // 最後の遷移より後は末尾の TZ 文字列を使う
+    #[case(&[], "2018-01-01T00:00:00.000", "2018-01-01T09:00:00.000+09:00 JST")]
+    // right/ のゾーンは遷移時刻がうるう秒を含む
+    #[case(&[(1483228826, 27)], "2017-01-02T00:00:00.000", "2017-01-02T09:00:00.000+09:00 JST")]
+    #[case(&[(1483228826, 27)], "2017-01-01T23:59:59.000", "2017-01-02T09:18:58.000+09:18 LMT")]
+    fn test_from_tzif(#[case] leaps: &[(i64, i32)], #[case] utc: &str, #[case] expected: &str) {
+        // 2017-01-02T00:00:00, 2017-01-03T00:00:00, 2017-01-04T00:00:00
+        let correction = |time: i64| {
+            leaps
+                .iter()
+                .rfind(|(occurrence, _)| *occurrence <= time)
+                .map_or(0, |(_, correction)| i64::from(*correction))
+        };
+        let transitions = [1483315200, 1483401600, 1483488000]
+            .iter()
+            .map(|time| time + correction(*time + 27))
+            .zip([1, 2, 1])
+            .collect::<Vec<_>>();
+        let bytes = tzif(
+            &transitions,
+            &[
+                (33539, false, "LMT"),
+                (32400, false, "JST"),
+                (36000, true, "JDT"),
+            ],
+            leaps,
+            "JST-9",
+        );
+        let tz = TimeZone::from_tzif(&bytes).unwrap();
+        let utc = UtcLabel::parse_from_str(utc, DT_FMT).unwrap();
+        assert_eq!(tz.format(&utc, "%Y-%m-%dT%H:%M:%S%.3f%:z %Z"), expected);
+    }
+
+    #[rstest]
+    #[case(b"TZif", "not TZif")]
+    #[case(b"TZif3\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0", "not TZif")]
+    #[case(&tzif(&[], &[], &[], ""), "no local time types")]
+    #[case(&tzif(&[], &[(32400, false, "JST")], &[], "JST-9")[..100], "truncated data")]
+    #[case(&tzif(&[], &[(32400, false, "JST")], &[], "JST"), "JST")]
+    fn test_from_tzif_error(#[case] bytes: &[u8], #[case] expected: &str) {
+        assert_eq!(
+            TimeZone::from_tzif(bytes),
+            Err(Error::TimeZoneParseError(expected.to_string()))
+        );
+    }
+
+    #[rstest]
+    #[case("%Y %Z", 32400, "JST", "%Y JST")]
+    #[case("%z %:z %#z", 32400, "JST", "+0900 +09:00 +0900")]
+    #[case("%:z", -12600, "NST", "-03:30")]
+    #[case("%:z", 33539, "LMT", "+09:18")]
+    #[case("%%Z %%z %Z", 0, "UTC", "%%Z %%z UTC")]
+    #[case("%Z", 0, "%", "%%")]
+    #[case("%H%", 0, "UTC", "%H%")]
+    fn test_replace_zone_specifiers(
+        #[case] fmt: &str,
+        #[case] offset: i32,
+        #[case] abbreviation: &str,
+        #[case] expected: &str,
+    ) {
+        let local_time_type = LocalTimeType {
+            offset,
+            is_dst: false,
+            abbreviation: abbreviation.to_string(),
+        };
+        assert_eq!(replace_zone_specifiers(fmt, &local_time_type), expected);
+    }
+
+    #[test]
+    fn test_load() {
+        let dir = crate::testmod::tmp_dir(Some("")).unwrap();
+        let tzif = tzif(&[], &[(32400, false, "JST")], &[], "JST-9");
+        std::fs::create_dir(dir.path().join("Asia")).unwrap();
+        std::fs::write(dir.path().join("Asia").join("Tokyo"), &tzif).unwrap();
+        std::fs::write(dir.path().join("Broken"), b"TZif").unwrap();
+
+        let utc = UtcLabel::parse_from_str("2016-12-31T23:59:60.000", DT_FMT).unwrap();
+        let format = |tz: &TimeZone| tz.format(&utc, "%Y-%m-%dT%H:%M:%S%.3f%:z");
+        let load = |name: &str| TimeZone::load(name, dir.path());
+
+        assert_eq!(
+            format(&load("Asia/Tokyo").unwrap()),
+            "2017-01-01T08:59:60.000+09:00"
+        );
+        assert_eq!(
+            format(&load("+09:00").unwrap()),
+            "2017-01-01T08:59:60.000+09:00"
+        );
+        assert_eq!(
+            format(&load("JST-9").unwrap()),
+            "2017-01-01T08:59:60.000+09:00"
+        );
+        assert_eq!(
+            load("Asia/Tokio"),
+            Err(Error::UnknownTimeZoneError("Asia/Tokio".to_string()))
+        );
+        assert_eq!(
+            load("../Asia/Tokyo"),
+            Err(Error::UnknownTimeZoneError("../Asia/Tokyo".to_string()))
+        );
+        assert_eq!(
+            load("Broken"),
+            Err(Error::TimeZoneFileError(
+                dir.path().join("Broken"),
+                Box::new(Error::TimeZoneParseError("not TZif".to_string()))
+            ))
+        );
+
+        let missing_dir = dir.path().join("missing");
+        assert_eq!(
+            TimeZone::load("Asia/Tokyo", &missing_dir),
+            Err(Error::ZoneinfoDirNotFoundError(
+                missing_dir.clone(),
+                "Asia/Tokyo".to_string()
+            ))
+        );
+        assert_eq!(
+            format(&TimeZone::load("JST-9", &missing_dir).unwrap()),
+            "2017-01-01T08:59:60.000+09:00"
+        );
+    }
+}